@@ -0,0 +1,137 @@
+//! Command line tool censoring texts with the Bad Words API, the same way the service does.
+//!
+//! Useful to check what the profanity filter makes of a text before it is posted, or to try a
+//! censor character out.
+//!
+//! ```text
+//! export API_LAYER_KEY=...
+//! bad_words --text "some text to check"
+//! bad_words --file post.md --censor-char '#' --json
+//! ```
+//!
+//! By default only the censored text is printed. With `--json` the whole response of the API is
+//! printed, including the list of the bad words that were found.
+//!
+//! The key for the API is taken from `--api-key`, the `API_LAYER_KEY` variable, or the `api_key`
+//! entry of the TOML file given with `--config`, in that order. With `--offline` no key is needed,
+//! and the text is censored with the list of words bundled with [webdev_core], which only catches
+//! the listed words, but prints the same output.
+#![warn(clippy::all)]
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser};
+use config::Config;
+use serde_json::to_string_pretty;
+use webdev_core::api::bad_words::{BadWordsAPI, BadWordsResponse};
+use webdev_core::api::wordlist::WordList;
+
+/// Censors texts with the Bad Words API
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    #[command(flatten)]
+    input: Input,
+    /// Character the bad words are replaced with
+    #[arg(long, default_value_t = '*')]
+    censor_char: char,
+    /// Prints the whole response of the API as JSON, instead of the censored text
+    #[arg(long)]
+    json: bool,
+    /// Key for the Bad Words API
+    #[arg(long, env = "API_LAYER_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+    /// TOML file with the `api_key` for the Bad Words API, used when the key is not given otherwise
+    #[arg(long, env = "BAD_WORDS_CONFIG")]
+    config: Option<PathBuf>,
+    /// Censors the text with the bundled list of words, without calling the API or needing a key
+    #[arg(long)]
+    offline: bool,
+}
+
+/// The text to censor, given either inline or as a file.
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+struct Input {
+    /// Text to censor
+    #[arg(long)]
+    text: Option<String>,
+    /// File with the text to censor
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+impl Input {
+    /// Reads the text to censor.
+    fn read(self) -> Result<String, String> {
+        match (self.text, self.file) {
+            (Some(text), _) => Ok(text),
+            (None, Some(file)) => {
+                std::fs::read_to_string(&file).map_err(|error| format!("cannot read {}: {error}", file.display()))
+            }
+            (None, None) => unreachable!("clap requires one of the inputs"),
+        }
+    }
+}
+
+/// Returns the key for the Bad Words API, from the arguments or from the configuration file.
+fn api_key(api_key: Option<String>, config: Option<PathBuf>) -> Result<String, String> {
+    if let Some(api_key) = api_key {
+        return Ok(api_key);
+    }
+    let Some(config) = config else {
+        return Err("missing key for the Bad Words API: pass --api-key, set API_LAYER_KEY, \
+                    or set api_key in the --config file, or censor --offline"
+            .to_string());
+    };
+    Config::builder()
+        .add_source(config::File::from(config.as_path()))
+        .build()
+        .and_then(|settings| settings.get_string("api_key"))
+        .map_err(|error| format!("cannot read the key from {}: {error}", config.display()))
+}
+
+/// Formats the response of the API for printing.
+fn render(response: &BadWordsResponse, json: bool) -> String {
+    if json {
+        to_string_pretty(response).expect("responses are always serializable")
+    } else {
+        response.censored_content.clone()
+    }
+}
+
+/// Censors the text, and returns the output to print.
+async fn run(cli: Cli) -> Result<String, String> {
+    let Cli {
+        input,
+        censor_char,
+        json,
+        api_key,
+        config,
+        offline,
+    } = cli;
+    let text = input.read()?;
+    let response = if offline {
+        WordList::bundled(censor_char).check_profanity(text)
+    } else {
+        let api_key = self::api_key(api_key, config)?;
+        let api = BadWordsAPI::build(&api_key, censor_char).map_err(|error| error.to_string())?;
+        api.check_profanity(text).await.map_err(|error| error.to_string())?
+    };
+    Ok(render(&response, json))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
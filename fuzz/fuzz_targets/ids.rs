@@ -0,0 +1,16 @@
+//! Parses arbitrary path segments as ids, which have to either fail or round trip.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webdev_book::types::answer::AnswerId;
+use webdev_book::types::question::QuestionId;
+
+fuzz_target!(|segment: &str| {
+    if let Ok(id) = segment.parse::<QuestionId>() {
+        assert!(id.0 >= 0);
+        assert_eq!(id.0.to_string().parse::<QuestionId>().unwrap(), id);
+    }
+    if let Ok(id) = segment.parse::<AnswerId>() {
+        assert!(id.0 >= 0);
+    }
+});
@@ -0,0 +1,14 @@
+//! Extracts the pagination from arbitrary query parameters, which have to either be rejected or be valid.
+#![no_main]
+
+use std::collections::HashMap;
+
+use libfuzzer_sys::fuzz_target;
+use webdev_book::types::pagination::Pagination;
+
+fuzz_target!(|params: HashMap<String, String>| {
+    if let Ok(pagination) = Pagination::extract(&params) {
+        assert!(pagination.validate().is_ok());
+        assert!(params.keys().all(|key| Pagination::PARAMETERS.contains(&key.as_str())));
+    }
+});
@@ -0,0 +1,17 @@
+//! Verifies arbitrary tokens, and deserializes arbitrary claims, neither of which may panic.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use webdev_book::authentication::{session_from_claims, verify_token};
+use webdev_book::clock::SystemClock;
+use webdev_book::tokens::PasetoSigner;
+use webdev_book::types::authentication::AuthKeys;
+
+fuzz_target!(|data: &[u8]| {
+    let signer = PasetoSigner::new(AuthKeys::new("fuzzing", b"FUZZING KEY FOR THE WEBDEV BOOKS").unwrap());
+
+    if let Ok(claims) = serde_json::from_slice(data) {
+        let _ = session_from_claims(claims);
+    }
+    let _ = verify_token(&signer, &SystemClock, String::from_utf8_lossy(data).into_owned());
+});
@@ -0,0 +1,210 @@
+use serde_json::json;
+use warp::http::StatusCode;
+use webdev_book::test_support::{
+    a_question, an_account, an_answer, authenticated, test_router, token_for, DEFAULT_PASSWORD,
+};
+
+#[tokio::test]
+async fn the_profile_of_the_account_does_not_include_its_password() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let alice_id = alice.id.unwrap();
+
+    let response = authenticated(alice_id).path("/accounts/me").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let profile: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(profile["id"], alice_id.0);
+    assert_eq!(profile["email"], alice.email);
+    assert!(profile["created_at"].is_string());
+    assert!(profile.get("password").is_none());
+
+    let response = warp::test::request().path("/accounts/me").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn the_profile_is_updated_and_shown_publicly_with_the_activity_of_the_account() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let alice_id = alice.id.unwrap();
+    let question_id = a_question().owned_by(alice_id).insert(&store).await.id.unwrap();
+    an_answer().to(question_id).owned_by(alice_id).insert(&store).await;
+    an_answer().to(question_id).owned_by(alice_id).insert(&store).await;
+    let update = |profile: serde_json::Value| {
+        let routes = routes.clone();
+        async move {
+            authenticated(alice_id)
+                .method("PUT")
+                .path("/accounts/me")
+                .json(&profile)
+                .reply(&routes)
+                .await
+        }
+    };
+
+    let response =
+        update(json!({ "display_name": "  Alice \n Liddell ", "website": "https://alice.example.com" })).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let profile: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(profile["display_name"], "Alice Liddell");
+    assert_eq!(profile["email"], alice.email);
+    assert!(profile["bio"].is_null());
+
+    for profile in [
+        json!({ "website": "javascript:alert(1)" }),
+        json!({ "display_name": "a".repeat(65) }),
+        json!({ "email": "mallory@example.com" }),
+    ] {
+        assert_ne!(update(profile.clone()).await.status(), StatusCode::OK, "{profile}");
+    }
+
+    let response = warp::test::request()
+        .path(&format!("/accounts/{}", alice_id.0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let profile: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(profile["display_name"], "Alice Liddell");
+    assert_eq!(profile["website"], "https://alice.example.com");
+    assert_eq!(profile["question_count"], 1);
+    assert_eq!(profile["answer_count"], 2);
+    assert!(profile.get("email").is_none());
+
+    let response = warp::test::request().path("/accounts/0").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn deleting_an_account_anonymizes_or_deletes_its_content_and_rejects_its_tokens() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+
+    for (query, kept) in [("", true), ("?content=delete", false)] {
+        let alice = an_account().insert(&store).await.id.unwrap();
+        let bob = an_account().insert(&store).await.id.unwrap();
+        let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+        let answered_id = a_question().owned_by(bob).insert(&store).await.id.unwrap();
+        an_answer().to(answered_id).owned_by(alice).insert(&store).await;
+        let other_token = token_for(alice);
+
+        let response = authenticated(alice)
+            .method("DELETE")
+            .path(&format!("/accounts/me{query}"))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT, "{query}");
+        assert!(response.body().is_empty());
+
+        let response = warp::test::request()
+            .path(&format!("/questions/{}", question_id.0))
+            .reply(&routes)
+            .await;
+        match kept {
+            true => {
+                assert_eq!(response.status(), StatusCode::OK);
+                let question: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+                assert!(question["author"].is_null());
+            }
+            false => assert_eq!(response.status(), StatusCode::NOT_FOUND),
+        }
+        let response = warp::test::request()
+            .path(&format!("/questions/{}/answers", answered_id.0))
+            .reply(&routes)
+            .await;
+        let answers: Vec<serde_json::Value> = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(answers.len(), kept as usize, "{query}");
+
+        let response = warp::test::request()
+            .path("/accounts/me/notifications")
+            .header("Authorization", other_token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.body(), "auth token was revoked");
+    }
+
+    let response = authenticated(an_account().insert(&store).await.id.unwrap())
+        .method("DELETE")
+        .path("/accounts/me?content=everything")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn the_content_of_a_deleted_account_neither_notifies_nor_earns_badges() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let [alice, bob, carol] = [
+        an_account().insert(&store).await.id.unwrap(),
+        an_account().insert(&store).await.id.unwrap(),
+        an_account().insert(&store).await.id.unwrap(),
+    ];
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    an_answer().to(question_id).owned_by(alice).insert(&store).await;
+    an_answer().to(question_id).owned_by(bob).insert(&store).await;
+    a_question().owned_by(carol).insert(&store).await;
+
+    let response = authenticated(alice)
+        .method("DELETE")
+        .path("/accounts/me")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    // Bob still watches the question of the deleted account
+    let answer = an_answer().to(question_id).owned_by(carol).insert(&store).await;
+    let answer_id = answer.id.unwrap();
+    assert_eq!(store.add_answer_notifications(answer_id).await.unwrap(), 1);
+    let response = authenticated(bob)
+        .path("/accounts/me/notifications")
+        .reply(&routes)
+        .await;
+    let notifications: Vec<serde_json::Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0]["answer_id"], answer_id.0);
+
+    // Only the question of Carol earns a badge
+    assert_eq!(store.award_badges().await.unwrap(), 1);
+    assert_eq!(store.get_badges(carol).await.unwrap().len(), 1);
+    assert!(store.get_badges(bob).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn emails_are_registered_and_logged_in_with_regardless_of_their_case() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let request = |path: &'static str, email: &'static str| {
+        let routes = routes.clone();
+        async move {
+            warp::test::request()
+                .method("POST")
+                .path(path)
+                .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+                .reply(&routes)
+                .await
+        }
+    };
+
+    assert_eq!(
+        request("/register", " Carol@Example.com").await.status(),
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        request("/register", "carol@example.COM").await.status(),
+        StatusCode::CONFLICT
+    );
+
+    let response = request("/login", "CAROL@example.com").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token: String = serde_json::from_slice(response.body()).unwrap();
+    let response = warp::test::request()
+        .path("/accounts/me")
+        .header("Authorization", token)
+        .reply(&routes)
+        .await;
+    let profile: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(profile["email"], "carol@example.com");
+}
@@ -0,0 +1,272 @@
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::error::ServiceError;
+use webdev_book::test_support::{a_question, an_account, an_answer, authenticated, test_router};
+use webdev_book::types::answer::AnswerId;
+use webdev_book::types::question::QuestionId;
+
+#[tokio::test]
+async fn the_answers_to_a_question_are_listed_in_pages() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let question_id = a_question().insert(&store).await.id.unwrap();
+    let other_question_id = a_question().insert(&store).await.id.unwrap();
+    let mut answer_ids = Vec::new();
+    for _ in 0..3 {
+        answer_ids.push(i64::from(
+            an_answer().to(question_id).insert(&store).await.id.unwrap().0,
+        ));
+    }
+    an_answer().to(other_question_id).insert(&store).await;
+
+    let path = format!("/questions/{}/answers", question_id.0);
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let ids: Vec<_> = answers.iter().map(|answer| answer["id"].as_i64().unwrap()).collect();
+    assert_eq!(ids, answer_ids);
+
+    let response = warp::test::request()
+        .path(&format!("{path}?offset=1&limit=1"))
+        .reply(&routes)
+        .await;
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(answers.len(), 1);
+    assert_eq!(answers[0]["id"], answer_ids[1]);
+
+    let response = warp::test::request()
+        .path(&format!("{path}?sort=newest&limit=2"))
+        .reply(&routes)
+        .await;
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let ids: Vec<_> = answers.iter().map(|answer| answer["id"].as_i64().unwrap()).collect();
+    assert_eq!(ids, [answer_ids[2], answer_ids[1]]);
+
+    for query in ["offset=-1", "sort=best"] {
+        let response = warp::test::request()
+            .path(&format!("{path}?{query}"))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = warp::test::request()
+        .path("/questions/2147483647/answers")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn the_owner_of_the_question_accepts_an_answer_listed_first() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let first = an_answer()
+        .to(question_id)
+        .owned_by(bob)
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    let second = an_answer()
+        .to(question_id)
+        .owned_by(bob)
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    let accept = |answer_id: AnswerId| format!("/questions/{}/answers/{}/accept", question_id.0, answer_id.0);
+
+    let response = authenticated(bob)
+        .method("POST")
+        .path(&accept(second))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    for answer_id in [first, second] {
+        let response = authenticated(alice)
+            .method("POST")
+            .path(&accept(answer_id))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let path = format!("/questions/{}/answers", question_id.0);
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let listed: Vec<_> = answers
+        .iter()
+        .map(|answer| (answer["id"].as_i64().unwrap(), answer["accepted"].as_bool().unwrap()))
+        .collect();
+    assert_eq!(listed, [(second.0.into(), true), (first.0.into(), false)]);
+
+    let response = warp::test::request()
+        .path(&format!("{path}?sort=oldest"))
+        .reply(&routes)
+        .await;
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(answers[0]["id"], first.0);
+
+    let other_question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}/answers/{}/accept", other_question_id.0, first.0);
+    let response = authenticated(alice).method("POST").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn answers_to_a_missing_question_are_not_found() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let account_id = an_account().insert(&store).await.id.unwrap();
+
+    let result = store
+        .add_answer(account_id, QuestionId(i32::MAX), "An answer".to_string(), true)
+        .await;
+    assert!(matches!(result, Err(ServiceError::QuestionNotFound(_))));
+
+    let response = authenticated(account_id)
+        .method("POST")
+        .path("/questions/2147483647/answers")
+        .json(&serde_json::json!({ "content": "An answer" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn updating_an_answer_deleted_since_it_was_checked_is_not_found() {
+    let store = it::store().await;
+    let account_id = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(account_id).insert(&store).await.id.unwrap();
+    let answer = an_answer().to(question_id).owned_by(account_id).insert(&store).await;
+    let answer_id = answer.id.unwrap();
+
+    // The owner was checked by the handler before the answer was deleted
+    assert!(store.is_answer_owner(answer_id, account_id).await.unwrap());
+    assert!(store.delete_answer(account_id, answer_id).await.unwrap());
+    let result = store
+        .update_answer(account_id, answer_id, "An update".to_string(), true)
+        .await;
+    assert!(matches!(result, Err(ServiceError::AnswerNotFound(_))));
+}
+
+#[tokio::test]
+async fn the_account_lists_its_own_answers() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().insert(&store).await;
+    let question_id = question.id.unwrap();
+    let first = an_answer()
+        .to(question_id)
+        .owned_by(alice)
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    let second = an_answer()
+        .to(question_id)
+        .owned_by(alice)
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    an_answer().to(question_id).owned_by(bob).insert(&store).await;
+
+    let response = warp::test::request().path("/accounts/me/answers").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = authenticated(alice).path("/accounts/me/answers").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let ids: Vec<_> = answers.iter().map(|answer| answer["id"].as_i64().unwrap()).collect();
+    assert_eq!(ids, [i64::from(second.0), i64::from(first.0)]);
+    assert_eq!(answers[0]["question_id"], question_id.0);
+    assert_eq!(answers[0]["question_title"], question.title);
+
+    let response = authenticated(alice)
+        .path("/accounts/me/answers?offset=1&limit=1")
+        .reply(&routes)
+        .await;
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(answers.len(), 1);
+    assert_eq!(answers[0]["id"], first.0);
+}
+
+#[tokio::test]
+async fn the_voted_answers_are_scored_and_listed_by_score() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().insert(&store).await.id.unwrap();
+    let first = an_answer()
+        .to(question_id)
+        .owned_by(alice)
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    let second = an_answer().to(question_id).insert(&store).await.id.unwrap();
+    let vote = |account, answer_id: AnswerId, direction: &str| {
+        authenticated(account)
+            .method("POST")
+            .path(&format!("/answers/{}/vote", answer_id.0))
+            .json(&json!({ "direction": direction }))
+    };
+
+    let response = vote(alice, first, "up").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    for (account, answer_id, direction, score) in [
+        (alice, second, "up", 1),
+        (bob, second, "up", 2),
+        (bob, first, "down", -1),
+    ] {
+        let response = vote(account, answer_id, direction).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body, json!({ "score": score, "vote": direction }));
+    }
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{}/answers", question_id.0))
+        .reply(&routes)
+        .await;
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let scores: Vec<_> = answers
+        .iter()
+        .map(|answer| (answer["id"].as_i64().unwrap() as i32, answer["score"].as_i64().unwrap()))
+        .collect();
+    assert_eq!(scores, [(second.0, 2), (first.0, -1)]);
+
+    let response = warp::test::request()
+        .path(&format!("/answers/{}", first.0))
+        .reply(&routes)
+        .await;
+    let answer: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(answer["score"], -1);
+
+    let response = vote(bob, first, "down").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body["code"], "already_voted");
+
+    let response = authenticated(bob)
+        .method("DELETE")
+        .path(&format!("/answers/{}/vote", first.0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body, json!({ "score": 0, "vote": null }));
+
+    let response = vote(bob, AnswerId(i32::MAX), "up").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
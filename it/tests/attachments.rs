@@ -0,0 +1,74 @@
+//! Tests of the files attached to the questions, uploaded to and downloaded from the service.
+use serde_json::Value;
+use warp::http::StatusCode;
+use webdev_book::attachments::MAX_ATTACHMENT_SIZE;
+use webdev_book::test_support::{a_question, an_account, authenticated, test_router};
+
+#[tokio::test]
+async fn the_attached_files_are_downloaded_as_uploaded() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let path = format!("/questions/{}/attachments?name=trace.bin", question.id.unwrap().0);
+    let content: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&path)
+        .header("content-type", "application/x-trace")
+        .body(content.clone())
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let attachment: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(attachment["size"], content.len());
+
+    let response = warp::test::request()
+        .path(&format!("/attachments/{}", attachment["id"]))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-trace");
+    assert_eq!(response.headers()["content-length"], content.len().to_string().as_str());
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"trace.bin\""
+    );
+    assert_eq!(response.body().as_ref(), content.as_slice());
+}
+
+#[tokio::test]
+async fn only_the_author_attaches_files_of_the_allowed_size() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let path = format!("/questions/{}/attachments?name=notes.txt", question.id.unwrap().0);
+
+    let response = authenticated(bob)
+        .method("POST")
+        .path(&path)
+        .body("Notes")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // The announced length is rejected before the body is read
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&path)
+        .header("content-length", MAX_ATTACHMENT_SIZE + 1)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&format!("/questions/{}/attachments", question.id.unwrap().0))
+        .body("Notes")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
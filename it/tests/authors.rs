@@ -0,0 +1,106 @@
+//! Tests of the authors of the questions and the answers, which are loaded in a batch per page.
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::test_support::{a_question, an_account, an_answer, test_router};
+use webdev_book::types::authentication::AccountId;
+
+#[tokio::test]
+async fn every_listed_item_gets_the_author_of_its_own() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().with_display_name("Alice").insert(&store).await.id.unwrap();
+    let bob = an_account().with_display_name("Bob").insert(&store).await.id.unwrap();
+    let question_id = a_question()
+        .with_title("Asked by alice")
+        .owned_by(alice)
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    a_question()
+        .with_title("Asked by bob")
+        .owned_by(bob)
+        .insert(&store)
+        .await;
+    for (author, content) in [
+        (bob, "Answered by bob"),
+        (alice, "Answered by alice"),
+        (bob, "Answered by bob again"),
+    ] {
+        an_answer()
+            .with_content(content)
+            .to(question_id)
+            .owned_by(author)
+            .insert(&store)
+            .await;
+    }
+
+    let response = warp::test::request().path("/questions").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let page: Value = serde_json::from_slice(response.body()).unwrap();
+    let mut questions: Vec<_> = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|question| (question["title"].clone(), question["author"].clone()))
+        .collect();
+    questions.sort_by(|(a, _), (b, _)| a.as_str().cmp(&b.as_str()));
+    assert_eq!(
+        questions,
+        [
+            (
+                Value::from("Asked by alice"),
+                json!({ "id": alice.0, "display_name": "Alice" })
+            ),
+            (
+                Value::from("Asked by bob"),
+                json!({ "id": bob.0, "display_name": "Bob" })
+            ),
+        ]
+    );
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{}/answers", question_id.0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let answers: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let mut answers: Vec<_> = answers
+        .iter()
+        .map(|answer| (answer["content"].clone(), answer["author"].clone()))
+        .collect();
+    answers.sort_by(|(a, _), (b, _)| a.as_str().cmp(&b.as_str()));
+    assert_eq!(
+        answers,
+        [
+            (
+                Value::from("Answered by alice"),
+                json!({ "id": alice.0, "display_name": "Alice" })
+            ),
+            (
+                Value::from("Answered by bob"),
+                json!({ "id": bob.0, "display_name": "Bob" })
+            ),
+            (
+                Value::from("Answered by bob again"),
+                json!({ "id": bob.0, "display_name": "Bob" })
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn the_authors_are_loaded_once_per_account_and_the_missing_ones_skipped() {
+    let store = it::store().await;
+    let alice = an_account().with_display_name("Alice").insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+
+    let authors = store
+        .get_authors([alice, bob, alice, AccountId(bob.0 + 1)])
+        .await
+        .unwrap();
+    assert_eq!(authors.len(), 2);
+    assert_eq!(authors[&alice].display_name.as_deref(), Some("Alice"));
+    assert_eq!(authors[&bob].display_name, None);
+    assert!(store.get_authors([]).await.unwrap().is_empty());
+}
@@ -0,0 +1,43 @@
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::test_support::{a_question, an_account, an_answer, test_router};
+use webdev_book::types::authentication::AccountId;
+use webdev_book::types::badge::Badge;
+
+#[tokio::test]
+async fn badges_are_awarded_once_for_the_contributions() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let carol = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    for _ in 0..Badge::TEN_ANSWERS {
+        an_answer().to(question.id.unwrap()).owned_by(bob).insert(&store).await;
+    }
+
+    // The job evaluating the badges, which is queued periodically
+    assert_eq!(store.award_badges().await.unwrap(), 3);
+    assert_eq!(store.award_badges().await.unwrap(), 0);
+
+    let badges = |account_id: AccountId| {
+        let routes = routes.clone();
+        async move {
+            let response = warp::test::request()
+                .path(&format!("/accounts/{}/badges", account_id.0))
+                .reply(&routes)
+                .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let badges: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+            badges
+                .into_iter()
+                .map(|badge| badge["badge"].clone())
+                .collect::<Vec<_>>()
+        }
+    };
+    let mut for_alice = badges(alice).await;
+    for_alice.sort_by_key(|badge| badge.to_string());
+    assert_eq!(for_alice, [json!("first_question"), json!("popular_question")]);
+    assert_eq!(badges(bob).await, [json!("ten_answers")]);
+    assert!(badges(carol).await.is_empty());
+}
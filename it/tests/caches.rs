@@ -0,0 +1,97 @@
+//! Tests of the in-process caches of the store, which serve the reads until a write through the
+//! store invalidates them.
+//!
+//! The database is changed behind the store to tell the cached responses from the fresh ones.
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::store::Store;
+use webdev_book::test_support::{a_question, an_account, authenticated, test_router};
+use webdev_book::types::question::QuestionId;
+
+/// Changes the title of the question in the database, without going through the store.
+async fn change_title_behind_the_store(store: &Store, question_id: QuestionId, title: &str) {
+    sqlx::query("UPDATE questions SET title = $1 WHERE id = $2")
+        .bind(title)
+        .bind(question_id.0)
+        .execute(&store.connection)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn the_single_questions_are_served_from_the_cache_until_updated() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question()
+        .with_title("Cached title")
+        .owned_by(alice)
+        .insert(&store)
+        .await;
+    let question_id = question.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+    let title = || {
+        let (routes, path) = (routes.clone(), path.clone());
+        async move {
+            let response = warp::test::request().path(&path).reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            serde_json::from_slice::<Value>(response.body()).unwrap()["title"].clone()
+        }
+    };
+
+    assert_eq!(title().await, "Cached title");
+    change_title_behind_the_store(&store, question_id, "Changed behind the store").await;
+    assert_eq!(title().await, "Cached title");
+
+    let response = authenticated(alice)
+        .method("PUT")
+        .path(&path)
+        .json(&json!({ "title": "Updated title", "content": question.content }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(title().await, "Updated title");
+}
+
+#[tokio::test]
+async fn the_listings_are_served_from_the_cache_until_a_question_is_written() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question()
+        .with_title("Cached title")
+        .owned_by(alice)
+        .insert(&store)
+        .await;
+    let listing = || {
+        let routes = routes.clone();
+        async move {
+            let response = warp::test::request().path("/questions").reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let page: Value = serde_json::from_slice(response.body()).unwrap();
+            let titles: Vec<_> = page["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|question| question["title"].as_str().unwrap().to_string())
+                .collect();
+            (page["total"].as_i64().unwrap(), titles)
+        }
+    };
+
+    assert_eq!(listing().await, (1, vec!["Cached title".to_string()]));
+    change_title_behind_the_store(&store, question.id.unwrap(), "Changed behind the store").await;
+    assert_eq!(listing().await, (1, vec!["Cached title".to_string()]));
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/questions")
+        .json(&json!({ "title": "How do I cache a listing?", "content": "With moka." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let (total, titles) = listing().await;
+    assert_eq!(total, 2);
+    assert!(titles.contains(&"Changed behind the store".to_string()), "{titles:?}");
+    assert!(titles.contains(&"How do I cache a listing?".to_string()), "{titles:?}");
+}
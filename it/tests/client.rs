@@ -0,0 +1,70 @@
+//! Tests of the typed client of the API, against the service served on a local port.
+use webdev_book::test_support::test_router;
+use webdev_book::types::pagination::Pagination;
+use webdev_book::types::question::{NewQuestion, UpdateQuestion};
+use webdev_client::{Client, ClientError};
+
+/// Serves the API of the store on a local port, and returns a client for it.
+fn serve(store: &webdev_book::store::Store) -> Client {
+    let (addr, server) = warp::serve(test_router(store)).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    Client::new(&format!("http://{addr}")).unwrap()
+}
+
+#[tokio::test]
+async fn the_logged_in_client_asks_edits_and_answers_the_questions() {
+    let store = it::store().await;
+    let client = serve(&store);
+    let question = NewQuestion {
+        title: "How do I use the typed client?".to_string(),
+        content: "It shares the types with the service.".to_string(),
+        tags: Some(vec!["client".to_string()]),
+    };
+
+    let error = client.add_question(&question, false).await.unwrap_err();
+    assert!(matches!(error, ClientError::BadRequest(_)), "{error}");
+
+    client.register("alice@example.com", "correct horse").await.unwrap();
+    let token = client.login("alice@example.com", "correct horse").await.unwrap();
+    assert_eq!(client.token(), Some(token));
+
+    let question_id = client.add_question(&question, false).await.unwrap().id.unwrap();
+    let update = UpdateQuestion {
+        title: "How do I use the typed Rust client?".to_string(),
+        content: question.content.clone(),
+        tags: question.tags.clone(),
+    };
+    client.update_question(question_id, &update).await.unwrap();
+    let answer = client.add_answer(question_id, "Log in first.").await.unwrap();
+    assert_eq!(answer.question_id, Some(question_id));
+
+    let detail = client.get_question_with_answers(question_id).await.unwrap();
+    assert_eq!(detail.question.title, "How do I use the typed Rust client?");
+    assert_eq!(detail.answers.unwrap().len(), 1);
+    let page = client
+        .get_questions(Pagination {
+            offset: 0,
+            limit: Some(10),
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.total, 1);
+
+    client.delete_question(question_id).await.unwrap();
+    let error = client.get_question(question_id).await.unwrap_err();
+    assert!(matches!(error, ClientError::NotFound(_)), "{error}");
+}
+
+#[tokio::test]
+async fn the_rejected_credentials_are_not_stored() {
+    let store = it::store().await;
+    let client = serve(&store);
+    client.register("alice@example.com", "correct horse").await.unwrap();
+
+    let error = client.login("alice@example.com", "wrong horse").await.unwrap_err();
+    assert!(matches!(error, ClientError::Unauthorized(_)), "{error}");
+    assert_eq!(client.token(), None);
+
+    let error = client.register("alice@example.com", "correct horse").await.unwrap_err();
+    assert!(matches!(error, ClientError::Conflict(_)), "{error}");
+}
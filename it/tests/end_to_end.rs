@@ -0,0 +1,104 @@
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+
+#[tokio::test]
+async fn register_login_ask_answer_and_delete() {
+    let store = it::store().await;
+    let routes = webdev_book::test_support::test_router(&store);
+    let account = json!({ "email": "alice@example.com", "password": "correct horse battery staple" });
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/register")
+        .json(&account)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let created: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(created["email"], "alice@example.com");
+    assert!(created.get("password").is_none());
+    let location = format!("/accounts/{}", created["id"]);
+    assert_eq!(response.headers()["location"], location.as_str());
+    let alice_id = created["id"].clone();
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&json!({ "email": "alice@example.com", "password": "wrong password" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&account)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token: String = serde_json::from_slice(response.body()).unwrap();
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/questions")
+        .json(&json!({ "title": "How do I test warp?", "content": "With warp::test.", "tags": ["warp"] }))
+        .reply(&routes)
+        .await;
+    assert_eq!(
+        response.status(),
+        StatusCode::BAD_REQUEST,
+        "questions can't be asked without a token"
+    );
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/questions")
+        .header("Authorization", &token)
+        .json(&json!({ "title": "How do I test warp?", "content": "With warp::test.", "tags": ["warp"] }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    let question_id = question["id"].as_i64().unwrap();
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&format!("/questions/{question_id}/answers"))
+        .header("Authorization", &token)
+        .json(&json!({ "content": "Use warp::test::request." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let answer: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(answer["content"], "Use warp::test::request.");
+    assert_eq!(answer["question_id"], question_id);
+    assert_eq!(answer["author"], json!({ "id": alice_id, "display_name": null }));
+    let location = format!("/answers/{}", answer["id"]);
+    assert_eq!(response.headers()["location"], location.as_str());
+
+    let response = warp::test::request().path(&location).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{question_id}"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["title"], "How do I test warp?");
+    assert_eq!(question["author"], json!({ "id": alice_id, "display_name": null }));
+
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&format!("/questions/{question_id}"))
+        .header("Authorization", &token)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{question_id}"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,64 @@
+//! Tests of the fallback of the frontend to `index.html`, which must leave the API responses alone.
+use warp::http::StatusCode;
+
+/// Content of the `index.html` of the frontend.
+const INDEX: &str = "<!doctype html><title>webdev book</title>";
+
+/// Returns a frontend directory, with only its `index.html`.
+fn frontend_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("webdev_book_frontend_{}", uuid::Uuid::new_v4().simple()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), INDEX).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn the_api_requests_do_not_fall_back_to_the_frontend() {
+    let store = it::store().await;
+    let routes = webdev_book::routes(&store, Some(frontend_dir()), std::env::temp_dir());
+
+    for (path, status) in [
+        ("/questions/abc", StatusCode::BAD_REQUEST),
+        ("/refresh", StatusCode::METHOD_NOT_ALLOWED),
+        ("/accounts/me", StatusCode::BAD_REQUEST),
+    ] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_eq!(response.status(), status, "{path}");
+    }
+
+    // Whether they are rejected or served, the paths of the API are answered by the API
+    for path in [
+        "/accounts/me",
+        "/accounts/me/notifications",
+        "/admin/moderation-log",
+        "/tags",
+        "/tags/popular",
+        "/logout",
+        "/oauth/github",
+        "/refresh",
+    ] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_ne!(
+            response
+                .headers()
+                .get("content-type")
+                .map(|value| value.to_str().unwrap()),
+            Some("text/html"),
+            "{path}"
+        );
+        assert_ne!(response.body(), INDEX, "{path}");
+    }
+
+    // The paths no route matches are the frontend's, whatever their first segment
+    for (method, path) in [
+        ("GET", "/profile/settings"),
+        ("HEAD", "/profile/settings"),
+        ("GET", "/questions/asked/today"),
+    ] {
+        let response = warp::test::request().method(method).path(path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK, "{method} {path}");
+        assert_eq!(response.headers()["content-type"], "text/html", "{method} {path}");
+    }
+    let response = warp::test::request().path("/profile/settings").reply(&routes).await;
+    assert_eq!(response.body(), INDEX);
+}
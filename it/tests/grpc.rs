@@ -0,0 +1,100 @@
+//! Tests of the gRPC service, served on a local port, through the clients generated from its
+//! service definitions.
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::time::Duration;
+
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+use webdev_book::grpc::proto::accounts_client::AccountsClient;
+use webdev_book::grpc::proto::questions_client::QuestionsClient;
+use webdev_book::grpc::proto::{Credentials, ListQuestionsRequest, NewQuestion, QuestionId};
+use webdev_book::store::Store;
+
+/// Serves the gRPC service of the store on a free local port, and returns a channel to it.
+async fn serve(store: Store) -> Channel {
+    let addr: SocketAddr = {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener.local_addr().unwrap()
+    };
+    tokio::spawn(webdev_book::grpc::serve(store, addr));
+    let endpoint = Channel::from_shared(format!("http://{addr}")).unwrap();
+    for _ in 0..50 {
+        if let Ok(channel) = endpoint.connect().await {
+            return channel;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("the gRPC service did not start on {addr}");
+}
+
+/// Returns the request, authenticated with the token.
+fn authenticated<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("authorization", token.parse().unwrap());
+    request
+}
+
+#[tokio::test]
+async fn the_questions_are_asked_and_read_by_the_logged_in_accounts() {
+    let store = it::store().await;
+    let channel = serve(store).await;
+    let mut accounts = AccountsClient::new(channel.clone());
+    let mut questions = QuestionsClient::new(channel);
+    let credentials = Credentials {
+        email: "alice@example.com".to_string(),
+        password: "correct horse battery staple".to_string(),
+    };
+    let question = NewQuestion {
+        title: "How do I call a gRPC service?".to_string(),
+        content: "With the generated client.".to_string(),
+        tags: vec!["grpc".to_string()],
+    };
+
+    accounts.register(credentials.clone()).await.unwrap();
+    let token = accounts.login(credentials).await.unwrap().into_inner().token;
+
+    let error = questions.add_question(question.clone()).await.unwrap_err();
+    assert_eq!(error.code(), Code::Unauthenticated);
+    let error = questions
+        .add_question(authenticated(question.clone(), "not a token"))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), Code::Unauthenticated);
+
+    let asked = questions
+        .add_question(authenticated(question, &token))
+        .await
+        .unwrap()
+        .into_inner();
+    let read = questions
+        .get_question(QuestionId { id: asked.id })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(read.title, "How do I call a gRPC service?");
+    assert_eq!(read.tags, ["grpc"]);
+
+    let listed = questions
+        .list_questions(ListQuestionsRequest {
+            offset: 0,
+            limit: Some(10),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.questions.len(), 1);
+
+    let error = questions
+        .get_question(QuestionId { id: asked.id + 1 })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), Code::NotFound);
+    let error = questions
+        .list_questions(ListQuestionsRequest {
+            offset: -1,
+            limit: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code(), Code::InvalidArgument);
+}
@@ -0,0 +1,225 @@
+//! Tests of the queue of background jobs, run by the worker and listed to the administrators at /jobs.
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::api::mock::MockProfanityFilter;
+use webdev_book::jobs::{self, Task};
+use webdev_book::store::Store;
+use webdev_book::test_support::{a_question, an_account, authenticated, test_router};
+use webdev_book::types::job::{Job, JobStatus};
+use webdev_book::types::pagination::Pagination;
+
+/// Returns the jobs of the queue with the status.
+async fn jobs_with_status(store: &Store, status: JobStatus) -> Vec<Job> {
+    store
+        .get_jobs(Some(status), Pagination { offset: 0, limit: None })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn the_failed_jobs_are_retried_until_they_run_out_of_attempts() {
+    let store = it::store().await;
+    let first = store.enqueue_job("first", json!({})).await.unwrap();
+    let second = store.enqueue_job("second", json!({})).await.unwrap();
+
+    let claimed = store.claim_job().await.unwrap().unwrap();
+    assert_eq!(
+        (claimed.id, claimed.status, claimed.attempts),
+        (first.id, JobStatus::Running, 1)
+    );
+    let claimed = store.claim_job().await.unwrap().unwrap();
+    assert_eq!(claimed.id, second.id);
+    assert!(store.claim_job().await.unwrap().is_none());
+    store.complete_job(second.id).await.unwrap();
+
+    for attempt in 1..first.max_attempts {
+        let status = store.fail_job(first.id, "broken", Duration::ZERO).await.unwrap();
+        assert_eq!(status, JobStatus::Pending);
+        let claimed = store.claim_job().await.unwrap().unwrap();
+        assert_eq!((claimed.id, claimed.attempts), (first.id, attempt + 1));
+        assert_eq!(claimed.last_error.as_deref(), Some("broken"));
+    }
+    let status = store.fail_job(first.id, "still broken", Duration::ZERO).await.unwrap();
+    assert_eq!(status, JobStatus::Failed);
+    assert!(store.claim_job().await.unwrap().is_none());
+
+    // The retries wait for their delay
+    let third = store.enqueue_job("third", json!({})).await.unwrap();
+    store.claim_job().await.unwrap().unwrap();
+    store
+        .fail_job(third.id, "broken", Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(store.claim_job().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn the_queued_tasks_are_run_by_the_worker() {
+    let store = it::store().await;
+    let job = Task::AwardBadges.enqueue(&store).await.unwrap();
+    assert_eq!(job.kind, "award_badges");
+    jobs::spawn_worker(store.clone());
+
+    let done = async {
+        loop {
+            let jobs = jobs_with_status(&store, JobStatus::Done).await;
+            if jobs.iter().any(|done| done.id == job.id) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), done)
+        .await
+        .expect("the job was not run");
+}
+
+#[tokio::test]
+async fn the_jobs_are_listed_by_status_to_the_administrators() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let done = store.enqueue_job("done", json!({ "n": 1 })).await.unwrap();
+    store.enqueue_job("pending", json!({ "n": 2 })).await.unwrap();
+    store.claim_job().await.unwrap().unwrap();
+    store.complete_job(done.id).await.unwrap();
+
+    let response = warp::test::request().path("/jobs").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let jobs = |query: &'static str| {
+        warp::test::request()
+            .path(&format!("/jobs{query}"))
+            .header("X-Admin-Token", it::ADMIN_TOKEN)
+            .reply(&routes)
+    };
+    let response = jobs("").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(serde_json::from_slice::<Vec<Value>>(response.body()).unwrap().len(), 2);
+
+    let response = jobs("?status=done").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["kind"], "done");
+    assert_eq!(listed[0]["payload"], json!({ "n": 1 }));
+
+    let response = jobs("?status=stuck").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn the_jobs_caused_by_a_new_answer_are_queued_with_it() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/webhooks")
+        .json(&json!({ "url": "https://example.com/hook", "events": ["answer_created"], "secret": "shh" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // No worker listens on the events, the jobs are added by the write itself
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&format!("/questions/{}/answers", question_id.0))
+        .json(&json!({ "content": "Queued with the answer." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let answer: Value = serde_json::from_slice(response.body()).unwrap();
+
+    let mut queued = jobs_with_status(&store, JobStatus::Pending).await;
+    queued.sort_by(|a, b| a.kind.cmp(&b.kind));
+    let [delivery, notification] = <[_; 2]>::try_from(queued).unwrap();
+    assert_eq!(delivery.kind, "deliver_webhook");
+    assert_eq!(delivery.payload["event"], "answer_created");
+    assert_eq!(notification.kind, "notify_answer");
+    assert_eq!(notification.payload["answer_id"], answer["id"]);
+}
+
+#[tokio::test]
+async fn the_content_is_censored_by_a_job_while_the_filter_is_unavailable() {
+    let filter = MockProfanityFilter::new()
+        .with_response("Why is it darn slow?", "Why is it **** slow?")
+        .with_response("It is darn slow.", "It is **** slow.");
+    let store = it::store().await.with_profanity_filter(filter.clone());
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    filter.fail_with(StatusCode::SERVICE_UNAVAILABLE);
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/questions")
+        .json(&json!({ "title": "Why is it darn slow?", "content": "It is darn slow." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["title"], "Why is it darn slow?");
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&format!("/questions/{}/answers", question["id"]))
+        .json(&json!({ "content": "It is darn slow." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let answer: Value = serde_json::from_slice(response.body()).unwrap();
+
+    let queued: Vec<String> = jobs_with_status(&store, JobStatus::Pending)
+        .await
+        .into_iter()
+        .map(|job| job.kind)
+        .collect();
+    assert!(queued.contains(&"censor_question".to_string()), "{queued:?}");
+    assert!(queued.contains(&"censor_answer".to_string()), "{queued:?}");
+
+    filter.recover();
+    jobs::spawn_worker(store.clone());
+    let censored = async {
+        loop {
+            let question = warp::test::request()
+                .path(&format!("/questions/{}", question["id"]))
+                .reply(&routes)
+                .await;
+            let question: Value = serde_json::from_slice(question.body()).unwrap();
+            let answer = warp::test::request()
+                .path(&format!("/answers/{}", answer["id"]))
+                .reply(&routes)
+                .await;
+            let answer: Value = serde_json::from_slice(answer.body()).unwrap();
+            if question["title"] == "Why is it **** slow?"
+                && question["content"] == "It is **** slow."
+                && answer["content"] == "It is **** slow."
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), censored)
+        .await
+        .expect("the content was not censored");
+}
+
+#[tokio::test]
+async fn the_content_rejected_by_the_filter_is_not_stored() {
+    let filter = MockProfanityFilter::new();
+    let store = it::store().await.with_profanity_filter(filter.clone());
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    filter.fail_with(StatusCode::BAD_REQUEST);
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/questions")
+        .json(&json!({ "title": "Is this stored?", "content": "It is rejected." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(jobs_with_status(&store, JobStatus::Pending).await.is_empty());
+}
@@ -0,0 +1,77 @@
+//! Tests of the live updates, sent over the WebSocket at /ws and the Server-Sent Events of the
+//! questions at /questions/{id}/events.
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use warp::Reply;
+use webdev_book::test_support::{a_question, an_account, authenticated, test_router};
+
+#[tokio::test]
+async fn the_websocket_sends_the_events_of_the_subscribed_tags() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let ask = |title: &str, tag: &str| {
+        authenticated(alice)
+            .method("POST")
+            .path("/questions")
+            .json(&json!({ "title": title, "content": "Content", "tags": [tag] }))
+            .reply(&routes)
+    };
+    let mut client = warp::test::ws().path("/ws").handshake(routes.clone()).await.unwrap();
+
+    client
+        .send_text(json!({ "type": "subscribe", "tag": "rust" }).to_string())
+        .await;
+    assert_eq!(
+        ask("Which channel should a goroutine close?", "go").await.status(),
+        StatusCode::CREATED
+    );
+    assert_eq!(
+        ask("Why is my future never polled?", "rust").await.status(),
+        StatusCode::CREATED
+    );
+
+    let message = client.recv().await.unwrap();
+    let event: Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+    assert_eq!(event["type"], "question_created");
+    assert_eq!(event["question"]["title"], "Why is my future never polled?");
+}
+
+#[tokio::test]
+async fn the_events_of_a_question_are_streamed_until_it_is_deleted() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let path = format!("/questions/{}", question.id.unwrap().0);
+
+    let events = warp::test::request()
+        .path(&format!("{path}/events"))
+        .filter(&routes)
+        .await
+        .unwrap()
+        .into_response();
+    assert_eq!(events.status(), StatusCode::OK);
+    assert_eq!(events.headers()["content-type"], "text/event-stream");
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&format!("{path}/answers"))
+        .json(&json!({ "content": "Subscribe to the events." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = authenticated(alice).method("DELETE").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = warp::hyper::body::to_bytes(events.into_body()).await.unwrap();
+    let names: Vec<_> = std::str::from_utf8(&body)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("event:"))
+        .collect();
+    assert_eq!(names, ["answer_created", "question_deleted"]);
+
+    let response = warp::test::request().path("/questions/0/events").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,291 @@
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::clock::TestClock;
+use webdev_book::store::Store;
+use webdev_book::test_support::{
+    a_question, an_account, authenticated, test_router, token_valid_between, DEFAULT_PASSWORD,
+};
+use webdev_book::types::moderation::AccountRole;
+
+#[tokio::test]
+async fn banned_accounts_are_rejected_until_the_ban_ends() {
+    let store = it::store().await;
+    let start = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    let clock = TestClock::new(start);
+    let store = store.with_clock(clock.clone());
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let token = token_valid_between(alice, start, start + Duration::try_days(7).unwrap());
+    let path = format!("/admin/accounts/{}/ban", alice.0);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&path)
+        .json(&json!({ "duration_hours": 24, "reason": "spam" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&path)
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .json(&json!({ "duration_hours": 24, "reason": "spam" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let ban: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(ban["account_id"], alice.0);
+    assert_eq!(ban["reason"], "spam");
+
+    for (elapsed, status) in [
+        (Duration::try_hours(23).unwrap(), StatusCode::FORBIDDEN),
+        (Duration::try_hours(2).unwrap(), StatusCode::OK),
+    ] {
+        clock.advance(elapsed);
+        let question = a_question().owned_by(alice).insert(&store).await;
+        let response = warp::test::request()
+            .method("DELETE")
+            .path(&format!("/questions/{}", question.id.unwrap().0))
+            .header("Authorization", &token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status);
+    }
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/admin/accounts/2147483647/ban")
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .json(&json!({ "reason": "spam" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn moderators_change_the_questions_of_the_other_accounts_and_are_logged() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let mallory = an_account().insert(&store).await.id.unwrap();
+    let moderator_account = an_account().insert(&store).await;
+    let moderator = moderator_account.id.unwrap();
+    let login = || {
+        let (routes, email) = (routes.clone(), moderator_account.email.clone());
+        async move {
+            let response = warp::test::request()
+                .method("POST")
+                .path("/login")
+                .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+                .reply(&routes)
+                .await;
+            serde_json::from_slice::<String>(response.body()).unwrap()
+        }
+    };
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+    let update = json!({ "title": "Moderated title", "content": "Moderated content" });
+
+    let response = authenticated(mallory)
+        .method("PUT")
+        .path(&path)
+        .json(&update)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // The tokens carry the role, so the sessions started before the role changed are signed out
+    let user_token = login().await;
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&format!("/admin/accounts/{}/role", moderator.0))
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .json(&json!({ "role": "moderator" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&path)
+        .header("Authorization", &user_token)
+        .json(&update)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.body(), "auth token was revoked");
+
+    let token = login().await;
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&path)
+        .header("Authorization", &token)
+        .json(&update)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&path)
+        .header("Authorization", &token)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let own_question = a_question().owned_by(moderator).insert(&store).await.id.unwrap();
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&format!("/questions/{}", own_question.0))
+        .header("Authorization", &token)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = warp::test::request()
+        .path("/admin/moderation-log")
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let entries: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let actions: Vec<_> = entries.iter().map(|entry| entry["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, ["delete_question", "update_question"]);
+    for entry in entries {
+        assert_eq!(entry["moderator_id"], moderator.0);
+        assert_eq!(entry["owner_id"], alice.0);
+        assert_eq!(entry["question_id"], question_id.0);
+    }
+}
+
+#[tokio::test]
+async fn the_changes_of_the_moderators_are_not_made_when_they_cannot_be_logged() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let moderator_account = an_account().insert(&store).await;
+    store
+        .set_account_role(moderator_account.id.unwrap(), AccountRole::Moderator)
+        .await
+        .unwrap();
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&json!({ "email": moderator_account.email, "password": DEFAULT_PASSWORD }))
+        .reply(&routes)
+        .await;
+    let token: String = serde_json::from_slice(response.body()).unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let path = format!("/questions/{}", question.id.unwrap().0);
+
+    // The entries of the moderation log cannot be written from now on
+    sqlx::query("ALTER TABLE moderation_log RENAME TO moderation_log_unavailable")
+        .execute(&store.connection)
+        .await
+        .unwrap();
+
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&path)
+        .header("Authorization", &token)
+        .json(&json!({ "title": "Moderated title", "content": "Moderated content" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&path)
+        .header("Authorization", &token)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let stored: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(stored["title"], question.title);
+    assert_eq!(stored["content"], question.content);
+}
+
+#[tokio::test]
+async fn accounts_are_locked_after_consecutive_failed_logins_until_unlocked() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let login = |password: &'static str| {
+        let (routes, email) = (routes.clone(), alice.email.clone());
+        // The failures are throttled in memory as well, which is not under test here
+        store.failed_logins.invalidate_all();
+        async move {
+            warp::test::request()
+                .method("POST")
+                .path("/login")
+                .json(&json!({ "email": email, "password": password }))
+                .reply(&routes)
+                .await
+        }
+    };
+
+    for _ in 1..Store::LOCKOUT_FAILED_LOGINS {
+        assert_eq!(login("wrong password").await.status(), StatusCode::UNAUTHORIZED);
+    }
+    assert_eq!(login(DEFAULT_PASSWORD).await.status(), StatusCode::OK);
+    for _ in 1..Store::LOCKOUT_FAILED_LOGINS {
+        assert_eq!(login("wrong password").await.status(), StatusCode::UNAUTHORIZED);
+    }
+    assert_eq!(login("wrong password").await.status(), StatusCode::LOCKED);
+    let response = login(DEFAULT_PASSWORD).await;
+    assert_eq!(response.status(), StatusCode::LOCKED);
+    assert!(String::from_utf8_lossy(response.body()).starts_with("account is locked"));
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&format!("/admin/accounts/{}/unlock", alice.id.unwrap().0))
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(login(DEFAULT_PASSWORD).await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn writes_are_rejected_in_maintenance_mode_while_the_reads_are_served() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let path = format!("/questions/{}", question.id.unwrap().0);
+    let set_maintenance = |enabled: bool| {
+        warp::test::request()
+            .method("PUT")
+            .path("/admin/maintenance")
+            .header("X-Admin-Token", it::ADMIN_TOKEN)
+            .json(&json!({ "enabled": enabled }))
+            .reply(&routes)
+    };
+
+    let response = set_maintenance(true).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        serde_json::from_slice::<Value>(response.body()).unwrap(),
+        json!({ "enabled": true })
+    );
+
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = authenticated(alice).method("DELETE").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let response = warp::test::request()
+        .path("/admin/maintenance")
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .reply(&routes)
+        .await;
+    assert_eq!(
+        serde_json::from_slice::<Value>(response.body()).unwrap(),
+        json!({ "enabled": true })
+    );
+
+    assert_eq!(set_maintenance(false).await.status(), StatusCode::OK);
+    let response = authenticated(alice).method("DELETE").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
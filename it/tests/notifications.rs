@@ -0,0 +1,53 @@
+use serde_json::Value;
+use warp::http::StatusCode;
+use webdev_book::test_support::{a_question, an_account, an_answer, authenticated, test_router};
+
+#[tokio::test]
+async fn the_owner_and_the_watchers_are_notified_about_new_answers() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let [alice, bob, carol] = [
+        an_account().insert(&store).await.id.unwrap(),
+        an_account().insert(&store).await.id.unwrap(),
+        an_account().insert(&store).await.id.unwrap(),
+    ];
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let question_id = question.id.unwrap();
+    an_answer().to(question_id).owned_by(bob).insert(&store).await;
+    let answer = an_answer().to(question_id).owned_by(carol).insert(&store).await;
+    let answer_id = answer.id.unwrap();
+
+    // The job notifying about the answer, which is retried if it fails
+    assert_eq!(store.add_answer_notifications(answer_id).await.unwrap(), 2);
+    assert_eq!(store.add_answer_notifications(answer_id).await.unwrap(), 0);
+
+    let mut notified = Vec::new();
+    for account_id in [alice, bob, carol] {
+        let response = authenticated(account_id)
+            .path("/accounts/me/notifications")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        notified.push(serde_json::from_slice::<Vec<Value>>(response.body()).unwrap());
+    }
+    let [for_alice, for_bob, for_carol] = <[_; 3]>::try_from(notified).unwrap();
+    assert_eq!(for_alice.len(), 1);
+    assert_eq!(for_alice[0]["answer_id"], answer_id.0);
+    assert_eq!(for_alice[0]["question_id"], question_id.0);
+    assert_eq!(for_alice[0]["read"], false);
+    assert_eq!(for_bob.len(), 1);
+    assert!(for_carol.is_empty());
+
+    let path = format!("/notifications/{}/read", for_alice[0]["id"]);
+    let response = authenticated(bob).method("POST").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = authenticated(alice).method("POST").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = authenticated(alice)
+        .path("/accounts/me/notifications")
+        .reply(&routes)
+        .await;
+    let for_alice: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(for_alice[0]["read"], true);
+}
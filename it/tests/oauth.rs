@@ -0,0 +1,66 @@
+use warp::http::header::{LOCATION, SET_COOKIE};
+use warp::http::StatusCode;
+use webdev_book::test_support::test_router;
+use webdev_book::types::authentication::{OAuthClient, OAuthConfig, OAuthProvider};
+
+#[tokio::test]
+async fn the_login_is_redirected_to_the_enabled_providers_and_the_callback_checks_the_state() {
+    let store = it::store().await;
+    let oauth = OAuthConfig::new("https://webdev.example.com").with_client(
+        OAuthProvider::GitHub,
+        OAuthClient::new("github-client", "github-secret"),
+    );
+    let routes = test_router(&store.with_oauth(oauth));
+
+    let response = warp::test::request().path("/oauth/github").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    let location = url::Url::parse(response.headers()[LOCATION].to_str().unwrap()).unwrap();
+    assert_eq!(location.host_str(), Some("github.com"));
+    let query: Vec<(String, String)> = location.query_pairs().into_owned().collect();
+    let param = |name: &str| {
+        query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .unwrap()
+    };
+    assert_eq!(param("client_id"), "github-client");
+    assert_eq!(
+        param("redirect_uri"),
+        "https://webdev.example.com/oauth/github/callback"
+    );
+    assert!(!query.iter().any(|(_, value)| value.contains("github-secret")));
+    let state = param("state");
+    let cookie = response.headers()[SET_COOKIE].to_str().unwrap();
+    let nonce = cookie.split(';').next().unwrap().to_string();
+    assert!(nonce.starts_with("oauth_nonce="));
+    assert!(cookie.contains("HttpOnly"));
+
+    for (path, cookie) in [
+        (format!("/oauth/github/callback?code=code&state={state}"), None),
+        (
+            "/oauth/github/callback?code=code&state=forged".to_string(),
+            Some(nonce.clone()),
+        ),
+        (
+            format!("/oauth/github/callback?code=code&state={state}"),
+            Some("oauth_nonce=other".to_string()),
+        ),
+        (
+            format!("/oauth/github/callback?error=access_denied&state={state}"),
+            Some(nonce.clone()),
+        ),
+    ] {
+        let mut request = warp::test::request().path(&path);
+        if let Some(cookie) = &cookie {
+            request = request.header("cookie", cookie);
+        }
+        let response = request.reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{path} with {cookie:?}");
+    }
+
+    for path in ["/oauth/google", "/oauth/gitlab", "/oauth/google/callback?code=code"] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{path}");
+    }
+}
@@ -0,0 +1,112 @@
+//! Contract tests between the OpenAPI document and the filter tree.
+//!
+//! Every path and method in the document has to be routable, and every other method on the
+//! documented paths has to be rejected as an unknown route. Requests that are routed, but rejected
+//! by the handler (missing token, invalid body, missing question, ...), still count as routable,
+//! only the fallback response of [return_error](webdev_book::error::return_error) does not.
+use utoipa::openapi::path::HttpMethod;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+use webdev_book::openapi::openapi;
+use webdev_book::test_support::test_router;
+
+/// Body of the response to the requests that do not match any route.
+const ROUTE_NOT_FOUND: &str = "route not found";
+
+/// Methods the routes are probed with.
+const METHODS: [HttpMethod; 5] = [
+    HttpMethod::Get,
+    HttpMethod::Post,
+    HttpMethod::Put,
+    HttpMethod::Delete,
+    HttpMethod::Patch,
+];
+
+/// Routes served outside of the document, which are routable without being documented.
+const UNDOCUMENTED: [&str; 2] = ["/api-docs", "/api-docs/openapi.json"];
+
+/// Returns the name of the method, as sent in the request.
+fn method_name(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "GET",
+        HttpMethod::Post => "POST",
+        HttpMethod::Put => "PUT",
+        HttpMethod::Delete => "DELETE",
+        HttpMethod::Patch => "PATCH",
+        HttpMethod::Head => "HEAD",
+        HttpMethod::Options => "OPTIONS",
+        HttpMethod::Trace => "TRACE",
+    }
+}
+
+/// Replaces the path parameters of the documented path, e.g. `{id}`, with an id.
+fn concrete_path(template: &str) -> String {
+    template
+        .split('/')
+        .map(|segment| match segment.starts_with('{') && segment.ends_with('}') {
+            true => "1",
+            false => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Checks whether the request is routed to a handler.
+///
+/// The requests whose path is routed, but not with their method, are not.
+async fn is_routable<F>(routes: &F, method: &str, path: &str) -> bool
+where
+    F: Filter + 'static,
+    F::Extract: Reply + Send,
+{
+    let response = warp::test::request()
+        .method(method)
+        .path(path)
+        .header("content-type", "application/json")
+        .body("{}")
+        .reply(routes)
+        .await;
+    let not_routed = response.status() == StatusCode::NOT_FOUND && response.body() == ROUTE_NOT_FOUND;
+    !not_routed && response.status() != StatusCode::METHOD_NOT_ALLOWED
+}
+
+#[tokio::test]
+async fn documented_routes_are_routable() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+
+    let document = openapi();
+    assert!(!document.paths.paths.is_empty(), "the document has no paths");
+    let mut mismatches = Vec::new();
+    for (template, item) in &document.paths.paths {
+        let path = concrete_path(template);
+        for method in &METHODS {
+            let documented = match method {
+                HttpMethod::Get => item.get.is_some(),
+                HttpMethod::Post => item.post.is_some(),
+                HttpMethod::Put => item.put.is_some(),
+                HttpMethod::Delete => item.delete.is_some(),
+                HttpMethod::Patch => item.patch.is_some(),
+                _ => unreachable!("only the methods used by the API are probed"),
+            };
+            let method = method_name(method);
+            match (documented, is_routable(&routes, method, &path).await) {
+                (true, false) => mismatches.push(format!("{method} {template} is documented, but not routed")),
+                (false, true) => mismatches.push(format!("{method} {template} is routed, but not documented")),
+                _ => {}
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+#[tokio::test]
+async fn undocumented_routes_are_routable() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+
+    for path in UNDOCUMENTED {
+        assert!(is_routable(&routes, "GET", path).await, "GET {path} is not routed");
+    }
+    assert!(!is_routable(&routes, "GET", "/no-such-route").await);
+}
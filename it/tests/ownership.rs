@@ -0,0 +1,102 @@
+use warp::http::StatusCode;
+use webdev_book::api::mock::MockProfanityFilter;
+use webdev_book::test_support::{a_question, an_account, an_answer, authenticated, test_router};
+
+#[tokio::test]
+async fn only_the_author_can_delete_a_question() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let bob = an_account().insert(&store).await;
+    let question = a_question()
+        .with_tags(["ownership"])
+        .owned_by(alice.id.unwrap())
+        .insert(&store)
+        .await;
+    an_answer().to(question.id.unwrap()).insert(&store).await;
+    let path = format!("/questions/{}", question.id.unwrap().0);
+
+    let response = authenticated(bob.id.unwrap())
+        .method("DELETE")
+        .path(&path)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = authenticated(alice.id.unwrap())
+        .method("DELETE")
+        .path(&path)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn only_the_author_can_update_an_answer() {
+    let store = it::store().await;
+    let filter = MockProfanityFilter::new().with_response("darn, fixed", "****, fixed");
+    let store = store.with_profanity_filter(filter);
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().insert(&store).await;
+    let answer = an_answer()
+        .to(question.id.unwrap())
+        .owned_by(alice)
+        .insert(&store)
+        .await;
+    let path = format!("/answers/{}", answer.id.unwrap().0);
+    let body = serde_json::json!({ "content": "darn, fixed" });
+
+    let response = authenticated(bob)
+        .method("PUT")
+        .path(&path)
+        .json(&body)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = authenticated(alice)
+        .method("PUT")
+        .path(&path)
+        .json(&body)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let updated: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(updated["content"], "****, fixed");
+
+    let response = authenticated(alice)
+        .method("PUT")
+        .path("/answers/2147483647")
+        .json(&body)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn only_the_author_can_delete_an_answer() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().insert(&store).await;
+    let answer = an_answer()
+        .to(question.id.unwrap())
+        .owned_by(alice)
+        .insert(&store)
+        .await;
+    let path = format!("/answers/{}", answer.id.unwrap().0);
+
+    let response = authenticated(bob).method("DELETE").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = authenticated(alice).method("DELETE").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = authenticated(alice).method("DELETE").path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
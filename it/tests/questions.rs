@@ -0,0 +1,538 @@
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::test_support::{a_question, an_account, an_answer, authenticated, test_router};
+
+#[tokio::test]
+async fn the_updates_of_a_question_are_listed_as_revisions() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question()
+        .with_title("First title")
+        .owned_by(alice)
+        .insert(&store)
+        .await;
+    let path = format!("/questions/{}", question.id.unwrap().0);
+    let revisions_path = format!("{path}/revisions");
+
+    let response = warp::test::request().path(&revisions_path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(serde_json::from_slice::<Vec<Value>>(response.body()).unwrap().len(), 0);
+
+    for title in ["Second title", "Third title"] {
+        let response = authenticated(alice)
+            .method("PUT")
+            .path(&path)
+            .json(&json!({ "title": title, "content": question.content }))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = warp::test::request().path(&revisions_path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let revisions: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    let listed: Vec<_> = revisions
+        .iter()
+        .map(|revision| {
+            (
+                revision["version"].as_i64().unwrap(),
+                revision["title"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(listed, [(2, "Second title"), (1, "First title")]);
+
+    let response = warp::test::request()
+        .path(&format!("{revisions_path}?offset=1&limit=1"))
+        .reply(&routes)
+        .await;
+    let revisions: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0]["version"], 1);
+
+    let response = warp::test::request()
+        .path("/questions/2147483647/revisions")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn the_updates_based_on_a_previous_version_are_rejected() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question = a_question().owned_by(alice).insert(&store).await;
+    let path = format!("/questions/{}", question.id.unwrap().0);
+    let update = |title: &'static str, if_match: &'static str| {
+        let (routes, path, content) = (routes.clone(), path.clone(), question.content.clone());
+        async move {
+            authenticated(alice)
+                .method("PUT")
+                .path(&path)
+                .header("If-Match", if_match)
+                .json(&json!({ "title": title, "content": content }))
+                .reply(&routes)
+                .await
+        }
+    };
+
+    // Both clients read the first version, the update of the second one is based on a stale version
+    assert_eq!(update("First update", "\"1\"").await.status(), StatusCode::OK);
+    let response = update("Second update", "\"1\"").await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(
+        response.body(),
+        "version mismatch: the resource is at version 2, but the update is based on version 1"
+    );
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    let current: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(current["title"], "First update");
+    assert_eq!(current["version"], 2);
+
+    assert_eq!(update("Second update", "W/\"2\"").await.status(), StatusCode::OK);
+    assert_eq!(update("Third update", "*").await.status(), StatusCode::OK);
+    assert_eq!(
+        update("Fourth update", "latest").await.status(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn closed_questions_cannot_be_answered_until_reopened() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let original_id = a_question().insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+    let answer = || {
+        authenticated(alice)
+            .method("POST")
+            .path(&format!("{path}/answers"))
+            .json(&json!({ "content": "An answer" }))
+    };
+
+    let response = authenticated(bob)
+        .method("POST")
+        .path(&format!("{path}/close"))
+        .json(&json!({}))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    for duplicate_of in [question_id.0, i32::MAX] {
+        let response = authenticated(alice)
+            .method("POST")
+            .path(&format!("{path}/close"))
+            .json(&json!({ "duplicate_of": duplicate_of }))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path(&format!("{path}/close"))
+        .json(&json!({ "duplicate_of": original_id.0 }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["status"], json!({ "duplicate_of": original_id.0 }));
+
+    let response = answer().reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&format!("{path}/reopen"))
+        .header("X-Admin-Token", it::ADMIN_TOKEN)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["status"], "open");
+
+    let response = answer().reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn similar_questions_are_asked_only_when_forced() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let asked = a_question()
+        .with_title("How do I test warp filters?")
+        .insert(&store)
+        .await;
+    let ask = |path: &str| {
+        authenticated(alice)
+            .method("POST")
+            .path(path)
+            .json(&json!({ "title": "How do I test a warp filter?", "content": "With warp::test." }))
+    };
+
+    let response = ask("/questions").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let similar: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(similar.len(), 1);
+    assert_eq!(similar[0]["id"], asked.id.unwrap().0);
+
+    let response = ask("/questions?force=maybe").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = ask("/questions?force=true").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/questions")
+        .json(&json!({ "title": "Why does my borrow checker complain?", "content": "It does." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn the_answers_are_embedded_in_the_question_on_request() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let question_id = a_question().insert(&store).await.id.unwrap();
+    let answer_id = an_answer().to(question_id).insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["id"], question_id.0);
+    assert!(question.get("answers").is_none());
+
+    let response = warp::test::request()
+        .path(&format!("{path}?include=answers"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["id"], question_id.0);
+    assert_eq!(question["answers"].as_array().unwrap().len(), 1);
+    assert_eq!(question["answers"][0]["id"], answer_id.0);
+
+    let response = warp::test::request()
+        .path(&format!("{path}?include=comments"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = warp::test::request()
+        .path("/questions/2147483647?include=answers")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn the_content_is_rendered_to_sanitized_html_on_request() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let question_id = a_question()
+        .with_content("Why is **this** slow? <script>alert(1)</script>")
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    let answer_id = an_answer().to(question_id).insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert!(question.get("content_html").is_none());
+
+    let response = warp::test::request()
+        .path(&format!("{path}?include=answers&format=html"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    let content_html = question["content_html"].as_str().unwrap();
+    assert!(content_html.contains("<strong>this</strong>"));
+    assert!(!content_html.contains("script"));
+    assert!(question["answers"][0]["content_html"].is_string());
+
+    let response = warp::test::request()
+        .path(&format!("/answers/{}?format=html", answer_id.0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let answer: Value = serde_json::from_slice(response.body()).unwrap();
+    assert!(answer["content_html"].as_str().unwrap().starts_with("<p>"));
+
+    let response = warp::test::request()
+        .path(&format!("{path}?format=pdf"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn the_questions_are_listed_newest_first_and_polled_since_a_time() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let mut asked = Vec::new();
+    for _ in 0..3 {
+        asked.push(a_question().insert(&store).await.id.unwrap().0);
+    }
+    let list = |query: String| {
+        let routes = routes.clone();
+        async move {
+            let response = warp::test::request()
+                .path(&format!("/questions?{query}"))
+                .reply(&routes)
+                .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let page: Value = serde_json::from_slice(response.body()).unwrap();
+            let ids: Vec<_> = page["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|question| question["id"].as_i64().unwrap() as i32)
+                .collect();
+            (ids, page["total"].as_i64().unwrap())
+        }
+    };
+
+    asked.reverse();
+    assert_eq!(list(String::new()).await, (asked.clone(), 3));
+
+    let hour_ago = (chrono::Utc::now() - chrono::Duration::try_hours(1).unwrap()).timestamp();
+    assert_eq!(list(format!("since={hour_ago}")).await, (asked, 3));
+
+    let in_an_hour = (chrono::Utc::now() + chrono::Duration::try_hours(1).unwrap()).to_rfc3339();
+    let query = format!("since={}", in_an_hour.replace('+', "%2B"));
+    assert_eq!(list(query).await, (vec![], 0));
+
+    let response = warp::test::request()
+        .path("/questions?since=yesterday")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn the_listings_are_limited_to_the_maximum_page_size() {
+    let store = it::store().await.with_max_page_size(2);
+    let routes = test_router(&store);
+    for _ in 0..3 {
+        a_question().insert(&store).await;
+    }
+
+    let response = warp::test::request().path("/questions").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let page: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+    assert_eq!((&page["total"], &page["limit"]), (&json!(3), &json!(2)));
+
+    let response = warp::test::request().path("/questions?limit=3").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn the_question_bodies_accept_only_the_fields_set_by_the_author() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+
+    for (body, status) in [
+        (
+            json!({ "id": 42, "title": "How do I test warp?", "content": "With warp::test." }),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({ "title": "How do I test warp?", "content": "With warp::test.", "status": "closed" }),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({ "title": "How do I test warp?" }),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({ "title": " ", "content": "With warp::test." }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({ "title": "How do I test warp?", "content": "\n\n" }),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let response = authenticated(alice)
+            .method("POST")
+            .path("/questions?force=true")
+            .json(&body)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status, "{body}");
+
+        let response = authenticated(alice)
+            .method("PUT")
+            .path(&path)
+            .json(&body)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status, "{body}");
+    }
+
+    let response = authenticated(alice)
+        .method("PUT")
+        .path(&path)
+        .json(&json!({ "title": "How do I test warp?", "content": "With warp::test.", "tags": ["warp"] }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn the_tags_are_normalized_and_the_invalid_ones_rejected_by_their_field() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let ask = |tags: Value| {
+        authenticated(alice)
+            .method("POST")
+            .path("/questions?force=true")
+            .json(&json!({ "title": "How do I test warp?", "content": "With warp::test.", "tags": tags }))
+    };
+
+    let response = ask(json!([" Rust ", "rust", "Web  Dev", "C++"])).reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["tags"], json!(["rust", "web dev", "c++"]));
+
+    for (tags, field) in [
+        (json!(["rust", "<script>"]), "tags[1]"),
+        (json!(["a".repeat(65)]), "tags[0]"),
+        (json!(["a", "b", "c", "d", "e", "f"]), "tags"),
+    ] {
+        let response = ask(tags).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(error["field"], field);
+    }
+}
+
+#[tokio::test]
+async fn the_votes_on_a_question_are_changed_and_retracted() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let carol = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+    let path = format!("/questions/{}", question_id.0);
+    let vote = |account, direction: &str| {
+        authenticated(account)
+            .method("POST")
+            .path(&format!("{path}/vote"))
+            .json(&json!({ "direction": direction }))
+    };
+
+    let response = vote(alice, "up").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    for (account, direction, score, voted) in [
+        (bob, "up", 1, json!("up")),
+        (carol, "up", 2, json!("up")),
+        (carol, "down", 0, json!("down")),
+    ] {
+        let response = vote(account, direction).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body, json!({ "score": score, "vote": voted }));
+    }
+
+    let response = vote(bob, "up").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body["code"], "already_voted");
+
+    // Retracting is idempotent
+    for _ in 0..2 {
+        let response = authenticated(bob)
+            .method("DELETE")
+            .path(&format!("{path}/vote"))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body, json!({ "score": -1, "vote": null }));
+    }
+
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["score"], -1);
+
+    let response = vote(bob, "sideways").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = authenticated(bob)
+        .method("POST")
+        .path("/questions/2147483647/vote")
+        .json(&json!({ "direction": "up" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn the_bookmarked_questions_are_listed_for_the_account() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let first = a_question().insert(&store).await.id.unwrap();
+    let second = a_question().insert(&store).await.id.unwrap();
+    let bookmark = |method: &'static str, question_id: i32| {
+        let routes = routes.clone();
+        async move {
+            authenticated(alice)
+                .method(method)
+                .path(&format!("/questions/{question_id}/bookmark"))
+                .reply(&routes)
+                .await
+                .status()
+        }
+    };
+    let bookmarks = |account_id| {
+        let routes = routes.clone();
+        async move {
+            let response = authenticated(account_id)
+                .path("/accounts/me/bookmarks")
+                .reply(&routes)
+                .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let questions: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+            questions
+                .into_iter()
+                .map(|question| question["id"].as_i64().unwrap() as i32)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Bookmarking twice does nothing, and the most recently bookmarked questions come first
+    assert_eq!(bookmark("PUT", second.0).await, StatusCode::OK);
+    assert_eq!(bookmark("PUT", first.0).await, StatusCode::OK);
+    assert_eq!(bookmark("PUT", first.0).await, StatusCode::OK);
+    assert_eq!(bookmarks(alice).await, [first.0, second.0]);
+    assert_eq!(bookmarks(bob).await, Vec::<i32>::new());
+
+    assert_eq!(bookmark("DELETE", second.0).await, StatusCode::OK);
+    assert_eq!(bookmark("DELETE", second.0).await, StatusCode::OK);
+    assert_eq!(bookmarks(alice).await, [first.0]);
+
+    assert_eq!(bookmark("PUT", i32::MAX).await, StatusCode::NOT_FOUND);
+    assert_eq!(bookmark("DELETE", i32::MAX).await, StatusCode::NOT_FOUND);
+}
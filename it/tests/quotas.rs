@@ -0,0 +1,100 @@
+use chrono::Utc;
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::error::ServiceError;
+use webdev_book::test_support::{a_question, an_account, authenticated, test_router};
+use webdev_book::types::quota::{start_of_day, Quotas};
+
+#[tokio::test]
+async fn questions_over_the_daily_limit_are_rejected() {
+    let store = it::store().await;
+    let store = store.with_quotas(Quotas {
+        questions_per_day: Some(2),
+        answers_per_day: None,
+    });
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let ask = |account_id| {
+        authenticated(account_id)
+            .method("POST")
+            .path("/questions?force=true")
+            .json(&json!({ "title": "How?", "content": "Like this." }))
+            .reply(&routes)
+    };
+
+    assert_eq!(ask(alice).await.status(), StatusCode::CREATED);
+    assert_eq!(ask(alice).await.status(), StatusCode::CREATED);
+    let response = ask(alice).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let body: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body["resource"], "questions");
+    assert_eq!(body["limit"], 2);
+    assert_eq!(body["usage"], 2);
+    assert!(body["resets_at"].as_str().unwrap().ends_with("T00:00:00Z"));
+
+    assert_eq!(ask(bob).await.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn deleted_questions_still_count_toward_the_daily_limit() {
+    let store = it::store().await;
+    let store = store.with_quotas(Quotas {
+        questions_per_day: Some(1),
+        answers_per_day: None,
+    });
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let ask = || {
+        authenticated(alice)
+            .method("POST")
+            .path("/questions?force=true")
+            .json(&json!({ "title": "How?", "content": "Like this." }))
+            .reply(&routes)
+    };
+
+    let response = ask().await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    let response = authenticated(alice)
+        .method("DELETE")
+        .path(&format!("/questions/{}", question["id"]))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = ask().await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let body: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(body["usage"], 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_answers_do_not_go_over_the_daily_limit() {
+    let store = it::store().await;
+    let store = store.with_quotas(Quotas {
+        questions_per_day: None,
+        answers_per_day: Some(3),
+    });
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question().owned_by(alice).insert(&store).await.id.unwrap();
+
+    let answers: Vec<_> = (0..10)
+        .map(|_| {
+            let store = store.clone();
+            tokio::spawn(async move { store.add_answer(bob, question_id, "Like this.".to_string(), true).await })
+        })
+        .collect();
+    let mut added = 0;
+    for answer in answers {
+        match answer.await.unwrap() {
+            Ok(_) => added += 1,
+            Err(ServiceError::QuotaExceeded(exceeded)) => assert_eq!(exceeded.limit, 3),
+            Err(error) => panic!("unexpected error: {error}"),
+        }
+    }
+    assert_eq!(added, 3);
+    let today = start_of_day(Utc::now());
+    assert_eq!(store.count_answers_since(bob, today).await.unwrap(), 3);
+}
@@ -0,0 +1,46 @@
+use webdev_book::error::ServiceError;
+use webdev_book::seed::{seed, Profile};
+use webdev_book::store::Store;
+
+/// Rows of the seeded resources, without the creation times, which depend on when they were seeded.
+type Rows = (
+    Vec<(i32, String)>,
+    Vec<(i32, i32, String, String, Option<Vec<String>>)>,
+    Vec<(i32, i32, i32, String)>,
+);
+
+/// Returns the seeded resources, in the order they were inserted.
+async fn rows(store: &Store) -> Rows {
+    let accounts = sqlx::query_as("SELECT id, email FROM accounts ORDER BY id")
+        .fetch_all(&store.connection)
+        .await
+        .unwrap();
+    let questions = sqlx::query_as(
+        "SELECT id, account_id, title, content, \
+        (SELECT array_agg(tags.name ORDER BY question_tags.position) FROM question_tags \
+        JOIN tags ON tags.id = question_tags.tag_id WHERE question_tags.question_id = questions.id) AS tags \
+        FROM questions ORDER BY id",
+    )
+    .fetch_all(&store.connection)
+    .await
+    .unwrap();
+    let answers = sqlx::query_as("SELECT id, question_id, account_id, content FROM answers ORDER BY id")
+        .fetch_all(&store.connection)
+        .await
+        .unwrap();
+    (accounts, questions, answers)
+}
+
+#[tokio::test]
+async fn seeding_is_reproducible() {
+    let first = it::store().await;
+    let second = it::store().await;
+
+    let summary = seed(&first, Profile::Demo).await.unwrap();
+    assert_eq!(seed(&second, Profile::Demo).await.unwrap(), summary);
+    assert!(summary.questions > 0 && summary.answers > 0);
+    assert_eq!(rows(&first).await, rows(&second).await);
+
+    let error = seed(&first, Profile::Demo).await.unwrap_err();
+    assert!(matches!(error, ServiceError::Conflict(_)));
+}
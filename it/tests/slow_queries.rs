@@ -0,0 +1,60 @@
+//! Tests of the plans of the slow queries, logged with the `explain-slow-queries` feature, e.g.
+//! `cargo test -p it --features explain-slow-queries`.
+#![cfg(feature = "explain-slow-queries")]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use webdev_book::test_support::a_question;
+
+/// Writer of the logs, into a buffer shared with the test.
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Logs {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn the_plans_of_the_slow_reads_are_logged() {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_max_level(tracing::Level::WARN)
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let mut store = it::store().await;
+    let question_id = a_question().insert(&store).await.id.unwrap();
+    store.slow_query_threshold = Duration::from_secs(60);
+    store.get_question(question_id).await.unwrap().unwrap();
+    assert!(!logs.contents().contains("slow query"), "{}", logs.contents());
+
+    // Every query is slow, as none of them is faster than nothing
+    store.slow_query_threshold = Duration::ZERO;
+    store.question_cache.invalidate(&question_id).await;
+    store.get_question(question_id).await.unwrap().unwrap();
+    let logs = logs.contents();
+    let line = logs
+        .lines()
+        .find(|line| line.contains("slow query: SELECT questions.*"))
+        .unwrap_or_else(|| panic!("{logs}"));
+    assert!(line.contains("store::slow_queries"), "{line}");
+    assert!(line.contains("\"Plan\""), "{line}");
+    assert!(line.contains("\"Actual Rows\""), "{line}");
+}
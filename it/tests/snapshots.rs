@@ -0,0 +1,155 @@
+//! Snapshot tests of the wire format of representative responses.
+//!
+//! The snapshots are stored in `tests/snapshots`, so any change to the format shows up in review.
+//! After an intended change, the snapshots are updated with `cargo insta review`, or with
+//! `INSTA_UPDATE=always` set while running the tests.
+use insta::{assert_json_snapshot, assert_snapshot};
+use serde_json::{json, Value};
+use warp::http::Response;
+use warp::hyper::body::Bytes;
+use webdev_book::test_support::{a_question, an_account, an_answer, authenticated, test_router, DEFAULT_PASSWORD};
+
+/// Parses the JSON body of the response.
+fn body(response: &Response<Bytes>) -> Value {
+    serde_json::from_slice(response.body()).expect("the response body is JSON")
+}
+
+/// Renders the status and the body of an error response.
+fn error(response: &Response<Bytes>) -> String {
+    format!("{}\n{}", response.status(), String::from_utf8_lossy(response.body()))
+}
+
+#[tokio::test]
+async fn question_detail() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().with_email("alice@example.com").insert(&store).await;
+    let question = a_question()
+        .with_title("How do I test warp?")
+        .with_content("With warp::test.")
+        .with_tags(["warp", "testing"])
+        .owned_by(alice.id.unwrap())
+        .insert(&store)
+        .await;
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{}", question.id.unwrap().0))
+        .reply(&routes)
+        .await;
+    assert_json_snapshot!(body(&response), {
+        ".id" => "[id]",
+        ".author.id" => "[id]",
+    });
+}
+
+#[tokio::test]
+async fn question_listing() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().with_email("alice@example.com").insert(&store).await;
+    for n in 1..=3 {
+        let question = a_question()
+            .with_title(format!("Question {n}"))
+            .with_content(format!("Content {n}"))
+            .owned_by(alice.id.unwrap())
+            .insert(&store)
+            .await;
+        for _ in 1..n {
+            an_answer().to(question.id.unwrap()).insert(&store).await;
+        }
+    }
+
+    let response = warp::test::request()
+        .path("/questions?offset=1&limit=2")
+        .reply(&routes)
+        .await;
+    assert_json_snapshot!(body(&response), {
+        ".items[].id" => "[id]",
+        ".items[].author.id" => "[id]",
+    });
+}
+
+#[tokio::test]
+async fn errors() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().with_email("alice@example.com").insert(&store).await;
+    let bob = an_account().with_email("bob@example.com").insert(&store).await;
+    let question = a_question().owned_by(alice.id.unwrap()).insert(&store).await;
+    an_answer().to(question.id.unwrap()).insert(&store).await;
+    let question_path = format!("/questions/{}", question.id.unwrap().0);
+
+    let response = warp::test::request().path("/questions/abc").reply(&routes).await;
+    assert_snapshot!("invalid_id", error(&response));
+
+    let response = warp::test::request().path("/questions?limit=-1").reply(&routes).await;
+    assert_snapshot!("invalid_pagination", error(&response));
+
+    let response = warp::test::request().path("/questions/999999").reply(&routes).await;
+    assert_snapshot!("question_not_found", error(&response));
+
+    let response = warp::test::request().path("/answers/999999").reply(&routes).await;
+    assert_snapshot!("answer_not_found", error(&response));
+
+    let response = warp::test::request().path("/attachments/999999").reply(&routes).await;
+    assert_snapshot!("attachment_not_found", error(&response));
+
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&question_path)
+        .reply(&routes)
+        .await;
+    assert_snapshot!("missing_token", error(&response));
+
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&question_path)
+        .header("Authorization", "not a token")
+        .reply(&routes)
+        .await;
+    assert_snapshot!("invalid_token", error(&response));
+
+    let response = authenticated(bob.id.unwrap())
+        .method("DELETE")
+        .path(&question_path)
+        .reply(&routes)
+        .await;
+    assert_snapshot!("unauthorized", error(&response));
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&json!({ "email": "alice@example.com", "password": "wrong password" }))
+        .reply(&routes)
+        .await;
+    assert_snapshot!("wrong_password", error(&response));
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/register")
+        .json(&json!({ "email": "Alice@Example.com", "password": DEFAULT_PASSWORD }))
+        .reply(&routes)
+        .await;
+    assert_snapshot!("duplicate_account", error(&response));
+
+    let response = authenticated(alice.id.unwrap())
+        .method("POST")
+        .path("/questions")
+        .header("content-type", "application/json")
+        .body("{\"title\": ")
+        .reply(&routes)
+        .await;
+    assert_snapshot!("malformed_body", error(&response));
+
+    let response = authenticated(alice.id.unwrap())
+        .method("POST")
+        .path("/questions")
+        .header("content-type", "text/plain")
+        .body("How do I test warp?")
+        .reply(&routes)
+        .await;
+    assert_snapshot!("unsupported_media_type", error(&response));
+
+    let response = warp::test::request().path("/no-such-route").reply(&routes).await;
+    assert_snapshot!("route_not_found", error(&response));
+}
@@ -0,0 +1,175 @@
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use webdev_book::test_support::{a_question, an_account, authenticated, test_router};
+
+#[tokio::test]
+async fn the_tags_are_listed_by_the_number_of_the_questions_using_them() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let question_id = a_question()
+        .owned_by(alice)
+        .with_tags(["warp", "rust"])
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    a_question().with_tags(["rust"]).insert(&store).await;
+    let tags = || {
+        let routes = routes.clone();
+        async move {
+            let response = warp::test::request().path("/tags").reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let tags: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+            tags.into_iter()
+                .map(|tag| {
+                    (
+                        tag["name"].as_str().unwrap().to_string(),
+                        tag["question_count"].as_i64().unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(tags().await, [("rust".to_string(), 2), ("warp".to_string(), 1)]);
+
+    // The tags of a question are kept in the order they were written
+    let path = format!("/questions/{}", question_id.0);
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["tags"], json!(["warp", "rust"]));
+
+    let response = authenticated(alice)
+        .method("PUT")
+        .path(&path)
+        .json(&json!({ "title": "Retagged", "content": "Retagged", "tags": ["tokio"] }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = warp::test::request().path(&path).reply(&routes).await;
+    let question: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(question["tags"], json!(["tokio"]));
+
+    // The tags the questions stop using are kept
+    assert_eq!(
+        tags().await,
+        [
+            ("rust".to_string(), 1),
+            ("tokio".to_string(), 1),
+            ("warp".to_string(), 0)
+        ]
+    );
+
+    let response = warp::test::request()
+        .path("/tags?offset=1&limit=1")
+        .reply(&routes)
+        .await;
+    let page: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0]["name"], "tokio");
+}
+
+#[tokio::test]
+async fn the_popular_tags_are_counted_over_the_recent_window() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    a_question().with_tags(["rust", "warp"]).insert(&store).await;
+    a_question().with_tags(["rust"]).insert(&store).await;
+    let old_question_id = a_question().with_tags(["tokio"]).insert(&store).await.id.unwrap();
+    sqlx::query("UPDATE questions SET created_on = NOW() - INTERVAL '10 days' WHERE id = $1")
+        .bind(old_question_id.0)
+        .execute(&store.connection)
+        .await
+        .unwrap();
+    let popular_tags = |query: &str| {
+        let routes = routes.clone();
+        let path = format!("/tags/popular{query}");
+        async move {
+            let response = warp::test::request().path(&path).reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let tags: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+            tags.into_iter()
+                .map(|tag| {
+                    (
+                        tag["name"].as_str().unwrap().to_string(),
+                        tag["question_count"].as_i64().unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let recent = [("rust".to_string(), 2), ("warp".to_string(), 1)];
+    assert_eq!(popular_tags("").await, recent);
+    assert_eq!(popular_tags("?window=7d").await, recent);
+    assert_eq!(
+        popular_tags("?window=2w").await,
+        [
+            ("rust".to_string(), 2),
+            ("tokio".to_string(), 1),
+            ("warp".to_string(), 1)
+        ]
+    );
+
+    for window in ["7x", "0h", "366d"] {
+        let response = warp::test::request()
+            .path(&format!("/tags/popular?window={window}"))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{window}");
+    }
+}
+
+#[tokio::test]
+async fn the_feed_lists_the_questions_in_the_followed_tags() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let rust_question_id = a_question().with_tags(["rust"]).insert(&store).await.id.unwrap();
+    let web_dev_question_id = a_question()
+        .with_tags(["web dev", "rust"])
+        .insert(&store)
+        .await
+        .id
+        .unwrap();
+    a_question().with_tags(["tokio"]).insert(&store).await;
+    a_question().insert(&store).await;
+    let feed = || {
+        let routes = routes.clone();
+        async move {
+            let response = authenticated(alice).path("/questions/feed").reply(&routes).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let questions: Vec<Value> = serde_json::from_slice(response.body()).unwrap();
+            questions
+                .into_iter()
+                .map(|question| question["id"].as_i64().unwrap() as i32)
+                .collect::<Vec<_>>()
+        }
+    };
+    let follow = |method: &'static str, tag: &'static str| {
+        let routes = routes.clone();
+        async move {
+            authenticated(alice)
+                .method(method)
+                .path(&format!("/accounts/me/tags/{tag}"))
+                .reply(&routes)
+                .await
+                .status()
+        }
+    };
+
+    assert_eq!(feed().await, Vec::<i32>::new());
+
+    // The names are normalized like the tags of the questions, and following twice does nothing
+    assert_eq!(follow("PUT", "Rust").await, StatusCode::OK);
+    assert_eq!(follow("PUT", "rust").await, StatusCode::OK);
+    assert_eq!(feed().await, [web_dev_question_id.0, rust_question_id.0]);
+
+    assert_eq!(follow("PUT", "Web%20Dev").await, StatusCode::OK);
+    assert_eq!(follow("DELETE", "rust").await, StatusCode::OK);
+    assert_eq!(feed().await, [web_dev_question_id.0]);
+
+    assert_eq!(follow("PUT", "haskell").await, StatusCode::NOT_FOUND);
+    assert_eq!(follow("DELETE", "haskell").await, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,733 @@
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::json;
+use warp::http::StatusCode;
+use webdev_book::authentication::authenticate;
+use webdev_book::clock::{Clock, TestClock};
+use webdev_book::store::Store;
+use webdev_book::test_support::{
+    a_question, an_account, test_auth_keys, test_router, token_for, token_valid_between, token_with_keys,
+    DEFAULT_PASSWORD, TEST_PASETO_KEY,
+};
+use webdev_book::tokens::paseto::{encrypt, key_footer};
+use webdev_book::tokens::{JwtSigner, TokenSigner};
+use webdev_book::types::authentication::{AuthKeys, PasswordHashing, Session, SessionLifetimes};
+use webdev_book::types::moderation::AccountRole;
+
+#[tokio::test]
+async fn only_tokens_valid_now_are_accepted() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let alice_id = alice.id.unwrap();
+    let hour = Duration::try_hours(1).unwrap();
+    let now = Utc::now();
+
+    for (not_before, expiration, status) in [
+        (now - hour * 2, now - hour, StatusCode::UNAUTHORIZED),
+        (now + hour, now + hour * 2, StatusCode::UNAUTHORIZED),
+        (now - hour, now + hour, StatusCode::OK),
+    ] {
+        let question = a_question().owned_by(alice_id).insert(&store).await;
+        let response = warp::test::request()
+            .method("DELETE")
+            .path(&format!("/questions/{}", question.id.unwrap().0))
+            .header("Authorization", token_valid_between(alice_id, not_before, expiration))
+            .reply(&routes)
+            .await;
+        assert_eq!(
+            response.status(),
+            status,
+            "token valid from {not_before} to {expiration}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn tokens_expire_a_day_after_login() {
+    let store = it::store().await;
+    let clock = TestClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap());
+    let store = store.with_clock(clock.clone());
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&json!({ "email": alice.email, "password": DEFAULT_PASSWORD }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token: String = serde_json::from_slice(response.body()).unwrap();
+
+    for (elapsed, status) in [
+        (Duration::try_hours(23).unwrap(), StatusCode::OK),
+        (Duration::try_hours(2).unwrap(), StatusCode::UNAUTHORIZED),
+    ] {
+        clock.advance(elapsed);
+        let question = a_question().owned_by(alice.id.unwrap()).insert(&store).await;
+        let response = warp::test::request()
+            .method("DELETE")
+            .path(&format!("/questions/{}", question.id.unwrap().0))
+            .header("Authorization", &token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status, "at {}", clock.now());
+    }
+}
+
+#[tokio::test]
+async fn logging_out_revokes_only_the_token_of_the_request() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let (token, other_token) = (token_for(alice), token_for(alice));
+    let logout = |token: String| {
+        let routes = routes.clone();
+        async move {
+            warp::test::request()
+                .method("POST")
+                .path("/logout")
+                .header("Authorization", token)
+                .reply(&routes)
+                .await
+        }
+    };
+
+    assert_eq!(logout(token.clone()).await.status(), StatusCode::OK);
+
+    let response = logout(token.clone()).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.body(), "auth token was revoked");
+
+    for (token, status) in [(token, StatusCode::UNAUTHORIZED), (other_token, StatusCode::OK)] {
+        let question = a_question().owned_by(alice).insert(&store).await;
+        let response = warp::test::request()
+            .method("DELETE")
+            .path(&format!("/questions/{}", question.id.unwrap().0))
+            .header("Authorization", token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status);
+    }
+}
+
+#[tokio::test]
+async fn the_tokens_of_the_previous_keys_are_accepted_until_the_keys_are_dropped() {
+    let store = it::store().await;
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let old_keys = AuthKeys::new("old", b"OLD KEY FOR THE WEBDEV BOOK API!").unwrap();
+    let rotated_keys = AuthKeys::new("new", b"NEW KEY FOR THE WEBDEV BOOK API!")
+        .and_then(|keys| keys.with_previous_key("old", b"OLD KEY FOR THE WEBDEV BOOK API!"))
+        .unwrap();
+    let new_keys = AuthKeys::new("new", b"NEW KEY FOR THE WEBDEV BOOK API!").unwrap();
+    let forged_keys = AuthKeys::new("old", b"FORGED KEY NAMED AS THE OLD ONE!").unwrap();
+    let status = |keys: &AuthKeys, token: &str| {
+        let routes = webdev_book::routes(&store.clone().with_auth_keys(keys.clone()), None, std::env::temp_dir());
+        let request = warp::test::request()
+            .path("/accounts/me/notifications")
+            .header("Authorization", token);
+        async move { request.reply(&routes).await.status() }
+    };
+    let (old_token, new_token) = (token_with_keys(&old_keys, alice), token_with_keys(&rotated_keys, alice));
+
+    assert_eq!(status(&rotated_keys, &old_token).await, StatusCode::OK);
+    assert_eq!(status(&rotated_keys, &new_token).await, StatusCode::OK);
+    assert_eq!(
+        status(&rotated_keys, &token_with_keys(&forged_keys, alice)).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    assert_eq!(status(&new_keys, &old_token).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status(&new_keys, &new_token).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn the_sessions_are_listed_and_signed_out_one_device_at_a_time() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let bob = an_account().insert(&store).await.id.unwrap();
+    let login = |user_agent: &'static str| {
+        let (routes, email) = (routes.clone(), alice.email.clone());
+        async move {
+            let response = warp::test::request()
+                .method("POST")
+                .path("/login")
+                .header("User-Agent", user_agent)
+                .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+                .reply(&routes)
+                .await;
+            serde_json::from_slice::<String>(response.body()).unwrap()
+        }
+    };
+    let (laptop, phone) = (login("laptop").await, login("phone").await);
+
+    let response = warp::test::request()
+        .path("/accounts/me/sessions")
+        .header("Authorization", &laptop)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let sessions: Vec<serde_json::Value> = serde_json::from_slice(response.body()).unwrap();
+    let devices = sessions
+        .iter()
+        .map(|session| {
+            (
+                session["user_agent"].as_str().unwrap(),
+                session["current"].as_bool().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(devices, [("phone", false), ("laptop", true)]);
+    let phone_session = sessions[0]["id"].as_i64().unwrap();
+
+    let delete = |token: String| {
+        let routes = routes.clone();
+        async move {
+            warp::test::request()
+                .method("DELETE")
+                .path(&format!("/accounts/me/sessions/{phone_session}"))
+                .header("Authorization", token)
+                .reply(&routes)
+                .await
+                .status()
+        }
+    };
+    assert_eq!(delete(token_for(bob)).await, StatusCode::NOT_FOUND);
+    assert_eq!(delete(laptop.clone()).await, StatusCode::NO_CONTENT);
+    assert_eq!(delete(laptop.clone()).await, StatusCode::NOT_FOUND);
+
+    for (token, status) in [(phone, StatusCode::UNAUTHORIZED), (laptop, StatusCode::OK)] {
+        let response = warp::test::request()
+            .path("/accounts/me/sessions")
+            .header("Authorization", token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status);
+    }
+}
+
+#[tokio::test]
+async fn logins_are_rejected_after_too_many_failures_by_account_and_by_address() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let (alice, bob) = (an_account().insert(&store).await, an_account().insert(&store).await);
+    let login = |email: String, password: &'static str, address: &'static str| {
+        let routes = routes.clone();
+        async move {
+            warp::test::request()
+                .method("POST")
+                .path("/login")
+                .remote_addr(address.parse().unwrap())
+                .json(&json!({ "email": email, "password": password }))
+                .reply(&routes)
+                .await
+        }
+    };
+
+    for _ in 0..Store::FAILED_LOGINS_PER_ACCOUNT {
+        let response = login(alice.email.clone(), "wrong password", "192.0.2.1:4000").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+    let response = login(alice.email.clone(), DEFAULT_PASSWORD, "192.0.2.2:4000").await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.headers()["Retry-After"].to_str().unwrap().parse().unwrap();
+    assert!((1..=Store::FAILED_LOGINS_WINDOW.as_secs()).contains(&retry_after));
+
+    for attempt in 0..Store::FAILED_LOGINS_PER_ADDRESS {
+        login(
+            format!("nobody-{attempt}@example.com"),
+            "wrong password",
+            "192.0.2.3:4000",
+        )
+        .await;
+    }
+    for (address, status) in [
+        ("192.0.2.3:4000", StatusCode::TOO_MANY_REQUESTS),
+        ("192.0.2.4:4000", StatusCode::OK),
+    ] {
+        assert_eq!(
+            login(bob.email.clone(), DEFAULT_PASSWORD, address).await.status(),
+            status
+        );
+    }
+}
+
+#[tokio::test]
+async fn passwords_hashed_with_other_parameters_are_hashed_again_on_login() {
+    let store = it::store().await;
+    let hashing = PasswordHashing::new("argon2id", 1024, 1, 1).unwrap();
+    let store = store.with_password_hashing(hashing);
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    assert!(hashing.needs_rehash(&store.get_account(&alice.email).await.unwrap().password));
+
+    for _ in 0..2 {
+        let response = warp::test::request()
+            .method("POST")
+            .path("/login")
+            .json(&json!({ "email": alice.email, "password": DEFAULT_PASSWORD }))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let password = store.get_account(&alice.email).await.unwrap().password;
+        assert!(password.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"), "{password}");
+    }
+}
+
+#[tokio::test]
+async fn tokens_are_accepted_with_the_bearer_scheme_and_in_the_session_cookie() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let token = token_for(alice.id.unwrap());
+
+    for authorization in [format!("Bearer {token}"), format!("bearer {token}"), token.clone()] {
+        let response = warp::test::request()
+            .path("/accounts/me")
+            .header("Authorization", authorization)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&json!({ "email": alice.email, "password": DEFAULT_PASSWORD }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = serde_json::from_slice::<String>(response.body()).unwrap();
+    let cookie = response.headers()["set-cookie"].to_str().unwrap();
+    assert!(cookie.starts_with(&format!("session={token};")));
+    assert!(cookie.contains("HttpOnly"));
+
+    let cookie = format!("session={token}");
+    let response = warp::test::request()
+        .path("/accounts/me")
+        .header("Cookie", &cookie)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/logout")
+        .header("Cookie", &cookie)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["set-cookie"]
+        .to_str()
+        .unwrap()
+        .starts_with("session=;"));
+
+    let response = warp::test::request()
+        .path("/accounts/me")
+        .header("Cookie", &cookie)
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn the_browsers_of_other_origins_may_send_the_authorization_header() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+
+    let response = warp::test::request()
+        .method("OPTIONS")
+        .path("/accounts/me")
+        .header("Origin", "https://frontend.example.com")
+        .header("Access-Control-Request-Method", "GET")
+        .header("Access-Control-Request-Headers", "authorization")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let allowed_headers = response.headers()["access-control-allow-headers"].to_str().unwrap();
+    assert!(allowed_headers
+        .split(',')
+        .any(|header| header.trim() == "authorization"));
+}
+
+#[tokio::test]
+async fn v4_tokens_are_issued_and_v2_tokens_are_accepted_until_the_end_of_the_transition() {
+    let store = it::store().await;
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let now = Utc::now();
+    // The tokens issued before the v4 ones, with the only claims they carried
+    let v2_token = paseto::tokens::PasetoBuilder::new()
+        .set_encryption_key(TEST_PASETO_KEY)
+        .set_expiration(&(now + Duration::try_hours(1).unwrap()))
+        .set_not_before(&(now - Duration::try_hours(1).unwrap()))
+        .set_claim("account_id", json!(alice))
+        .build()
+        .unwrap();
+    let status = |keys: AuthKeys, token: String| {
+        let routes = webdev_book::routes(&store.clone().with_auth_keys(keys), None, std::env::temp_dir());
+        async move {
+            warp::test::request()
+                .path("/accounts/me")
+                .header("Authorization", token)
+                .reply(&routes)
+                .await
+                .status()
+        }
+    };
+
+    assert!(token_for(alice).starts_with("v4.local."));
+    assert_eq!(status(test_auth_keys(), token_for(alice)).await, StatusCode::OK);
+    assert_eq!(status(test_auth_keys(), v2_token.clone()).await, StatusCode::OK);
+    assert_eq!(
+        status(
+            test_auth_keys().with_v2_tokens_until(now + Duration::try_days(1).unwrap()),
+            v2_token.clone()
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        status(
+            test_auth_keys().with_v2_tokens_until(now - Duration::try_minutes(1).unwrap()),
+            v2_token
+        )
+        .await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        status(
+            test_auth_keys().with_v2_tokens_until(now - Duration::try_minutes(1).unwrap()),
+            token_for(alice)
+        )
+        .await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn the_v2_tokens_get_the_email_and_the_role_of_the_account_and_can_be_revoked() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let alice_id = alice.id.unwrap();
+    store.set_account_role(alice_id, AccountRole::Moderator).await.unwrap();
+    let now = Utc::now();
+    // The claims of the tokens issued before the v4 ones
+    let v2_token = paseto::tokens::PasetoBuilder::new()
+        .set_encryption_key(TEST_PASETO_KEY)
+        .set_expiration(&(now + Duration::try_hours(1).unwrap()))
+        .set_not_before(&(now - Duration::try_hours(1).unwrap()))
+        .set_claim("account_id", json!(alice_id))
+        .build()
+        .unwrap();
+
+    let session = authenticate(&store.clone().with_auth_keys(test_auth_keys()), v2_token.clone())
+        .await
+        .unwrap();
+    assert_eq!(session.account_id, alice_id);
+    assert_eq!(session.email, alice.email);
+    assert_eq!(session.role, AccountRole::Moderator);
+    assert!(session.jti.starts_with("v2-"));
+
+    let logout = || {
+        warp::test::request()
+            .method("POST")
+            .path("/logout")
+            .header("Authorization", format!("Bearer {v2_token}"))
+            .reply(&routes)
+    };
+    assert_eq!(logout().await.status(), StatusCode::OK);
+    let response = logout().await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.body(), "auth token was revoked");
+}
+
+#[tokio::test]
+async fn the_tokens_are_issued_as_jwts_when_the_store_signs_them_so() {
+    let store = it::store().await;
+    let alice = an_account().insert(&store).await;
+    let signer = JwtSigner::hs256(b"A SECRET OF AT LEAST THIRTY-TWO BYTES").unwrap();
+    let store = store
+        .with_auth_keys(test_auth_keys())
+        .with_token_signer(Arc::new(signer));
+    let routes = webdev_book::routes(&store, None, std::env::temp_dir());
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/login")
+        .json(&json!({ "email": alice.email, "password": DEFAULT_PASSWORD }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = serde_json::from_slice::<String>(response.body()).unwrap();
+    assert_eq!(token.split('.').count(), 3);
+
+    for (token, status) in [
+        (format!("Bearer {token}"), StatusCode::OK),
+        (token_for(alice.id.unwrap()), StatusCode::UNAUTHORIZED),
+    ] {
+        let response = warp::test::request()
+            .path("/accounts/me")
+            .header("Authorization", token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status);
+    }
+}
+
+#[tokio::test]
+async fn only_jwts_valid_at_the_time_of_the_clock_are_accepted() {
+    let store = it::store().await;
+    let alice = an_account().insert(&store).await;
+    let signer = JwtSigner::hs256(b"A SECRET OF AT LEAST THIRTY-TWO BYTES").unwrap();
+    let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    // The signer does not check the times, so they are only checked against the clock of the store
+    let store = store
+        .with_clock(TestClock::new(now))
+        .with_auth_keys(test_auth_keys())
+        .with_token_signer(Arc::new(signer.clone()));
+    let routes = webdev_book::routes(&store, None, std::env::temp_dir());
+    let hour = Duration::try_hours(1).unwrap();
+
+    for (not_before, expiration, status) in [
+        (now - hour * 2, now - hour, StatusCode::UNAUTHORIZED),
+        (now + hour, now + hour * 2, StatusCode::UNAUTHORIZED),
+        (now - hour, now + hour, StatusCode::OK),
+    ] {
+        let token = signer.sign(&Session {
+            ver: Session::CLAIMS_VERSION,
+            exp: expiration,
+            nbf: not_before,
+            account_id: alice.id.unwrap(),
+            email: alice.email.clone(),
+            role: AccountRole::User,
+            jti: format!("jti-{}", not_before.timestamp()),
+            sid: None,
+        });
+        let response = warp::test::request()
+            .path("/accounts/me")
+            .header("Authorization", format!("Bearer {token}"))
+            .reply(&routes)
+            .await;
+        assert_eq!(
+            response.status(),
+            status,
+            "token valid from {not_before} to {expiration}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn the_tokens_issued_before_the_claims_version_are_rejected_as_outdated() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let now = Utc::now();
+    let hour = Duration::try_hours(1).unwrap();
+    let token = |version: Option<u32>| {
+        let mut claims = json!({
+            "exp": now + hour,
+            "nbf": now - hour,
+            "account_id": alice,
+            "jti": "issued-before-the-claims-version",
+        });
+        if let Some(version) = version {
+            claims["ver"] = json!(version);
+        }
+        encrypt(TEST_PASETO_KEY, &key_footer("test"), &claims)
+    };
+
+    for (token, status, body) in [
+        (
+            token(None),
+            StatusCode::UNAUTHORIZED,
+            "auth token is outdated, log in again",
+        ),
+        (
+            token(Some(Session::CLAIMS_VERSION + 1)),
+            StatusCode::UNAUTHORIZED,
+            "auth token is outdated, log in again",
+        ),
+        (token_for(alice), StatusCode::OK, ""),
+    ] {
+        let response = warp::test::request()
+            .path("/accounts/me")
+            .header("Authorization", token)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), status);
+        if status != StatusCode::OK {
+            assert_eq!(response.body(), body);
+        }
+    }
+}
+
+#[tokio::test]
+async fn remembered_sessions_are_refreshed_until_left_unused_or_for_at_most_their_maximum_lifetime() {
+    let store = it::store().await;
+    let clock = TestClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap());
+    let store = store
+        .with_clock(clock.clone())
+        .with_session_lifetimes(SessionLifetimes {
+            token: Duration::try_hours(1).unwrap(),
+            remember_me: Duration::try_days(2).unwrap(),
+            remember_me_max: Duration::try_days(5).unwrap(),
+            ..SessionLifetimes::default()
+        });
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let refresh_cookie = |response: &warp::http::Response<warp::hyper::body::Bytes>| {
+        response
+            .headers()
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|cookie| cookie.to_str().unwrap().strip_prefix("refresh_token="))
+            .map(|cookie| cookie.split(';').next().unwrap().to_string())
+            .next()
+    };
+    let login = |query: &'static str| {
+        let (routes, email) = (routes.clone(), alice.email.clone());
+        async move {
+            warp::test::request()
+                .method("POST")
+                .path(&format!("/login{query}"))
+                .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+                .reply(&routes)
+                .await
+        }
+    };
+    let refresh = |refresh_token: String| {
+        let routes = routes.clone();
+        async move {
+            warp::test::request()
+                .method("POST")
+                .path("/refresh")
+                .header("Cookie", format!("refresh_token={refresh_token}"))
+                .reply(&routes)
+                .await
+        }
+    };
+    let status = |token: String| {
+        let routes = routes.clone();
+        async move {
+            warp::test::request()
+                .path("/accounts/me")
+                .header("Authorization", token)
+                .reply(&routes)
+                .await
+                .status()
+        }
+    };
+
+    let response = login("").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(refresh_cookie(&response), None);
+
+    let response = login("?remember_me=true").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = serde_json::from_slice::<String>(response.body()).unwrap();
+    let mut refresh_token = refresh_cookie(&response).unwrap();
+
+    clock.advance(Duration::try_hours(2).unwrap());
+    assert_eq!(status(token.clone()).await, StatusCode::UNAUTHORIZED);
+    let response = refresh(refresh_token.clone()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = serde_json::from_slice::<String>(response.body()).unwrap();
+    assert_eq!(status(token).await, StatusCode::OK);
+    let previous_refresh_token = std::mem::replace(&mut refresh_token, refresh_cookie(&response).unwrap());
+    let response = refresh(previous_refresh_token).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.body(), "refresh token is invalid or expired, log in again");
+
+    // Every refresh extends the session by two days, until five days after the login
+    for (elapsed, refreshed) in [(47, true), (47, true), (47, false)] {
+        clock.advance(Duration::try_hours(elapsed).unwrap());
+        let response = refresh(refresh_token.clone()).await;
+        match refreshed {
+            true => {
+                assert_eq!(response.status(), StatusCode::OK, "at {}", clock.now());
+                refresh_token = refresh_cookie(&response).unwrap();
+            }
+            false => assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "at {}", clock.now()),
+        }
+    }
+
+    let response = login("?remember_me=true").await;
+    let refresh_token = refresh_cookie(&response).unwrap();
+    clock.advance(Duration::try_hours(49).unwrap());
+    assert_eq!(refresh(refresh_token).await.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(refresh("unknown".to_string()).await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn remembered_sessions_are_extended_by_their_use_at_most_once_per_slide() {
+    let store = it::store().await;
+    let clock = TestClock::new(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap());
+    let store = store
+        .with_clock(clock.clone())
+        .with_session_lifetimes(SessionLifetimes {
+            token: Duration::try_hours(1).unwrap(),
+            remember_me: Duration::try_days(2).unwrap(),
+            remember_me_max: Duration::try_days(5).unwrap(),
+            remember_me_slide: Duration::try_minutes(30).unwrap(),
+        });
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await;
+    let login = |query: &'static str| {
+        let (routes, email) = (routes.clone(), alice.email.clone());
+        async move {
+            let response = warp::test::request()
+                .method("POST")
+                .path(&format!("/login{query}"))
+                .json(&json!({ "email": email, "password": DEFAULT_PASSWORD }))
+                .reply(&routes)
+                .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            serde_json::from_slice::<String>(response.body()).unwrap()
+        }
+    };
+    let expires_on = |token: String| {
+        let routes = routes.clone();
+        async move {
+            let response = warp::test::request()
+                .path("/accounts/me/sessions")
+                .header("Authorization", token)
+                .reply(&routes)
+                .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let sessions: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+            let current = sessions
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|session| session["current"] == true)
+                .unwrap()
+                .clone();
+            serde_json::from_value::<chrono::DateTime<Utc>>(current["expires_on"].clone()).unwrap()
+        }
+    };
+    let login_time = clock.now();
+    let remembered = login("?remember_me=true").await;
+    let not_remembered = login("").await;
+    let not_remembered_expiry = expires_on(not_remembered.clone()).await;
+    assert_eq!(
+        expires_on(remembered.clone()).await,
+        login_time + Duration::try_days(2).unwrap()
+    );
+
+    clock.advance(Duration::try_minutes(20).unwrap());
+    assert_eq!(
+        expires_on(remembered.clone()).await,
+        login_time + Duration::try_days(2).unwrap()
+    );
+
+    clock.advance(Duration::try_minutes(20).unwrap());
+    assert_eq!(
+        expires_on(remembered).await,
+        clock.now() + Duration::try_days(2).unwrap()
+    );
+    assert_eq!(expires_on(not_remembered).await, not_remembered_expiry);
+}
@@ -0,0 +1,90 @@
+//! Tests of the delivery of the events to the webhooks, by the jobs of the service, to a receiver
+//! served on a local port.
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use warp::http::{HeaderMap, StatusCode};
+use warp::hyper::body::Bytes;
+use warp::Filter;
+use webdev_book::jobs;
+use webdev_book::test_support::{an_account, authenticated, test_router};
+
+/// Serves a receiver of the deliveries on a local port, and returns its URL and the deliveries.
+fn receiver() -> (String, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+    let (sender, deliveries) = mpsc::unbounded_channel();
+    let route = warp::post()
+        .and(warp::header::headers_cloned())
+        .and(warp::body::bytes())
+        .map(move |headers, body| {
+            sender.send((headers, body)).unwrap();
+            StatusCode::OK
+        });
+    let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    (format!("http://{addr}/hook"), deliveries)
+}
+
+#[tokio::test]
+async fn the_subscribed_events_are_delivered_signed_with_the_secret() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+    let (url, mut deliveries) = receiver();
+    jobs::spawn_worker(store.clone());
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/webhooks")
+        .json(&json!({ "url": url, "events": ["question_created"], "secret": "shh" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let webhook: Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(webhook.get("secret"), None);
+
+    let response = authenticated(alice)
+        .method("POST")
+        .path("/questions")
+        .json(&json!({ "title": "Who receives the webhooks?", "content": "The registered URLs." }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let (headers, body) = tokio::time::timeout(Duration::from_secs(10), deliveries.recv())
+        .await
+        .expect("the event was not delivered")
+        .unwrap();
+    assert_eq!(headers["x-webhook-event"], "question_created");
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"shh").unwrap();
+    mac.update(&body);
+    let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(headers["x-webhook-signature"], signature.as_str());
+    let event: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(event["type"], "question_created");
+    assert_eq!(event["question"]["title"], "Who receives the webhooks?");
+}
+
+#[tokio::test]
+async fn the_webhooks_with_invalid_urls_or_events_are_rejected() {
+    let store = it::store().await;
+    let routes = test_router(&store);
+    let alice = an_account().insert(&store).await.id.unwrap();
+
+    for webhook in [
+        json!({ "url": "ftp://example.com/hook", "events": ["question_created"], "secret": "shh" }),
+        json!({ "url": "https://example.com/hook", "events": [], "secret": "shh" }),
+        json!({ "url": "https://example.com/hook", "events": ["question_viewed"], "secret": "shh" }),
+        json!({ "url": "https://example.com/hook", "events": ["question_created"], "secret": "" }),
+    ] {
+        let response = authenticated(alice)
+            .method("POST")
+            .path("/webhooks")
+            .json(&webhook)
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{webhook}");
+    }
+}
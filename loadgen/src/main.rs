@@ -0,0 +1,240 @@
+//! Load generator for the webdev book service.
+//!
+//! Drives a configurable mix of reads and writes against a running instance for a fixed duration,
+//! and reports the throughput and the latency percentiles of every operation.
+//!
+//! ```text
+//! loadgen --url http://localhost:8080 --duration 30 --concurrency 16 --read-ratio 0.9 \
+//!     --email user@example.com --password password
+//! ```
+//!
+//! The reads are a mix of `GET /questions` and `GET /questions/{id}`. The writes are
+//! `POST /questions`, and need the credentials of an account, without which only reads are sent.
+#![warn(clippy::all)]
+
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use hdrhistogram::Histogram;
+use rand::Rng;
+use webdev_client::types::pagination::Pagination;
+use webdev_client::types::question::{NewQuestion, QuestionId};
+use webdev_client::{Client, ClientError};
+
+/// Load generator for the webdev book service
+#[derive(Parser, Debug, Clone)]
+#[command(version, about)]
+struct Args {
+    /// URL at which the service is served
+    #[arg(long, env = "WEBDEV_URL", default_value = "http://localhost:8080")]
+    url: String,
+    /// How long to run, in seconds
+    #[arg(long, default_value_t = 10)]
+    duration: u64,
+    /// Number of concurrent workers, each sending one request at a time
+    #[arg(long, default_value_t = 8)]
+    concurrency: usize,
+    /// Share of the requests that are reads, between 0 and 1
+    #[arg(long, default_value_t = 0.9)]
+    read_ratio: f64,
+    /// Size of the pages of questions that are read
+    #[arg(long, default_value_t = 20)]
+    page_size: i64,
+    /// Email of the account creating the questions
+    #[arg(long, env = "WEBDEV_EMAIL")]
+    email: Option<String>,
+    /// Password of the account creating the questions
+    #[arg(long, env = "WEBDEV_PASSWORD", hide_env_values = true)]
+    password: Option<String>,
+}
+
+/// Operation sent by the workers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Operation {
+    /// `GET /questions`
+    ListQuestions,
+    /// `GET /questions/{id}`
+    GetQuestion,
+    /// `POST /questions`
+    AddQuestion,
+}
+
+impl Operation {
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::ListQuestions => "GET /questions",
+            Operation::GetQuestion => "GET /questions/{id}",
+            Operation::AddQuestion => "POST /questions",
+        }
+    }
+}
+
+/// Latencies and errors of a single operation.
+struct Stats {
+    /// Latencies of the successful requests, in microseconds
+    latencies: Histogram<u64>,
+    /// Number of failed requests
+    errors: u64,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            latencies: Histogram::new(3).expect("3 significant figures are supported"),
+            errors: 0,
+        }
+    }
+}
+
+impl Stats {
+    fn record(&mut self, latency: Duration, result: Result<(), ClientError>) {
+        match result {
+            Ok(()) => self.latencies.saturating_record(latency.as_micros() as u64),
+            Err(_) => self.errors += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &Stats) {
+        self.latencies
+            .add(&other.latencies)
+            .expect("histograms have the same bounds");
+        self.errors += other.errors;
+    }
+}
+
+/// Picks the next operation according to the read ratio.
+fn pick_operation(args: &Args, can_write: bool, known_ids: &[QuestionId]) -> Operation {
+    let mut rng = rand::thread_rng();
+    if can_write && !rng.gen_bool(args.read_ratio.clamp(0.0, 1.0)) {
+        Operation::AddQuestion
+    } else if known_ids.is_empty() || rng.gen_bool(0.5) {
+        Operation::ListQuestions
+    } else {
+        Operation::GetQuestion
+    }
+}
+
+/// Sends requests until the deadline, and returns the stats of every operation.
+async fn worker(client: Client, args: Args, can_write: bool, deadline: Instant) -> BTreeMap<Operation, Stats> {
+    let mut stats: BTreeMap<Operation, Stats> = BTreeMap::new();
+    let mut known_ids: Vec<QuestionId> = Vec::new();
+    let mut sequence = 0u64;
+
+    while Instant::now() < deadline {
+        let operation = pick_operation(&args, can_write, &known_ids);
+        let start = Instant::now();
+        let result = match operation {
+            Operation::ListQuestions => {
+                let offset = match known_ids.len() as i64 {
+                    0 => 0,
+                    known => rand::thread_rng().gen_range(0..known),
+                };
+                let pagination = Pagination {
+                    offset,
+                    limit: Some(args.page_size),
+                };
+                client.get_questions(pagination).await.map(|page| {
+                    known_ids.extend(page.items.iter().filter_map(|question| question.id));
+                    known_ids.sort_by_key(|QuestionId(id)| *id);
+                    known_ids.dedup();
+                })
+            }
+            Operation::GetQuestion => {
+                let id = known_ids[rand::thread_rng().gen_range(0..known_ids.len())];
+                client.get_question(id).await.map(|_| ())
+            }
+            Operation::AddQuestion => {
+                sequence += 1;
+                let question = NewQuestion {
+                    title: format!("Load test question {sequence}"),
+                    content: "Generated by loadgen".to_string(),
+                    tags: Some(vec!["loadgen".to_string()]),
+                };
+                // The generated titles are all alike, so they are asked regardless of the similar ones
+                client
+                    .add_question(&question, true)
+                    .await
+                    .map(|question| known_ids.extend(question.id))
+            }
+        };
+        stats.entry(operation).or_default().record(start.elapsed(), result);
+    }
+    stats
+}
+
+/// Prints the report for all operations.
+fn report(stats: &BTreeMap<Operation, Stats>, elapsed: Duration) {
+    println!(
+        "{:<22} {:>9} {:>7} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "operation", "requests", "errors", "req/s", "p50 ms", "p90 ms", "p99 ms", "max ms"
+    );
+    for (operation, stats) in stats {
+        let latencies = &stats.latencies;
+        let millis = |micros: u64| micros as f64 / 1000.0;
+        println!(
+            "{:<22} {:>9} {:>7} {:>10.1} {:>10.2} {:>10.2} {:>10.2} {:>10.2}",
+            operation.name(),
+            latencies.len(),
+            stats.errors,
+            latencies.len() as f64 / elapsed.as_secs_f64(),
+            millis(latencies.value_at_quantile(0.50)),
+            millis(latencies.value_at_quantile(0.90)),
+            millis(latencies.value_at_quantile(0.99)),
+            millis(latencies.max()),
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let client = match Client::new(&args.url) {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let can_write = match (&args.email, &args.password) {
+        (Some(email), Some(password)) => match client.login(email, password).await {
+            Ok(_) => true,
+            Err(error) => {
+                eprintln!("error: cannot log in: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("no credentials given, sending only reads");
+            false
+        }
+    };
+
+    println!(
+        "running for {}s with {} workers against {}",
+        args.duration, args.concurrency, args.url
+    );
+    let start = Instant::now();
+    let deadline = start + Duration::from_secs(args.duration);
+    let workers: Vec<_> = (0..args.concurrency)
+        .map(|_| tokio::spawn(worker(client.clone(), args.clone(), can_write, deadline)))
+        .collect();
+
+    let mut stats: BTreeMap<Operation, Stats> = BTreeMap::new();
+    for worker in workers {
+        match worker.await {
+            Ok(worker_stats) => {
+                for (operation, worker_stats) in worker_stats {
+                    stats.entry(operation).or_default().merge(&worker_stats);
+                }
+            }
+            Err(error) => eprintln!("worker failed: {error}"),
+        }
+    }
+
+    report(&stats, start.elapsed());
+    ExitCode::SUCCESS
+}
@@ -2,15 +2,20 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 
-use quote::quote;
-use syn::DeriveInput;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Expr, Fields, GenericArgument, LitStr, Path, PathArguments, Type};
 
 /// Derive the `From<i32>` and `FromStr` traits for types that represent a database object id.
 ///
 /// This macro is intended to be used with types that represent an id of a database object. It
 /// derives the `From<i32>` and `FromStr` traits for the type. The `From<i32>` trait allows
 /// converting an `i32` to the type, and the `FromStr` trait allows parsing a string to the type.
-/// String that is parsed to the type must be a valid `i32` string.
+/// String that is parsed to the type must be a valid non-negative `i32`, written with digits only,
+/// so ids like `-1` or `+1` are rejected instead of silently missing in the database.
+///
+/// Parsing errors are reported through a dedicated error type, generated next to the id type and
+/// named `Parse{Name}Error` (e.g. `ParseAccountIdError` for `AccountId`). The error type
+/// implements [`std::error::Error`] and tells apart a missing id from a malformed one.
 /// ```
 /// use macros::DbObjectId;
 ///
@@ -22,12 +27,25 @@ use syn::DeriveInput;
 ///
 /// let id: AccountId = "1".parse().unwrap();
 /// debug_assert_eq!(id.0, 1);
+///
+/// let error = "one".parse::<AccountId>().unwrap_err();
+/// debug_assert_eq!(error, ParseAccountIdError::Invalid("one".to_string()));
+/// debug_assert_eq!(error.to_string(), "invalid AccountId format: \"one\"");
+///
+/// let error = "-1".parse::<AccountId>().unwrap_err();
+/// debug_assert_eq!(error, ParseAccountIdError::Invalid("-1".to_string()));
 /// ```
 ///
 #[proc_macro_derive(DbObjectId)]
 pub fn derive_db_object_id_fn(_item: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(_item).unwrap();
     let name = &ast.ident;
+    let vis = &ast.vis;
+    let error = format_ident!("Parse{}Error", name);
+    let error_doc = format!("Error returned when a string cannot be parsed as [`{name}`].");
+    let invalid_message = format!("invalid {name} format: {{0:?}}");
+    let empty_message = format!("no {name} provided");
+
     TokenStream::from(quote!(
         impl From<i32> for #name {
             fn from(id: i32) -> Self {
@@ -35,22 +53,496 @@ pub fn derive_db_object_id_fn(_item: TokenStream) -> TokenStream {
             }
         }
 
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error {
+            /// The provided string was empty.
+            Empty,
+            /// The provided string is not a valid non-negative `i32`.
+            Invalid(String),
+        }
+
+        impl std::fmt::Display for #error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::Empty => write!(f, #empty_message),
+                    Self::Invalid(id) => write!(f, #invalid_message, id),
+                }
+            }
+        }
+
+        impl std::error::Error for #error {}
+
         impl std::str::FromStr for #name {
-            type Err = std::io::Error;
+            type Err = #error;
 
             fn from_str(id: &str) -> Result<Self, Self::Err> {
-                id.parse()
-                .map(Self)
-                .map_err(
-                    |_| Self::Err::new(
-                        std::io::ErrorKind::InvalidInput,
-                        if id.is_empty() {
-                            "No id provided"
-                        } else {
-                            "Invalid id format"
-                        }
-                    ))
+                if id.is_empty() {
+                    return Err(#error::Empty);
+                }
+                if !id.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return Err(#error::Invalid(id.to_string()));
+                }
+
+                id.parse().map(Self).map_err(|_| #error::Invalid(id.to_string()))
             }
         }
     ))
 }
+
+/// Derive a typed extractor for query parameters.
+///
+/// This macro is intended to be used with structs that are extracted from the query string of a
+/// request, which warp hands over as a `HashMap<String, String>`. It generates an `extract`
+/// associated function that looks up every field in the map and parses it with `FromStr`.
+///
+/// Fields are handled according to their type and `#[query(...)]` attributes:
+/// - `Option<T>` fields are `None` when the parameter is missing.
+/// - Fields with `#[query(default = expr)]` fall back to `expr` when the parameter is missing.
+/// - All other fields are required.
+/// - `#[query(rename = "name")]` reads the field from the parameter `name` instead of the field name.
+/// - `#[query(validate = path)]` checks the parsed value with the function at `path`, which takes a
+///   reference to the value and returns `Err(reason)` when the value is out of range.
+///
+/// With `#[query(deny_unknown)]` on the struct, parameters that do not belong to any field are rejected.
+///
+/// Errors are reported through a dedicated error type, generated next to the struct and named
+/// `{Name}ParsingError` (e.g. `PaginationParsingError` for `Pagination`). Its messages contain the
+/// name of the offending parameter, the rejected value, and the reason it was rejected.
+/// ```
+/// use std::collections::HashMap;
+///
+/// use macros::QueryParams;
+///
+/// #[derive(QueryParams, Debug)]
+/// struct Pagination {
+///     #[query(default = 0)]
+///     offset: i64,
+///     limit: Option<i64>,
+/// }
+///
+/// let params = HashMap::from([("limit".to_string(), "10".to_string())]);
+/// let pagination = Pagination::extract(&params).unwrap();
+/// debug_assert_eq!(pagination.offset, 0);
+/// debug_assert_eq!(pagination.limit, Some(10));
+///
+/// let params = HashMap::from([("offset".to_string(), "ten".to_string())]);
+/// let error = Pagination::extract(&params).unwrap_err();
+/// debug_assert_eq!(
+///     error.to_string(),
+///     "invalid value \"ten\" for query parameter \"offset\": invalid digit found in string"
+/// );
+/// ```
+///
+/// Values can be checked beyond parsing, and unknown parameters rejected:
+/// ```
+/// use std::collections::HashMap;
+///
+/// use macros::QueryParams;
+///
+/// fn non_negative(value: &i64) -> Result<(), String> {
+///     match *value >= 0 {
+///         true => Ok(()),
+///         false => Err("must not be negative".to_string()),
+///     }
+/// }
+///
+/// #[derive(QueryParams, Debug)]
+/// #[query(deny_unknown)]
+/// struct Pagination {
+///     #[query(default = 0, validate = non_negative)]
+///     offset: i64,
+/// }
+///
+/// let params = HashMap::from([("offset".to_string(), "-1".to_string())]);
+/// let error = Pagination::extract(&params).unwrap_err();
+/// debug_assert_eq!(
+///     error.to_string(),
+///     "invalid value \"-1\" for query parameter \"offset\": must not be negative"
+/// );
+///
+/// let params = HashMap::from([("page".to_string(), "1".to_string())]);
+/// let error = Pagination::extract(&params).unwrap_err();
+/// debug_assert_eq!(error.to_string(), "unknown query parameter \"page\"");
+/// ```
+///
+#[proc_macro_derive(QueryParams, attributes(query))]
+pub fn derive_query_params_fn(item: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(item).unwrap();
+    match expand_query_params(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Options of a single field, parsed from its `#[query(...)]` attributes.
+struct QueryField {
+    /// Name of the query parameter the field is read from.
+    parameter: String,
+    /// Expression used when the parameter is missing.
+    default: Option<Expr>,
+    /// Function checking the parsed value.
+    validate: Option<Path>,
+}
+
+impl QueryField {
+    /// Parses the `#[query(...)]` attributes of a field.
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut options = QueryField {
+            parameter: field.ident.as_ref().unwrap().to_string(),
+            default: None,
+            validate: None,
+        };
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("query")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    options.default = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    options.parameter = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("validate") {
+                    options.validate = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported query attribute, expected `default`, `rename` or `validate`"))
+                }
+            })?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// Checks whether the struct has the `#[query(deny_unknown)]` attribute.
+fn denies_unknown(ast: &DeriveInput) -> syn::Result<bool> {
+    let mut deny_unknown = false;
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("query")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("deny_unknown") {
+                deny_unknown = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported query attribute, expected `deny_unknown`"))
+            }
+        })?;
+    }
+    Ok(deny_unknown)
+}
+
+/// Returns the inner type if the type is an `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// Generates the `extract` function and the error type for the [QueryParams] derive.
+fn expand_query_params(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let error = format_ident!("{}ParsingError", name);
+    let error_doc = format!("Error returned when the query parameters cannot be extracted as [`{name}`].");
+
+    let Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new_spanned(
+            ast,
+            "QueryParams can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(ast, "QueryParams requires named fields"));
+    };
+
+    let mut extractions = Vec::new();
+    let mut field_names = Vec::new();
+    let mut parameters = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let QueryField {
+            parameter,
+            default,
+            validate,
+        } = QueryField::parse(field)?;
+
+        let (ty, missing) = match (option_inner(&field.ty), default) {
+            (Some(inner), None) => (inner, quote!(None)),
+            (None, Some(default)) => (&field.ty, quote!(#default)),
+            (None, None) => (&field.ty, quote!(return Err(#error::Missing { parameter: #parameter }))),
+            (Some(_), Some(default)) => {
+                return Err(syn::Error::new_spanned(
+                    default,
+                    "optional fields cannot have a default",
+                ));
+            }
+        };
+        let present = if option_inner(&field.ty).is_some() {
+            quote!(Some(value))
+        } else {
+            quote!(value)
+        };
+        let validation = validate.map(|validate| {
+            quote! {
+                #validate(&parsed).map_err(|reason| #error::Invalid {
+                    parameter: #parameter,
+                    value: value.clone(),
+                    reason,
+                })?;
+            }
+        });
+
+        extractions.push(quote! {
+            let #ident = match params.get(#parameter) {
+                Some(value) => {
+                    let parsed = value.parse::<#ty>().map_err(|error| #error::Invalid {
+                        parameter: #parameter,
+                        value: value.clone(),
+                        reason: error.to_string(),
+                    })?;
+                    #validation
+                    let value = parsed;
+                    #present
+                }
+                None => #missing,
+            };
+        });
+        field_names.push(ident);
+        parameters.push(parameter);
+    }
+
+    let unknown_check = denies_unknown(ast)?.then(|| {
+        quote! {
+            if let Some(parameter) = params.keys().find(|key| !Self::PARAMETERS.contains(&key.as_str())) {
+                return Err(#error::Unknown { parameter: parameter.clone() });
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error {
+            /// A required parameter was not provided.
+            Missing {
+                /// Name of the missing parameter.
+                parameter: &'static str,
+            },
+            /// A parameter was provided, but its value could not be parsed.
+            Invalid {
+                /// Name of the invalid parameter.
+                parameter: &'static str,
+                /// The rejected value.
+                value: String,
+                /// Why the value was rejected.
+                reason: String,
+            },
+            /// A parameter that does not belong to any field was provided.
+            Unknown {
+                /// Name of the unknown parameter.
+                parameter: String,
+            },
+        }
+
+        impl std::fmt::Display for #error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::Missing { parameter } => write!(f, "missing query parameter {parameter:?}"),
+                    Self::Invalid { parameter, value, reason } => {
+                        write!(f, "invalid value {value:?} for query parameter {parameter:?}: {reason}")
+                    }
+                    Self::Unknown { parameter } => write!(f, "unknown query parameter {parameter:?}"),
+                }
+            }
+        }
+
+        impl std::error::Error for #error {}
+
+        impl #name {
+            /// Names of the query parameters the struct is extracted from.
+            #vis const PARAMETERS: &'static [&'static str] = &[#(#parameters),*];
+
+            /// Extracts the struct from the query parameters of a request.
+            #vis fn extract(params: &std::collections::HashMap<String, String>) -> Result<Self, #error> {
+                #unknown_check
+                #(#extractions)*
+                Ok(Self { #(#field_names),* })
+            }
+        }
+    })
+}
+
+/// Derive a builder for a struct.
+///
+/// This macro generates a `{Name}Builder` type with one setter per field, and a `builder`
+/// associated function on the struct that creates an empty builder. Setters accept anything that
+/// can be converted into the type of the field, so string literals can be passed to `String`
+/// fields directly.
+///
+/// Fields are handled according to their type and `#[builder(...)]` attributes:
+/// - `Option<T>` fields are `None` unless set, and their setter accepts either a `T` or an `Option<T>`.
+/// - Fields with `#[builder(default)]` fall back to `Default::default()` unless set.
+/// - Fields with `#[builder(default = expr)]` fall back to `expr` unless set.
+/// - All other fields are required.
+///
+/// Calling `build` with a required field left unset returns a dedicated error type, generated
+/// next to the struct and named `{Name}BuilderError`.
+/// ```
+/// use macros::Builder;
+///
+/// #[derive(Builder, Debug)]
+/// struct Question {
+///     id: Option<i32>,
+///     title: String,
+///     #[builder(default)]
+///     tags: Vec<String>,
+/// }
+///
+/// let question = Question::builder().title("How?").build().unwrap();
+/// debug_assert_eq!(question.id, None);
+/// debug_assert_eq!(question.title, "How?");
+/// debug_assert!(question.tags.is_empty());
+///
+/// let error = Question::builder().id(1).build().unwrap_err();
+/// debug_assert_eq!(error.to_string(), "missing field `title` when building Question");
+/// ```
+///
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder_fn(item: TokenStream) -> TokenStream {
+    let ast: DeriveInput = syn::parse(item).unwrap();
+    match expand_builder(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Parses the `#[builder(...)]` attributes of a field, returning the default value expression.
+///
+/// `#[builder(default)]` is returned as `Default::default()`.
+fn builder_default(field: &syn::Field) -> syn::Result<Option<Expr>> {
+    let mut default = None;
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                default = Some(if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse()?
+                } else {
+                    syn::parse_quote!(Default::default())
+                });
+                Ok(())
+            } else {
+                Err(meta.error("unsupported builder attribute, expected `default`"))
+            }
+        })?;
+    }
+
+    Ok(default)
+}
+
+/// Generates the builder type and the error type for the [Builder] derive.
+fn expand_builder(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let builder = format_ident!("{}Builder", name);
+    let error = format_ident!("{}BuilderError", name);
+    let builder_doc = format!("Builder for [`{name}`], created with [`{name}::builder`].");
+    let error_doc = format!("Error returned when a required field of [`{name}`] was not set on the builder.");
+    let error_message = format!("missing field `{{0}}` when building {name}");
+
+    let Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new_spanned(ast, "Builder can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(ast, "Builder requires named fields"));
+    };
+
+    let mut builder_fields = Vec::new();
+    let mut setters = Vec::new();
+    let mut assignments = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let field_name = ident.to_string();
+        let default = builder_default(field)?;
+        let inner = option_inner(&field.ty);
+        let ty = inner.unwrap_or(&field.ty);
+
+        builder_fields.push(quote!(#ident: Option<#ty>));
+        setters.push(match inner {
+            Some(_) => quote! {
+                #[doc = concat!("Sets the `", #field_name, "` field, which also accepts an `Option`.")]
+                #vis fn #ident(mut self, #ident: impl Into<Option<#ty>>) -> Self {
+                    self.#ident = #ident.into();
+                    self
+                }
+            },
+            None => quote! {
+                #[doc = concat!("Sets the `", #field_name, "` field.")]
+                #vis fn #ident(mut self, #ident: impl Into<#ty>) -> Self {
+                    self.#ident = Some(#ident.into());
+                    self
+                }
+            },
+        });
+        assignments.push(match (inner, default) {
+            (Some(_), None) => quote!(#ident: self.#ident),
+            (None, Some(default)) => quote!(#ident: self.#ident.unwrap_or_else(|| #default)),
+            (None, None) => quote!(#ident: self.#ident.ok_or(#error(#field_name))?),
+            (Some(_), Some(default)) => {
+                return Err(syn::Error::new_spanned(
+                    default,
+                    "optional fields cannot have a default",
+                ));
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #[derive(Debug, Clone, Default)]
+        #vis struct #builder {
+            #(#builder_fields),*
+        }
+
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #error(pub &'static str);
+
+        impl std::fmt::Display for #error {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, #error_message, self.0)
+            }
+        }
+
+        impl std::error::Error for #error {}
+
+        impl #name {
+            /// Creates a builder with none of the fields set.
+            #vis fn builder() -> #builder {
+                #builder::default()
+            }
+        }
+
+        impl #builder {
+            #(#setters)*
+
+            /// Builds the struct, failing if any of the required fields was not set.
+            #vis fn build(self) -> Result<#name, #error> {
+                Ok(#name {
+                    #(#assignments),*
+                })
+            }
+        }
+    })
+}
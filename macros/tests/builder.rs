@@ -0,0 +1,50 @@
+//! Tests of the `Builder` derive, on the cases the example of its documentation leaves out.
+use macros::Builder;
+
+/// An answer, with every kind of field.
+#[derive(Builder, Debug, Clone, PartialEq)]
+struct Answer {
+    id: Option<i32>,
+    content: String,
+    question_id: i32,
+    #[builder(default = 1)]
+    score: i64,
+    #[builder(default)]
+    accepted: bool,
+}
+
+#[test]
+fn the_fields_left_unset_get_their_defaults() {
+    let answer = Answer::builder()
+        .content("Use warp::test")
+        .question_id(1)
+        .build()
+        .unwrap();
+    assert_eq!(
+        answer,
+        Answer {
+            id: None,
+            content: "Use warp::test".to_string(),
+            question_id: 1,
+            score: 1,
+            accepted: false,
+        }
+    );
+}
+
+#[test]
+fn the_optional_fields_are_set_from_a_value_or_an_option() {
+    let builder = Answer::builder().content("Use warp::test").question_id(1);
+    assert_eq!(builder.clone().id(2).build().unwrap().id, Some(2));
+    assert_eq!(builder.clone().id(Some(2)).id(None).build().unwrap().id, None);
+    assert_eq!(builder.score(5).accepted(true).build().unwrap().score, 5);
+}
+
+#[test]
+fn the_first_required_field_left_unset_is_reported() {
+    let error = Answer::builder().build().unwrap_err();
+    assert_eq!(error, AnswerBuilderError("content"));
+
+    let error = Answer::builder().content("Use warp::test").build().unwrap_err();
+    assert_eq!(error.to_string(), "missing field `question_id` when building Answer");
+}
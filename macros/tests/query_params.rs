@@ -0,0 +1,61 @@
+//! Tests of the `QueryParams` derive, on the cases the examples of its documentation leave out.
+use std::collections::HashMap;
+
+use macros::QueryParams;
+
+/// Parameters of a search, with every kind of field.
+#[derive(QueryParams, Debug, PartialEq)]
+struct Search {
+    /// Required, read from another parameter than its name.
+    #[query(rename = "q")]
+    text: String,
+    #[query(default = 10)]
+    size: u32,
+    tag: Option<String>,
+}
+
+fn params(params: &[(&str, &str)]) -> HashMap<String, String> {
+    params
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn the_fields_are_read_from_their_parameters_or_defaulted() {
+    let search = Search::extract(&params(&[("q", "warp"), ("tag", "rust")])).unwrap();
+    assert_eq!(
+        search,
+        Search {
+            text: "warp".to_string(),
+            size: 10,
+            tag: Some("rust".to_string()),
+        }
+    );
+
+    let search = Search::extract(&params(&[("q", "warp"), ("size", "5")])).unwrap();
+    assert_eq!((search.size, search.tag), (5, None));
+    assert_eq!(Search::PARAMETERS, ["q", "size", "tag"]);
+}
+
+#[test]
+fn the_missing_required_parameters_are_rejected_by_their_name() {
+    let error = Search::extract(&params(&[("text", "warp")])).unwrap_err();
+    assert_eq!(error, SearchParsingError::Missing { parameter: "q" });
+    assert_eq!(error.to_string(), "missing query parameter \"q\"");
+}
+
+#[test]
+fn the_unknown_parameters_are_ignored_unless_denied() {
+    let search = Search::extract(&params(&[("q", "warp"), ("page", "2")])).unwrap();
+    assert_eq!(search.text, "warp");
+}
+
+#[test]
+fn the_invalid_values_are_rejected_with_the_reason() {
+    let error = Search::extract(&params(&[("q", "warp"), ("size", "-1")])).unwrap_err();
+    assert!(matches!(
+        error,
+        SearchParsingError::Invalid { parameter: "size", ref value, .. } if value == "-1"
+    ));
+}
@@ -2,4 +2,9 @@
 fn main() {
     // trigger recompilation when a new migration is added
     println!("cargo:rerun-if-changed=migrations");
+
+    // compile the gRPC service definitions with the vendored protoc
+    println!("cargo:rerun-if-changed=proto");
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+    tonic_build::compile_protos("proto/webdev_book.proto").unwrap();
 }
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS webhooks
+(
+    id         SERIAL PRIMARY KEY,
+    url        TEXT      NOT NULL,
+    events     TEXT[]    NOT NULL,
+    secret     TEXT      NOT NULL,
+    account_id INTEGER   NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    created_on TIMESTAMP NOT NULL DEFAULT NOW()
+);
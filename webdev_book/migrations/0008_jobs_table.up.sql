@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS jobs
+(
+    id           SERIAL PRIMARY KEY,
+    kind         TEXT        NOT NULL,
+    payload      JSONB       NOT NULL,
+    status       TEXT        NOT NULL DEFAULT 'pending',
+    attempts     INTEGER     NOT NULL DEFAULT 0,
+    max_attempts INTEGER     NOT NULL DEFAULT 5,
+    last_error   TEXT,
+    run_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_on   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_on   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT job_status CHECK (status IN ('pending', 'running', 'done', 'failed'))
+);
+
+CREATE INDEX IF NOT EXISTS jobs_pending_idx ON jobs (run_at) WHERE status = 'pending';
@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS attachments
+(
+    id           SERIAL PRIMARY KEY,
+    question_id  INTEGER   NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    file_name    TEXT      NOT NULL,
+    content_type TEXT      NOT NULL,
+    size         BIGINT    NOT NULL,
+    storage_key  TEXT      NOT NULL UNIQUE,
+    created_on   TIMESTAMP NOT NULL DEFAULT NOW()
+);
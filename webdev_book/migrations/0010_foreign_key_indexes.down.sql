@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS attachments_question_id_idx;
+DROP INDEX IF EXISTS answers_account_id_idx;
+DROP INDEX IF EXISTS answers_question_id_idx;
+DROP INDEX IF EXISTS questions_account_id_idx;
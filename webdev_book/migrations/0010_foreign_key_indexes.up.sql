@@ -0,0 +1,7 @@
+-- The foreign keys are not indexed by PostgreSQL on their own, so the lookups by the owner or by
+-- the question, and the cascading deletes, scan the whole tables without these indexes.
+-- accounts.email is already indexed by its UNIQUE constraint.
+CREATE INDEX IF NOT EXISTS questions_account_id_idx ON questions (account_id);
+CREATE INDEX IF NOT EXISTS answers_question_id_idx ON answers (question_id);
+CREATE INDEX IF NOT EXISTS answers_account_id_idx ON answers (account_id);
+CREATE INDEX IF NOT EXISTS attachments_question_id_idx ON attachments (question_id);
@@ -0,0 +1,3 @@
+ALTER TABLE answers
+    DROP CONSTRAINT answers_question_id_fkey,
+    ADD CONSTRAINT answers_question_id_fkey FOREIGN KEY (question_id) REFERENCES questions (id);
@@ -0,0 +1,4 @@
+-- Deleting a question failed for as long as it had answers, delete them together with the question instead.
+ALTER TABLE answers
+    DROP CONSTRAINT answers_question_id_fkey,
+    ADD CONSTRAINT answers_question_id_fkey FOREIGN KEY (question_id) REFERENCES questions (id) ON DELETE CASCADE;
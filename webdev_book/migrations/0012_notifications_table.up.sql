@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS notifications
+(
+    id          SERIAL PRIMARY KEY,
+    account_id  INTEGER     NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    question_id INTEGER     NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    answer_id   INTEGER     NOT NULL REFERENCES answers (id) ON DELETE CASCADE,
+    read        BOOLEAN     NOT NULL DEFAULT FALSE,
+    created_on  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- The job notifying about an answer can be retried, without notifying anyone twice
+    UNIQUE (account_id, answer_id)
+);
+-- account_id is indexed by the UNIQUE constraint, the other foreign keys need indexes for the cascading deletes.
+CREATE INDEX IF NOT EXISTS notifications_answer_id_idx ON notifications (answer_id);
+CREATE INDEX IF NOT EXISTS notifications_question_id_idx ON notifications (question_id);
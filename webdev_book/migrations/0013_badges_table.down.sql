@@ -0,0 +1 @@
+DROP TABLE IF EXISTS badges;
\ No newline at end of file
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS badges
+(
+    account_id INTEGER     NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    badge      TEXT        NOT NULL,
+    awarded_on TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Every badge is awarded to an account at most once
+    PRIMARY KEY (account_id, badge),
+    CONSTRAINT badge_name CHECK (badge IN ('first_question', 'ten_answers', 'popular_question'))
+);
@@ -0,0 +1,3 @@
+ALTER TABLE accounts
+    DROP COLUMN banned_until,
+    DROP COLUMN ban_reason;
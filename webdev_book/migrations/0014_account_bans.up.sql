@@ -0,0 +1,4 @@
+-- An account is banned while ban_reason is set, until banned_until, or for good if it is not set.
+ALTER TABLE accounts
+    ADD COLUMN banned_until TIMESTAMPTZ,
+    ADD COLUMN ban_reason   TEXT;
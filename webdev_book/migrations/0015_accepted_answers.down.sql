@@ -0,0 +1,4 @@
+DROP INDEX answers_accepted_idx;
+
+ALTER TABLE answers
+    DROP COLUMN accepted;
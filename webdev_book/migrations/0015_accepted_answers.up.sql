@@ -0,0 +1,5 @@
+-- A question has at most one accepted answer, chosen by the owner of the question.
+ALTER TABLE answers
+    ADD COLUMN accepted BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE UNIQUE INDEX answers_accepted_idx ON answers (question_id) WHERE accepted;
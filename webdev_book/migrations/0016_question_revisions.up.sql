@@ -0,0 +1,13 @@
+-- The previous versions of the questions, recorded whenever a question is updated.
+CREATE TABLE IF NOT EXISTS question_revisions
+(
+    id          SERIAL PRIMARY KEY,
+    question_id INTEGER      NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    version     INTEGER      NOT NULL,
+    title       VARCHAR(255) NOT NULL,
+    content     TEXT         NOT NULL,
+    tags        TEXT[],
+    replaced_on TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    -- Also indexes question_id, for the listings and the cascading deletes
+    UNIQUE (question_id, version)
+);
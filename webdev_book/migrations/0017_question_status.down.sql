@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS questions_duplicate_of_idx;
+
+ALTER TABLE questions
+    DROP COLUMN duplicate_of;
+ALTER TABLE questions
+    DROP COLUMN status;
@@ -0,0 +1,8 @@
+-- A question is open, closed, or closed as a duplicate of another question.
+-- The duplicate_of column is cleared when the other question is deleted, and the question is then just closed.
+ALTER TABLE questions
+    ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'closed', 'duplicate_of'));
+ALTER TABLE questions
+    ADD COLUMN duplicate_of INTEGER REFERENCES questions (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS questions_duplicate_of_idx ON questions (duplicate_of);
@@ -0,0 +1,4 @@
+-- The titles are compared by their trigrams, to find the questions similar to a new one.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS questions_title_trgm_idx ON questions USING GIN (title gin_trgm_ops);
@@ -0,0 +1,4 @@
+ALTER TABLE answers
+    DROP COLUMN content_html;
+ALTER TABLE questions
+    DROP COLUMN content_html;
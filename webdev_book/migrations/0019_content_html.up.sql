@@ -0,0 +1,6 @@
+-- The content rendered to sanitized HTML, written by the store with the content.
+-- It is NULL for the content written before the column was added, which is rendered when it is read.
+ALTER TABLE questions
+    ADD COLUMN content_html TEXT;
+ALTER TABLE answers
+    ADD COLUMN content_html TEXT;
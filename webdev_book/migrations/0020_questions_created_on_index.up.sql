@@ -0,0 +1,2 @@
+-- The questions are listed from the most recent to the oldest, and polled for the ones created since a given time
+CREATE INDEX IF NOT EXISTS questions_created_on_idx ON questions (created_on DESC, id DESC);
@@ -0,0 +1,8 @@
+-- Every account has at most one vote on a question, which is +1 or -1
+CREATE TABLE IF NOT EXISTS question_votes
+(
+    question_id INTEGER  NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    account_id  INTEGER  NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    value       SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    PRIMARY KEY (question_id, account_id)
+);
@@ -0,0 +1,8 @@
+-- Every account has at most one vote on an answer, which is +1 or -1
+CREATE TABLE IF NOT EXISTS answer_votes
+(
+    answer_id  INTEGER  NOT NULL REFERENCES answers (id) ON DELETE CASCADE,
+    account_id INTEGER  NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    value      SMALLINT NOT NULL CHECK (value IN (-1, 1)),
+    PRIMARY KEY (answer_id, account_id)
+);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS answers_score_idx;
+
+ALTER TABLE answers
+    DROP COLUMN score;
+ALTER TABLE questions
+    DROP COLUMN score;
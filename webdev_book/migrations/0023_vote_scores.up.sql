@@ -0,0 +1,18 @@
+-- The scores are kept with the questions and the answers, and changed in the same transaction as the votes,
+-- so the listings read them without aggregating the votes.
+ALTER TABLE questions
+    ADD COLUMN score BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE answers
+    ADD COLUMN score BIGINT NOT NULL DEFAULT 0;
+
+UPDATE questions
+SET score = votes.score
+FROM (SELECT question_id, SUM(value) AS score FROM question_votes GROUP BY question_id) AS votes
+WHERE votes.question_id = questions.id;
+UPDATE answers
+SET score = votes.score
+FROM (SELECT answer_id, SUM(value) AS score FROM answer_votes GROUP BY answer_id) AS votes
+WHERE votes.answer_id = answers.id;
+
+-- The answers to a question are listed by their score by default
+CREATE INDEX IF NOT EXISTS answers_score_idx ON answers (question_id, accepted DESC, score DESC, id);
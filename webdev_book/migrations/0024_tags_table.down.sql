@@ -0,0 +1,13 @@
+ALTER TABLE questions
+    ADD COLUMN tags TEXT[];
+
+UPDATE questions
+SET tags = question_tags.tags
+FROM (SELECT question_tags.question_id, array_agg(tags.name ORDER BY question_tags.position) AS tags
+      FROM question_tags
+               JOIN tags ON tags.id = question_tags.tag_id
+      GROUP BY question_tags.question_id) AS question_tags
+WHERE question_tags.question_id = questions.id;
+
+DROP TABLE IF EXISTS question_tags;
+DROP TABLE IF EXISTS tags;
@@ -0,0 +1,30 @@
+-- The tags are kept once, and linked to the questions using them, so they can be listed with their usage
+CREATE TABLE IF NOT EXISTS tags
+(
+    id   SERIAL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS question_tags
+(
+    question_id INTEGER  NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    tag_id      INTEGER  NOT NULL REFERENCES tags (id) ON DELETE CASCADE,
+    -- The tags of a question are listed in the order they were written
+    position    SMALLINT NOT NULL,
+    PRIMARY KEY (question_id, tag_id)
+);
+-- question_id is indexed by the primary key, tag_id needs an index for the usage counts and the cascading deletes.
+CREATE INDEX IF NOT EXISTS question_tags_tag_id_idx ON question_tags (tag_id);
+
+INSERT INTO tags (name)
+SELECT DISTINCT unnest(tags)
+FROM questions
+ON CONFLICT (name) DO NOTHING;
+INSERT INTO question_tags (question_id, tag_id, position)
+SELECT DISTINCT ON (questions.id, tags.id) questions.id, tags.id, question_tag.position
+FROM questions
+         CROSS JOIN LATERAL unnest(questions.tags) WITH ORDINALITY AS question_tag (name, position)
+         JOIN tags ON tags.name = question_tag.name
+ORDER BY questions.id, tags.id, question_tag.position;
+
+ALTER TABLE questions
+    DROP COLUMN tags;
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS tag_subscriptions;
\ No newline at end of file
@@ -0,0 +1,11 @@
+-- The tags followed by the accounts, whose questions make up the feed of the account
+CREATE TABLE IF NOT EXISTS tag_subscriptions
+(
+    account_id INTEGER     NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    tag_id     INTEGER     NOT NULL REFERENCES tags (id) ON DELETE CASCADE,
+    created_on TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Every tag is followed by an account at most once
+    PRIMARY KEY (account_id, tag_id)
+);
+-- account_id is indexed by the primary key, tag_id needs an index for the cascading deletes.
+CREATE INDEX IF NOT EXISTS tag_subscriptions_tag_id_idx ON tag_subscriptions (tag_id);
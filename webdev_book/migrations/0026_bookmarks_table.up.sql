@@ -0,0 +1,11 @@
+-- The questions saved by the accounts for later
+CREATE TABLE IF NOT EXISTS bookmarks
+(
+    account_id  INTEGER     NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    question_id INTEGER     NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    created_on  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Every question is bookmarked by an account at most once
+    PRIMARY KEY (account_id, question_id)
+);
+-- account_id is indexed by the primary key, question_id needs an index for the cascading deletes.
+CREATE INDEX IF NOT EXISTS bookmarks_question_id_idx ON bookmarks (question_id);
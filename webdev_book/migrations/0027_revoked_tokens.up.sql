@@ -0,0 +1,8 @@
+-- The tokens revoked before they expire, by their jti claim, e.g. on logout
+CREATE TABLE IF NOT EXISTS revoked_tokens
+(
+    jti        TEXT PRIMARY KEY,
+    -- The revocations are kept until the tokens expire, as the expired tokens are rejected anyway
+    expires_on TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX IF NOT EXISTS revoked_tokens_expires_on_idx ON revoked_tokens (expires_on);
@@ -0,0 +1,2 @@
+ALTER TABLE accounts
+    DROP COLUMN created_at;
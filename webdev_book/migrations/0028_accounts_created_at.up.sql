@@ -0,0 +1,4 @@
+-- The time the account was registered at.
+-- The accounts registered before the column was added get the time of the migration instead.
+ALTER TABLE accounts
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
@@ -0,0 +1,4 @@
+ALTER TABLE accounts
+    DROP COLUMN display_name,
+    DROP COLUMN bio,
+    DROP COLUMN website;
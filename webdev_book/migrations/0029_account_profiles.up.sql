@@ -0,0 +1,5 @@
+-- The public profile of an account, set by the account itself. The fields are not set by default.
+ALTER TABLE accounts
+    ADD COLUMN display_name VARCHAR(64),
+    ADD COLUMN bio          TEXT,
+    ADD COLUMN website      TEXT;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS moderation_log;
+ALTER TABLE accounts
+    DROP COLUMN role;
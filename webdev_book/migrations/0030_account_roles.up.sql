@@ -0,0 +1,17 @@
+-- The role of an account. The moderators and the administrators may edit and delete the content of the other accounts.
+ALTER TABLE accounts
+    ADD COLUMN role VARCHAR(16) NOT NULL DEFAULT 'user' CHECK (role IN ('user', 'moderator', 'admin'));
+
+-- The audit trail of the changes made by the moderators to the content of the other accounts.
+-- The question is not referenced, so the entries are kept after the question is deleted.
+CREATE TABLE IF NOT EXISTS moderation_log
+(
+    id           SERIAL PRIMARY KEY,
+    moderator_id INTEGER     REFERENCES accounts (id) ON DELETE SET NULL,
+    action       VARCHAR(32) NOT NULL,
+    question_id  INTEGER     NOT NULL,
+    owner_id     INTEGER     NOT NULL,
+    created_on   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+-- The foreign key needs an index for the deletes of the accounts
+CREATE INDEX IF NOT EXISTS moderation_log_moderator_id_idx ON moderation_log (moderator_id);
@@ -0,0 +1,13 @@
+-- The sessions started by logging in, one for every token issued. Deleting a session signs its
+-- device out, as the tokens naming a session are rejected once it is gone.
+CREATE TABLE IF NOT EXISTS sessions
+(
+    id         SERIAL PRIMARY KEY,
+    account_id INTEGER     NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    jti        TEXT        NOT NULL UNIQUE,
+    user_agent TEXT,
+    created_on TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_on TIMESTAMPTZ NOT NULL
+);
+-- The sessions are listed by account, and the foreign key needs an index for the deletes of the accounts
+CREATE INDEX IF NOT EXISTS sessions_account_id_idx ON sessions (account_id);
@@ -0,0 +1,3 @@
+ALTER TABLE accounts
+    DROP COLUMN locked_on,
+    DROP COLUMN failed_logins;
@@ -0,0 +1,5 @@
+-- The failed logins of the account since its last login, and the time it was locked at, once they
+-- reached the limit. The logins of a locked account are rejected until an administrator unlocks it.
+ALTER TABLE accounts
+    ADD COLUMN failed_logins INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN locked_on     TIMESTAMPTZ;
@@ -0,0 +1,5 @@
+-- The emails are stored in lowercase from now on, see normalize_email. The column is citext, so the
+-- emails were already unique regardless of their case, and lowercasing them cannot conflict.
+UPDATE accounts
+SET email = LOWER(email::TEXT)
+WHERE email::TEXT <> LOWER(email::TEXT);
@@ -0,0 +1,4 @@
+-- The remembered sessions have a refresh token renewing their token, stored as its SHA-256 hash,
+-- and last until their expiration, which every refresh extends
+ALTER TABLE sessions
+    ADD COLUMN IF NOT EXISTS refresh_token_hash TEXT UNIQUE;
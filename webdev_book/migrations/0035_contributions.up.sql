@@ -0,0 +1,17 @@
+-- The contributions of the accounts, recorded when their questions and answers are added and kept
+-- when these are deleted, so deleting and posting again does not give back the daily quotas
+CREATE TABLE IF NOT EXISTS contributions
+(
+    id         SERIAL PRIMARY KEY,
+    account_id INTEGER   NOT NULL REFERENCES accounts (id) ON DELETE CASCADE,
+    kind       TEXT      NOT NULL CHECK (kind IN ('question', 'answer')),
+    created_on TIMESTAMP NOT NULL DEFAULT NOW()
+);
+-- The quotas count the contributions of a kind made by an account since the start of the day
+CREATE INDEX IF NOT EXISTS contributions_account_id_kind_created_on_idx ON contributions (account_id, kind, created_on);
+
+-- The contributions made before they were recorded are the ones still stored
+INSERT INTO contributions (account_id, kind, created_on)
+SELECT account_id, 'question', created_on FROM questions WHERE account_id IN (SELECT id FROM accounts)
+UNION ALL
+SELECT account_id, 'answer', created_on FROM answers WHERE account_id IN (SELECT id FROM accounts);
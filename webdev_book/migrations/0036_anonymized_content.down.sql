@@ -0,0 +1,16 @@
+-- The owners are required again, so the questions and answers kept without one are deleted.
+ALTER TABLE answers
+    DROP CONSTRAINT IF EXISTS answers_account_id_fkey;
+ALTER TABLE questions
+    DROP CONSTRAINT IF EXISTS questions_account_id_fkey;
+
+DELETE FROM moderation_log WHERE owner_id IS NULL;
+DELETE FROM answers WHERE account_id IS NULL;
+DELETE FROM questions WHERE account_id IS NULL;
+
+ALTER TABLE moderation_log
+    ALTER COLUMN owner_id SET NOT NULL;
+ALTER TABLE answers
+    ALTER COLUMN account_id SET NOT NULL;
+ALTER TABLE questions
+    ALTER COLUMN account_id SET NOT NULL;
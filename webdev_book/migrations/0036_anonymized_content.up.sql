@@ -0,0 +1,21 @@
+-- The questions and answers kept when their account is deleted have no owner, instead of the id of
+-- the deleted account, which the notifications and the badges cannot reference. The owners were
+-- serial columns, whose default is dropped as well. The moderators may still change the questions
+-- of no owner, so the moderation log records them without an owner too.
+ALTER TABLE questions
+    ALTER COLUMN account_id DROP DEFAULT,
+    ALTER COLUMN account_id DROP NOT NULL;
+ALTER TABLE answers
+    ALTER COLUMN account_id DROP DEFAULT,
+    ALTER COLUMN account_id DROP NOT NULL;
+ALTER TABLE moderation_log
+    ALTER COLUMN owner_id DROP NOT NULL;
+
+-- The content anonymized before loses the id of its deleted account
+UPDATE questions SET account_id = NULL WHERE account_id NOT IN (SELECT id FROM accounts);
+UPDATE answers SET account_id = NULL WHERE account_id NOT IN (SELECT id FROM accounts);
+
+ALTER TABLE questions
+    ADD CONSTRAINT questions_account_id_fkey FOREIGN KEY (account_id) REFERENCES accounts (id) ON DELETE SET NULL;
+ALTER TABLE answers
+    ADD CONSTRAINT answers_account_id_fkey FOREIGN KEY (account_id) REFERENCES accounts (id) ON DELETE SET NULL;
@@ -1,48 +1,463 @@
+use std::collections::HashMap;
+
 use tracing::{debug, info, instrument, trace};
-use warp::http::StatusCode;
-use warp::reply::with_status;
-use warp::{Rejection, Reply};
+use warp::Rejection;
 
+use crate::api::profanity::censor_or_defer;
 use crate::error::ServiceError;
+use crate::quotas::{self, Contribution};
+use crate::responses::{JsonResponse, MessageResponse};
+use crate::storage::Storage;
 use crate::store::Store;
-use crate::types::answer::Answer;
+use crate::types::answer::{AccountAnswer, Answer, AnswerId, AnswerOrder, AnswerParams};
 use crate::types::authentication::Session;
+use crate::types::markdown::ContentFormat;
+use crate::types::pagination::Pagination;
 use crate::types::question::QuestionId;
+use crate::types::quota::QuotaExceeded;
+use crate::types::sanitize;
+use crate::types::vote::{Score, Vote};
 
 /// Handler for `POST /questions/{id}/answers`
 ///
 /// Adds a new answer to the store for the given question.
 ///
 /// # Parameters
-/// - `store` - [Store] instance
+/// - `store` - [Storage] instance, usually the [Store]
 /// - `question_id` - [QuestionId] for the question the answer is associated with
 /// - `new_answer` - [Answer] object containing answer content
+#[utoipa::path(
+    post,
+    path = "/questions/{id}/answers",
+    tag = "answers",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    request_body = Answer,
+    security(("token" = [])),
+    responses(
+        (status = 201, description = "The created answer", body = Answer,
+            headers(("location" = String, description = "Path of the created answer"))),
+        (status = 400, description = "Empty or too long content", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Question not found", body = String),
+        (status = 429, description = "Daily limit of answers reached", body = QuotaExceeded),
+    )
+)]
 #[instrument(target = "webdev_book::answers", skip(store))]
-pub async fn add_answer(
-    store: Store,
+pub async fn add_answer<S: Storage>(
+    store: S,
     question_id: QuestionId,
     new_answer: Answer,
     session: Session,
-) -> Result<impl Reply, Rejection> {
+) -> Result<JsonResponse<Answer>, Rejection> {
     let Session { account_id, .. } = session;
     trace!("checking if the account is the owner of the question");
     if !store.is_question_owner(question_id, account_id).await? {
         return Err(ServiceError::Unauthorized.into());
     }
+    quotas::check(&store, account_id, Contribution::Answer).await?;
 
     trace!("adding an answer for the question with question_id = {question_id:?}");
-    // Check if the question exists
+    trace!("normalizing the answer content");
+    let content = sanitize::content(&new_answer.content, store.limits())?;
 
     trace!("censoring the answer content");
-    let content = store.bad_words_api.censor(new_answer.content).await?;
+    let (content, censored) = censor_or_defer(store.profanity_filter(), content).await?;
     debug!("censored content: {content}");
 
-    match store.add_answer(session.account_id, question_id, content).await {
+    match store.add_answer(account_id, question_id, content, censored).await {
         Ok(answer) => {
             info!("created the answer for the question with question_id = {question_id:?}");
             debug!("created the answer: {:?}", answer);
-            Ok(with_status("Answer created", StatusCode::CREATED))
+            let answer = Answer {
+                author: store.get_authors(vec![account_id]).await?.remove(&account_id),
+                ..answer
+            };
+            let location = format!("/answers/{}", answer.id.expect("stored answers have an id").0);
+            Ok(JsonResponse::created(answer).with_location(location))
         }
         Err(error) => Err(warp::reject::custom(error)),
     }
 }
+
+/// Handler for `GET /questions/{id}/answers?offset={i64}&limit={i64}&sort={newest|oldest|score}&format={markdown|html}`
+///
+/// Returns the answers to the question with the given id, in the order given by the `sort` query
+/// parameter, which is [AnswerOrder::Score] by default, so the accepted answer comes first.
+///
+/// With `format=html`, the answers have their content rendered to sanitized HTML, see [with_content_html].
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `question_id` - [QuestionId] for the question the answers are associated with
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+///   - `sort` - The order of the answers: `newest`, `oldest` or `score`
+///   - `format` - The format of the content: `markdown` or `html`
+#[utoipa::path(
+    get,
+    path = "/questions/{id}/answers",
+    tag = "answers",
+    params(
+        ("id" = QuestionId, Path, description = "Id of the question"),
+        ("sort" = Option<AnswerOrder>, Query, description = "Order of the answers, `score` by default"),
+        ("format" = Option<ContentFormat>, Query, description = "Format of the content, `markdown` by default"),
+        Pagination
+    ),
+    responses(
+        (status = 200, description = "Paginated list of the answers to the question", body = [Answer]),
+        (status = 400, description = "Invalid question id, order, format or pagination parameters", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn get_answers<S: Storage>(
+    store: S,
+    question_id: QuestionId,
+    mut params: HashMap<String, String>,
+) -> Result<JsonResponse<Vec<Answer>>, Rejection> {
+    trace!("querying answers for question_id = {question_id:?}");
+    let order = match params.remove("sort") {
+        Some(sort) => sort.parse().map_err(ServiceError::ValidationError)?,
+        None => AnswerOrder::default(),
+    };
+    let format = match params.remove("format") {
+        Some(format) => format.parse().map_err(ServiceError::ValidationError)?,
+        None => ContentFormat::default(),
+    };
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag, ?order, ?format);
+
+    if store.get_question(question_id).await?.is_none() {
+        return Err(ServiceError::QuestionNotFound(question_id.into()).into());
+    }
+
+    let mut answers = store.get_answers(question_id, order, pag).await?;
+    if format == ContentFormat::Html {
+        answers = with_content_html(&store, answers).await?;
+    }
+    info!("returning {} answers for question_id = {question_id:?}", answers.len());
+    Ok(JsonResponse::ok(answers))
+}
+
+/// Handler for `GET /accounts/me/answers?offset={i64}&limit={i64}`
+///
+/// Returns the answers of the account making the request, with the titles of the questions they
+/// answer, the most recent ones first.
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/accounts/me/answers",
+    tag = "answers",
+    params(Pagination),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Paginated list of the answers of the account", body = [AccountAnswer]),
+        (status = 400, description = "Invalid pagination parameters", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn get_account_answers<S: Storage>(
+    store: S,
+    params: HashMap<String, String>,
+    session: Session,
+) -> Result<JsonResponse<Vec<AccountAnswer>>, Rejection> {
+    trace!("querying the answers of the account");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    let answers = store.get_account_answers(session.account_id, pag).await?;
+    info!("returning {} answers of the account", answers.len());
+    Ok(JsonResponse::ok(answers))
+}
+
+/// Handler for `POST /questions/{qid}/answers/{aid}/accept`
+///
+/// Accepts the answer to the question, in place of the answer accepted before.
+/// Only the owner of the question can accept its answers.
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `question_id` - [QuestionId] for the question the answer is associated with
+/// - `answer_id` - [AnswerId] for the answer to accept
+#[utoipa::path(
+    post,
+    path = "/questions/{qid}/answers/{aid}/accept",
+    tag = "answers",
+    params(
+        ("qid" = QuestionId, Path, description = "Id of the question"),
+        ("aid" = AnswerId, Path, description = "Id of the answer")
+    ),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The accepted answer", body = Answer),
+        (status = 401, description = "Not the owner of the question", body = String),
+        (status = 404, description = "Question or answer not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn accept_answer<S: Storage>(
+    store: S,
+    question_id: QuestionId,
+    answer_id: AnswerId,
+    session: Session,
+) -> Result<JsonResponse<Answer>, Rejection> {
+    trace!("checking if the account is the owner of the question");
+    if !store.is_question_owner(question_id, session.account_id).await? {
+        return Err(ServiceError::Unauthorized.into());
+    }
+
+    match store.accept_answer(question_id, answer_id).await? {
+        Some(answer) => {
+            info!("accepted answer with answer_id = {}", answer_id.0);
+            Ok(JsonResponse::ok(answer))
+        }
+        None => Err(ServiceError::AnswerNotFound(answer_id.into()).into()),
+    }
+}
+
+/// Handler for `GET /answers/{id}?format={markdown|html}`
+///
+/// Returns the answer with the given id.
+///
+/// With `format=html`, the answer has its content rendered to sanitized HTML, see [with_content_html].
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `answer_id` - [AnswerId] for the answer to retrieve
+/// - `params` - HashMap of query parameters
+///   - `format` - The format of the content: `markdown` or `html`
+#[utoipa::path(
+    get,
+    path = "/answers/{id}",
+    tag = "answers",
+    params(("id" = AnswerId, Path, description = "Id of the answer"), AnswerParams),
+    responses(
+        (status = 200, description = "The answer", body = Answer),
+        (status = 400, description = "Invalid answer id or query parameters", body = String),
+        (status = 404, description = "Answer not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn get_answer<S: Storage>(
+    store: S,
+    answer_id: AnswerId,
+    params: HashMap<String, String>,
+) -> Result<JsonResponse<Answer>, Rejection> {
+    trace!("querying answer_id = {answer_id:?}");
+    let AnswerParams { format } =
+        AnswerParams::extract(&params).map_err(|error| ServiceError::ValidationError(error.to_string()))?;
+    debug!(?format);
+
+    let answer = store.get_answer(answer_id).await?;
+    debug!(answer_found = answer.is_some());
+
+    match answer {
+        Some(answer) if format == Some(ContentFormat::Html) => {
+            info!("returning answer with answer_id = {answer_id:?}, rendered to HTML");
+            let mut answers = with_content_html(&store, vec![answer]).await?;
+            Ok(JsonResponse::ok(answers.remove(0)))
+        }
+        Some(answer) => {
+            info!("returning answer with answer_id = {answer_id:?}");
+            Ok(JsonResponse::ok(answer))
+        }
+        None => Err(ServiceError::AnswerNotFound(answer_id.into()).into()),
+    }
+}
+
+/// Sets the content rendered to HTML on the answers, from [Storage::get_answers_html].
+///
+/// The HTML is not loaded with the answers, so the responses that don't request it don't carry it.
+pub(crate) async fn with_content_html<S: Storage>(
+    store: &S,
+    answers: Vec<Answer>,
+) -> Result<Vec<Answer>, ServiceError> {
+    let answer_ids = answers.iter().filter_map(|answer| answer.id).collect();
+    let mut contents = store.get_answers_html(answer_ids).await?;
+    Ok(answers
+        .into_iter()
+        .map(|answer| Answer {
+            content_html: answer.id.and_then(|answer_id| contents.remove(&answer_id)),
+            ..answer
+        })
+        .collect())
+}
+
+/// Handler for `PUT /answers/{id}`
+///
+/// Updates the answer with the given id
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `answer_id` - [AnswerId] for the answer to update
+/// - `answer` - [Answer] object containing updated answer content
+#[utoipa::path(
+    put,
+    path = "/answers/{id}",
+    tag = "answers",
+    params(("id" = AnswerId, Path, description = "Id of the answer")),
+    request_body = Answer,
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The updated answer", body = Answer),
+        (status = 400, description = "Empty or too long content", body = String),
+        (status = 401, description = "Not the author of the answer", body = String),
+        (status = 404, description = "Answer not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn update_answer<S: Storage>(
+    store: S,
+    answer_id: AnswerId,
+    answer: Answer,
+    session: Session,
+) -> Result<JsonResponse<Answer>, Rejection> {
+    let Session { account_id, .. } = session;
+    trace!("checking if the account is the author of the answer");
+    if !store.is_answer_owner(answer_id, account_id).await? {
+        return Err(ServiceError::Unauthorized.into());
+    }
+
+    trace!("normalizing the answer content");
+    let content = sanitize::content(&answer.content, store.limits())?;
+
+    trace!("censoring the answer content");
+    let (content, censored) = censor_or_defer(store.profanity_filter(), content).await?;
+    debug!("censored content: {content}");
+
+    match store.update_answer(account_id, answer_id, content, censored).await {
+        Ok(answer) => {
+            info!("updated answer with answer_id = {}", answer_id.0);
+            debug!(updated_answer = ?answer);
+            Ok(JsonResponse::ok(answer))
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Handler for `POST /answers/{id}/vote`
+///
+/// Votes on the answer with the given id, up or down. Voting in the other direction changes the
+/// vote, and voting again in the same direction is a conflict, see [Store::vote_answer]. The vote
+/// is retracted with `DELETE /answers/{id}/vote`.
+///
+/// The author of the answer cannot vote on it.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `answer_id` - [AnswerId] for the answer to vote on
+/// - `vote` - [Vote] object containing the direction of the vote
+#[utoipa::path(
+    post,
+    path = "/answers/{id}/vote",
+    tag = "answers",
+    params(("id" = AnswerId, Path, description = "Id of the answer")),
+    request_body = Vote,
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The score of the answer, with the vote of the account", body = Score),
+        (status = 400, description = "Vote on the own answer", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Answer not found", body = String),
+        (status = 409, description = "Already voted in this direction", body = Object),
+        (status = 422, description = "Missing or unknown direction", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn vote_answer(
+    store: Store,
+    answer_id: AnswerId,
+    vote: Vote,
+    session: Session,
+) -> Result<JsonResponse<Score>, Rejection> {
+    trace!("checking if the account is the author of the answer");
+    if store.is_answer_owner(answer_id, session.account_id).await? {
+        return Err(ServiceError::ValidationError("cannot vote on the own answer".to_string()).into());
+    }
+
+    let score = store.vote_answer(session.account_id, answer_id, vote.direction).await?;
+    info!("voted on answer with answer_id = {}", answer_id.0);
+    debug!(?score);
+    Ok(JsonResponse::ok(score))
+}
+
+/// Handler for `DELETE /answers/{id}/vote`
+///
+/// Retracts the vote of the account on the answer with the given id. Retracting is idempotent, the
+/// score is returned whether the account has voted or not, see [Store::retract_answer_vote].
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `answer_id` - [AnswerId] for the answer to retract the vote from
+#[utoipa::path(
+    delete,
+    path = "/answers/{id}/vote",
+    tag = "answers",
+    params(("id" = AnswerId, Path, description = "Id of the answer")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The score of the answer, without a vote of the account", body = Score),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Answer not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn retract_answer_vote(
+    store: Store,
+    answer_id: AnswerId,
+    session: Session,
+) -> Result<JsonResponse<Score>, Rejection> {
+    let score = store.retract_answer_vote(session.account_id, answer_id).await?;
+    info!("retracted the vote on answer with answer_id = {}", answer_id.0);
+    debug!(?score);
+    Ok(JsonResponse::ok(score))
+}
+
+/// Handler for `DELETE /answers/{id}`
+///
+/// Deletes the answer with the given id
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `answer_id` - [AnswerId] for the answer to delete
+#[utoipa::path(
+    delete,
+    path = "/answers/{id}",
+    tag = "answers",
+    params(("id" = AnswerId, Path, description = "Id of the answer")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Answer deleted", body = String),
+        (status = 401, description = "Not the author of the answer", body = String),
+        (status = 404, description = "Answer not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::answers", skip(store))]
+pub async fn delete_answer<S: Storage>(
+    store: S,
+    answer_id: AnswerId,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    let Session { account_id, .. } = session;
+    trace!("checking if the account is the author of the answer");
+    if !store.is_answer_owner(answer_id, account_id).await? {
+        return Err(ServiceError::Unauthorized.into());
+    }
+
+    trace!("deleting the answer with answer_id = {}", answer_id.0);
+    match store.delete_answer(account_id, answer_id).await {
+        Ok(true) => {
+            info!("deleted answer with answer_id = {}", answer_id.0);
+            Ok(MessageResponse::ok("Answer deleted"))
+        }
+        Ok(false) => Err(ServiceError::AnswerNotFound(answer_id.into()).into()),
+        Err(error) => Err(error.into()),
+    }
+}
@@ -3,9 +3,11 @@
 //! This module contains the following submodules:
 //! - `handlers` - Contains the request handlers for the `Answer` resource.
 //! - `routes` - Contains the filters for the `Answer` resource.
+use utoipa::OpenApi;
 use warp::filters::BoxedFilter;
 use warp::{Filter, Reply};
 
+use crate::storage::Storage;
 use crate::store::Store;
 
 /// Handlers for the `Answer` resource.
@@ -13,15 +15,79 @@ mod handlers;
 /// Routes for the `Answer` resource.
 mod routes;
 
+pub(crate) use handlers::with_content_html;
+
+/// OpenAPI document for the `Answer` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::add_answer,
+        handlers::get_answers,
+        handlers::get_account_answers,
+        handlers::accept_answer,
+        handlers::get_answer,
+        handlers::update_answer,
+        handlers::vote_answer,
+        handlers::retract_answer_vote,
+        handlers::delete_answer
+    ),
+    tags((name = "answers", description = "Answers to the questions"))
+)]
+pub struct AnswersApi;
+
 /// Filter for the `Answer` resource.
 ///
 /// Creates a filter that handles requests for the `Answer` resource.
 ///
 /// The filter combines the following filters:
 /// - `add_answer`, for handling `POST /questions/{id}/answers`
+/// - `get_answers`, for handling `GET /questions/{id}/answers`
+/// - `get_account_answers`, for handling `GET /accounts/me/answers`
+/// - `accept_answer`, for handling `POST /questions/{qid}/answers/{aid}/accept`
+/// - `get_answer`, for handling `GET /answers/{id}`
+/// - `update_answer`, for handling `PUT /answers/{id}`
+/// - `vote_answer`, for handling `POST /answers/{id}/vote`
+/// - `retract_answer_vote`, for handling `DELETE /answers/{id}/vote`
+/// - `delete_answer`, for handling `DELETE /answers/{id}`
 ///
 /// # Parameters
 /// - `store` - The [Store] to use for handling requests.
 pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
-    routes::add_answer(store.clone()).boxed()
+    routes::add_answer(store.clone())
+        .or(routes::get_answers(store.clone()))
+        .or(routes::get_account_answers(store.clone()))
+        .or(routes::accept_answer(store.clone()))
+        .or(routes::get_answer(store.clone()))
+        .or(routes::update_answer(store.clone()))
+        .or(routes::vote_answer(store.clone()))
+        .or(routes::retract_answer_vote(store.clone()))
+        .or(routes::delete_answer(store.clone()))
+        .boxed()
+}
+
+/// Filter for the `Answer` resource, served from any [Storage].
+///
+/// Combines the routes of [filter] that only depend on the [Storage], so they can be served from
+/// another backend than the [Store], e.g. the in-memory one of the tests:
+/// - `add_answer`, for handling `POST /questions/{id}/answers`
+/// - `get_answers`, for handling `GET /questions/{id}/answers`
+/// - `get_account_answers`, for handling `GET /accounts/me/answers`
+/// - `accept_answer`, for handling `POST /questions/{qid}/answers/{aid}/accept`
+/// - `get_answer`, for handling `GET /answers/{id}`
+/// - `update_answer`, for handling `PUT /answers/{id}`
+/// - `delete_answer`, for handling `DELETE /answers/{id}`
+///
+/// # Parameters
+/// - `store` - The [Storage] to use for handling requests.
+pub fn storage_filter<S: Storage>(store: &S) -> BoxedFilter<(impl Reply,)> {
+    routes::add_answer(store.clone())
+        .or(routes::get_answers(store.clone()))
+        .or(routes::get_account_answers(store.clone()))
+        .or(routes::accept_answer(store.clone()))
+        .or(routes::get_answer(store.clone()))
+        .or(routes::update_answer(store.clone()))
+        .or(routes::delete_answer(store.clone()))
+        .boxed()
 }
@@ -1,9 +1,12 @@
-use warp::{filters::BoxedFilter, Filter, Reply};
+use warp::{Filter, Rejection, Reply};
 
 use crate::answers::handlers;
 use crate::authentication;
-use crate::filters::{store_filter, with_trace};
+use crate::codec;
+use crate::filters::route;
+use crate::storage::Storage;
 use crate::store::Store;
+use crate::types::answer::AnswerId;
 use crate::types::question::QuestionId;
 
 /// POST /questions/{id}/answers
@@ -12,14 +15,163 @@ use crate::types::question::QuestionId;
 /// The filter expects a JSON payload containing the answer content.
 ///
 /// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn add_answer<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions" / {QuestionId} / "answers",
+        extract: [codec::body(), authentication::auth(&store)],
+        handler: handlers::add_answer,
+        trace: "add_answer request",
+    }
+}
+
+/// GET /questions/{id}/answers?offset={i64}&limit={i64}&sort={newest|oldest|score}&format={markdown|html}
+///
+/// Creates a filter for a route that handles fetching the answers to a question.
+///
+/// The filter extracts the `QuestionId` from the URL path, parses the query parameters, and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_answers<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "questions" / {QuestionId} / "answers",
+        extract: [warp::query()],
+        handler: handlers::get_answers,
+        trace: "get_answers request",
+    }
+}
+
+/// GET /accounts/me/answers?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles listing the answers of the account making the request.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_account_answers<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / "me" / "answers",
+        extract: [warp::query(), authentication::auth(&store)],
+        handler: handlers::get_account_answers,
+        trace: "get_account_answers request",
+    }
+}
+
+/// POST /questions/{qid}/answers/{aid}/accept
+///
+/// Creates a filter for a route that handles accepting an answer to a question.
+///
+/// The filter extracts the `QuestionId` and the `AnswerId` from the URL path and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn accept_answer<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions" / {QuestionId} / "answers" / {AnswerId} / "accept",
+        extract: [authentication::auth(&store)],
+        handler: handlers::accept_answer,
+        trace: "accept_answer request",
+    }
+}
+
+/// GET /answers/{id}?format={markdown|html}
+///
+/// Creates a filter for a route that handles fetching a single answer.
+/// The filter extracts the `AnswerId` from the URL path, parses the query parameters, and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_answer<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "answers" / {AnswerId},
+        extract: [warp::query()],
+        handler: handlers::get_answer,
+        trace: "get_answer request",
+    }
+}
+
+/// PUT /answers/{id}
+///
+/// Creates a filter for a route that handles updating an answer.
+///
+/// The filter extracts the `AnswerId` from the URL path and the `Answer` from the request body as JSON and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn update_answer<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "answers" / {AnswerId},
+        extract: [codec::body(), authentication::auth(&store)],
+        handler: handlers::update_answer,
+        trace: "update_answer request",
+    }
+}
+
+/// POST /answers/{id}/vote
+///
+/// Creates a filter for a route that handles voting on an answer.
+///
+/// The filter extracts the `AnswerId` from the URL path and the `Vote` from the request body and passes them to the handler.
+///
+/// # Parameters
 /// - `store` - [Store] object available to the route handler
-pub fn add_answer(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::post())
-        .and(warp::path!("questions" / QuestionId / "answers"))
-        .and(warp::body::json())
-        .and(authentication::auth())
-        .and_then(handlers::add_answer)
-        .with(with_trace!("add_answer request"))
-        .boxed()
+pub fn vote_answer(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "answers" / {AnswerId} / "vote",
+        extract: [codec::body(), authentication::auth(&store)],
+        handler: handlers::vote_answer,
+        trace: "vote_answer request",
+    }
+}
+
+/// DELETE /answers/{id}/vote
+///
+/// Creates a filter for a route that handles retracting the vote on an answer.
+///
+/// The filter extracts the `AnswerId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn retract_answer_vote(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "answers" / {AnswerId} / "vote",
+        extract: [authentication::auth(&store)],
+        handler: handlers::retract_answer_vote,
+        trace: "retract_answer_vote request",
+    }
+}
+
+/// DELETE /answers/{id}
+///
+/// Creates a filter for a route that handles deleting an answer.
+///
+/// The filter extracts the `AnswerId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn delete_answer<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "answers" / {AnswerId},
+        extract: [authentication::auth(&store)],
+        handler: handlers::delete_answer,
+        trace: "delete_answer request",
+    }
 }
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use tokio_util::io::ReaderStream;
+use tracing::{debug, info, instrument, trace, warn};
+use warp::http::header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE};
+use warp::http::Response;
+use warp::hyper::Body;
+use warp::{Rejection, Reply};
+
+use crate::attachments::{Storage, MAX_ATTACHMENT_SIZE};
+use crate::error::ServiceError;
+use crate::filters::BodyStream;
+use crate::responses::JsonResponse;
+use crate::store::Store;
+use crate::types::attachment::{Attachment, AttachmentId};
+use crate::types::authentication::Session;
+use crate::types::question::QuestionId;
+
+/// Media type of the files uploaded without the `Content-Type` header.
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+/// The longest accepted file name.
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+/// Returns the file name from the query, if it is valid.
+fn file_name(params: &HashMap<String, String>) -> Result<String, ServiceError> {
+    match params.get("name").map(|name| name.trim()) {
+        None | Some("") => Err(ServiceError::ValidationError("missing file name".to_string())),
+        Some(name) if name.chars().count() > MAX_FILE_NAME_LENGTH => Err(ServiceError::ValidationError(format!(
+            "file name is longer than {MAX_FILE_NAME_LENGTH} characters"
+        ))),
+        Some(name) => Ok(name.to_string()),
+    }
+}
+
+/// Returns the `Content-Disposition` header value for downloading the file under its name.
+///
+/// Characters that cannot appear in a quoted header value are replaced with `_`.
+fn content_disposition(file_name: &str) -> String {
+    let file_name: String = file_name
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii_graphic() || c == ' ' => c,
+            _ => '_',
+        })
+        .collect();
+    format!("attachment; filename=\"{file_name}\"")
+}
+
+/// Handler for `POST /questions/{id}/attachments?name={file name}`
+///
+/// Attaches the file in the request body to the question. Only the author of the question
+/// can attach files to it.
+///
+/// The body is written to disk as it arrives, and is never held in memory as a whole.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `question_id` - [QuestionId] of the question the file is attached to
+/// - `session` - [Session] of the account making the request
+/// - `params` - HashMap of query parameters
+///   - `name` - The name of the file
+/// - `content_type` - The media type of the file, from the `Content-Type` header
+/// - `storage` - [Storage] the file is written to
+/// - `body` - The content of the file
+#[utoipa::path(
+    post,
+    path = "/questions/{id}/attachments",
+    tag = "attachments",
+    params(
+        ("id" = QuestionId, Path, description = "Id of the question"),
+        ("name" = String, Query, description = "Name of the file"),
+    ),
+    request_body(content = Vec<u8>, content_type = "application/octet-stream", description = "Content of the file"),
+    security(("token" = [])),
+    responses(
+        (status = 201, description = "File attached", body = Attachment),
+        (status = 400, description = "Missing or invalid file name", body = String),
+        (status = 401, description = "Missing or invalid token, or not the author of the question", body = String),
+        (status = 404, description = "Question not found", body = String),
+        (status = 413, description = "File is too large", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::attachments", skip(store, session, storage, body))]
+pub async fn add_attachment(
+    store: Store,
+    question_id: QuestionId,
+    session: Session,
+    params: HashMap<String, String>,
+    content_type: Option<String>,
+    storage: Storage,
+    body: BodyStream,
+) -> Result<JsonResponse<Attachment>, Rejection> {
+    let file_name = file_name(&params)?;
+    let content_type = content_type.unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string());
+
+    trace!("checking if the account is the owner of the question");
+    if !store.is_question_owner(question_id, session.account_id).await? {
+        return Err(ServiceError::Unauthorized.into());
+    }
+
+    trace!("storing the file");
+    let (key, size) = storage.save(body, MAX_ATTACHMENT_SIZE).await?;
+    debug!(size, key);
+
+    let attachment = match store
+        .add_attachment(question_id, &file_name, &content_type, size as i64, &key)
+        .await
+    {
+        Ok(attachment) => attachment,
+        Err(error) => {
+            if let Err(error) = storage.remove(&key).await {
+                warn!("cannot remove the stored file {key}: {error}");
+            }
+            return Err(error.into());
+        }
+    };
+    info!("attached the file with id = {:?}", attachment.id);
+    Ok(JsonResponse::created(attachment))
+}
+
+/// Handler for `GET /attachments/{id}`
+///
+/// Returns the content of the attached file, streamed from disk.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `attachment_id` - [AttachmentId] of the attachment
+/// - `storage` - [Storage] the file is read from
+#[utoipa::path(
+    get,
+    path = "/attachments/{id}",
+    tag = "attachments",
+    params(("id" = AttachmentId, Path, description = "Id of the attachment")),
+    responses(
+        (status = 200, description = "Content of the file", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 404, description = "Attachment not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::attachments", skip(store, storage))]
+pub async fn get_attachment(
+    store: Store,
+    attachment_id: AttachmentId,
+    storage: Storage,
+) -> Result<impl Reply, Rejection> {
+    trace!("querying attachment_id = {attachment_id:?}");
+    let Some(attachment) = store.get_attachment(attachment_id).await? else {
+        return Err(ServiceError::AttachmentNotFound(attachment_id.into()).into());
+    };
+
+    let file = storage.open(&attachment.storage_key).await?;
+    info!("returning attachment with attachment_id = {attachment_id:?}");
+    Response::builder()
+        .header(CONTENT_TYPE, &attachment.content_type)
+        .header(CONTENT_LENGTH, attachment.size)
+        .header(CONTENT_DISPOSITION, content_disposition(&attachment.file_name))
+        .body(Body::wrap_stream(ReaderStream::new(file)))
+        .map_err(|error| ServiceError::ValidationError(error.to_string()).into())
+}
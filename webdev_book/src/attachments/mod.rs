@@ -0,0 +1,53 @@
+//! Module for the `Attachment` resource.
+//!
+//! Attachments are files uploaded to a question by its author. The files are streamed to disk as
+//! they arrive, so large uploads are never held in memory, and are streamed back the same way.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the `Attachment` resource.
+//! - `routes` - Contains the filters for the `Attachment` resource.
+//! - `storage` - Contains the storage of the uploaded files.
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Handlers for the `Attachment` resource.
+mod handlers;
+/// Routes for the `Attachment` resource.
+mod routes;
+/// Storage of the uploaded files.
+mod storage;
+
+pub use storage::Storage;
+
+/// The largest file that can be attached to a question, in bytes.
+pub const MAX_ATTACHMENT_SIZE: u64 = 16 * 1024 * 1024;
+
+/// OpenAPI document for the `Attachment` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(handlers::add_attachment, handlers::get_attachment),
+    tags((name = "attachments", description = "Files attached to the questions"))
+)]
+pub struct AttachmentsApi;
+
+/// Filter for the `Attachment` resource.
+///
+/// Creates a filter that handles requests for the `Attachment` resource.
+///
+/// The filter combines the following filters:
+/// - `add_attachment`, for handling `POST /questions/{id}/attachments`
+/// - `get_attachment`, for handling `GET /attachments/{id}`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `storage` - The [Storage] holding the uploaded files.
+pub fn filter(store: &Store, storage: &Storage) -> BoxedFilter<(impl Reply,)> {
+    routes::add_attachment(store.clone(), storage.clone())
+        .or(routes::get_attachment(store.clone(), storage.clone()))
+        .boxed()
+}
@@ -0,0 +1,66 @@
+use std::convert::Infallible;
+
+use warp::{Filter, Rejection, Reply};
+
+use crate::attachments::{handlers, Storage, MAX_ATTACHMENT_SIZE};
+use crate::authentication;
+use crate::filters::{body_stream, route};
+use crate::store::Store;
+use crate::types::attachment::AttachmentId;
+use crate::types::question::QuestionId;
+
+/// Returns a filter that passes the storage to the handler.
+fn storage_filter(storage: Storage) -> impl Filter<Extract = (Storage,), Error = Infallible> + Clone {
+    warp::any().map(move || storage.clone())
+}
+
+/// POST /questions/{id}/attachments?name={file name}
+///
+/// Creates a filter for a route that handles attaching files to a question.
+/// The filter expects the content of the file as the request body, of any media type,
+/// which is passed to the handler as a stream.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+/// - `storage` - [Storage] the files are written to
+pub fn add_attachment(
+    store: Store,
+    storage: Storage,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions" / {QuestionId} / "attachments",
+        extract: [
+            authentication::auth(&store),
+            warp::query(),
+            warp::header::optional::<String>("content-type"),
+            storage_filter(storage),
+            body_stream(MAX_ATTACHMENT_SIZE),
+        ],
+        handler: handlers::add_attachment,
+        trace: "add_attachment request",
+    }
+}
+
+/// GET /attachments/{id}
+///
+/// Creates a filter for a route that handles downloading an attached file.
+/// The filter extracts the `AttachmentId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+/// - `storage` - [Storage] the files are read from
+pub fn get_attachment(
+    store: Store,
+    storage: Storage,
+) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "attachments" / {AttachmentId},
+        extract: [storage_filter(storage)],
+        handler: handlers::get_attachment,
+        trace: "get_attachment request",
+    }
+}
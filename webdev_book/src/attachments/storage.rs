@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use futures_util::TryStreamExt;
+use tokio::fs::{self, File};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tracing::{trace, warn};
+
+use crate::error::ServiceError;
+use crate::filters::BodyStream;
+
+/// Storage of the uploaded files.
+///
+/// Every file is stored in the storage directory under a random key. Files are first written
+/// to a `.part` file, which is renamed once the whole body is received, so a file under a key is
+/// always complete.
+#[derive(Debug, Clone)]
+pub struct Storage {
+    /// The directory containing the files.
+    dir: PathBuf,
+}
+
+impl Storage {
+    /// Creates a storage keeping the files in the given directory.
+    ///
+    /// The directory is created on the first upload, if it does not exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the path of the file stored under the key.
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+
+    /// Writes the body to a new file, chunk by chunk.
+    ///
+    /// # Returns
+    /// - The key of the file, and its size in bytes.
+    /// - [ServiceError::PayloadTooLarge] if the body is longer than `limit` bytes.
+    /// - An error if the body cannot be received, or the file cannot be written.
+    pub async fn save(&self, body: BodyStream, limit: u64) -> Result<(String, u64), ServiceError> {
+        fs::create_dir_all(&self.dir).await?;
+
+        let key = uuid::Uuid::new_v4().simple().to_string();
+        let partial = self.dir.join(format!("{key}.part"));
+        match write(&partial, body, limit).await {
+            Ok(size) => {
+                fs::rename(&partial, self.path(&key)).await?;
+                trace!("stored {size} bytes under {key}");
+                Ok((key, size))
+            }
+            Err(error) => {
+                if let Err(error) = fs::remove_file(&partial).await {
+                    warn!("cannot remove the partial upload {}: {error}", partial.display());
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Opens the file stored under the key for reading.
+    pub async fn open(&self, key: &str) -> Result<File, ServiceError> {
+        Ok(File::open(self.path(key)).await?)
+    }
+
+    /// Removes the file stored under the key.
+    pub async fn remove(&self, key: &str) -> Result<(), ServiceError> {
+        Ok(fs::remove_file(self.path(key)).await?)
+    }
+}
+
+/// Writes the body to the file at the path, and returns the number of bytes written.
+async fn write(path: &PathBuf, mut body: BodyStream, limit: u64) -> Result<u64, ServiceError> {
+    let mut file = BufWriter::new(File::create(path).await?);
+    let mut size = 0;
+    while let Some(chunk) = body
+        .try_next()
+        .await
+        .map_err(|error| ServiceError::BodyDecodeError(error.to_string()))?
+    {
+        size += chunk.len() as u64;
+        if size > limit {
+            return Err(ServiceError::PayloadTooLarge(limit));
+        }
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    Ok(size)
+}
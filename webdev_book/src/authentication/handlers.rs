@@ -1,53 +1,91 @@
-use argon2::Config;
-use chrono::Utc;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+
+use chrono::{DateTime, Duration, Utc};
 use rand::random;
-use tracing::{debug, info, instrument, trace};
-use warp::http::StatusCode;
-use warp::reply::{json, with_status};
-use warp::{Rejection, Reply};
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, instrument, trace, warn};
+use uuid::Uuid;
+use warp::Rejection;
 
+use crate::authentication::{REFRESH_COOKIE, SESSION_COOKIE};
+#[cfg(feature = "test-util")]
+use crate::clock::Clock;
 use crate::error::ServiceError;
+use crate::responses::{JsonResponse, MessageResponse};
+use crate::storage::Storage;
 use crate::store::Store;
-use crate::types::authentication::{Account, AccountId};
+#[cfg(feature = "test-util")]
+use crate::tokens::TokenSigner;
+use crate::types::authentication::{
+    Account, AccountId, AccountProfile, ActiveSession, DeleteAccountParams, LoginParams, PasswordHashing,
+    ProfileUpdate, PublicProfile, Session, SessionId, SessionLifetimes,
+};
+#[cfg(feature = "test-util")]
+use crate::types::moderation::AccountRole;
+use crate::types::sanitize;
+
+/// The cookie clearing the [SESSION_COOKIE] of the client, set by the logout.
+const CLEARED_SESSION_COOKIE: &str = "session=; Max-Age=0; Path=/; HttpOnly; Secure; SameSite=Strict";
+/// The cookie clearing the [REFRESH_COOKIE] of the client, set by the logout.
+const CLEARED_REFRESH_COOKIE: &str = "refresh_token=; Max-Age=0; Path=/refresh; HttpOnly; Secure; SameSite=Strict";
 
 /// Hashes a password using Argon2.
 ///
 /// Hashes a password using Argon2 and returns the hash as a string.
-/// Hashing is done using a random salt and the default Argon2 configuration.
+/// Hashing is done using a random salt and the given parameters, see [PasswordHashing].
 ///
 /// Salt is generated using the `rand` crate. Size of the salt is 32 bytes.
 ///
 /// # Parameters
+/// - `hashing` - The parameters to hash the password with.
 /// - `password` - The password to hash.
-pub fn hash_password(password: &[u8]) -> Result<String, argon2::Error> {
+pub fn hash_password(hashing: &PasswordHashing, password: &[u8]) -> Result<String, argon2::Error> {
     let salt = random::<[u8; 32]>();
-    let config = Config::default();
-    argon2::hash_encoded(password, &salt, &config)
+    argon2::hash_encoded(password, &salt, &hashing.config())
 }
 
 /// Handler for the `POST /register` route.
 ///
-/// This handler is used to register a new account.
+/// This handler is used to register a new account. It returns the profile of the created account,
+/// without its password, and its path in the `Location` header.
 ///
 /// # Parameters
-/// - `store` - The [Store] to use for handling requests.
+/// - `store` - The [Storage] to use for handling requests, usually the [Store].
+/// - `account` - The email and the password of the account.
+#[utoipa::path(
+    post,
+    path = "/register",
+    tag = "authentication",
+    request_body = Account,
+    responses(
+        (status = 201, description = "Account created", body = AccountProfile,
+            headers(("location" = String, description = "Path of the created account"))),
+        (status = 409, description = "Duplicate email", body = Object),
+        (status = 422, description = "Invalid email", body = String),
+    )
+)]
 #[instrument(target = "webdev_books::accounts", skip(store))]
-pub async fn register(store: Store, account: Account) -> Result<impl Reply, Rejection> {
+pub async fn register<S: Storage>(store: S, account: Account) -> Result<JsonResponse<AccountProfile>, Rejection> {
     trace!("creating a new account");
     let Account { id, email, password } = account;
     trace!("hashing the password");
-    let hashed_password = hash_password(password.as_bytes()).map_err(ServiceError::ArgonLibraryError)?;
+    let hashed_password =
+        hash_password(store.password_hashing(), password.as_bytes()).map_err(ServiceError::ArgonLibraryError)?;
 
-    let account = Account {
-        id,
-        email,
-        password: hashed_password,
-    };
+    let account = Account::builder()
+        .id(id)
+        .email(email)
+        .password(hashed_password)
+        .build()
+        .expect("all required fields are set");
 
     match store.add_account(account).await {
-        Ok(_) => {
+        Ok(account) => {
             info!("account created");
-            Ok(with_status("Account created", StatusCode::CREATED))
+            let location = format!("/accounts/{}", account.id.0);
+            Ok(JsonResponse::created(account).with_location(location))
         }
         Err(error) => Err(warp::reject::custom(error)),
     }
@@ -60,65 +98,654 @@ pub async fn register(store: Store, account: Account) -> Result<impl Reply, Reje
 /// # Parameters
 /// - `hashed` - The hashed password to verify against.
 /// - `password` - The password to verify.
-fn verify_password(hashed: &str, password: &str) -> Result<bool, argon2::Error> {
+pub fn verify_password(hashed: &str, password: &str) -> Result<bool, argon2::Error> {
     argon2::verify_encoded(hashed, password.as_bytes())
 }
 
-/// Generates a PASETO token for an account.
+/// Generates a token for an account.
 ///
-/// Generates a PASETO token for an account using the account's ID.
+/// Generates a token for an account using the account's ID.
+/// The token is valid from the time of the `clock`, for one day. It carries the default role, and
+/// no email, as the account is not read, see [issue_token_valid_between].
 ///
 /// # Parameters
+/// - `signer` - The signer the token is issued with.
+/// - `clock` - The clock the validity of the token starts from.
 /// - `account_id` - The ID of the account to generate a token for.
 ///
 /// # Returns
-/// A PASETO token as a string.
+/// A token as a string, in the format of the signer.
 ///
 /// # Panics
 /// - If the final date cannot be constructed.
 /// - If the token cannot be constructed.
-fn issue_token(account_id: AccountId) -> String {
-    let key = std::env::var("PASETO_KEY").unwrap();
-
-    let current_datetime = Utc::now();
+#[cfg(feature = "test-util")]
+pub fn issue_token(signer: &dyn TokenSigner, clock: &dyn Clock, account_id: AccountId) -> String {
+    let current_datetime = clock.now();
     let dt = current_datetime + chrono::Duration::try_days(1).unwrap();
 
-    paseto::tokens::PasetoBuilder::new()
-        .set_encryption_key(key.as_bytes())
-        .set_expiration(&dt)
-        .set_not_before(&Utc::now())
-        .set_claim("account_id", serde_json::json!(account_id))
-        .build()
-        .expect("Failed to construct paseto token w/ builder")
+    issue_token_valid_between(signer, account_id, current_datetime, dt)
 }
 
-/// Handler for the `POST /login` route.
+/// Generates a token for an account, valid between the given dates.
 ///
-/// This handler is used to log in an account.
+/// Every token gets a random `jti` claim, so it can be revoked before it expires, see [logout].
+/// The token has no session, see [start_session] for the tokens issued by logging in, and carries
+/// the default role, [AccountRole::User], and an empty email.
 ///
 /// # Parameters
-/// - `store` - The [Store] to use for handling requests.
-/// - `login` - The login details.
+/// - `signer` - The signer the token is issued with.
+/// - `account_id` - The ID of the account to generate a token for.
+/// - `not_before` - The date the token becomes valid.
+/// - `expiration` - The date the token expires.
+///
+/// # Panics
+/// - If the token cannot be constructed.
+#[cfg(feature = "test-util")]
+pub fn issue_token_valid_between(
+    signer: &dyn TokenSigner,
+    account_id: AccountId,
+    not_before: DateTime<Utc>,
+    expiration: DateTime<Utc>,
+) -> String {
+    signer.sign(&Session {
+        ver: Session::CLAIMS_VERSION,
+        exp: expiration,
+        nbf: not_before,
+        account_id,
+        email: String::new(),
+        role: AccountRole::default(),
+        jti: Uuid::new_v4().to_string(),
+        sid: None,
+    })
+}
+
+/// Issues the token of a session, valid from the time of the clock of the store, for
+/// [SessionLifetimes::token], but not past the expiration of the session.
+///
+/// The token names the session in its `sid` claim, and carries the email and the role of the
+/// account, see [Session], read now, so the refreshed tokens carry the current ones.
+async fn sign_session(
+    store: &Store,
+    account_id: AccountId,
+    session_id: SessionId,
+    jti: String,
+    session_expiration: DateTime<Utc>,
+) -> Result<String, ServiceError> {
+    let not_before = store.clock.now();
+    let expiration = session_expiration.min(not_before + store.session_lifetimes.token);
+
+    // The email and the role are carried by the token, so they are not read on every request
+    let profile = store.get_account_by_id(account_id).await?;
+    let role = store.get_account_role(account_id).await?;
+    Ok(store.token_signer.sign(&Session {
+        ver: Session::CLAIMS_VERSION,
+        exp: expiration,
+        nbf: not_before,
+        account_id,
+        email: profile.email,
+        role,
+        jti,
+        sid: Some(session_id),
+    }))
+}
+
+/// Returns a new random refresh token, hex encoded.
+fn new_refresh_token() -> String {
+    hex::encode(random::<[u8; 32]>())
+}
+
+/// Returns the hash of a refresh token, the only form it is stored in, see [Store::refresh_session].
+fn hash_refresh_token(refresh_token: &str) -> String {
+    hex::encode(Sha256::digest(refresh_token.as_bytes()))
+}
+
+/// Starts a session of the account, and returns its token.
+///
+/// The session is recorded in the store, see [Store::add_session], with the `User-Agent` of the
+/// client, so the account can list its sessions and sign the devices out, see [delete_session].
+/// The session expires with its token, see [sign_session].
+///
+/// # Parameters
+/// - `store` - The [Store] the session is recorded in.
+/// - `account_id` - The ID of the account that logged in.
+/// - `user_agent` - The `User-Agent` of the client, if it sent one.
+pub async fn start_session(
+    store: &Store,
+    account_id: AccountId,
+    user_agent: Option<&str>,
+) -> Result<String, ServiceError> {
+    let expiration = store.clock.now() + store.session_lifetimes.token;
+    let jti = Uuid::new_v4().to_string();
+    let session_id = store
+        .add_session(account_id, &jti, user_agent, expiration, None)
+        .await?;
+    debug!("session {session_id:?} started");
+    sign_session(store, account_id, session_id, jti, expiration).await
+}
+
+/// Starts a remembered session of the account, and returns its token and its refresh token.
+///
+/// The session is recorded like the ones of [start_session], with the hash of the refresh token,
+/// and lasts for [SessionLifetimes::remember_me], extended by every refresh, see [refresh], and by its
+/// use, see [Store::slide_session].
+///
+/// # Parameters
+/// - `store` - The [Store] the session is recorded in.
+/// - `account_id` - The ID of the account that logged in.
+/// - `user_agent` - The `User-Agent` of the client, if it sent one.
+pub async fn start_remembered_session(
+    store: &Store,
+    account_id: AccountId,
+    user_agent: Option<&str>,
+) -> Result<(String, String), ServiceError> {
+    let SessionLifetimes {
+        remember_me,
+        remember_me_max,
+        ..
+    } = store.session_lifetimes;
+    let expiration = store.clock.now() + remember_me.min(remember_me_max);
+    let jti = Uuid::new_v4().to_string();
+    let refresh_token = new_refresh_token();
+    let session_id = store
+        .add_session(
+            account_id,
+            &jti,
+            user_agent,
+            expiration,
+            Some(&hash_refresh_token(&refresh_token)),
+        )
+        .await?;
+    debug!("remembered session {session_id:?} started");
+    let token = sign_session(store, account_id, session_id, jti, expiration).await?;
+    Ok((token, refresh_token))
+}
+
+/// Returns the [SESSION_COOKIE] holding the token of a session, which expires with the token.
+///
+/// The cookie is HttpOnly, so the scripts of the page cannot read the token, and is only sent
+/// with the requests from the same site, so other sites cannot make requests on behalf of the
+/// account.
+pub(crate) fn session_cookie(token: &str, lifetimes: &SessionLifetimes) -> String {
+    format!(
+        "{SESSION_COOKIE}={token}; Max-Age={}; Path=/; HttpOnly; Secure; SameSite=Strict",
+        lifetimes.token.num_seconds()
+    )
+}
+
+/// Returns the [REFRESH_COOKIE] holding the refresh token of a remembered session, which expires
+/// with the session.
+///
+/// The cookie is HttpOnly and only sent with the requests from the same site, like the
+/// [session_cookie], and only to `POST /refresh`.
+fn refresh_cookie(refresh_token: &str, max_age: Duration) -> String {
+    format!(
+        "{REFRESH_COOKIE}={refresh_token}; Max-Age={}; Path=/refresh; HttpOnly; Secure; SameSite=Strict",
+        max_age.num_seconds()
+    )
+}
+
+/// Hashes the verified password of the account again, with the current parameters of the store.
+///
+/// The login does not depend on it, so the errors are only logged, and the password is hashed
+/// again on the next login.
+async fn rehash_password(store: &Store, account_id: AccountId, password: &str) {
+    trace!("hashing the password again with the current parameters");
+    let result = match hash_password(&store.password_hashing, password.as_bytes()) {
+        Ok(hashed) => store.update_password(account_id, &hashed).await,
+        Err(error) => Err(ServiceError::ArgonLibraryError(error)),
+    };
+    match result {
+        Ok(()) => debug!("password hashed again"),
+        Err(error) => warn!("cannot hash the password again: {error}"),
+    }
+}
+
+/// Checks the credentials of a login, and returns the account they are for.
+///
+/// The logins to an account, and from a client address, are rejected for a while after too many
+/// failed ones, see [Store::check_login_attempts], before the password is verified. The logins to
+/// the accounts locked after too many consecutive wrong passwords are rejected with
+/// [ServiceError::AccountLocked], until an administrator unlocks them, see [Store::record_failed_password].
+///
+/// The passwords hashed with other parameters than the current ones are hashed again once
+/// verified, see [PasswordHashing::needs_rehash].
+///
+/// It is shared by `POST /login` and the gRPC `Login`, so both are protected the same way.
+///
+/// # Parameters
+/// - `store` - The [Store] the accounts and the failed logins are kept in.
+/// - `email` - The email of the account.
+/// - `password` - The password to verify.
+/// - `address` - The address of the client, if it is known.
 ///
 /// # Panics
 /// - If the account ID is not found.
+pub async fn check_credentials(
+    store: &Store,
+    email: &str,
+    password: &str,
+    address: Option<IpAddr>,
+) -> Result<AccountId, ServiceError> {
+    store.check_login_attempts(email, address).await?;
+    trace!("querying account with email = {email:?}");
+    let account = match store.get_account(email).await {
+        Ok(account) => account,
+        Err(error) => {
+            if matches!(error, ServiceError::DatabaseQueryError(sqlx::Error::RowNotFound)) {
+                store.record_failed_login(email, address).await;
+            }
+            return Err(error);
+        }
+    };
+    let account_id = account.id.expect("Account id not found");
+    store.check_account_lock(account_id).await?;
+
+    trace!("account found. verifying password");
+    match verify_password(&account.password, password) {
+        Ok(true) => {
+            debug!("password verified");
+            store.clear_failed_logins(email).await;
+            store.reset_failed_passwords(account_id).await?;
+            if store.password_hashing.needs_rehash(&account.password) {
+                rehash_password(store, account_id, password).await;
+            }
+            Ok(account_id)
+        }
+        Ok(false) => {
+            store.record_failed_login(email, address).await;
+            match store.record_failed_password(account_id).await? {
+                Some(locked_on) => Err(ServiceError::AccountLocked(locked_on)),
+                None => Err(ServiceError::WrongPassword),
+            }
+        }
+        Err(error) => Err(ServiceError::ArgonLibraryError(error)),
+    }
+}
+
+/// Handler for the `POST /login` route.
+///
+/// This handler is used to log in an account, starting a new session, see [start_session]. The
+/// credentials are checked by [check_credentials]. The token is returned in the body, and set in
+/// the session cookie, see [session_cookie], for the browsers.
+///
+/// With `remember_me=true` the session is remembered, see [start_remembered_session], and its
+/// refresh token is set in the refresh cookie, see [refresh_cookie], to renew the token with
+/// `POST /refresh` once it expires.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `params` - HashMap of query parameters
+///   - `remember_me` - Whether the session is remembered, `false` by default
+/// - `login` - The login details.
+/// - `user_agent` - The `User-Agent` of the client, recorded with the session.
+/// - `address` - The address of the client, if it is known.
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "authentication",
+    params(LoginParams),
+    request_body = Account,
+    responses(
+        (status = 200, description = "Token for the account, also set in the `session` cookie", body = String,
+            headers(("set-cookie" = String, description = "HttpOnly `session` cookie holding the token, and `refresh_token` cookie holding the refresh token with `remember_me`"))),
+        (status = 400, description = "Invalid query parameters", body = String),
+        (status = 401, description = "Wrong credentials", body = String),
+        (status = 423, description = "Account locked after too many failed logins", body = String),
+        (status = 429, description = "Too many failed logins, retry after the `Retry-After` seconds", body = String),
+    )
+)]
 #[instrument(target = "webdev_books::accounts", skip(store))]
-pub async fn login(store: Store, login: Account) -> Result<impl Reply, Rejection> {
+pub async fn login(
+    store: Store,
+    params: HashMap<String, String>,
+    login: Account,
+    user_agent: Option<String>,
+    address: Option<SocketAddr>,
+) -> Result<JsonResponse<String>, Rejection> {
+    let LoginParams { remember_me } =
+        LoginParams::extract(&params).map_err(|error| ServiceError::ValidationError(error.to_string()))?;
     let Account { email, password, .. } = login;
-    trace!("querying account with email = {email:?}");
-    match store.get_account(&email).await {
-        Ok(account) => {
-            trace!("account found. verifying password");
-            match verify_password(&account.password, &password) {
-                Ok(true) => {
-                    debug!("password verified. issuing token");
-                    info!("account logged in, issuing token...");
-                    Ok(json(&issue_token(account.id.expect("Account id not found"))))
-                }
-                Ok(false) => Err(warp::reject::custom(ServiceError::WrongPassword)),
-                Err(error) => Err(warp::reject::custom(ServiceError::ArgonLibraryError(error))),
+    let account_id = check_credentials(&store, &email, &password, address.map(|address| address.ip())).await?;
+    debug!("issuing token");
+    let lifetimes = store.session_lifetimes;
+    if remember_me {
+        let (token, refresh_token) = start_remembered_session(&store, account_id, user_agent.as_deref()).await?;
+        info!("account logged in, issuing token and refresh token...");
+        let max_age = lifetimes.remember_me.min(lifetimes.remember_me_max);
+        return Ok(JsonResponse::ok(token.clone())
+            .with_cookie(session_cookie(&token, &lifetimes))
+            .with_cookie(refresh_cookie(&refresh_token, max_age)));
+    }
+    let token = start_session(&store, account_id, user_agent.as_deref()).await?;
+    info!("account logged in, issuing token...");
+    let cookie = session_cookie(&token, &lifetimes);
+    Ok(JsonResponse::ok(token).with_cookie(cookie))
+}
+
+/// Handler for the `POST /refresh` route.
+///
+/// This handler is used to renew the token of a remembered session, started by logging in with
+/// `remember_me`, see [Store::refresh_session]. The refresh token is replaced with a new one on
+/// every refresh, so the previous token and refresh token are rejected from now on, and the
+/// session is extended, so it only expires once it is left unused.
+///
+/// The new token is returned in the body and set in the session cookie, and the new refresh token
+/// is set in the refresh cookie, like with `POST /login`.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `refresh_token` - The refresh token, read from its cookie, if the client sent one.
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    tag = "authentication",
+    responses(
+        (status = 200, description = "New token for the session, also set in the `session` cookie", body = String,
+            headers(("set-cookie" = String, description = "HttpOnly `session` cookie holding the token, and `refresh_token` cookie holding the new refresh token"))),
+        (status = 401, description = "Missing, invalid or expired refresh token", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip_all)]
+pub async fn refresh(store: Store, refresh_token: Option<String>) -> Result<JsonResponse<String>, Rejection> {
+    let refresh_token = refresh_token.ok_or(ServiceError::InvalidRefreshToken)?;
+    let jti = Uuid::new_v4().to_string();
+    let new_refresh_token = new_refresh_token();
+    trace!("refreshing the session");
+    let Some((session_id, account_id, expiration)) = store
+        .refresh_session(
+            &hash_refresh_token(&refresh_token),
+            &jti,
+            &hash_refresh_token(&new_refresh_token),
+        )
+        .await?
+    else {
+        return Err(ServiceError::InvalidRefreshToken.into());
+    };
+    let token = sign_session(&store, account_id, session_id, jti, expiration).await?;
+    info!("session {session_id:?} refreshed, issuing token...");
+    let max_age = expiration - store.clock.now();
+    Ok(JsonResponse::ok(token.clone())
+        .with_cookie(session_cookie(&token, &store.session_lifetimes))
+        .with_cookie(refresh_cookie(&new_refresh_token, max_age)))
+}
+
+/// Handler for the `POST /logout` route.
+///
+/// This handler is used to log out an account, by revoking the token the request is authenticated
+/// with, so it is rejected from now on, even though it has not expired. Its session is ended, with
+/// its refresh token, and the session cookie and the refresh cookie of the client are cleared.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `session` - The session read from the token to revoke.
+#[utoipa::path(
+    post,
+    path = "/logout",
+    tag = "authentication",
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Token revoked, and the `session` and `refresh_token` cookies cleared", body = String),
+        (status = 401, description = "Missing, invalid or revoked token", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn logout(store: Store, session: Session) -> Result<MessageResponse, Rejection> {
+    trace!(
+        "revoking the token of the account with account_id = {:?}",
+        session.account_id
+    );
+    store.revoke_token(&session.jti, session.exp).await?;
+    info!("account logged out");
+    Ok(MessageResponse::ok("Logged out").with_cookies(&[CLEARED_SESSION_COOKIE, CLEARED_REFRESH_COOKIE]))
+}
+
+/// Handler for the `GET /accounts/me` route.
+///
+/// This handler is used to read the profile of the account making the request, see
+/// [Store::get_account_by_id]. The profile does not include the password of the account.
+///
+/// # Parameters
+/// - `store` - The [Storage] to use for handling requests, usually the [Store].
+/// - `session` - The session of the account.
+#[utoipa::path(
+    get,
+    path = "/accounts/me",
+    tag = "authentication",
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The profile of the account", body = AccountProfile),
+        (status = 401, description = "Missing, invalid or revoked token", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn get_account<S: Storage>(store: S, session: Session) -> Result<JsonResponse<AccountProfile>, Rejection> {
+    trace!("querying the account with account_id = {:?}", session.account_id);
+    let profile = store.get_account_by_id(session.account_id).await?;
+    info!("returning the profile of the account");
+    Ok(JsonResponse::ok(profile))
+}
+
+/// Handler for the `GET /accounts/me/sessions` route.
+///
+/// This handler is used to list the sessions of the account making the request, the devices it is
+/// logged in on, see [Store::get_sessions]. The session of the request is marked as current.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `session` - The session of the account.
+#[utoipa::path(
+    get,
+    path = "/accounts/me/sessions",
+    tag = "authentication",
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The sessions of the account", body = [ActiveSession]),
+        (status = 401, description = "Missing, invalid or revoked token", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn get_sessions(store: Store, session: Session) -> Result<JsonResponse<Vec<ActiveSession>>, Rejection> {
+    trace!(
+        "querying the sessions of the account with account_id = {:?}",
+        session.account_id
+    );
+    let sessions = store.get_sessions(session.account_id, session.sid).await?;
+    info!("returning {} sessions", sessions.len());
+    Ok(JsonResponse::ok(sessions))
+}
+
+/// Handler for the `DELETE /accounts/me/sessions/{id}` route.
+///
+/// This handler is used to sign a device of the account out, by deleting its session, see
+/// [Store::delete_session]. The token of the session is rejected from now on, even though it has
+/// not expired. The session of the request can be deleted as well, like with `POST /logout`.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `session_id` - [SessionId] of the session to delete.
+/// - `session` - The session of the account.
+#[utoipa::path(
+    delete,
+    path = "/accounts/me/sessions/{id}",
+    tag = "authentication",
+    params(("id" = SessionId, Path, description = "Id of the session")),
+    security(("token" = [])),
+    responses(
+        (status = 204, description = "Session deleted"),
+        (status = 400, description = "Invalid session id", body = String),
+        (status = 401, description = "Missing, invalid or revoked token", body = String),
+        (status = 404, description = "Session not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn delete_session(
+    store: Store,
+    session_id: SessionId,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    trace!(
+        "deleting the session {session_id:?} of the account with account_id = {:?}",
+        session.account_id
+    );
+    if !store.delete_session(session.account_id, session_id).await? {
+        return Err(ServiceError::SessionNotFound(session_id.into()).into());
+    }
+    info!("session deleted");
+    Ok(MessageResponse::no_content())
+}
+
+/// Normalizes the profile, and checks that it can be stored.
+///
+/// The display name is normalized as a single line and the bio as many lines, see
+/// [sanitize](crate::types::sanitize), and the fields that are empty once normalized are cleared.
+/// The fields must not be longer than the limits of [ProfileUpdate], and the website has to be an
+/// absolute `http` or `https` URL.
+fn validate_profile(profile: ProfileUpdate) -> Result<ProfileUpdate, ServiceError> {
+    let non_empty = |text: String| (!text.is_empty()).then_some(text);
+    let profile = ProfileUpdate {
+        display_name: profile
+            .display_name
+            .map(|name| sanitize::single_line(&name))
+            .and_then(non_empty),
+        bio: profile.bio.map(|bio| sanitize::multi_line(&bio)).and_then(non_empty),
+        website: profile
+            .website
+            .map(|website| website.trim().to_string())
+            .and_then(non_empty),
+    };
+    for (name, field, limit) in [
+        ("display name", &profile.display_name, ProfileUpdate::DISPLAY_NAME_BYTES),
+        ("bio", &profile.bio, ProfileUpdate::BIO_BYTES),
+        ("website", &profile.website, ProfileUpdate::WEBSITE_BYTES),
+    ] {
+        if field.as_ref().is_some_and(|text| text.len() > limit) {
+            return Err(ServiceError::ValidationError(format!(
+                "{name} is longer than {limit} bytes"
+            )));
+        }
+    }
+    if let Some(website) = &profile.website {
+        match Url::parse(website) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            _ => {
+                return Err(ServiceError::ValidationError(format!(
+                    "invalid website url: {website:?}"
+                )))
             }
         }
-        Err(error) => Err(warp::reject::custom(error)),
     }
+    Ok(profile)
+}
+
+/// Handler for the `PUT /accounts/me` route.
+///
+/// This handler is used to replace the profile of the account making the request, see
+/// [validate_profile] for the accepted values.
+///
+/// # Parameters
+/// - `store` - The [Storage] to use for handling requests, usually the [Store].
+/// - `session` - The session of the account.
+/// - `profile` - [ProfileUpdate] object containing the new profile.
+#[utoipa::path(
+    put,
+    path = "/accounts/me",
+    tag = "authentication",
+    request_body = ProfileUpdate,
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The updated profile of the account", body = AccountProfile),
+        (status = 400, description = "Invalid display name, bio or website", body = String),
+        (status = 401, description = "Missing, invalid or revoked token", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn update_account<S: Storage>(
+    store: S,
+    session: Session,
+    profile: ProfileUpdate,
+) -> Result<JsonResponse<AccountProfile>, Rejection> {
+    let profile = validate_profile(profile)?;
+    trace!(
+        "updating the profile of the account with account_id = {:?}",
+        session.account_id
+    );
+    let profile = store.update_profile(session.account_id, &profile).await?;
+    info!("profile of the account updated");
+    Ok(JsonResponse::ok(profile))
+}
+
+/// Handler for the `GET /accounts/{id}` route.
+///
+/// This handler is used to read the public profile of any account, with the number of the
+/// questions and the answers it posted. The email of the account is not included.
+///
+/// # Parameters
+/// - `store` - The [Storage] to use for handling requests, usually the [Store].
+/// - `account_id` - [AccountId] of the account.
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}",
+    tag = "authentication",
+    params(("id" = AccountId, Path, description = "Id of the account")),
+    responses(
+        (status = 200, description = "The public profile of the account", body = PublicProfile),
+        (status = 400, description = "Invalid account id", body = String),
+        (status = 404, description = "Account not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn get_public_profile<S: Storage>(
+    store: S,
+    account_id: AccountId,
+) -> Result<JsonResponse<PublicProfile>, Rejection> {
+    trace!("querying the profile of the account with account_id = {account_id:?}");
+    let profile = store.get_public_profile(account_id).await?;
+    info!("returning the public profile of the account");
+    Ok(JsonResponse::ok(profile))
+}
+
+/// Handler for the `DELETE /accounts/me` route.
+///
+/// This handler is used to delete the account making the request, see [Store::delete_account].
+/// Its questions and answers are anonymized, or deleted with `content=delete`, and its tokens are
+/// rejected from now on.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `params` - HashMap of query parameters
+///   - `content` - What happens to the questions and the answers: `anonymize` or `delete`
+/// - `session` - The session of the account to delete.
+#[utoipa::path(
+    delete,
+    path = "/accounts/me",
+    tag = "authentication",
+    params(DeleteAccountParams),
+    security(("token" = [])),
+    responses(
+        (status = 204, description = "Account deleted"),
+        (status = 400, description = "Invalid query parameters", body = String),
+        (status = 401, description = "Missing, invalid or revoked token", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn delete_account(
+    store: Store,
+    params: HashMap<String, String>,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    let DeleteAccountParams { content } =
+        DeleteAccountParams::extract(&params).map_err(|error| ServiceError::ValidationError(error.to_string()))?;
+    trace!(
+        "deleting the account with account_id = {:?}, content = {content:?}",
+        session.account_id
+    );
+    if !store
+        .delete_account(session.account_id, content, &session.jti, session.exp)
+        .await?
+    {
+        return Err(ServiceError::AccountNotFound(session.account_id.into()).into());
+    }
+    info!("account deleted");
+    Ok(MessageResponse::no_content())
 }
@@ -2,60 +2,302 @@
 //!
 //! This module contains the following submodules:
 //! - `handlers`- Contains the handlers for the `Authentication` resource
+//! - `oauth`- Contains the handlers of the login with the external providers
 //! - `routes`- Contains the routes for the `Authentication` resource
 use std::future;
+use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use utoipa::OpenApi;
 use warp::filters::BoxedFilter;
 use warp::{Filter, Reply};
 
+use crate::clock::Clock;
 use crate::error::ServiceError;
+use crate::storage::Storage;
 use crate::store::Store;
+use crate::tokens::paseto::V2_LOCAL;
+use crate::tokens::TokenSigner;
 use crate::types::authentication::Session;
+use crate::types::moderation::AccountRole;
 
 /// Handlers for the `Authentication` resource.
 mod handlers;
+/// Login with the external providers, using OAuth 2.0.
+mod oauth;
 /// Routes for the `Authentication` resource.
 mod routes;
 
+pub(crate) use handlers::{check_credentials, hash_password, session_cookie, start_session};
+#[cfg(feature = "test-util")]
+pub(crate) use handlers::{issue_token, issue_token_valid_between};
+
+/// The HttpOnly cookie holding the token of the session, set by the logins, so the browsers can
+/// authenticate without exposing the token to the scripts of the page.
+pub(crate) const SESSION_COOKIE: &str = "session";
+
+/// The HttpOnly cookie holding the refresh token of a remembered session, set by the logins with
+/// `remember_me`, and only sent to `POST /refresh`.
+pub(crate) const REFRESH_COOKIE: &str = "refresh_token";
+
+/// OpenAPI document for the `Authentication` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::register,
+        handlers::login,
+        handlers::refresh,
+        handlers::logout,
+        handlers::get_account,
+        handlers::update_account,
+        handlers::delete_account,
+        handlers::get_public_profile,
+        handlers::get_sessions,
+        handlers::delete_session,
+        oauth::authorize,
+        oauth::callback
+    ),
+    tags((name = "authentication", description = "Registration, login, logout, sessions, profiles and deletion of accounts"))
+)]
+pub struct AuthenticationApi;
+
 /// Filter for the `Authentication` resource.
 ///
 /// Creates a filter that handles requests for the `Authentication` resource.
 ///
 /// The filter combines the following filters:
 /// - `register`, for handling `POST /register`
+/// - `login`, for handling `POST /login`
+/// - `refresh`, for handling `POST /refresh`
+/// - `logout`, for handling `POST /logout`
+/// - `get_account`, for handling `GET /accounts/me`
+/// - `update_account`, for handling `PUT /accounts/me`
+/// - `delete_account`, for handling `DELETE /accounts/me`
+/// - `get_public_profile`, for handling `GET /accounts/{id}`
+/// - `get_sessions`, for handling `GET /accounts/me/sessions`
+/// - `delete_session`, for handling `DELETE /accounts/me/sessions/{id}`
+/// - `oauth_authorize`, for handling `GET /oauth/{provider}`
+/// - `oauth_callback`, for handling `GET /oauth/{provider}/callback`
 ///
 /// # Parameters
 /// - `store` - The [Store] to use for handling requests.
 pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
-    routes::register(store.clone()).or(routes::login(store.clone())).boxed()
+    routes::register(store.clone())
+        .or(routes::login(store.clone()))
+        .or(routes::refresh(store.clone()))
+        .or(routes::logout(store.clone()))
+        .or(routes::get_account(store.clone()))
+        .or(routes::update_account(store.clone()))
+        .or(routes::delete_account(store.clone()))
+        .or(routes::get_public_profile(store.clone()))
+        .or(routes::get_sessions(store.clone()))
+        .or(routes::delete_session(store.clone()))
+        .or(routes::oauth_authorize(store.clone()))
+        .or(routes::oauth_callback(store.clone()))
+        .boxed()
+}
+
+/// Filter for the `Authentication` resource, served from any [Storage].
+///
+/// Combines the routes of [filter] that only depend on the [Storage], so they can be served from
+/// another backend than the [Store], e.g. the in-memory one of the tests. The accounts log in
+/// through the [Store] only, as the sessions are kept there:
+/// - `register`, for handling `POST /register`
+/// - `get_account`, for handling `GET /accounts/me`
+/// - `update_account`, for handling `PUT /accounts/me`
+/// - `get_public_profile`, for handling `GET /accounts/{id}`
+///
+/// # Parameters
+/// - `store` - The [Storage] to use for handling requests.
+pub fn storage_filter<S: Storage>(store: &S) -> BoxedFilter<(impl Reply,)> {
+    routes::register(store.clone())
+        .or(routes::get_account(store.clone()))
+        .or(routes::update_account(store.clone()))
+        .or(routes::get_public_profile(store.clone()))
+        .boxed()
+}
+
+/// Verifies a token and returns the [`Session`] it was issued for.
+///
+/// The token is valid if it was issued by the `signer`, see [TokenSigner::verify], the time of the
+/// `clock` is between its `nbf` and `exp` claims, and it has a `jti` claim. Otherwise,
+/// it returns a [`ServiceError::CannotDecrpytToken`](ServiceError::CannotDecryptToken). The valid
+/// tokens issued with other claims, see [Session::CLAIMS_VERSION], are rejected with
+/// [ServiceError::OutdatedToken], except the v2 tokens, see [legacy_claims].
+pub fn verify_token(signer: &dyn TokenSigner, clock: &dyn Clock, token: String) -> Result<Session, ServiceError> {
+    let now = clock.now();
+    let mut claims = signer.verify(&token, now)?;
+    if claim_time(&claims, "exp")?.is_some_and(|expiration| expiration < now)
+        || claim_time(&claims, "nbf")?.is_some_and(|not_before| not_before > now)
+    {
+        return Err(ServiceError::CannotDecryptToken);
+    }
+    if token.starts_with(V2_LOCAL) && claims.get("ver").is_none() {
+        legacy_claims(&token, &mut claims);
+    }
+    // The tokens issued before the claims were versioned have no version
+    match claims.get("ver").map_or(Some(0), serde_json::Value::as_u64) {
+        Some(version) if version == u64::from(Session::CLAIMS_VERSION) => {}
+        Some(_) => return Err(ServiceError::OutdatedToken),
+        None => return Err(ServiceError::CannotDecryptToken),
+    }
+
+    session_from_claims(claims)
+}
+
+/// Completes the claims of a v2 token issued before the claims were versioned.
+///
+/// These tokens only carry the `exp`, `nbf` and `account_id` claims. They are still accepted
+/// during the transition to the v4 tokens, see [AuthKeys::accepts_v2_tokens](crate::types::authentication::AuthKeys::accepts_v2_tokens),
+/// so their claims are completed with the current version, a `jti` derived from the token, so they
+/// can be revoked, and the default role and an empty email, which [authenticate] replaces with the
+/// ones of the account.
+fn legacy_claims(token: &str, claims: &mut serde_json::Value) {
+    let Some(claims) = claims.as_object_mut() else {
+        return;
+    };
+    claims.insert("ver".to_string(), serde_json::json!(Session::CLAIMS_VERSION));
+    claims
+        .entry("jti")
+        .or_insert_with(|| serde_json::json!(format!("v2-{}", hex::encode(Sha256::digest(token.as_bytes())))));
+    claims.entry("email").or_insert_with(|| serde_json::json!(""));
+    claims
+        .entry("role")
+        .or_insert_with(|| serde_json::json!(AccountRole::default()));
+}
+
+/// Reads a time claim of a decrypted token, if present.
+///
+/// The time is a date in the RFC 3339 format in the PASETO tokens, and a number of seconds since the
+/// Unix epoch in the JWTs.
+fn claim_time(claims: &serde_json::Value, claim: &str) -> Result<Option<DateTime<Utc>>, ServiceError> {
+    claims
+        .get(claim)
+        .map(|value| {
+            let time = match value {
+                serde_json::Value::String(value) => DateTime::parse_from_rfc3339(value)
+                    .ok()
+                    .map(|value| value.with_timezone(&Utc)),
+                serde_json::Value::Number(seconds) => seconds
+                    .as_i64()
+                    .and_then(|seconds| DateTime::from_timestamp(seconds, 0)),
+                _ => None,
+            };
+            time.ok_or(ServiceError::CannotDecryptToken)
+        })
+        .transpose()
 }
 
-/// Verifies a password using Argon2.
+/// Reads the [`Session`] from the claims of a decrypted token.
 ///
-/// Verifies a password using Argon2 and returns a [`Session`]
+/// Claims that do not describe a session are rejected with
+/// [`ServiceError::CannotDecryptToken`](ServiceError::CannotDecryptToken), like the tokens that
+/// cannot be decrypted.
+pub fn session_from_claims(claims: serde_json::Value) -> Result<Session, ServiceError> {
+    serde_json::from_value::<Session>(claims).map_err(|_| ServiceError::CannotDecryptToken)
+}
+
+/// Returns the token from the value of an `Authorization` header.
 ///
-/// If the password is correct, otherwise,
-/// it returns a [`ServiceError::CannotDecrpytToken`](ServiceError::CannotDecryptToken).
-pub fn verify_token(token: String) -> Result<Session, ServiceError> {
-    let key = std::env::var("PASETO_KEY").unwrap();
-    use paseto::tokens::{validate_local_token, TimeBackend};
-    let token = validate_local_token(&token, None, key.as_bytes(), &TimeBackend::Chrono)
-        .map_err(|_| ServiceError::CannotDecryptToken)?;
+/// The token is sent either raw, or with the `Bearer` scheme, whose name is case-insensitive,
+/// e.g. `Bearer v4.local...`.
+fn bearer_token(value: &str) -> &str {
+    match value.trim().split_once(' ') {
+        Some((scheme, token)) if scheme.eq_ignore_ascii_case("bearer") => token.trim_start(),
+        _ => value.trim(),
+    }
+}
 
-    serde_json::from_value::<Session>(token).map_err(|_| ServiceError::CannotDecryptToken)
+/// Authenticates a request with the token it carries.
+///
+/// The token may carry the `Bearer` scheme, which is stripped from it. It is verified with [verify_token], against the signer and the clock of the storage. The
+/// session of the token is then checked by the storage, see [Storage::validate_session]: the [Store] rejects the revoked tokens
+/// with [ServiceError::TokenRevoked], like the tokens of the deleted sessions and of the deleted
+/// accounts, and the requests of the banned accounts with [ServiceError::AccountBanned]. The remembered
+/// sessions are extended by their use, see [Store::slide_session]. The v2 tokens, issued before the
+/// claims carried the email and the role of the account, get the current ones of the account.
+///
+/// # Parameters
+/// - `store` - The [Storage] whose token signer, clock and accounts are used, usually the [Store].
+/// - `token` - The token sent with the request.
+pub async fn authenticate<S: Storage>(store: &S, token: String) -> Result<Session, ServiceError> {
+    let token = bearer_token(&token).to_string();
+    let is_v2 = token.starts_with(V2_LOCAL);
+    let mut session = verify_token(store.token_signer(), store.clock(), token)?;
+    store.validate_session(&session).await?;
+    if is_v2 {
+        // The v2 tokens carry neither the email nor the role, see legacy_claims
+        let account = |error| match error {
+            ServiceError::AccountNotFound(_) => ServiceError::TokenRevoked,
+            error => error,
+        };
+        session.email = store
+            .get_account_by_id(session.account_id)
+            .await
+            .map_err(account)?
+            .email;
+        session.role = store.get_account_role(session.account_id).await.map_err(account)?;
+    }
+    Ok(session)
 }
 
 /// Filter for authenticating requests.
 ///
-/// Creates a filter that authenticates requests using the `Authorization` header.
+/// Creates a filter that authenticates requests using the `Authorization` header, or the
+/// [SESSION_COOKIE] if the header is missing. The requests with neither are rejected as missing
+/// the header.
 ///
-/// The filter extracts a `Session` if the request is authenticated,
+/// The filter extracts a `Session` if the request is authenticated, see [authenticate],
 /// otherwise it rejects the request.
-pub fn auth() -> impl Filter<Extract = (Session,), Error = warp::Rejection> + Clone {
-    warp::header("Authorization").and_then(|token| {
-        future::ready(match verify_token(token) {
-            Ok(session) => Ok(session),
-            Err(error) => Err(warp::reject::custom(error)),
-        })
+///
+/// The store is held behind an [Arc], so the filter is cheap to clone, see [store_filter](crate::filters::store_filter).
+///
+/// # Parameters
+/// - `store` - The [Storage] whose token signer, clock and accounts are used, usually the [Store].
+pub fn auth<S: Storage>(store: &S) -> impl Filter<Extract = (Session,), Error = warp::Rejection> + Clone {
+    let store = Arc::new(store.clone());
+    let token = warp::header::<String>("Authorization")
+        .or(warp::cookie::<String>(SESSION_COOKIE))
+        .unify();
+    token.and_then(move |token| {
+        let store = Arc::clone(&store);
+        async move { authenticate(store.as_ref(), token).await.map_err(warp::reject::custom) }
     })
 }
+
+/// Filter for authorizing administrative requests.
+///
+/// Creates a filter that accepts the requests carrying the administrator token of the store, see
+/// [Store::with_admin_token], in the `X-Admin-Token` header.
+///
+/// The filter rejects all requests with [ServiceError::Unauthorized] if the token is not set.
+///
+/// # Parameters
+/// - `store` - The [Store] whose administrator token is used.
+pub fn admin(store: &Store) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    let admin_token: Option<Arc<str>> = store.admin_token.as_deref().map(Arc::from);
+    warp::header::optional::<String>("X-Admin-Token")
+        .and_then(move |token: Option<String>| {
+            future::ready(match (token, admin_token.as_deref()) {
+                (Some(token), Some(admin_token)) if !admin_token.is_empty() && token == admin_token => Ok(()),
+                _ => Err(warp::reject::custom(ServiceError::Unauthorized)),
+            })
+        })
+        .untuple_one()
+}
+
+/// Filter for authorizing the requests of the accounts, or of the administrators.
+///
+/// Creates a filter that authenticates the request with the `Authorization` header or the session
+/// cookie, see [auth], and extracts the `Session` of the account. Requests without a valid token
+/// are accepted if they carry the administrator token instead, see [admin], and `None` is
+/// extracted for them.
+///
+/// # Parameters
+/// - `store` - The [Store] whose token signer, clock and accounts are used.
+pub fn auth_or_admin(store: &Store) -> impl Filter<Extract = (Option<Session>,), Error = warp::Rejection> + Clone {
+    auth(store).map(Some).or(admin(store).map(|| None)).unify()
+}
@@ -0,0 +1,310 @@
+//! Login with the external providers, using the OAuth 2.0 authorization code flow.
+//!
+//! The login goes through three steps:
+//! 1. `GET /oauth/{provider}` redirects the client to the provider, where the account authorizes the
+//!    service to read its email.
+//! 2. The provider redirects the client back to `GET /oauth/{provider}/callback`, with a code, which
+//!    the service exchanges for an access token, with which it reads the verified email of the
+//!    account from the provider.
+//! 3. The account with the email is logged in, and is created if there is none, see
+//!    [Store::get_or_add_external_account], and a token is returned like by `POST /login`.
+//!
+//! The `state` sent to the provider is a PASETO token encrypted with the current key, whatever the
+//! format of the tokens of the sessions, naming the provider and a random nonce, which is also set
+//! in the [NONCE_COOKIE] of the client. So the callback is
+//! only accepted from the client that started the login, and only for [STATE_VALIDITY_MINUTES].
+//!
+//! The providers are enabled by their clients, see [OAuthConfig](crate::types::authentication::OAuthConfig).
+use std::collections::HashMap;
+
+use chrono::Duration;
+use rand::random;
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::Url;
+use serde::Deserialize;
+use tracing::{debug, info, instrument, trace};
+use uuid::Uuid;
+use warp::Rejection;
+
+use crate::authentication::{claim_time, hash_password, session_cookie, start_session};
+use crate::error::ServiceError;
+use crate::responses::{JsonResponse, RedirectResponse};
+use crate::store::Store;
+use crate::tokens::paseto::{self, key_footer};
+use crate::types::authentication::{OAuthClient, OAuthProvider};
+
+/// The cookie holding the nonce of the login in progress.
+pub(crate) const NONCE_COOKIE: &str = "oauth_nonce";
+/// How long a login is accepted for, from the redirect to the provider to the callback, in minutes.
+const STATE_VALIDITY_MINUTES: i64 = 10;
+
+/// The endpoints of a provider, and the scope that grants reading the email of the account.
+struct Endpoints {
+    /// The page the clients are redirected to, to authorize the service.
+    authorize: &'static str,
+    /// The endpoint exchanging the codes for access tokens.
+    token: &'static str,
+    /// The endpoint returning the email of the account.
+    email: &'static str,
+    /// The scope requested from the account.
+    scope: &'static str,
+}
+
+/// Returns the endpoints of the provider.
+fn endpoints(provider: OAuthProvider) -> Endpoints {
+    match provider {
+        OAuthProvider::GitHub => Endpoints {
+            authorize: "https://github.com/login/oauth/authorize",
+            token: "https://github.com/login/oauth/access_token",
+            email: "https://api.github.com/user/emails",
+            scope: "user:email",
+        },
+        OAuthProvider::Google => Endpoints {
+            authorize: "https://accounts.google.com/o/oauth2/v2/auth",
+            token: "https://oauth2.googleapis.com/token",
+            email: "https://openidconnect.googleapis.com/v1/userinfo",
+            scope: "openid email",
+        },
+    }
+}
+
+/// Returns the provider with the name from the path, and its client, if it is enabled.
+fn enabled_provider<'a>(store: &'a Store, name: &str) -> Result<(OAuthProvider, &'a OAuthClient), ServiceError> {
+    name.parse::<OAuthProvider>()
+        .ok()
+        .and_then(|provider| store.oauth.client(provider).map(|client| (provider, client)))
+        .ok_or_else(|| ServiceError::OAuthProviderNotFound(name.to_string()))
+}
+
+/// Returns the `state` of a login with the provider, encrypted with the current key.
+fn issue_state(store: &Store, provider: OAuthProvider, nonce: &str) -> String {
+    let (key_id, key) = store.auth_keys.current_key();
+    let expiration = store.clock.now() + Duration::try_minutes(STATE_VALIDITY_MINUTES).unwrap();
+    let claims = serde_json::json!({
+        "exp": expiration.to_rfc3339(),
+        "oauth_provider": provider,
+        "nonce": nonce,
+    });
+    paseto::encrypt(key, &key_footer(key_id), &claims)
+}
+
+/// Checks that the `state` was issued by [issue_state] for the provider, has not expired, and
+/// names the nonce of the cookie of the client.
+fn verify_state(store: &Store, provider: OAuthProvider, state: &str, nonce: Option<&str>) -> Result<(), ServiceError> {
+    let invalid = || ServiceError::OAuthFailed("invalid or expired state".to_string());
+    let (key_id, key) = store.auth_keys.current_key();
+    // The states are only issued as v4 tokens, and are short lived, so no v2 state is accepted
+    let claims = paseto::decrypt(state, key, Some(&key_footer(key_id)), false)
+        .and_then(|claims| serde_json::from_str::<serde_json::Value>(&claims).ok())
+        .ok_or_else(invalid)?;
+
+    let expired = claim_time(&claims, "exp")
+        .map_err(|_| invalid())?
+        .is_none_or(|expiration| expiration < store.clock.now());
+    if expired
+        || claims["oauth_provider"] != serde_json::json!(provider)
+        || nonce.is_none_or(|nonce| claims["nonce"] != nonce)
+    {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// The response of the token endpoint of a provider.
+///
+/// GitHub answers the rejected codes with `200 OK` and an error, so both fields are optional.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+}
+
+/// An email of a GitHub account, listed by `GET /user/emails`.
+#[derive(Debug, Deserialize)]
+struct GitHubEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+/// The claims of a Google account, returned by the `userinfo` endpoint.
+#[derive(Debug, Deserialize)]
+struct GoogleUserInfo {
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+}
+
+/// Exchanges the code for an access token, and returns the email of the account, which the
+/// provider verified.
+///
+/// # Errors
+/// - [ServiceError::OAuthFailed] if the code is rejected, or the account has no verified email.
+/// - [ServiceError::ReqwestAPIError] if the provider cannot be reached.
+async fn verified_email(
+    store: &Store,
+    provider: OAuthProvider,
+    client: &OAuthClient,
+    code: &str,
+) -> Result<String, ServiceError> {
+    let http = reqwest::Client::new();
+    let endpoints = endpoints(provider);
+    let redirect_uri = store.oauth.redirect_uri(provider);
+
+    trace!("exchanging the code for an access token");
+    let token = http
+        .post(endpoints.token)
+        .header(ACCEPT, "application/json")
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("client_id", client.client_id.as_str()),
+            ("client_secret", client.client_secret()),
+        ])
+        .send()
+        .await?
+        .json::<TokenResponse>()
+        .await?;
+    let access_token = match token {
+        TokenResponse {
+            access_token: Some(access_token),
+            ..
+        } => access_token,
+        TokenResponse { error, .. } => {
+            let error = error.unwrap_or_else(|| "no access token".to_string());
+            return Err(ServiceError::OAuthFailed(format!("the code was rejected: {error}")));
+        }
+    };
+
+    trace!("reading the email of the account");
+    let response = http
+        .get(endpoints.email)
+        .bearer_auth(access_token)
+        .header(ACCEPT, "application/json")
+        // GitHub rejects the requests without a user agent
+        .header(USER_AGENT, "webdev-book")
+        .send()
+        .await?
+        .error_for_status()
+        .map_err(|error| ServiceError::OAuthFailed(format!("the email could not be read: {error}")))?;
+    let email = match provider {
+        OAuthProvider::GitHub => response
+            .json::<Vec<GitHubEmail>>()
+            .await?
+            .into_iter()
+            .find(|email| email.primary && email.verified)
+            .map(|email| email.email),
+        OAuthProvider::Google => {
+            let user_info = response.json::<GoogleUserInfo>().await?;
+            user_info.email.filter(|_| user_info.email_verified)
+        }
+    };
+    email.ok_or_else(|| ServiceError::OAuthFailed("the account has no verified email".to_string()))
+}
+
+/// Handler for the `GET /oauth/{provider}` route.
+///
+/// This handler starts the login with the provider, by redirecting the client to it, see the
+/// [module](self) documentation.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `provider` - The name of the provider, `github` or `google`.
+#[utoipa::path(
+    get,
+    path = "/oauth/{provider}",
+    tag = "authentication",
+    params(("provider" = String, Path, description = "Name of the provider: `github` or `google`")),
+    responses(
+        (status = 303, description = "Redirect to the provider, setting the nonce cookie of the login"),
+        (status = 404, description = "Unknown or disabled provider", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store))]
+pub async fn authorize(store: Store, provider: String) -> Result<RedirectResponse, Rejection> {
+    let (provider, client) = enabled_provider(&store, &provider)?;
+    let nonce = Uuid::new_v4().to_string();
+    let endpoints = endpoints(provider);
+
+    let mut location = Url::parse(endpoints.authorize).expect("the endpoints are valid URLs");
+    location
+        .query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &client.client_id)
+        .append_pair("redirect_uri", &store.oauth.redirect_uri(provider))
+        .append_pair("scope", endpoints.scope)
+        .append_pair("state", &issue_state(&store, provider, &nonce));
+    let cookie = format!(
+        "{NONCE_COOKIE}={nonce}; Max-Age={}; Path=/oauth; HttpOnly; Secure; SameSite=Lax",
+        STATE_VALIDITY_MINUTES * 60
+    );
+
+    info!("redirecting to the OAuth provider {}", provider.as_str());
+    Ok(RedirectResponse::see_other(location).with_cookie(cookie))
+}
+
+/// Handler for the `GET /oauth/{provider}/callback` route.
+///
+/// This handler completes the login with the provider, and returns a token, also set in the
+/// session cookie, like `POST /login`.
+/// The account is linked by the email verified by the provider, and created if there is none.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `provider` - The name of the provider, `github` or `google`.
+/// - `params` - HashMap of query parameters
+///   - `code` - The code to exchange for an access token
+///   - `state` - The state issued by [authorize]
+///   - `error` - The error, if the account denied the login
+/// - `nonce` - The nonce of the login, from the [NONCE_COOKIE].
+/// - `user_agent` - The `User-Agent` of the client, recorded with the session.
+#[utoipa::path(
+    get,
+    path = "/oauth/{provider}/callback",
+    tag = "authentication",
+    params(
+        ("provider" = String, Path, description = "Name of the provider: `github` or `google`"),
+        ("code" = Option<String>, Query, description = "Code to exchange for an access token"),
+        ("state" = Option<String>, Query, description = "State of the login, issued with the redirect to the provider"),
+        ("error" = Option<String>, Query, description = "Error returned by the provider, e.g. if the login was denied"),
+    ),
+    responses(
+        (status = 200, description = "The token of the account, also set in the `session` cookie", body = String),
+        (status = 400, description = "Email not accepted for the accounts", body = String),
+        (status = 401, description = "Denied login, invalid state or code, or no verified email", body = String),
+        (status = 404, description = "Unknown or disabled provider", body = String),
+    )
+)]
+#[instrument(target = "webdev_books::accounts", skip(store, params, nonce))]
+pub async fn callback(
+    store: Store,
+    provider: String,
+    params: HashMap<String, String>,
+    nonce: Option<String>,
+    user_agent: Option<String>,
+) -> Result<JsonResponse<String>, Rejection> {
+    let (provider, client) = enabled_provider(&store, &provider)?;
+    if let Some(error) = params.get("error") {
+        return Err(ServiceError::OAuthFailed(format!("the provider returned {error:?}")).into());
+    }
+    let (Some(code), Some(state)) = (params.get("code"), params.get("state")) else {
+        return Err(ServiceError::OAuthFailed("missing code or state".to_string()).into());
+    };
+    verify_state(&store, provider, state, nonce.as_deref())?;
+
+    let email = verified_email(&store, provider, client, code).await?;
+    debug!(%email, "email verified by the provider");
+    // The accounts created here log in only with the provider, as their password is never known
+    let password =
+        hash_password(&store.password_hashing, &random::<[u8; 32]>()).map_err(ServiceError::ArgonLibraryError)?;
+    let account_id = store.get_or_add_external_account(&email, &password).await?;
+
+    let token = start_session(&store, account_id, user_agent.as_deref()).await?;
+    info!(
+        "account logged in with the OAuth provider {}, issuing token...",
+        provider.as_str()
+    );
+    let cookie = session_cookie(&token, &store.session_lifetimes);
+    Ok(JsonResponse::ok(token).with_cookie(cookie))
+}
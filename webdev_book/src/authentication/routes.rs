@@ -1,36 +1,233 @@
-use crate::authentication::handlers;
-use crate::filters::store_filter;
-use warp::filters::BoxedFilter;
-use warp::{Filter, Reply};
+use crate::authentication::{self, handlers, oauth};
+use crate::codec;
+use crate::filters::route;
+use warp::{Filter, Rejection, Reply};
 
+use crate::storage::Storage;
 use crate::store::Store;
+use crate::types::authentication::{AccountId, SessionId};
 
 /// POST /register
 ///
 /// Creates a filter for a route that handles user registration.
 ///
 /// # Parameters
-/// - `store` - [Store] object available to the route handler
-pub fn register(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::post())
-        .and(warp::path("register"))
-        .and(warp::body::json())
-        .and_then(handlers::register)
-        .boxed()
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn register<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "register",
+        extract: [codec::body()],
+        handler: handlers::register,
+        trace: "register request",
+    }
 }
 
-/// POST /login
+/// POST /login?remember_me={true|false}
 ///
 /// Creates a filter for a route that handles user login.
 ///
+/// The filter extracts the query parameters, the `User-Agent` header, recorded with the session,
+/// and the address of the client, whose failed logins are counted, and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn login(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "login",
+        extract: [
+            warp::query(),
+            codec::body(),
+            warp::header::optional::<String>("user-agent"),
+            warp::addr::remote(),
+        ],
+        handler: handlers::login,
+        trace: "login request",
+    }
+}
+
+/// POST /refresh
+///
+/// Creates a filter for a route that handles renewing the token of a remembered session.
+///
+/// The filter extracts the refresh token from its cookie and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn refresh(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "refresh",
+        extract: [warp::cookie::optional(authentication::REFRESH_COOKIE)],
+        handler: handlers::refresh,
+        trace: "refresh request",
+    }
+}
+
+/// POST /logout
+///
+/// Creates a filter for a route that handles user logout.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn logout(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "logout",
+        extract: [authentication::auth(&store)],
+        handler: handlers::logout,
+        trace: "logout request",
+    }
+}
+
+/// GET /accounts/me
+///
+/// Creates a filter for a route that handles reading the profile of the account making the request.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_account<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / "me",
+        extract: [authentication::auth(&store)],
+        handler: handlers::get_account,
+        trace: "get_account request",
+    }
+}
+
+/// PUT /accounts/me
+///
+/// Creates a filter for a route that handles updating the profile of the account making the request.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn update_account<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "accounts" / "me",
+        extract: [authentication::auth(&store), codec::body()],
+        handler: handlers::update_account,
+        trace: "update_account request",
+    }
+}
+
+/// GET /accounts/{id}
+///
+/// Creates a filter for a route that handles reading the public profile of an account.
+///
+/// The filter extracts the `AccountId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_public_profile<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / {AccountId},
+        handler: handlers::get_public_profile,
+        trace: "get_public_profile request",
+    }
+}
+
+/// GET /accounts/me/sessions
+///
+/// Creates a filter for a route that handles listing the sessions of the account making the request.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_sessions(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / "me" / "sessions",
+        extract: [authentication::auth(&store)],
+        handler: handlers::get_sessions,
+        trace: "get_sessions request",
+    }
+}
+
+/// DELETE /accounts/me/sessions/{id}
+///
+/// Creates a filter for a route that handles deleting a session of the account making the request.
+///
+/// The filter extracts the `SessionId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn delete_session(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "accounts" / "me" / "sessions" / {SessionId},
+        extract: [authentication::auth(&store)],
+        handler: handlers::delete_session,
+        trace: "delete_session request",
+    }
+}
+
+/// DELETE /accounts/me?content={anonymize|delete}
+///
+/// Creates a filter for a route that handles deleting the account making the request.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn delete_account(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "accounts" / "me",
+        extract: [warp::query(), authentication::auth(&store)],
+        handler: handlers::delete_account,
+        trace: "delete_account request",
+    }
+}
+
+/// GET /oauth/{provider}
+///
+/// Creates a filter for a route that handles starting the login with an external provider.
+///
+/// The filter extracts the name of the provider from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn oauth_authorize(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "oauth" / {String},
+        handler: oauth::authorize,
+        trace: "oauth_authorize request",
+    }
+}
+
+/// GET /oauth/{provider}/callback?code={code}&state={state}
+///
+/// Creates a filter for a route that handles completing the login with an external provider.
+///
+/// The filter extracts the name of the provider from the URL path, the query parameters, the
+/// nonce of the login from its cookie, and the `User-Agent` header, and passes them to the handler.
+///
 /// # Parameters
 /// - `store` - [Store] object available to the route handler
-pub fn login(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::post())
-        .and(warp::path("login"))
-        .and(warp::body::json())
-        .and_then(handlers::login)
-        .boxed()
+pub fn oauth_callback(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "oauth" / {String} / "callback",
+        extract: [
+            warp::query(),
+            warp::cookie::optional(oauth::NONCE_COOKIE),
+            warp::header::optional::<String>("user-agent"),
+        ],
+        handler: oauth::callback,
+        trace: "oauth_callback request",
+    }
 }
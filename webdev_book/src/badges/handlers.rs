@@ -0,0 +1,32 @@
+use tracing::{info, instrument, trace};
+use warp::Rejection;
+
+use crate::responses::JsonResponse;
+use crate::store::Store;
+use crate::types::authentication::AccountId;
+use crate::types::badge::AwardedBadge;
+
+/// Handler for `GET /accounts/{id}/badges`
+///
+/// Returns the badges awarded to the account, in the order they were awarded.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `account_id` - [AccountId] for the account whose badges are returned
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/badges",
+    tag = "badges",
+    params(("id" = AccountId, Path, description = "Id of the account")),
+    responses(
+        (status = 200, description = "Badges of the account", body = [AwardedBadge]),
+        (status = 400, description = "Invalid account id", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::badges", skip(store))]
+pub async fn get_badges(store: Store, account_id: AccountId) -> Result<JsonResponse<Vec<AwardedBadge>>, Rejection> {
+    trace!("querying the badges of account_id = {account_id:?}");
+    let badges = store.get_badges(account_id).await?;
+    info!("returning {} badges", badges.len());
+    Ok(JsonResponse::ok(badges))
+}
@@ -0,0 +1,46 @@
+//! Module for the badges awarded to the accounts.
+//!
+//! The badges are not awarded as the contributions are posted, but by a job in the
+//! [queue](crate::jobs), which is queued periodically by the worker started with [spawn_badge_scheduler].
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the badges.
+//! - `routes` - Contains the filters for the badges.
+//! - `schedule` - Contains the background worker queueing the evaluations of the badges.
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Handlers for the badges.
+mod handlers;
+/// Routes for the badges.
+mod routes;
+/// Periodic evaluation of the badges.
+mod schedule;
+
+pub use schedule::spawn_badge_scheduler;
+
+/// OpenAPI document for the badges.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(handlers::get_badges),
+    tags((name = "badges", description = "Badges awarded to the accounts for their contributions"))
+)]
+pub struct BadgesApi;
+
+/// Filter for the badges.
+///
+/// Creates a filter that handles requests for the badges.
+///
+/// The filter combines the following filters:
+/// - `get_badges`, for handling `GET /accounts/{id}/badges`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::get_badges(store.clone()).boxed()
+}
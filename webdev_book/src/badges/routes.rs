@@ -0,0 +1,24 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::badges::handlers;
+use crate::filters::route;
+use crate::store::Store;
+use crate::types::authentication::AccountId;
+
+/// GET /accounts/{id}/badges
+///
+/// Creates a filter for a route that handles listing the badges awarded to an account.
+///
+/// The filter extracts the `AccountId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_badges(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / {AccountId} / "badges",
+        handler: handlers::get_badges,
+        trace: "get_badges request",
+    }
+}
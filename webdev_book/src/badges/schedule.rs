@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info};
+
+use crate::jobs::Task;
+use crate::store::Store;
+
+/// The time between the evaluations of the badges.
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Spawns the worker queueing the evaluations of the badges.
+///
+/// The worker adds a [Task::AwardBadges] job to the queue on startup, and then every
+/// [EVALUATION_INTERVAL]. The badges are awarded by the [job worker](crate::jobs::spawn_worker).
+/// Every instance of the service queues its own evaluations, which is harmless, as the badges
+/// are awarded only once.
+///
+/// # Parameters
+/// - `store` - The [Store] holding the queue.
+pub fn spawn_badge_scheduler(store: Store) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("badge scheduler started");
+        let mut interval = tokio::time::interval(EVALUATION_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(error) = Task::AwardBadges.enqueue(&store).await {
+                error!("cannot queue the evaluation of the badges: {error}");
+            }
+        }
+    })
+}
@@ -0,0 +1,218 @@
+//! Module implementing the codecs for the request and response bodies.
+//!
+//! Besides JSON, the bodies can be encoded as MessagePack (`application/msgpack`) or CBOR
+//! (`application/cbor`), which are smaller and cheaper to parse on constrained clients.
+//!
+//! - Request bodies are decoded by the [body] filter, according to the `Content-Type` header.
+//! - Response bodies are written by the handlers in the format requested by the `Accept` header,
+//!   which [encode] negotiates, see [response_format]. Only the bodies encoded as JSON beforehand,
+//!   e.g. the cached listings, are re-encoded.
+
+use std::cell::Cell;
+
+use futures_util::TryStreamExt;
+use serde::de::DeserializeOwned;
+use warp::http::header::{CONTENT_LENGTH, CONTENT_TYPE, VARY};
+use warp::http::HeaderValue;
+use warp::hyper::body::to_bytes;
+use warp::hyper::Body;
+use warp::reply::Response;
+use warp::{Filter, Rejection, Reply};
+
+use crate::error::ServiceError;
+use crate::filters::{body_stream, BodyStream};
+
+/// The largest request body decoded by [body], in bytes.
+pub const MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+thread_local! {
+    /// The format of the response being built by [encode], see [response_format].
+    static RESPONSE_FORMAT: Cell<Format> = const { Cell::new(Format::Json) };
+}
+
+/// Encoding of a request or response body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `application/json`
+    Json,
+    /// `application/msgpack`
+    MessagePack,
+    /// `application/cbor`
+    Cbor,
+}
+
+impl Format {
+    /// Returns the format for the media type, ignoring its parameters, e.g. `; charset=utf-8`.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next().unwrap_or_default().trim();
+        match essence.to_ascii_lowercase().as_str() {
+            "application/json" => Some(Format::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => Some(Format::MessagePack),
+            "application/cbor" => Some(Format::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Returns the format requested by the `Accept` header.
+    ///
+    /// The supported media type with the highest quality, its `q` parameter, which is 1 if missing,
+    /// is used, the first one listed on a tie. The media types with `q=0` are not acceptable. JSON is
+    /// used if no supported media type is acceptable.
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        let mut best: Option<(Self, f32)> = None;
+        for item in accept.into_iter().flat_map(|accept| accept.split(',')) {
+            let Some(format) = Self::from_media_type(item) else {
+                continue;
+            };
+            let quality = item
+                .split(';')
+                .skip(1)
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())
+                .unwrap_or(0.0);
+            if quality > 0.0 && best.is_none_or(|(_, best)| quality > best) {
+                best = Some((format, quality));
+            }
+        }
+        best.map_or(Format::Json, |(format, _)| format)
+    }
+
+    /// Returns the media type of the format.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Format::Json => "application/json",
+            Format::MessagePack => "application/msgpack",
+            Format::Cbor => "application/cbor",
+        }
+    }
+
+    /// Decodes a value from the bytes in this format.
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, ServiceError> {
+        match self {
+            Format::Json => serde_json::from_slice(bytes).map_err(|error| error.to_string()),
+            Format::MessagePack => rmp_serde::from_slice(bytes).map_err(|error| error.to_string()),
+            Format::Cbor => ciborium::from_reader(bytes).map_err(|error| error.to_string()),
+        }
+        .map_err(ServiceError::BodyDecodeError)
+    }
+
+    /// Encodes a value to bytes in this format.
+    pub fn encode<T: serde::Serialize>(&self, value: &T) -> Vec<u8> {
+        match self {
+            Format::Json => serde_json::to_vec(value).expect("values are always serializable to JSON"),
+            // Maps are written with the field names, so the bodies are self-describing, like JSON
+            Format::MessagePack => {
+                rmp_serde::to_vec_named(value).expect("values are always serializable to MessagePack")
+            }
+            Format::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).expect("values are always serializable to CBOR");
+                bytes
+            }
+        }
+    }
+}
+
+/// This function returns a filter that decodes the request body.
+///
+/// The body is decoded according to the `Content-Type` header, and as JSON if the header is missing.
+/// The request is rejected with [ServiceError::UnsupportedMediaType] for any other content type,
+/// with [ServiceError::PayloadTooLarge] if the body is larger than [MAX_BODY_SIZE], and with
+/// [ServiceError::BodyDecodeError] if the body cannot be decoded.
+pub fn body<T>() -> impl Filter<Extract = (T,), Error = Rejection> + Clone
+where
+    T: DeserializeOwned + Send,
+{
+    warp::header::optional::<String>("content-type")
+        .and(body_stream(MAX_BODY_SIZE))
+        .and_then(|content_type: Option<String>, body: BodyStream| async move {
+            let format = match content_type {
+                Some(content_type) => {
+                    Format::from_media_type(&content_type).ok_or(ServiceError::UnsupportedMediaType(content_type))?
+                }
+                None => Format::Json,
+            };
+            let bytes = read_body(body, MAX_BODY_SIZE).await?;
+            format.decode(&bytes).map_err(Rejection::from)
+        })
+}
+
+/// Reads the whole body, which may not announce its length, up to `limit` bytes.
+///
+/// # Returns
+/// - The bytes of the body.
+/// - [ServiceError::PayloadTooLarge] if the body is longer than `limit` bytes.
+/// - [ServiceError::BodyDecodeError] if the body cannot be received.
+async fn read_body(mut body: BodyStream, limit: u64) -> Result<Vec<u8>, ServiceError> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body
+        .try_next()
+        .await
+        .map_err(|error| ServiceError::BodyDecodeError(error.to_string()))?
+    {
+        if (bytes.len() + chunk.len()) as u64 > limit {
+            return Err(ServiceError::PayloadTooLarge(limit));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Returns the format the response being built is encoded in.
+///
+/// It is the format [encode] negotiated for the request, while it turns the reply of the handler
+/// into a response, so the replies, e.g. [JsonResponse](crate::responses::JsonResponse), serialize
+/// their values straight into it. It is JSON everywhere else.
+pub fn response_format() -> Format {
+    RESPONSE_FORMAT.with(Cell::get)
+}
+
+/// Restores the previous [response_format] when dropped, even if building the response panicked.
+struct ResponseFormatGuard(Format);
+
+impl Drop for ResponseFormatGuard {
+    fn drop(&mut self) {
+        RESPONSE_FORMAT.with(|format| format.set(self.0));
+    }
+}
+
+/// Encodes the response in the format requested by the `Accept` header.
+///
+/// The reply is turned into a response with the negotiated [response_format], so the replies
+/// that support it are encoded in that format directly. The JSON responses encoded beforehand,
+/// e.g. [EncodedJsonResponse](crate::responses::EncodedJsonResponse), are re-encoded. Other
+/// responses are returned unchanged. All responses vary with the `Accept` header.
+///
+/// # Parameters
+/// - `accept` - The `Accept` header of the request
+/// - `reply` - The reply returned by the handler
+pub async fn encode(accept: Option<String>, reply: impl Reply) -> Result<Response, Rejection> {
+    let format = Format::from_accept(accept.as_deref());
+    let mut response = {
+        let _guard = ResponseFormatGuard(RESPONSE_FORMAT.with(|current| current.replace(format)));
+        reply.into_response()
+    };
+    response.headers_mut().append(VARY, HeaderValue::from_static("accept"));
+
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Format::from_media_type)
+        == Some(Format::Json);
+    if format == Format::Json || !is_json {
+        return Ok(response);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = to_bytes(body)
+        .await
+        .map_err(|error| ServiceError::BodyDecodeError(error.to_string()))?;
+    let value: serde_json::Value = Format::Json.decode(&bytes)?;
+
+    parts.headers.remove(CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(format.media_type()));
+    Ok(Response::from_parts(parts, Body::from(format.encode(&value))))
+}
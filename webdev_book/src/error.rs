@@ -1,153 +1,26 @@
 //! Module that implements the error handling for the API.
-pub use argon2::Error as ArgonError;
-pub use reqwest::Error as ReqwestError;
-pub use reqwest_middleware::Error as ReqwestMiddlewareError;
-pub use sqlx::Error as SqlxError;
+//!
+//! The error types are defined in [webdev_core::error], and re-exported from this module.
+use serde_json::json;
 use tracing::{error, instrument, warn};
 use warp::{
     filters::{body::BodyDeserializeError, cors::CorsForbidden},
-    http::StatusCode,
-    reject::{MissingHeader, Reject},
+    http::{header::RETRY_AFTER, StatusCode},
+    reject::{MethodNotAllowed, MissingHeader},
+    reply::Response,
     Rejection, Reply,
 };
 
-use crate::{api, types::pagination::PaginationParsingError};
-use crate::{api::bad_words::BadWordsAPIBuildError, types::question::QuestionId};
+pub use webdev_core::error::*;
 
-/// Error type for missing questions
+/// Rejection of a path segment that is not an id, by [id](crate::filters::id).
 ///
-/// This error is used when a question is not found in the database.
-#[derive(thiserror::Error, Debug)]
-#[error("{0:?}")]
-pub struct MissingQuestion(pub QuestionId);
+/// It wraps the [ServiceError::InvalidId] apart from the other [ServiceError] rejections, so
+/// [return_error] can tell them apart, as only the first [ServiceError] of a rejection is found.
+#[derive(Debug)]
+pub struct InvalidPathId(pub ServiceError);
 
-impl From<QuestionId> for MissingQuestion {
-    fn from(id: QuestionId) -> Self {
-        MissingQuestion(id)
-    }
-}
-
-/// Error type for the API layer
-///
-/// This error is used when the API layer returns an error.
-/// API layer errors are errors that are returned from the external API.
-#[derive(thiserror::Error, Debug, Clone)]
-#[error("status: {status}, message: {message}")]
-pub struct APILayerError {
-    pub status: StatusCode,
-    pub message: String,
-}
-
-impl Reject for APILayerError {}
-
-impl APILayerError {
-    pub async fn transform_error(res: reqwest::Response) -> Self {
-        Self {
-            status: res.status(),
-            message: res.json::<api::APIResponse>().await.unwrap().message,
-        }
-    }
-}
-
-/// Error type for all errors returned by the service
-#[derive(thiserror::Error, Debug)]
-pub enum ServiceError {
-    /// Error for parsing any value from a string
-    #[error("cannot parse value: {0}")]
-    ParseError(#[from] std::num::ParseIntError),
-    /// Error for when migrations fail on startup
-    #[error("cannot run migrations: {0}")]
-    MigrationError(#[from] sqlx::migrate::MigrateError),
-    /// Error for when BadWordsAPI handle cannot be created
-    #[error("cannot create BadWordsAPI handle : {0}")]
-    BadWordsAPIBuildError(#[from] BadWordsAPIBuildError),
-    /// Error for failing to connect to the database
-    #[error("cannot connect to the database, invalid connection string (or credentials)")]
-    DatabaseConnectionError,
-    /// Error while parsing the configuration file
-    #[error("cannot parse configuration file: {0}")]
-    ConfigParsingError(#[from] config::ConfigError),
-    /// Error for invalid pagination parameters
-    #[error("pagination error: {0}")]
-    PaginationError(#[from] PaginationParsingError),
-    /// Error for missing questions, used when a question is not found in the database
-    #[error("question {0} not found")]
-    QuestionNotFound(#[from] MissingQuestion),
-    /// Error for invalid database queries
-    #[error("cannot update, invalid data")]
-    DatabaseQueryError(#[from] SqlxError),
-    /// Error returned by the Argon2 hashing library
-    #[error("argon2 error")]
-    ArgonLibraryError(#[from] ArgonError),
-    /// Error for Reqwest errors
-    #[error("external API error:")]
-    ReqwestAPIError(#[from] ReqwestError),
-    /// Error for Reqwest middleware errors
-    #[error("external API error")]
-    MiddlewareReqwestAPIError(#[from] ReqwestMiddlewareError),
-    /// Error for client errors
-    #[error("external client error")]
-    ClientError(APILayerError),
-    /// Error for server errors
-    #[error("external server error")]
-    ServerError(APILayerError),
-    #[error("wrong credentials combination")]
-    WrongPassword,
-    #[error("auth token could not be decyphered")]
-    CannotDecryptToken,
-    #[error("unauthorized, no premission to modify the resource")]
-    Unauthorized,
-}
-
-impl ServiceError {
-    /// Returns the status code for the error
-    ///
-    /// This function returns the status code for the error, based on the error type.
-    ///
-    /// # Returns
-    /// - `StatusCode`: The status code for the error
-    ///     - `StatusCode::BAD_REQUEST`: For `PaginationError`
-    ///     - `StatusCode::NOT_FOUND`: For `QuestionNotFound`
-    ///     - `StatusCode::INTERNAL_SERVER_ERROR`: For all other errors
-    pub fn status_code(&self) -> StatusCode {
-        use ServiceError::*;
-        match self {
-            ParseError(_) => StatusCode::BAD_REQUEST,
-            PaginationError(_) => StatusCode::BAD_REQUEST,
-            QuestionNotFound(_) => StatusCode::NOT_FOUND,
-            DatabaseQueryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ArgonLibraryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ReqwestAPIError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            MiddlewareReqwestAPIError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ClientError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            WrongPassword => StatusCode::UNAUTHORIZED,
-            CannotDecryptToken => StatusCode::UNAUTHORIZED,
-            Unauthorized => StatusCode::UNAUTHORIZED,
-            MigrationError(_) => unreachable!("migration errors are not returned by the API"),
-            ConfigParsingError(_) => unreachable!("config parsing errors are not returned by the API"),
-            BadWordsAPIBuildError(_) => unreachable!("bad words API errors are not returned by the API"),
-            DatabaseConnectionError => unreachable!("database connection errors are not returned by the API"),
-        }
-    }
-}
-
-impl Reject for ServiceError {}
-
-/// Error codes for PostgreSQL
-pub mod pg_error_codes {
-    pub const UNIQUE_VIOLATION: &str = "23505";
-    pub const CHECK_VIOLATION: &str = "23514";
-
-    /// Returns the default error message for the error code
-    pub fn default_error_message(code: &str) -> &'static str {
-        match code {
-            UNIQUE_VIOLATION => "duplicate data",
-            CHECK_VIOLATION => "invalid data: constraint violation",
-            _ => "cannot update data",
-        }
-    }
-}
+impl warp::reject::Reject for InvalidPathId {}
 
 /// Error handler for the API
 ///
@@ -155,6 +28,18 @@ pub mod pg_error_codes {
 /// variants that implement the `Reject` trait.
 ///
 /// Errors are logged and a response is returned with the appropriate status code.
+/// The body is the message of the error, except for [ServiceError::QuotaExceeded], whose body is
+/// the reached limit, [ServiceError::SimilarQuestions], whose body is the list of the similar
+/// questions, and [ServiceError::InvalidField], whose body is the [FieldError], as JSON. The
+/// [ServiceError::TooManyLoginAttempts] responses have a `Retry-After` header. The unique
+/// violations of the database are conflicts, whose body has a machine-readable `code` and the
+/// `message`, as JSON, see [pg_error_codes::unique_violation].
+///
+/// The requests whose path matches a route, but not its method, are rejected with
+/// `405 Method Not Allowed`. The [InvalidPathId] rejections rank below all the others, as the
+/// malformed id may be a segment of another route, e.g. `me` in `/accounts/me`, whose rejection,
+/// such as a missing token, or a method the route does not allow, is the one that tells the client
+/// what is wrong.
 ///
 /// # Parameters
 /// - `rejection`: The rejection returned by the handler
@@ -163,9 +48,54 @@ pub mod pg_error_codes {
 /// - If error is a [DatabaseQueryError](ServiceError::DatabaseQueryError) and the error code is not recognized
 #[instrument(target = "webdev_book::errors", skip_all)]
 pub async fn return_error(rejection: Rejection) -> Result<impl Reply, Rejection> {
+    Ok(error_response(&rejection))
+}
+
+/// Error handler for the routes of the API, in front of the [frontend](crate::frontend)
+///
+/// This function builds the response for the rejection like [return_error], unless no route
+/// matched the request, i.e. the rejection is the plain not found one, which is passed on, so the
+/// request falls back to the frontend.
+///
+/// # Parameters
+/// - `rejection`: The rejection returned by the routes of the API
+#[instrument(target = "webdev_book::errors", skip_all)]
+pub async fn recover_api(rejection: Rejection) -> Result<Response, Rejection> {
+    match rejection.is_not_found() {
+        true => Err(rejection),
+        false => Ok(error_response(&rejection)),
+    }
+}
+
+/// Builds the response for the rejection, see [return_error].
+fn error_response(rejection: &Rejection) -> Response {
     use warp::reply::with_status;
-    if let Some(ServiceError::DatabaseQueryError(error)) = rejection.find() {
+    if let Some(ServiceError::QuotaExceeded(quota)) = rejection.find() {
+        warn!("{}", ServiceError::QuotaExceeded(quota.clone()));
+        // The body states the limit, so the clients can tell when to try again
+        with_status(warp::reply::json(quota), StatusCode::TOO_MANY_REQUESTS).into_response()
+    } else if let Some(error @ ServiceError::TooManyLoginAttempts(retry_after)) = rejection.find() {
+        warn!("{error}");
+        // The header tells the clients when the next login is accepted
+        let reply = with_status(error.to_string(), StatusCode::TOO_MANY_REQUESTS);
+        warp::reply::with_header(reply, RETRY_AFTER, retry_after.to_string()).into_response()
+    } else if let Some(ServiceError::InvalidField(field_error)) = rejection.find() {
+        warn!("{field_error}");
+        // The body names the field, so the clients can tell which value to correct
+        with_status(warp::reply::json(field_error), StatusCode::BAD_REQUEST).into_response()
+    } else if let Some(error @ ServiceError::SimilarQuestions(questions)) = rejection.find() {
+        warn!("{error}");
+        // The body lists the questions, so the clients can point to them instead
+        with_status(warp::reply::json(questions), StatusCode::CONFLICT).into_response()
+    } else if let Some(ServiceError::DatabaseQueryError(error)) = rejection.find() {
         let message = match error {
+            sqlx::Error::Database(err) if err.code().as_deref() == Some(pg_error_codes::UNIQUE_VIOLATION) => {
+                // The code tells the clients which data conflicts, e.g. that the account has already voted
+                let (code, message) = pg_error_codes::unique_violation(err.constraint());
+                warn!("{message}");
+                let body = json!({ "code": code, "message": message });
+                return with_status(warp::reply::json(&body), StatusCode::CONFLICT).into_response();
+            }
             sqlx::Error::Database(err) => {
                 let code = err.code().unwrap();
                 pg_error_codes::default_error_message(code.as_ref())
@@ -173,24 +103,31 @@ pub async fn return_error(rejection: Rejection) -> Result<impl Reply, Rejection>
             _ => "cannot update data",
         };
         error!("{message}");
-        Ok(with_status(message.to_string(), StatusCode::UNPROCESSABLE_ENTITY))
+        with_status(message.to_string(), StatusCode::UNPROCESSABLE_ENTITY).into_response()
     } else if let Some(service_error) = rejection.find::<ServiceError>() {
         error!("{service_error}");
-        Ok(with_status(service_error.to_string(), service_error.status_code()))
+        with_status(service_error.to_string(), service_error.status_code()).into_response()
     } else if let Some(error) = rejection.find::<MissingHeader>() {
         error!("{error}");
-        Ok(with_status(
+        with_status(
             format!("missing request header: \"{}\"", error.name()),
             StatusCode::BAD_REQUEST,
-        ))
+        )
+        .into_response()
     } else if let Some(error) = rejection.find::<CorsForbidden>() {
         error!("{error}");
-        Ok(with_status(error.to_string(), StatusCode::FORBIDDEN))
+        with_status(error.to_string(), StatusCode::FORBIDDEN).into_response()
     } else if let Some(error) = rejection.find::<BodyDeserializeError>() {
         error!("{error}");
-        Ok(with_status(error.to_string(), StatusCode::UNPROCESSABLE_ENTITY))
+        with_status(error.to_string(), StatusCode::UNPROCESSABLE_ENTITY).into_response()
+    } else if let Some(error) = rejection.find::<MethodNotAllowed>() {
+        warn!("{error}");
+        with_status("method not allowed".to_string(), StatusCode::METHOD_NOT_ALLOWED).into_response()
+    } else if let Some(InvalidPathId(error)) = rejection.find() {
+        warn!("{error}");
+        with_status(error.to_string(), error.status_code()).into_response()
     } else {
         warn!("request route not found: {rejection:?}");
-        Ok(with_status("route not found".to_string(), StatusCode::NOT_FOUND))
+        with_status("route not found".to_string(), StatusCode::NOT_FOUND).into_response()
     }
 }
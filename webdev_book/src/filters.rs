@@ -1,8 +1,16 @@
 //! Module containing filters that are used to process requests.
 
-use warp::{filters::BoxedFilter, http::Method, Filter};
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
 
-use crate::store::Store;
+use futures_util::{future, Stream, TryStreamExt};
+use warp::hyper::body::{Buf, Bytes};
+use warp::path::Peek;
+use warp::{http::Method, Filter, Rejection};
+
+use crate::error::{InvalidPathId, ServiceError};
 
 /// This function returns the CORS filter for the application.
 ///
@@ -13,20 +21,157 @@ use crate::store::Store;
 /// - PATCH
 /// - DELETE
 ///
-/// It also allows the `content-type` header.
+/// It also allows the following headers:
+/// - `authorization`, carrying the token of the session
+/// - `content-type`
+/// - `x-admin-token`, carrying the administrator token
 pub fn cors() -> warp::cors::Builder {
     warp::cors()
         .allow_any_origin()
-        .allow_header("content-type")
+        .allow_headers(["authorization", "content-type", "x-admin-token"])
         .allow_methods(&[Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
 }
 
 /// This function returns a filter that associates the store with the request.
 ///
-/// The filter takes a store and returns a boxed filter that takes no arguments and returns the
+/// The filter takes a store and returns a filter that takes no arguments and returns the
 /// store. This is useful for handlers that need access to the store.
-pub fn store_filter(store: Store) -> BoxedFilter<(Store,)> {
-    warp::any().map(move || store.clone()).boxed()
+///
+/// The store is cloned every time the filter runs, so it should be placed after the cheaper
+/// checks that reject the requests for other routes, such as the method. Until then, the filter
+/// only holds the store behind an [Arc], as warp clones the filters of a route, and their
+/// callbacks, for every request the route is tried on. Any store can be shared,
+/// the [Store](crate::store::Store) or another [Storage](crate::storage::Storage).
+pub fn store_filter<S>(store: S) -> impl Filter<Extract = (S,), Error = Infallible> + Clone
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let store = Arc::new(store);
+    warp::any().map(move || S::clone(&store))
+}
+
+/// This function returns a filter that extracts a typed id from the next path segment.
+///
+/// Unlike the ids parsed by `warp::path!`, which silently fall through to a 404 when the segment
+/// is malformed, this filter rejects the request with [InvalidPathId], so the client receives a
+/// 400 response describing what is wrong with the id. The segment may belong to another route,
+/// e.g. `me` in `/accounts/me`, so [return_error](crate::error::return_error) ranks the rejection
+/// below the rejections of the other routes, such as a missing token.
+pub fn id<T>() -> impl Filter<Extract = (T,), Error = Rejection> + Copy
+where
+    T: FromStr + Send,
+    T::Err: std::error::Error,
+{
+    warp::path::param::<String>().and_then(|segment: String| future::ready(parse_id::<T>(&segment)))
+}
+
+/// This function parses a typed id from a path segment, see [id].
+pub fn parse_id<T>(segment: &str) -> Result<T, Rejection>
+where
+    T: FromStr,
+    T::Err: std::error::Error,
+{
+    segment
+        .parse::<T>()
+        .map_err(|error| warp::reject::custom(InvalidPathId(ServiceError::InvalidId(error.to_string()))))
+}
+
+/// This function returns a filter that checks the whole request path against a route, without
+/// consuming it.
+///
+/// The `matches` function is given the rest of the path, and returns whether its segments are the
+/// ones of the route, or the rejection of a malformed id, see [parse_id]. The paths of other routes
+/// are rejected as not found. The routes generated by [route] check their path with it before their
+/// method, so the requests are only rejected as not allowed by the routes whose path they match.
+pub fn path_matches(
+    matches: fn(&Peek) -> Result<bool, Rejection>,
+) -> impl Filter<Extract = (), Error = Rejection> + Copy {
+    warp::path::peek()
+        .and_then(move |peek: Peek| {
+            future::ready(match matches(&peek) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(warp::reject::not_found()),
+                Err(rejection) => Err(rejection),
+            })
+        })
+        .untuple_one()
+}
+
+/// This function returns a filter that checks the first segment of the request path against a
+/// list, without consuming it.
+///
+/// The paths starting with another segment are rejected as not found. [routes](crate::routes)
+/// dispatches the requests with it, so only the resource modules with routes under the first
+/// segment of the path are tried.
+pub fn first_segment(segments: &'static [&'static str]) -> impl Filter<Extract = (), Error = Rejection> + Copy {
+    warp::path::peek()
+        .and_then(move |peek: Peek| {
+            future::ready(match peek.segments().next() {
+                Some(segment) if segments.contains(&segment) => Ok(()),
+                _ => Err(warp::reject::not_found()),
+            })
+        })
+        .untuple_one()
+}
+
+/// This function returns a filter that extracts the version an update is based on, from the
+/// `If-Match` header.
+///
+/// The header carries the `version` of the resource, as a strong or weak entity tag, e.g. `"3"` or
+/// `W/"3"`, or bare, e.g. `3`. `None` is extracted without the header, or with `*`, which matches
+/// any version. The malformed headers reject the request with [ServiceError::ValidationError].
+pub fn if_match() -> impl Filter<Extract = (Option<i32>,), Error = Rejection> + Clone {
+    warp::header::optional::<String>("if-match").and_then(|header: Option<String>| {
+        future::ready(match header.as_deref().map(str::trim) {
+            None | Some("*") => Ok(None),
+            Some(tag) => {
+                let version = tag.strip_prefix("W/").unwrap_or(tag);
+                let version = version
+                    .strip_prefix('"')
+                    .and_then(|version| version.strip_suffix('"'))
+                    .unwrap_or(version);
+                version.parse().map(Some).map_err(|_| {
+                    warp::reject::custom(ServiceError::ValidationError(format!(
+                        "If-Match must carry the version of the resource, not {tag:?}"
+                    )))
+                })
+            }
+        })
+    })
+}
+
+/// Stream of the chunks of a request body, as they arrive.
+pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, warp::Error>> + Send>>;
+
+/// This function returns a filter that extracts the request body as a stream of chunks.
+///
+/// Unlike `warp::body::bytes()`, the body is never buffered in memory as a whole, so the handler
+/// can write large bodies elsewhere, e.g. to disk, chunk by chunk. The chunks are the buffers
+/// received by the server, they are not copied.
+///
+/// Requests announcing a `Content-Length` larger than `limit` are rejected with
+/// [ServiceError::PayloadTooLarge] before the body is read. Bodies without the length have to
+/// be limited by the handler, as it reads them.
+pub fn body_stream(limit: u64) -> impl Filter<Extract = (BodyStream,), Error = Rejection> + Clone {
+    warp::header::optional::<u64>("content-length")
+        .and_then(move |length: Option<u64>| {
+            future::ready(match length {
+                Some(length) if length > limit => Err(warp::reject::custom(ServiceError::PayloadTooLarge(limit))),
+                _ => Ok(()),
+            })
+        })
+        .untuple_one()
+        .and(warp::body::stream())
+        .map(into_body_stream)
+}
+
+/// Converts the body received by warp into a [BodyStream].
+fn into_body_stream<S, B>(stream: S) -> BodyStream
+where
+    S: Stream<Item = Result<B, warp::Error>> + Send + 'static,
+    B: Buf,
+{
+    Box::pin(stream.map_ok(|mut chunk| chunk.copy_to_bytes(chunk.remaining())))
 }
 
 /// This macro creates a warp trace filter with the given text
@@ -44,3 +189,85 @@ macro_rules! with_trace {
 }
 
 pub(crate) use with_trace;
+
+/// This macro creates a route filter from its description.
+///
+/// It generates the wiring shared by all routes: the path check, the method, the store, the path,
+/// the extractors, the handler and the trace, in that order. The whole path, with its ids, is
+/// checked by [path_matches] before the method, so only the routes whose path matches reject the
+/// requests with other methods, and before the store is cloned, so the requests for the other
+/// routes don't pay for the clone. The path is a list of segments separated by `/`, where literals
+/// match the segment exactly, and types in braces extract an id with the [id] filter. The path has
+/// to match the whole request path.
+///
+/// ```ignore
+/// route! {
+///     store: store,
+///     method: put,
+///     path: "questions" / {QuestionId},
+///     extract: [codec::body(), authentication::auth(&store)],
+///     handler: handlers::update_question,
+///     trace: "update_question request",
+/// }
+/// ```
+///
+/// The handler receives the store, the ids from the path and the values produced by the
+/// extractors, in the order they are listed. The store stays available to the extractors,
+/// e.g. for [auth](crate::authentication::auth).
+///
+/// The filter is not boxed, the routes of a resource module are boxed together by its `filter`.
+macro_rules! route {
+    (
+        store: $store:expr,
+        method: $method:ident,
+        path: $($segment:tt)/+,
+        $(extract: [$($extractor:expr),* $(,)?],)?
+        handler: $handler:expr,
+        trace: $trace:literal $(,)?
+    ) => {{
+        let filter = $crate::filters::path_matches(|peek| {
+            // The ids are only parsed once the other segments match, so a malformed id is only
+            // reported for the paths of the route
+            let mut segments = peek.segments();
+            $(if !$crate::filters::route!(@matches segments, $segment) {
+                return Ok(false);
+            })+
+            if segments.next().is_some() {
+                return Ok(false);
+            }
+            let mut segments = peek.segments();
+            $($crate::filters::route!(@parse segments, $segment);)+
+            Ok(true)
+        })
+        .and(warp::$method())
+        .and($crate::filters::store_filter(::std::clone::Clone::clone(&$store)));
+        $(let filter = filter.and($crate::filters::route!(@segment $segment));)+
+        let filter = filter.and(warp::path::end());
+        $($(let filter = filter.and($extractor);)*)?
+        filter
+            .and_then($handler)
+            .with($crate::filters::with_trace!($trace))
+    }};
+    (@matches $segments:ident, {$id:ty}) => {
+        $segments.next().is_some()
+    };
+    (@matches $segments:ident, $segment:literal) => {
+        $segments.next() == Some($segment)
+    };
+    (@parse $segments:ident, {$id:ty}) => {
+        if let Some(segment) = $segments.next() {
+            $crate::filters::parse_id::<$id>(segment)?;
+        }
+    };
+    (@parse $segments:ident, $segment:literal) => {
+        $segments.next();
+    };
+    (@segment {$id:ty}) => {
+        $crate::filters::id::<$id>()
+    };
+    (@segment $segment:literal) => {
+        warp::path($segment)
+    };
+}
+
+pub(crate) use route;
@@ -0,0 +1,270 @@
+//! Module that serves the built frontend from a directory, when one is configured.
+//!
+//! The frontend is served for the requests no route of the API matched, see
+//! [recover_api](crate::error::recover_api). The files are served as they are, and any other `GET`
+//! or `HEAD` request falls back to `index.html`, so the frontend can handle its own routes (single
+//! page application).
+//!
+//! Files whose names contain a content hash, e.g. `assets/index-4f2a9c1b.js`, never change,
+//! so they are cached for a year. Everything else, including `index.html`, has to be revalidated.
+//!
+//! Files compressed at build time, e.g. `assets/index-4f2a9c1b.js.br` next to
+//! `assets/index-4f2a9c1b.js`, are served instead of the original to the clients accepting
+//! their encoding, so nothing is compressed on the fly. Like the original files, they are served
+//! to the `HEAD` requests without a body, and in part to the `Range` requests.
+
+use std::path::{Path, PathBuf};
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+use warp::filters::BoxedFilter;
+use warp::http::header::{
+    ACCEPT_RANGES, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, VARY,
+};
+use warp::http::{HeaderValue, Method, StatusCode};
+use warp::hyper::Body;
+use warp::path::Tail;
+use warp::reply::Response;
+use warp::{Filter, Rejection, Reply};
+
+use crate::filters::with_trace;
+
+/// `Cache-Control` value for the files with a content hash in their name.
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// `Cache-Control` value for all other files.
+const REVALIDATE: &str = "no-cache";
+
+/// Encodings of the precompressed files, in the order of preference, with the extensions of the files.
+const ENCODINGS: [(&str, &str); 2] = [("br", "br"), ("gzip", "gz")];
+
+/// Checks whether the file name contains a content hash.
+///
+/// The hash is expected to be a segment of at least 8 alphanumeric characters, with at least one
+/// digit, separated by a `.` or a `-`, as produced by the common bundlers.
+fn is_hashed(path: &Path) -> bool {
+    let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return false;
+    };
+    stem.split(['.', '-']).skip(1).any(|segment| {
+        segment.len() >= 8
+            && segment.chars().all(|c| c.is_ascii_alphanumeric())
+            && segment.chars().any(|c| c.is_ascii_digit())
+    })
+}
+
+/// Returns the `Cache-Control` value for the file at the path.
+fn cache_control(path: &Path) -> &'static str {
+    match is_hashed(path) {
+        true => IMMUTABLE,
+        false => REVALIDATE,
+    }
+}
+
+/// Adds the `Cache-Control` header to the served file.
+fn with_cache_control(file: warp::fs::File) -> Response {
+    let cache_control = cache_control(file.path());
+    warp::reply::with_header(file, CACHE_CONTROL, cache_control).into_response()
+}
+
+/// Adds the `Vary` header to the served file, as any file may have a precompressed version.
+fn with_vary(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(VARY, HeaderValue::from_static("accept-encoding"));
+    response
+}
+
+/// Checks whether the `Accept-Encoding` header accepts the encoding.
+///
+/// The encoding is accepted when it is listed without `q=0`. When it is not listed, it is accepted
+/// if `*` is listed without `q=0`, so `br;q=0, *` rejects only `br`.
+fn accepts(accept_encoding: &str, encoding: &str) -> bool {
+    let mut wildcard = false;
+    for item in accept_encoding.split(',') {
+        let mut params = item.split(';').map(str::trim);
+        let name = params.next().unwrap_or_default();
+        let accepted = !params.any(|param| {
+            param
+                .strip_prefix("q=")
+                .and_then(|q| q.parse::<f32>().ok())
+                .is_some_and(|q| q == 0.0)
+        });
+        if name.eq_ignore_ascii_case(encoding) {
+            return accepted;
+        }
+        if name == "*" {
+            wildcard = accepted;
+        }
+    }
+    wildcard
+}
+
+/// Returns the path of the requested file, relative to the directory.
+///
+/// Paths of directories point to their `index.html`. Paths leaving the directory are rejected.
+fn relative_path(tail: &str) -> Option<PathBuf> {
+    let decoded = percent_encoding::percent_decode_str(tail).decode_utf8().ok()?;
+    let mut path = PathBuf::new();
+    for segment in decoded.split('/').filter(|segment| !segment.is_empty()) {
+        if segment.starts_with('.') || segment.contains('\\') {
+            return None;
+        }
+        path.push(segment);
+    }
+    if decoded.is_empty() || decoded.ends_with('/') {
+        path.push("index.html");
+    }
+    Some(path)
+}
+
+/// Returns the bytes of a file of the given length requested in the `Range` header, as the
+/// inclusive bounds of the range.
+///
+/// Only a single range of bytes is served, e.g. `bytes=0-99`, `bytes=100-` or `bytes=-100`, any
+/// other header is ignored, so the whole file is served. Ranges that start past the end of the file
+/// cannot be served, and are an `Err`.
+fn byte_range(range: Option<&str>, len: u64) -> Option<Result<(u64, u64), ()>> {
+    let (start, end) = range?.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().ok()?;
+            (len.saturating_sub(suffix), len.checked_sub(1))
+        }
+        (start, "") => (start.parse().ok()?, len.checked_sub(1)),
+        (start, end) => {
+            let (start, end): (u64, u64) = (start.parse().ok()?, end.parse().ok()?);
+            if end < start {
+                return None;
+            }
+            (start, Some(end.min(len.saturating_sub(1))))
+        }
+    };
+    Some(match end {
+        Some(end) if start <= end && start < len => Ok((start, end)),
+        _ => Err(()),
+    })
+}
+
+/// Serves the precompressed version of the requested file, if there is one the client accepts.
+///
+/// The response has the `Content-Type` of the original file, and the `Content-Encoding` of the
+/// precompressed one. The `HEAD` requests get the headers only, and the `Range` requests the
+/// requested bytes of the precompressed file, see [byte_range]. Requests for which there is no such
+/// file are rejected, so they are served the original.
+async fn precompressed(
+    dir: PathBuf,
+    method: Method,
+    tail: Tail,
+    accept_encoding: Option<String>,
+    range: Option<String>,
+) -> Result<Response, Rejection> {
+    let accept_encoding = accept_encoding.ok_or_else(warp::reject::not_found)?;
+    let path = relative_path(tail.as_str()).ok_or_else(warp::reject::not_found)?;
+
+    for (encoding, extension) in ENCODINGS {
+        if !accepts(&accept_encoding, encoding) {
+            continue;
+        }
+        let mut compressed = dir.join(&path).into_os_string();
+        compressed.push(".");
+        compressed.push(extension);
+        let Ok(mut file) = tokio::fs::File::open(&compressed).await else {
+            continue;
+        };
+        let Ok(metadata) = file.metadata().await else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let len = metadata.len();
+        let (status, start, end) = match byte_range(range.as_deref(), len) {
+            None => (StatusCode::OK, 0, len.saturating_sub(1)),
+            Some(Ok((start, end))) => (StatusCode::PARTIAL_CONTENT, start, end),
+            Some(Err(())) => {
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::RANGE_NOT_SATISFIABLE;
+                response
+                    .headers_mut()
+                    .insert(CONTENT_RANGE, HeaderValue::from_str(&format!("bytes */{len}")).unwrap());
+                return Ok(response);
+            }
+        };
+        let content_length = if len == 0 { 0 } else { end - start + 1 };
+
+        let body = match method {
+            Method::HEAD => Body::empty(),
+            _ => {
+                if start > 0 && file.seek(std::io::SeekFrom::Start(start)).await.is_err() {
+                    continue;
+                }
+                Body::wrap_stream(ReaderStream::new(file.take(content_length)))
+            }
+        };
+        let content_type = mime_guess::from_path(&path).first_or_octet_stream();
+        let mut response = Response::new(body);
+        *response.status_mut() = status;
+        let headers = response.headers_mut();
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(encoding));
+        headers.insert(CONTENT_LENGTH, HeaderValue::from(content_length));
+        headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        if status == StatusCode::PARTIAL_CONTENT {
+            let content_range = format!("bytes {start}-{end}/{len}");
+            headers.insert(CONTENT_RANGE, HeaderValue::from_str(&content_range).unwrap());
+        }
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static(cache_control(&path)));
+        if let Ok(content_type) = HeaderValue::from_str(content_type.as_ref()) {
+            headers.insert(CONTENT_TYPE, content_type);
+        }
+        return Ok(response);
+    }
+    Err(warp::reject::not_found())
+}
+
+/// Filter for the frontend.
+///
+/// The filter combines the following routes, all of which answer `HEAD` as well:
+/// - `GET /{path}`, serving the precompressed version of the file at the path, if the client accepts it
+/// - `GET /{path}`, serving the file at the path in the directory
+/// - `GET /{path}` for any other path, serving `index.html` from the directory
+///
+/// The filter is tried only for the requests no route of the API matched, see [routes](crate::routes).
+///
+/// Without a directory, the filter rejects every request.
+///
+/// # Parameters
+/// - `dir` - The directory containing the built frontend.
+pub fn filter(dir: Option<PathBuf>) -> BoxedFilter<(Response,)> {
+    let Some(dir) = dir else {
+        return warp::any()
+            .and_then(|| async { Err::<Response, _>(warp::reject::not_found()) })
+            .boxed();
+    };
+
+    let get_or_head = warp::get().or(warp::head()).unify();
+
+    let compressed_dir = dir.clone();
+    let compressed = get_or_head
+        .and(warp::method())
+        .and(warp::path::tail())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(warp::header::optional::<String>("range"))
+        .and_then(move |method, tail, accept_encoding, range| {
+            precompressed(compressed_dir.clone(), method, tail, accept_encoding, range)
+        });
+
+    let files = get_or_head.and(warp::fs::dir(dir.clone()));
+
+    let fallback = get_or_head.and(warp::fs::file(dir.join("index.html")));
+
+    let files = files.or(fallback).unify().map(with_cache_control);
+
+    compressed
+        .or(files)
+        .unify()
+        .map(with_vary)
+        .with(with_trace!("frontend request"))
+        .map(Reply::into_response)
+        .boxed()
+}
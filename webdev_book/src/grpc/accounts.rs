@@ -0,0 +1,63 @@
+use tonic::{Request, Response, Status};
+use tracing::{instrument, trace};
+
+use crate::authentication::{check_credentials, hash_password, start_session};
+use crate::error::ServiceError;
+use crate::grpc::proto::{self, accounts_server::Accounts};
+use crate::grpc::status;
+use crate::store::Store;
+use crate::types::authentication::Account;
+
+/// Implementation of the `Accounts` gRPC service.
+#[derive(Debug)]
+pub struct AccountsService {
+    store: Store,
+}
+
+impl AccountsService {
+    /// Creates the service, backed by the given [Store].
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+}
+
+#[tonic::async_trait]
+impl Accounts for AccountsService {
+    #[instrument(target = "webdev_book::grpc", skip_all)]
+    async fn register(&self, request: Request<proto::Credentials>) -> Result<Response<proto::Empty>, Status> {
+        let proto::Credentials { email, password } = request.into_inner();
+
+        trace!("hashing the password");
+        let password = hash_password(&self.store.password_hashing, password.as_bytes())
+            .map_err(ServiceError::ArgonLibraryError)
+            .map_err(status)?;
+
+        let account = Account::builder()
+            .email(email)
+            .password(password)
+            .build()
+            .expect("all required fields are set");
+        self.store.add_account(account).await.map_err(status)?;
+
+        Ok(Response::new(proto::Empty {}))
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip_all)]
+    async fn login(&self, request: Request<proto::Credentials>) -> Result<Response<proto::Token>, Status> {
+        let user_agent = request
+            .metadata()
+            .get("user-agent")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let address = request.remote_addr().map(|address| address.ip());
+        let proto::Credentials { email, password } = request.into_inner();
+
+        let account_id = check_credentials(&self.store, &email, &password, address)
+            .await
+            .map_err(status)?;
+        let token = start_session(&self.store, account_id, user_agent.as_deref())
+            .await
+            .map_err(status)?;
+        Ok(Response::new(proto::Token { token }))
+    }
+}
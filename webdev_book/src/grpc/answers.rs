@@ -0,0 +1,133 @@
+use tonic::{Request, Response, Status};
+use tracing::{debug, instrument, trace};
+
+use crate::api::profanity::censor_or_defer;
+use crate::error::ServiceError;
+use crate::grpc::proto::{self, answers_server::Answers};
+use crate::grpc::{session, status};
+use crate::quotas::{self, Contribution};
+use crate::store::Store;
+use crate::types::answer::{Answer, AnswerId};
+use crate::types::authentication::Session;
+use crate::types::question::QuestionId;
+use crate::types::sanitize;
+
+/// Implementation of the `Answers` gRPC service.
+#[derive(Debug)]
+pub struct AnswersService {
+    store: Store,
+}
+
+impl AnswersService {
+    /// Creates the service, backed by the given [Store].
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Checks that the account is the author of the answer.
+    async fn check_owner(&self, answer_id: AnswerId, session: &Session) -> Result<(), Status> {
+        trace!("checking if the account is the author of the answer");
+        match self.store.is_answer_owner(answer_id, session.account_id).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(status(ServiceError::Unauthorized)),
+            Err(error) => Err(status(error)),
+        }
+    }
+}
+
+impl From<Answer> for proto::Answer {
+    fn from(answer: Answer) -> Self {
+        Self {
+            id: answer.id.map_or(0, |AnswerId(id)| id),
+            content: answer.content,
+            question_id: answer.question_id.map_or(0, |QuestionId(id)| id),
+            accepted: answer.accepted,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Answers for AnswersService {
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn add_answer(&self, request: Request<proto::NewAnswer>) -> Result<Response<proto::Answer>, Status> {
+        let Session { account_id, .. } = session(&self.store, &request).await?;
+        let proto::NewAnswer { question_id, content } = request.into_inner();
+        let question_id = QuestionId(question_id);
+
+        trace!("checking if the account is the owner of the question");
+        if !self
+            .store
+            .is_question_owner(question_id, account_id)
+            .await
+            .map_err(status)?
+        {
+            return Err(status(ServiceError::Unauthorized));
+        }
+        quotas::check(&self.store, account_id, Contribution::Answer)
+            .await
+            .map_err(status)?;
+
+        let content = sanitize::content(&content, &self.store.limits).map_err(status)?;
+        let (content, censored) = censor_or_defer(self.store.profanity_filter.as_ref(), content)
+            .await
+            .map_err(status)?;
+        debug!("censored content: {content}");
+
+        let answer = self
+            .store
+            .add_answer(account_id, question_id, content, censored)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(answer.into()))
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn get_answer(&self, request: Request<proto::AnswerId>) -> Result<Response<proto::Answer>, Status> {
+        let answer_id = AnswerId(request.into_inner().id);
+        match self.store.get_answer(answer_id).await.map_err(status)? {
+            Some(answer) => Ok(Response::new(answer.into())),
+            None => Err(status(ServiceError::AnswerNotFound(answer_id.into()))),
+        }
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn update_answer(
+        &self,
+        request: Request<proto::UpdateAnswerRequest>,
+    ) -> Result<Response<proto::Answer>, Status> {
+        let session = session(&self.store, &request).await?;
+        let proto::UpdateAnswerRequest { id, content } = request.into_inner();
+        let answer_id = AnswerId(id);
+        self.check_owner(answer_id, &session).await?;
+
+        let content = sanitize::content(&content, &self.store.limits).map_err(status)?;
+        let (content, censored) = censor_or_defer(self.store.profanity_filter.as_ref(), content)
+            .await
+            .map_err(status)?;
+        debug!("censored content: {content}");
+
+        let answer = self
+            .store
+            .update_answer(session.account_id, answer_id, content, censored)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(answer.into()))
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn delete_answer(&self, request: Request<proto::AnswerId>) -> Result<Response<proto::Empty>, Status> {
+        let session = session(&self.store, &request).await?;
+        let answer_id = AnswerId(request.into_inner().id);
+        self.check_owner(answer_id, &session).await?;
+
+        match self
+            .store
+            .delete_answer(session.account_id, answer_id)
+            .await
+            .map_err(status)?
+        {
+            true => Ok(Response::new(proto::Empty {})),
+            false => Err(status(ServiceError::AnswerNotFound(answer_id.into()))),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Module for the gRPC service.
+//!
+//! The service exposes the same operations as the REST API, for internal service-to-service
+//! consumers. It shares the [Store] with the REST API, and is served on a separate port.
+//!
+//! This module contains the following submodules:
+//! - `questions` - Implementation of the `Questions` service.
+//! - `answers` - Implementation of the `Answers` service.
+//! - `accounts` - Implementation of the `Accounts` service.
+//!
+//! The service definitions are in `proto/webdev_book.proto`.
+use std::net::SocketAddr;
+
+use tonic::transport::Server;
+use tonic::{Code, Request, Status};
+
+use crate::authentication;
+use crate::error::ServiceError;
+use crate::store::Store;
+use crate::types::authentication::Session;
+
+/// Implementation of the `Accounts` service.
+mod accounts;
+/// Implementation of the `Answers` service.
+mod answers;
+/// Implementation of the `Questions` service.
+mod questions;
+
+/// Types and service traits generated from the service definitions.
+pub mod proto {
+    tonic::include_proto!("webdev_book");
+}
+
+/// Runs the gRPC server on the given address, until it fails.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `addr` - The address to listen on.
+pub async fn serve(store: Store, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+    use proto::{accounts_server::AccountsServer, answers_server::AnswersServer, questions_server::QuestionsServer};
+
+    Server::builder()
+        .trace_fn(|_| tracing::info_span!("grpc request", id = %uuid::Uuid::new_v4()))
+        .add_service(QuestionsServer::new(questions::QuestionsService::new(store.clone())))
+        .add_service(AnswersServer::new(answers::AnswersService::new(store.clone())))
+        .add_service(AccountsServer::new(accounts::AccountsService::new(store)))
+        .serve(addr)
+        .await
+}
+
+/// Converts a [ServiceError] to a gRPC [Status], based on the HTTP status code of the error.
+fn status(error: ServiceError) -> Status {
+    use warp::http::StatusCode;
+
+    let code = match error.status_code() {
+        StatusCode::BAD_REQUEST => Code::InvalidArgument,
+        StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+        StatusCode::FORBIDDEN => Code::PermissionDenied,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::CONFLICT => Code::Aborted,
+        StatusCode::PRECONDITION_FAILED => Code::FailedPrecondition,
+        StatusCode::LOCKED => Code::FailedPrecondition,
+        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+        _ => Code::Internal,
+    };
+    Status::new(code, error.to_string())
+}
+
+/// Authenticates a request using the token in the `authorization` metadata.
+///
+/// The token is verified the same way as the `Authorization` header of the REST API, with
+/// [authenticate](authentication::authenticate).
+#[allow(clippy::result_large_err)] // `Status` is the error type of every gRPC method
+async fn session<T>(store: &Store, request: &Request<T>) -> Result<Session, Status> {
+    let token = request
+        .metadata()
+        .get("authorization")
+        .and_then(|token| token.to_str().ok())
+        .ok_or_else(|| Status::unauthenticated("missing request metadata: \"authorization\""))?;
+
+    authentication::authenticate(store, token.to_string())
+        .await
+        .map_err(status)
+}
@@ -0,0 +1,169 @@
+use tonic::{Request, Response, Status};
+use tracing::{debug, instrument, trace};
+
+use crate::api::profanity::censor_or_defer;
+use crate::error::ServiceError;
+use crate::grpc::proto::{self, questions_server::Questions};
+use crate::grpc::{session, status};
+use crate::quotas::{self, Contribution};
+use crate::store::Store;
+use crate::types::authentication::Session;
+use crate::types::pagination::Pagination;
+use crate::types::question::{Question, QuestionId};
+use crate::types::sanitize;
+
+/// Implementation of the `Questions` gRPC service.
+#[derive(Debug)]
+pub struct QuestionsService {
+    store: Store,
+}
+
+impl QuestionsService {
+    /// Creates the service, backed by the given [Store].
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+
+    /// Normalizes the question, censors its title and content, and builds the [Question] to store.
+    ///
+    /// Returns whether the title and the content were censored, see [censor_or_defer].
+    async fn censor(&self, question: proto::NewQuestion, id: Option<QuestionId>) -> Result<(Question, bool), Status> {
+        let proto::NewQuestion { title, content, tags } = question;
+
+        trace!("normalizing title, content and tags...");
+        let limits = &self.store.limits;
+        let title = sanitize::title(&title, limits).map_err(status)?;
+        let content = sanitize::content(&content, limits).map_err(status)?;
+        let tags = sanitize::tags((!tags.is_empty()).then_some(tags), limits).map_err(status)?;
+
+        trace!("censoring title and content...");
+        let profanity_filter = self.store.profanity_filter.as_ref();
+        let ((title, title_censored), (content, content_censored)) = tokio::try_join!(
+            censor_or_defer(profanity_filter, title),
+            censor_or_defer(profanity_filter, content)
+        )
+        .map_err(status)?;
+
+        let question = Question::builder()
+            .id(id)
+            .title(title)
+            .content(content)
+            .tags(tags.filter(|tags| !tags.is_empty()))
+            .build()
+            .expect("all required fields are set");
+        Ok((question, title_censored && content_censored))
+    }
+}
+
+impl From<Question> for proto::Question {
+    fn from(question: Question) -> Self {
+        Self {
+            id: question.id.map_or(0, |QuestionId(id)| id),
+            title: question.title,
+            content: question.content,
+            tags: question.tags.unwrap_or_default(),
+            version: question.version,
+            answer_count: question.answer_count.unwrap_or_default(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Questions for QuestionsService {
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn list_questions(
+        &self,
+        request: Request<proto::ListQuestionsRequest>,
+    ) -> Result<Response<proto::ListQuestionsResponse>, Status> {
+        let proto::ListQuestionsRequest { offset, limit } = request.into_inner();
+        let pag = Pagination { offset, limit };
+        pag.validate()
+            .map_err(|error| status(ServiceError::PaginationError(error)))?;
+        let questions = self.store.get_questions(pag, None).await.map_err(status)?;
+        debug!(questions_found = questions.len());
+
+        Ok(Response::new(proto::ListQuestionsResponse {
+            questions: questions.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn get_question(&self, request: Request<proto::QuestionId>) -> Result<Response<proto::Question>, Status> {
+        let question_id = QuestionId(request.into_inner().id);
+        match self.store.get_question(question_id).await.map_err(status)? {
+            Some(question) => Ok(Response::new(question.into())),
+            None => Err(status(ServiceError::QuestionNotFound(question_id.into()))),
+        }
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn add_question(&self, request: Request<proto::NewQuestion>) -> Result<Response<proto::Question>, Status> {
+        let session = session(&self.store, &request).await?;
+        quotas::check(&self.store, session.account_id, Contribution::Question)
+            .await
+            .map_err(status)?;
+        let (question, censored) = self.censor(request.into_inner(), None).await?;
+
+        let question = self
+            .store
+            .add_question(session.account_id, question, censored)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(question.into()))
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn update_question(
+        &self,
+        request: Request<proto::UpdateQuestionRequest>,
+    ) -> Result<Response<proto::Question>, Status> {
+        let Session { account_id, .. } = session(&self.store, &request).await?;
+        let proto::UpdateQuestionRequest { id, question } = request.into_inner();
+        let question_id = QuestionId(id);
+        let question = question.ok_or_else(|| Status::invalid_argument("missing question"))?;
+
+        trace!("checking if the account is the owner of the question");
+        if !self
+            .store
+            .is_question_owner(question_id, account_id)
+            .await
+            .map_err(status)?
+        {
+            return Err(status(ServiceError::Unauthorized));
+        }
+
+        let (question, censored) = self.censor(question, Some(question_id)).await?;
+        let question = self
+            .store
+            .update_question(Some(account_id), question, question_id, None, censored, None)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(question.into()))
+    }
+
+    #[instrument(target = "webdev_book::grpc", skip(self))]
+    async fn delete_question(&self, request: Request<proto::QuestionId>) -> Result<Response<proto::Empty>, Status> {
+        let Session { account_id, .. } = session(&self.store, &request).await?;
+        let question_id = QuestionId(request.into_inner().id);
+
+        trace!("checking if the account is the owner of the question");
+        if !self
+            .store
+            .is_question_owner(question_id, account_id)
+            .await
+            .map_err(status)?
+        {
+            return Err(status(ServiceError::Unauthorized));
+        }
+
+        match self
+            .store
+            .delete_question(Some(account_id), question_id, None)
+            .await
+            .map_err(status)?
+        {
+            true => Ok(Response::new(proto::Empty {})),
+            false => Err(status(ServiceError::QuestionNotFound(question_id.into()))),
+        }
+    }
+}
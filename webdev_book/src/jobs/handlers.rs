@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use tracing::{debug, info, instrument, trace};
+use warp::Rejection;
+
+use crate::error::ServiceError;
+use crate::responses::JsonResponse;
+use crate::store::Store;
+use crate::types::job::{Job, JobStatus};
+use crate::types::pagination::Pagination;
+
+/// Handler for `GET /jobs?status={status}&offset={i64}&limit={i64}`
+///
+/// Returns the jobs in the queue, the most recent ones first, optionally only the ones with the given status.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `status` - The status of the returned jobs: `pending`, `running`, `done` or `failed`
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/jobs",
+    tag = "jobs",
+    params(
+        ("status" = Option<JobStatus>, Query, description = "Status of the returned jobs"),
+        Pagination,
+    ),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Jobs in the queue", body = [Job]),
+        (status = 400, description = "Invalid status or pagination parameters", body = String),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::jobs", skip(store))]
+pub async fn get_jobs(store: Store, mut params: HashMap<String, String>) -> Result<JsonResponse<Vec<Job>>, Rejection> {
+    trace!("querying jobs");
+    // The status is taken out first, as the pagination rejects any other parameter
+    let status = params
+        .remove("status")
+        .map(|status| status.parse::<JobStatus>())
+        .transpose()
+        .map_err(ServiceError::ValidationError)?;
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag, ?status);
+
+    let jobs = store.get_jobs(status, pag).await?;
+    info!("returning {} jobs", jobs.len());
+    Ok(JsonResponse::ok(jobs))
+}
@@ -0,0 +1,50 @@
+//! Module for the queue of background jobs.
+//!
+//! Deferred work is described by a [Task], which is stored in the `jobs` table, so it survives
+//! restarts, and run by the worker started with [spawn_worker]. The jobs caused by the writes, e.g.
+//! the deliveries of the webhooks and the notifications of a new answer, are added in the same
+//! transaction as the writes, so none are lost. Failed jobs are retried with an
+//! exponential backoff, until they run out of attempts.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for inspecting the queue.
+//! - `routes` - Contains the filters for inspecting the queue.
+//! - `worker` - Contains the worker running the jobs.
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Handlers for inspecting the queue.
+mod handlers;
+/// Routes for inspecting the queue.
+mod routes;
+/// Worker running the jobs.
+mod worker;
+
+pub use crate::types::job::Task;
+pub use worker::spawn_worker;
+
+/// OpenAPI document for the queue of background jobs.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(handlers::get_jobs),
+    tags((name = "jobs", description = "Queue of the background jobs, for the administrators"))
+)]
+pub struct JobsApi;
+
+/// Filter for the queue of background jobs.
+///
+/// Creates a filter that handles requests for inspecting the queue.
+///
+/// The filter combines the following filters:
+/// - `get_jobs`, for handling `GET /jobs`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::get_jobs(store.clone()).boxed()
+}
@@ -0,0 +1,24 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::authentication;
+use crate::filters::route;
+use crate::jobs::handlers;
+use crate::store::Store;
+
+/// GET /jobs?status={status}&offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles listing the jobs in the queue.
+/// The route is only available to the administrators.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_jobs(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "jobs",
+        extract: [authentication::admin(&store), warp::query()],
+        handler: handlers::get_jobs,
+        trace: "get_jobs request",
+    }
+}
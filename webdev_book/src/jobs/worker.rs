@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::jobs::Task;
+use crate::store::Store;
+use crate::types::job::{Job, JobStatus};
+use crate::webhooks;
+
+/// The number of jobs run at the same time.
+const CONCURRENCY: usize = 8;
+/// The time to wait before looking for jobs again, when the queue is empty.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// The time after which a running job is considered abandoned, and returned to the queue on startup.
+const STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+/// The longest time to wait before a retry.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// Spawns the worker running the jobs from the queue.
+///
+/// On startup, the worker returns the jobs abandoned by crashed instances to the queue.
+/// Then it takes the due jobs one by one, and runs up to [CONCURRENCY] of them at the same time.
+/// Failed jobs are retried after `2^attempts` seconds, at most an hour.
+///
+/// # Parameters
+/// - `store` - The [Store] holding the queue.
+pub fn spawn_worker(store: Store) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        info!("job worker started");
+        match store.requeue_stale_jobs(STALE_AFTER).await {
+            Ok(0) => {}
+            Ok(requeued) => info!("returned {requeued} abandoned jobs to the queue"),
+            Err(error) => error!("cannot return abandoned jobs to the queue: {error}"),
+        }
+
+        let client = reqwest::Client::new();
+        let slots = Arc::new(Semaphore::new(CONCURRENCY));
+        loop {
+            let slot = slots
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("the semaphore is never closed");
+            match store.claim_job().await {
+                Ok(Some(job)) => {
+                    let (store, client) = (store.clone(), client.clone());
+                    tokio::spawn(async move {
+                        run_job(&store, &client, job).await;
+                        drop(slot);
+                    });
+                }
+                Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+                Err(error) => {
+                    error!("cannot take a job from the queue: {error}");
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    })
+}
+
+/// Runs the job, and records its outcome in the queue.
+#[instrument(target = "webdev_book::jobs", skip_all, fields(id = ?job.id, kind = %job.kind, attempt = job.attempts))]
+async fn run_job(store: &Store, client: &reqwest::Client, job: Job) {
+    let result = match serde_json::from_value::<Task>(job.payload) {
+        Ok(task) => run_task(store, client, task).await,
+        Err(error) => Err(format!("invalid payload: {error}")),
+    };
+
+    let outcome = match result {
+        Ok(()) => {
+            debug!("job done");
+            store.complete_job(job.id).await
+        }
+        Err(message) => {
+            let delay = retry_delay(job.attempts);
+            match store.fail_job(job.id, &message, delay).await {
+                Ok(JobStatus::Failed) => {
+                    error!("job failed after {} attempts: {message}", job.attempts);
+                    Ok(())
+                }
+                Ok(_) => {
+                    warn!("job failed, retrying in {delay:?}: {message}");
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            }
+        }
+    };
+    if let Err(error) = outcome {
+        error!("cannot record the outcome of the job: {error}");
+    }
+}
+
+/// Runs the task.
+async fn run_task(store: &Store, client: &reqwest::Client, task: Task) -> Result<(), String> {
+    match task {
+        Task::DeliverWebhook {
+            webhook_id,
+            event,
+            payload,
+        } => match store.get_webhook(webhook_id).await {
+            Ok(Some(webhook)) => webhooks::deliver(client, &webhook, &event, payload).await,
+            Ok(None) => {
+                debug!("webhook was deleted, skipping the delivery");
+                Ok(())
+            }
+            Err(error) => Err(error.to_string()),
+        },
+        Task::NotifyAnswer { answer_id } => match store.add_answer_notifications(answer_id).await {
+            Ok(notified) => {
+                debug!("notified {notified} accounts about the answer");
+                Ok(())
+            }
+            Err(error) => Err(error.to_string()),
+        },
+        Task::AwardBadges => match store.award_badges().await {
+            Ok(awarded) => {
+                debug!("awarded {awarded} badges");
+                Ok(())
+            }
+            Err(error) => Err(error.to_string()),
+        },
+        Task::CensorQuestion { question_id } => match store.censor_question(question_id).await {
+            Ok(censored) => {
+                debug!("censored the question: {censored}");
+                Ok(())
+            }
+            Err(error) => Err(error.to_string()),
+        },
+        Task::CensorAnswer { answer_id } => match store.censor_answer(answer_id).await {
+            Ok(censored) => {
+                debug!("censored the answer: {censored}");
+                Ok(())
+            }
+            Err(error) => Err(error.to_string()),
+        },
+    }
+}
+
+/// Returns the time to wait before retrying a job, after the given number of attempts.
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(0, 12) as u32;
+    Duration::from_secs(2u64.pow(exponent)).min(MAX_RETRY_DELAY)
+}
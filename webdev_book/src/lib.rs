@@ -0,0 +1,110 @@
+//! HTTP layer of the webdev book service.
+//!
+//! This crate contains the warp filters and handlers for the resources served by the service,
+//! built on top of the types and the store from [webdev_core].
+#![warn(clippy::all)]
+
+use std::path::PathBuf;
+
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+pub mod answers;
+pub mod attachments;
+pub mod authentication;
+pub mod badges;
+pub mod codec;
+pub mod error;
+pub mod filters;
+pub mod frontend;
+pub mod grpc;
+pub mod jobs;
+pub mod live;
+pub mod moderation;
+pub mod notifications;
+pub mod openapi;
+pub mod questions;
+pub mod quotas;
+pub mod responses;
+pub mod seed;
+pub mod tags;
+#[cfg(feature = "test-util")]
+pub mod test_support;
+pub mod webhooks;
+
+pub use webdev_core::{api, clock, storage, store, tokens, types};
+
+use filters::first_segment;
+use store::Store;
+
+/// This is the filter that will be used to serve the routes.
+///
+/// It is composed of the filters defined in the resource modules, which are only tried for the
+/// paths under their first segments, see [first_segment].
+/// It handles the CORS headers, the encoding of the bodies (see [codec]) and the error handling.
+/// It handles resources at the /questions, /answers, /attachments, /tags, /webhooks and /notifications endpoints,
+/// the notifications, the answers, the bookmarks and the followed tags of the account at
+/// /accounts/me/notifications, /accounts/me/answers, /accounts/me/bookmarks and /accounts/me/tags,
+/// the questions in the followed tags at /questions/feed,
+/// the profile and the deletion of the account at /accounts/me, its sessions at /accounts/me/sessions,
+/// the public profiles at /accounts/{id},
+/// the login with the external providers at /oauth,
+/// the badges at /accounts/{id}/badges,
+/// the job queue at /jobs, the moderation of the accounts at /admin,
+/// the live updates at /ws,
+/// and the API documentation at /api-docs.
+/// While the store is in maintenance mode, the writes are rejected by the [maintenance_guard](moderation::maintenance_guard).
+/// When a frontend directory is given, the [frontend] is served for the requests no route matched.
+/// The error handling is done by the [return_error](error::return_error) function defined in the error module.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+/// - `frontend_dir` - The directory containing the built frontend, if it should be served.
+/// - `attachments_dir` - The directory the files attached to the questions are stored in.
+pub fn routes(store: &Store, frontend_dir: Option<PathBuf>, attachments_dir: PathBuf) -> BoxedFilter<(impl Reply,)> {
+    let storage = attachments::Storage::new(attachments_dir);
+
+    let questions = questions::filter(store);
+    let answers = answers::filter(store);
+    let attachments = attachments::filter(store, &storage);
+    let tags = tags::filter(store);
+    let authentication = authentication::filter(store);
+    let notifications = notifications::filter(store);
+
+    // The requests are dispatched on the first segment of their path, so only the modules with
+    // routes under it are tried, in order, the questions and the answers first
+    let api = first_segment(&["questions"])
+        .and(questions.clone().or(answers.clone()).or(attachments.clone()))
+        .or(first_segment(&["accounts"]).and(
+            questions
+                .or(answers.clone())
+                .or(tags.clone())
+                .or(authentication.clone())
+                .or(notifications.clone())
+                .or(badges::filter(store)),
+        ))
+        .or(first_segment(&["answers"]).and(answers))
+        .or(first_segment(&["attachments"]).and(attachments))
+        .or(first_segment(&["tags"]).and(tags))
+        .or(first_segment(&["register", "login", "refresh", "logout", "oauth"]).and(authentication))
+        .or(first_segment(&["webhooks"]).and(webhooks::filter(store)))
+        .or(first_segment(&["notifications"]).and(notifications))
+        .or(first_segment(&["jobs"]).and(jobs::filter(store)))
+        .or(first_segment(&["admin"]).and(moderation::filter(store)))
+        .or(first_segment(&["ws"]).and(live::filter(store)))
+        .or(first_segment(&["api-docs"]).and(openapi::filter()));
+
+    // Responses are re-encoded in the format requested by the client, and the rejections become
+    // error responses, except for the requests no route matched, which fall back to the frontend
+    warp::header::optional::<String>("accept")
+        .and(moderation::maintenance_guard(store))
+        .and(api)
+        .and_then(codec::encode)
+        .recover(error::recover_api)
+        .unify()
+        .or(frontend::filter(frontend_dir))
+        .with(filters::cors())
+        .with(warp::trace::request())
+        .recover(error::return_error)
+        .boxed()
+}
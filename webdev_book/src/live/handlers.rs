@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, instrument, trace, warn};
+use warp::ws::{Message, WebSocket, Ws};
+use warp::{Rejection, Reply};
+
+use crate::store::Store;
+use crate::types::question::QuestionId;
+use webdev_core::events::Event;
+
+/// Message sent by the client to change what it is subscribed to.
+///
+/// Messages are JSON objects, e.g. `{"type": "subscribe", "tag": "rust"}` or
+/// `{"type": "unsubscribe", "question_id": 1}`. A message can name a tag, a question, or both.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Subscribe {
+        tag: Option<String>,
+        question_id: Option<QuestionId>,
+    },
+    Unsubscribe {
+        tag: Option<String>,
+        question_id: Option<QuestionId>,
+    },
+}
+
+/// Subscriptions of a single connection.
+///
+/// A connection without any subscriptions receives all events.
+#[derive(Debug, Default)]
+struct Subscriptions {
+    tags: HashSet<String>,
+    questions: HashSet<i32>,
+}
+
+impl Subscriptions {
+    /// Applies the message sent by the client.
+    fn apply(&mut self, message: ClientMessage) {
+        match message {
+            ClientMessage::Subscribe { tag, question_id } => {
+                self.tags.extend(tag);
+                self.questions.extend(question_id.map(|QuestionId(id)| id));
+            }
+            ClientMessage::Unsubscribe { tag, question_id } => {
+                if let Some(tag) = tag {
+                    self.tags.remove(&tag);
+                }
+                if let Some(QuestionId(id)) = question_id {
+                    self.questions.remove(&id);
+                }
+            }
+        }
+    }
+
+    /// Checks whether the event should be sent to the client.
+    fn matches(&self, event: &Event) -> bool {
+        if self.tags.is_empty() && self.questions.is_empty() {
+            return true;
+        }
+        let by_question = event
+            .question_id()
+            .is_some_and(|QuestionId(id)| self.questions.contains(&id));
+        by_question || event.tags().iter().any(|tag| self.tags.contains(tag))
+    }
+}
+
+/// Handler for `GET /ws`
+///
+/// Upgrades the connection to a WebSocket, over which the changes to questions and answers
+/// are sent as JSON encoded events. The client can narrow down the events it receives by sending
+/// subscription messages for tags or questions.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `ws` - [Ws] upgrade request
+#[instrument(target = "webdev_book::live", skip(store, ws))]
+pub async fn updates(store: Store, ws: Ws) -> Result<impl Reply, Rejection> {
+    let events = store.events.clone();
+    Ok(ws.on_upgrade(move |socket| async move {
+        info!("live updates connection opened");
+        stream_events(socket, events.subscribe()).await;
+        info!("live updates connection closed");
+    }))
+}
+
+/// Forwards the events to the socket until either side closes the connection.
+async fn stream_events(socket: WebSocket, mut events: tokio::sync::broadcast::Receiver<Event>) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut subscriptions = Subscriptions::default();
+
+    loop {
+        tokio::select! {
+            message = receiver.next() => match message {
+                Some(Ok(message)) if message.is_text() => {
+                    let text = message.to_str().unwrap_or_default();
+                    match serde_json::from_str::<ClientMessage>(text) {
+                        Ok(message) => {
+                            debug!("subscription message: {message:?}");
+                            subscriptions.apply(message);
+                        }
+                        Err(error) => warn!("invalid subscription message: {error}"),
+                    }
+                }
+                Some(Ok(message)) if message.is_close() => break,
+                Some(Ok(_)) => {}
+                Some(Err(error)) => {
+                    warn!("websocket error: {error}");
+                    break;
+                }
+                None => break,
+            },
+            event = events.recv() => match event {
+                Ok(event) if subscriptions.matches(&event) => {
+                    trace!("sending event: {event:?}");
+                    let text = serde_json::to_string(&event).expect("events are always serializable");
+                    if sender.send(Message::text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => warn!("connection lagged behind, skipped {skipped} events"),
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+}
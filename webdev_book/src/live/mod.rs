@@ -0,0 +1,27 @@
+//! Module for the live updates of the resources.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the live updates.
+//! - `routes` - Contains the filters for the live updates.
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Handlers for the live updates.
+mod handlers;
+/// Routes for the live updates.
+mod routes;
+
+/// Filter for the live updates.
+///
+/// Creates a filter that handles requests for the live updates.
+///
+/// The filter combines the following filters:
+/// - `updates`, for handling `GET /ws`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::updates(store.clone()).boxed()
+}
@@ -0,0 +1,23 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::filters::route;
+use crate::live::handlers;
+use crate::store::Store;
+
+/// GET /ws
+///
+/// Creates a filter for a route that upgrades the connection to a WebSocket,
+/// over which the changes to questions and answers are streamed.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn updates(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "ws",
+        extract: [warp::ws()],
+        handler: handlers::updates,
+        trace: "updates request",
+    }
+}
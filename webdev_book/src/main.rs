@@ -1,20 +1,40 @@
 #![warn(clippy::all)]
 
-use dotenv;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter, Registry};
-use warp::Filter;
-
-mod answers;
-mod api;
-mod authentication;
-mod error;
-mod filters;
-mod questions;
-mod store;
-mod types;
 
 use config::Config;
+use webdev_book::seed::Profile;
+use webdev_book::store::PoolConfig;
+use webdev_book::tokens::{JwtSigner, PasetoSigner, TokenSigner};
+use webdev_book::types::authentication::{AuthKeys, OAuthConfig, PasswordHashing, SessionLifetimes};
+use webdev_book::types::pagination::Pagination;
+use webdev_book::types::quota::Quotas;
+use webdev_book::types::sanitize::Limits;
+use webdev_book::{error, seed, store};
+
+/// The webdev book service
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    /// The command to run instead of serving the API
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Fills the database with a reproducible dataset, and exits
+    Seed {
+        /// The profile of the dataset
+        #[arg(long, default_value = "demo")]
+        profile: Profile,
+    },
+}
 
 /// The configuration of the application.
 ///
@@ -33,6 +53,81 @@ pub struct Args {
     database_user: String,
     /// The password to connect to the database.
     database_password: String,
+    /// The number of database connections established on startup and kept open.
+    #[serde(default)]
+    database_min_connections: u32,
+    /// The maximum number of database connections.
+    #[serde(default = "default_max_connections")]
+    database_max_connections: u32,
+    /// Whether to connect to the database lazily, so the service can start before the database is ready.
+    #[serde(default)]
+    database_lazy_connect: bool,
+    /// The number of prepared statements cached by each database connection.
+    #[serde(default = "default_statement_cache_capacity")]
+    database_statement_cache_capacity: usize,
+    /// Whether to check that a database connection is alive before using it.
+    #[serde(default = "default_test_before_acquire")]
+    database_test_before_acquire: bool,
+    /// The port for the gRPC service.
+    grpc_port: u16,
+    /// The directory containing the built frontend, which is not served if missing.
+    frontend_dir: Option<PathBuf>,
+    /// The directory the files attached to the questions are stored in.
+    #[serde(default = "default_attachments_dir")]
+    attachments_dir: PathBuf,
+    /// The number of questions an account can ask a day, unlimited if missing.
+    daily_question_limit: Option<u32>,
+    /// The number of answers an account can post a day, unlimited if missing.
+    daily_answer_limit: Option<u32>,
+    /// The maximum length of a title in bytes.
+    #[serde(default = "default_max_title_bytes")]
+    max_title_bytes: usize,
+    /// The maximum length of the content of a question or an answer in bytes.
+    #[serde(default = "default_max_content_bytes")]
+    max_content_bytes: usize,
+    /// The maximum length of a tag in bytes.
+    #[serde(default = "default_max_tag_bytes")]
+    max_tag_bytes: usize,
+    /// The maximum number of tags of a question.
+    #[serde(default = "default_max_tags")]
+    max_tags: usize,
+    /// The maximum number of items returned by a paginated listing.
+    #[serde(default = "default_max_page_size")]
+    max_page_size: i64,
+    /// The variant of Argon2 the passwords are hashed with: `argon2id`, `argon2i` or `argon2d`.
+    #[serde(default = "default_password_variant")]
+    password_variant: String,
+    /// The memory used to hash a password, in KiB.
+    #[serde(default = "default_password_memory_kib")]
+    password_memory_kib: u32,
+    /// The number of passes over the memory when hashing a password.
+    #[serde(default = "default_password_iterations")]
+    password_iterations: u32,
+    /// The number of lanes the memory is split into when hashing a password.
+    #[serde(default = "default_password_parallelism")]
+    password_parallelism: u32,
+    /// The number of hours the tokens of the sessions are valid for.
+    #[serde(default = "default_session_hours")]
+    session_hours: u32,
+    /// The number of days a session remembered on login lasts after it was last refreshed.
+    #[serde(default = "default_remember_me_days")]
+    remember_me_days: u32,
+    /// The number of days a session remembered on login lasts at most, however often it is refreshed.
+    #[serde(default = "default_remember_me_max_days")]
+    remember_me_max_days: u32,
+    /// The number of minutes after its last extension a remembered session is extended again by its use.
+    #[serde(default = "default_remember_me_slide_minutes")]
+    remember_me_slide_minutes: u32,
+    /// The format the tokens of the sessions are issued in: `paseto` or `jwt`.
+    #[serde(default = "default_token_format")]
+    token_format: String,
+    /// The algorithm the JWTs are signed with: `HS256`, with the `JWT_SECRET`, or `RS256`.
+    #[serde(default = "default_jwt_algorithm")]
+    jwt_algorithm: String,
+    /// The PEM file with the RSA private key signing the JWTs, for `RS256`.
+    jwt_private_key_file: Option<PathBuf>,
+    /// The PEM file with the RSA public key verifying the JWTs, for `RS256`.
+    jwt_public_key_file: Option<PathBuf>,
 }
 
 impl Args {
@@ -49,25 +144,226 @@ impl Args {
 
         format!("postgres://{user}:{password}@{host}:{port}/{name}")
     }
+
+    /// Returns the daily limits on the contributions of every account.
+    pub fn quotas(&self) -> Quotas {
+        Quotas {
+            questions_per_day: self.daily_question_limit,
+            answers_per_day: self.daily_answer_limit,
+        }
+    }
+
+    /// Returns the limits on the text posted by the users.
+    pub fn limits(&self) -> Limits {
+        Limits {
+            title_bytes: self.max_title_bytes,
+            content_bytes: self.max_content_bytes,
+            tag_bytes: self.max_tag_bytes,
+            tag_count: self.max_tags,
+        }
+    }
+
+    /// Returns the parameters the passwords are hashed with.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidPasswordHashing](error::ServiceError::InvalidPasswordHashing) if Argon2 does not accept them.
+    pub fn password_hashing(&self) -> Result<PasswordHashing, error::ServiceError> {
+        PasswordHashing::new(
+            &self.password_variant,
+            self.password_memory_kib,
+            self.password_iterations,
+            self.password_parallelism,
+        )
+    }
+
+    /// Returns how long the sessions started by the logins last.
+    pub fn session_lifetimes(&self) -> SessionLifetimes {
+        SessionLifetimes {
+            token: chrono::Duration::try_hours(self.session_hours.into()).unwrap(),
+            remember_me: chrono::Duration::try_days(self.remember_me_days.into()).unwrap(),
+            remember_me_max: chrono::Duration::try_days(self.remember_me_max_days.into()).unwrap(),
+            remember_me_slide: chrono::Duration::try_minutes(self.remember_me_slide_minutes.into()).unwrap(),
+        }
+    }
+
+    /// Returns the signer the tokens of the sessions are issued with, in the configured format.
+    ///
+    /// The PASETO tokens are encrypted with the `auth_keys`. The HS256 JWTs are signed with the
+    /// secret in the `JWT_SECRET` environment variable, and the RS256 ones with the keys read from
+    /// the configured files.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidAuthKey](error::ServiceError::InvalidAuthKey) if the format or the
+    ///   algorithm is unknown, or the keys of the JWTs are missing or invalid.
+    pub fn token_signer(&self, auth_keys: &AuthKeys) -> Result<Arc<dyn TokenSigner>, error::ServiceError> {
+        let invalid = |message: String| error::ServiceError::InvalidAuthKey(message);
+        let read_key = |file: &Option<PathBuf>, name: &str| match file {
+            Some(file) => {
+                std::fs::read(file).map_err(|error| invalid(format!("cannot read {}: {error}", file.display())))
+            }
+            None => Err(invalid(format!("{name} is not set"))),
+        };
+        match (self.token_format.as_str(), self.jwt_algorithm.as_str()) {
+            ("paseto", _) => Ok(Arc::new(PasetoSigner::new(auth_keys.clone()))),
+            ("jwt", "HS256") => {
+                let secret = std::env::var("JWT_SECRET").map_err(|_| invalid("JWT_SECRET is not set".to_string()))?;
+                Ok(Arc::new(JwtSigner::hs256(secret.as_bytes())?))
+            }
+            ("jwt", "RS256") => {
+                let private_key = read_key(&self.jwt_private_key_file, "jwt_private_key_file")?;
+                let public_key = read_key(&self.jwt_public_key_file, "jwt_public_key_file")?;
+                Ok(Arc::new(JwtSigner::rs256(&private_key, &public_key)?))
+            }
+            ("jwt", algorithm) => Err(invalid(format!("unknown JWT algorithm {algorithm:?}"))),
+            (format, _) => Err(invalid(format!("unknown token format {format:?}"))),
+        }
+    }
+
+    /// Returns the options for the database connection pool.
+    pub fn pool_config(&self) -> PoolConfig {
+        PoolConfig {
+            min_connections: self.database_min_connections,
+            max_connections: self.database_max_connections,
+            lazy: self.database_lazy_connect,
+            statement_cache_capacity: self.database_statement_cache_capacity,
+            test_before_acquire: self.database_test_before_acquire,
+        }
+    }
+}
+
+/// Returns the default maximum number of database connections.
+fn default_max_connections() -> u32 {
+    PoolConfig::default().max_connections
+}
+
+/// Returns the default number of prepared statements cached by each database connection.
+fn default_statement_cache_capacity() -> usize {
+    PoolConfig::default().statement_cache_capacity
+}
+
+/// Returns whether database connections are checked before use by default.
+fn default_test_before_acquire() -> bool {
+    PoolConfig::default().test_before_acquire
+}
+
+/// Returns the default maximum length of a title in bytes.
+fn default_max_title_bytes() -> usize {
+    Limits::default().title_bytes
+}
+
+/// Returns the default maximum length of the content in bytes.
+fn default_max_content_bytes() -> usize {
+    Limits::default().content_bytes
+}
+
+/// Returns the default maximum length of a tag in bytes.
+fn default_max_tag_bytes() -> usize {
+    Limits::default().tag_bytes
+}
+
+/// Returns the default maximum number of tags of a question.
+fn default_max_tags() -> usize {
+    Limits::default().tag_count
+}
+
+/// Returns the default maximum number of items returned by a paginated listing.
+fn default_max_page_size() -> i64 {
+    Pagination::MAX_LIMIT
+}
+
+/// Returns the default variant of Argon2 the passwords are hashed with.
+fn default_password_variant() -> String {
+    PasswordHashing::default().variant.to_string()
+}
+
+/// Returns the default memory used to hash a password, in KiB.
+fn default_password_memory_kib() -> u32 {
+    PasswordHashing::default().memory_kib
+}
+
+/// Returns the default number of passes over the memory when hashing a password.
+fn default_password_iterations() -> u32 {
+    PasswordHashing::default().iterations
+}
+
+/// Returns the default number of lanes used to hash a password.
+fn default_password_parallelism() -> u32 {
+    PasswordHashing::default().parallelism
+}
+
+/// Returns the default number of hours the tokens of the sessions are valid for.
+fn default_session_hours() -> u32 {
+    SessionLifetimes::default().token.num_hours() as u32
+}
+
+/// Returns the default number of days a remembered session lasts after it was last refreshed.
+fn default_remember_me_days() -> u32 {
+    SessionLifetimes::default().remember_me.num_days() as u32
+}
+
+/// Returns the default number of days a remembered session lasts at most.
+fn default_remember_me_max_days() -> u32 {
+    SessionLifetimes::default().remember_me_max.num_days() as u32
+}
+
+/// Returns the default number of minutes after which the use of a remembered session extends it again.
+fn default_remember_me_slide_minutes() -> u32 {
+    SessionLifetimes::default().remember_me_slide.num_minutes() as u32
+}
+
+/// Returns the default format of the tokens of the sessions.
+fn default_token_format() -> String {
+    "paseto".to_string()
+}
+
+/// Returns the default algorithm the JWTs are signed with.
+fn default_jwt_algorithm() -> String {
+    "HS256".to_string()
+}
+
+/// Returns the default directory for the files attached to the questions.
+fn default_attachments_dir() -> PathBuf {
+    PathBuf::from("attachments")
+}
+
+/// Runs the migrations, retrying until the database becomes available.
+///
+/// Used with the lazy connection pool, when the database may not be ready yet on startup.
+async fn migrate_when_ready(store: store::Store) {
+    let retry_interval = std::time::Duration::from_secs(5);
+    loop {
+        match sqlx::migrate!().run(&store.connection).await {
+            Ok(()) => {
+                tracing::info!("migrations applied");
+                return;
+            }
+            Err(error) => {
+                tracing::warn!("cannot run migrations, retrying in {retry_interval:?}: {error}");
+                tokio::time::sleep(retry_interval).await;
+            }
+        }
+    }
 }
 
 /// The main function of the application.
 ///
 /// It sets up the logger, the store, the migrations, and the routes.
 /// Then it starts the server on port 3030.
+/// With the `seed` command, it fills the database instead, see [seed].
 #[tokio::main]
 async fn main() -> Result<(), error::ServiceError> {
+    let cli = Cli::parse();
+
     // Load the environment variables from the .env file.
     dotenv::dotenv().ok();
 
     // Check if the environment variables are set.
-    if let Err(_) = std::env::var("API_LAYER_KEY") {
+    let Ok(api_layer_key) = std::env::var("API_LAYER_KEY") else {
         panic!("API_LAYER_KEY is not set");
-    }
+    };
 
-    if let Err(_) = std::env::var("PASETO_KEY") {
-        panic!("PASETO_KEY is not set");
-    }
+    // The keys of the tokens are validated once, so a wrong key stops the service here
+    let auth_keys = AuthKeys::from_env()?;
 
     let port = std::env::var("PORT")
         .ok()
@@ -83,7 +379,7 @@ async fn main() -> Result<(), error::ServiceError> {
     // Set up the logger filter
     let Args { ref log_level, .. } = config;
     let log_filter: EnvFilter = std::env::var("RUST_LOG")
-        .unwrap_or_else(|_| format!("webdev_book={log_level},warp={log_level}"))
+        .unwrap_or_else(|_| format!("webdev_book={log_level},webdev_core={log_level},warp={log_level}"))
         .parse()
         .unwrap();
 
@@ -99,24 +395,60 @@ async fn main() -> Result<(), error::ServiceError> {
         .with(log_filter)
         .init();
 
+    // The parameters are validated once, so wrong ones stop the service here
+    let password_hashing = config.password_hashing()?;
+    let token_signer = config.token_signer(&auth_keys)?;
+
     // This is the store that holds the questions and answers.
     let db_url = config.database_url();
-    let store = store::Store::build(&db_url).await?;
-
-    sqlx::migrate!().run(&store.connection).await?;
-
-    /* This is the filter that will be used to serve the routes.
-     * It is composed of the filters defined in the filters module.
-     * It handles the CORS headers and the error handling.
-     * It handles resources at the /questions and /answers endpoints.
-     * The error handling is done by the return_error function defined in the error module.
-     */
-    let filter = authentication::filter(&store)
-        .or(questions::filter(&store))
-        .or(answers::filter(&store))
-        .with(filters::cors())
-        .with(warp::trace::request())
-        .recover(error::return_error);
+    let store = store::Store::build(&db_url, &api_layer_key, config.pool_config())
+        .await?
+        .with_quotas(config.quotas())
+        .with_limits(config.limits())
+        .with_max_page_size(config.max_page_size)
+        .with_auth_keys(auth_keys)
+        .with_token_signer(token_signer)
+        .with_oauth(OAuthConfig::from_env())
+        .with_admin_token(std::env::var("ADMIN_TOKEN").ok())
+        .with_password_hashing(password_hashing)
+        .with_session_lifetimes(config.session_lifetimes());
+
+    if let Some(Command::Seed { profile }) = cli.command {
+        sqlx::migrate!().run(&store.connection).await?;
+        let summary = seed::seed(&store, profile).await?;
+        println!(
+            "seeded {} accounts, {} questions and {} answers, with the password {:?}",
+            summary.accounts,
+            summary.questions,
+            summary.answers,
+            seed::PASSWORD
+        );
+        return Ok(());
+    }
+
+    if config.database_lazy_connect {
+        tokio::spawn(migrate_when_ready(store.clone()));
+    } else {
+        sqlx::migrate!().run(&store.connection).await?;
+    }
+
+    // Start the gRPC service on its own port, sharing the store with the routes.
+    let grpc_addr = ([0, 0, 0, 0], config.grpc_port).into();
+    let grpc_store = store.clone();
+    tokio::spawn(async move {
+        if let Err(error) = webdev_book::grpc::serve(grpc_store, grpc_addr).await {
+            tracing::error!("gRPC service stopped: {error}");
+        }
+    });
+
+    // Run the queued jobs, among which the deliveries of the events to the registered webhooks and
+    // the notifications about the new answers, queued by the writes, and queue the periodic
+    // evaluations of the badges.
+    webdev_book::jobs::spawn_worker(store.clone());
+    webdev_book::badges::spawn_badge_scheduler(store.clone());
+
+    // This is the filter that will be used to serve the routes.
+    let filter = webdev_book::routes(&store, config.frontend_dir.clone(), config.attachments_dir.clone());
 
     // Start the server.
     warp::serve(filter).run(([0, 0, 0, 0], port)).await;
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use chrono::Duration;
+use tracing::{debug, info, instrument, trace};
+use warp::Rejection;
+
+use crate::error::ServiceError;
+use crate::responses::{JsonResponse, MessageResponse};
+use crate::store::Store;
+use crate::types::authentication::AccountId;
+use crate::types::moderation::{AccountBan, Ban, MaintenanceMode, ModerationLogEntry, RoleUpdate};
+use crate::types::pagination::Pagination;
+
+/// Checks that the ban can be applied.
+///
+/// The reason must not be empty, and the duration, if given, must be at least an hour.
+fn validate(ban: &Ban) -> Result<(), ServiceError> {
+    if ban.reason.trim().is_empty() {
+        return Err(ServiceError::ValidationError("ban reason is empty".to_string()));
+    }
+    if ban.duration_hours == Some(0) {
+        return Err(ServiceError::ValidationError("ban duration is zero".to_string()));
+    }
+    Ok(())
+}
+
+/// Handler for `POST /admin/accounts/{id}/ban`
+///
+/// Bans the account with the given id, for the given number of hours or for good.
+/// The ban replaces the previous ban of the account, if any.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `account_id` - [AccountId] for the account to ban
+/// - `ban` - [Ban] object containing the duration and the reason of the ban
+#[utoipa::path(
+    post,
+    path = "/admin/accounts/{id}/ban",
+    tag = "moderation",
+    params(("id" = AccountId, Path, description = "Id of the account")),
+    request_body = Ban,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Account banned", body = AccountBan),
+        (status = 400, description = "Invalid duration or reason", body = String),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+        (status = 404, description = "Account not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::moderation", skip(store))]
+pub async fn ban_account(store: Store, account_id: AccountId, ban: Ban) -> Result<JsonResponse<AccountBan>, Rejection> {
+    trace!("validating the ban");
+    validate(&ban)?;
+
+    let until = ban
+        .duration_hours
+        .map(|hours| {
+            Duration::try_hours(i64::from(hours))
+                .and_then(|duration| store.clock.now().checked_add_signed(duration))
+                .ok_or_else(|| ServiceError::ValidationError("ban duration is too long".to_string()))
+        })
+        .transpose()?;
+    let ban = store.ban_account(account_id, until, &ban.reason).await?;
+    info!("banned the account with account_id = {account_id:?}");
+    debug!(?ban);
+    Ok(JsonResponse::ok(ban))
+}
+
+/// Handler for `PUT /admin/accounts/{id}/role`
+///
+/// Sets the role of the account with the given id. The moderators and the administrators may edit
+/// and delete the questions of the other accounts. The sessions of the account are signed out, see
+/// [Store::delete_sessions], as their tokens carry the previous role.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `account_id` - [AccountId] for the account
+/// - `update` - [RoleUpdate] object containing the new role
+#[utoipa::path(
+    put,
+    path = "/admin/accounts/{id}/role",
+    tag = "moderation",
+    params(("id" = AccountId, Path, description = "Id of the account")),
+    request_body = RoleUpdate,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Role set, and the sessions of the account signed out", body = String),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+        (status = 404, description = "Account not found", body = String),
+        (status = 422, description = "Missing or unknown role", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::moderation", skip(store))]
+pub async fn set_account_role(
+    store: Store,
+    account_id: AccountId,
+    update: RoleUpdate,
+) -> Result<MessageResponse, Rejection> {
+    store.set_account_role(account_id, update.role).await?;
+    let signed_out = store.delete_sessions(account_id).await?;
+    debug!("signed out {signed_out} sessions of the account");
+    info!(
+        "set the role of the account with account_id = {account_id:?} to {:?}",
+        update.role
+    );
+    Ok(MessageResponse::ok("Role set"))
+}
+
+/// Handler for `POST /admin/accounts/{id}/unlock`
+///
+/// Unlocks the account with the given id, locked after too many consecutive failed logins, so it
+/// can log in again. The count of its failed logins starts over.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `account_id` - [AccountId] for the account to unlock
+#[utoipa::path(
+    post,
+    path = "/admin/accounts/{id}/unlock",
+    tag = "moderation",
+    params(("id" = AccountId, Path, description = "Id of the account")),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Account unlocked", body = String),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+        (status = 404, description = "Account not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::moderation", skip(store))]
+pub async fn unlock_account(store: Store, account_id: AccountId) -> Result<MessageResponse, Rejection> {
+    store.unlock_account(account_id).await?;
+    info!("unlocked the account with account_id = {account_id:?}");
+    Ok(MessageResponse::ok("Account unlocked"))
+}
+
+/// Handler for `GET /admin/maintenance`
+///
+/// Returns whether the service is in maintenance mode.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+#[utoipa::path(
+    get,
+    path = "/admin/maintenance",
+    tag = "moderation",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "The maintenance mode of the service", body = MaintenanceMode),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::moderation", skip(store))]
+pub async fn get_maintenance(store: Store) -> Result<JsonResponse<MaintenanceMode>, Rejection> {
+    Ok(JsonResponse::ok(MaintenanceMode {
+        enabled: store.in_maintenance(),
+    }))
+}
+
+/// Handler for `PUT /admin/maintenance`
+///
+/// Switches the maintenance mode of the service on or off. While it is on, the writes of the
+/// accounts are rejected with `503 Service Unavailable`, see [guard](super::maintenance_guard).
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `mode` - [MaintenanceMode] to switch to
+#[utoipa::path(
+    put,
+    path = "/admin/maintenance",
+    tag = "moderation",
+    request_body = MaintenanceMode,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Maintenance mode switched", body = MaintenanceMode),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+        (status = 422, description = "Missing or invalid mode", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::moderation", skip(store))]
+pub async fn set_maintenance(store: Store, mode: MaintenanceMode) -> Result<JsonResponse<MaintenanceMode>, Rejection> {
+    store.set_maintenance(mode.enabled);
+    Ok(JsonResponse::ok(mode))
+}
+
+/// Handler for `GET /admin/moderation-log?offset={i64}&limit={i64}`
+///
+/// Returns the changes the moderators made to the questions of the other accounts, the most
+/// recent ones first.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/admin/moderation-log",
+    tag = "moderation",
+    params(Pagination),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Entries of the moderation log", body = [ModerationLogEntry]),
+        (status = 400, description = "Invalid pagination parameters", body = String),
+        (status = 401, description = "Missing or invalid administrator token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::moderation", skip(store))]
+pub async fn get_moderation_log(
+    store: Store,
+    params: HashMap<String, String>,
+) -> Result<JsonResponse<Vec<ModerationLogEntry>>, Rejection> {
+    trace!("querying the moderation log");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    let entries = store.get_moderation_log(pag).await?;
+    info!("returning {} entries of the moderation log", entries.len());
+    Ok(JsonResponse::ok(entries))
+}
@@ -0,0 +1,99 @@
+//! Module for the moderation of the accounts.
+//!
+//! The administrators can ban an account, for a while or for good. The requests of a banned
+//! account are rejected by [auth](crate::authentication::auth), so its content is locked, i.e.
+//! it cannot be changed or deleted by the account, until the ban ends.
+//!
+//! The administrators can also make an account a moderator, who may edit and delete the questions
+//! of the other accounts. These changes are recorded in the moderation log.
+//!
+//! The accounts locked after too many consecutive failed logins are unlocked by the administrators.
+//!
+//! The administrators can also switch the service to maintenance mode, in which the writes of the
+//! accounts are rejected by [maintenance_guard], and only the reads are served.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the moderation.
+//! - `routes` - Contains the filters for the moderation.
+use std::future;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::http::Method;
+use warp::path::FullPath;
+use warp::{Filter, Rejection, Reply};
+
+use crate::error::ServiceError;
+use crate::store::Store;
+
+/// Handlers for the moderation.
+mod handlers;
+/// Routes for the moderation.
+mod routes;
+
+/// OpenAPI document for the moderation of the accounts.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::ban_account,
+        handlers::set_account_role,
+        handlers::unlock_account,
+        handlers::get_maintenance,
+        handlers::set_maintenance,
+        handlers::get_moderation_log
+    ),
+    tags((name = "moderation", description = "Moderation of the accounts, for the administrators"))
+)]
+pub struct ModerationApi;
+
+/// Filter for the moderation of the accounts.
+///
+/// Creates a filter that handles requests for the moderation.
+///
+/// The filter combines the following filters:
+/// - `ban_account`, for handling `POST /admin/accounts/{id}/ban`
+/// - `set_account_role`, for handling `PUT /admin/accounts/{id}/role`
+/// - `unlock_account`, for handling `POST /admin/accounts/{id}/unlock`
+/// - `get_maintenance`, for handling `GET /admin/maintenance`
+/// - `set_maintenance`, for handling `PUT /admin/maintenance`
+/// - `get_moderation_log`, for handling `GET /admin/moderation-log`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::ban_account(store.clone())
+        .or(routes::set_account_role(store.clone()))
+        .or(routes::unlock_account(store.clone()))
+        .or(routes::get_maintenance(store.clone()))
+        .or(routes::set_maintenance(store.clone()))
+        .or(routes::get_moderation_log(store.clone()))
+        .boxed()
+}
+
+/// Filter for the maintenance mode of the service.
+///
+/// Creates a filter that rejects the writes with [ServiceError::Maintenance] while the store is in
+/// maintenance mode, see [Store::set_maintenance]. The reads, i.e. the `GET`, `HEAD` and `OPTIONS`
+/// requests, and the requests under `/admin` pass, so the administrators can switch the mode off.
+///
+/// # Parameters
+/// - `store` - The [Store] whose maintenance mode is checked.
+pub fn maintenance_guard(store: &Store) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    // Only the flag is held, as the filter runs for every request
+    let maintenance = Arc::clone(&store.maintenance);
+    warp::method()
+        .and(warp::path::full())
+        .and_then(move |method: Method, path: FullPath| {
+            let is_read = matches!(method, Method::GET | Method::HEAD | Method::OPTIONS);
+            let is_admin = path.as_str() == "/admin" || path.as_str().starts_with("/admin/");
+            future::ready(match maintenance.load(Ordering::Relaxed) && !is_read && !is_admin {
+                true => Err(warp::reject::custom(ServiceError::Maintenance)),
+                false => Ok(()),
+            })
+        })
+        .untuple_one()
+}
@@ -0,0 +1,124 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::authentication;
+use crate::codec;
+use crate::filters::route;
+use crate::moderation::handlers;
+use crate::store::Store;
+use crate::types::authentication::AccountId;
+
+/// POST /admin/accounts/{id}/ban
+///
+/// Creates a filter for a route that handles banning an account.
+/// The route is only available to the administrators.
+///
+/// The filter extracts the `AccountId` from the URL path and the `Ban` from the request body and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn ban_account(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "admin" / "accounts" / {AccountId} / "ban",
+        extract: [authentication::admin(&store), codec::body()],
+        handler: handlers::ban_account,
+        trace: "ban_account request",
+    }
+}
+
+/// PUT /admin/accounts/{id}/role
+///
+/// Creates a filter for a route that handles setting the role of an account.
+/// The route is only available to the administrators.
+///
+/// The filter extracts the `AccountId` from the URL path and the `RoleUpdate` from the request body and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn set_account_role(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "admin" / "accounts" / {AccountId} / "role",
+        extract: [authentication::admin(&store), codec::body()],
+        handler: handlers::set_account_role,
+        trace: "set_account_role request",
+    }
+}
+
+/// POST /admin/accounts/{id}/unlock
+///
+/// Creates a filter for a route that handles unlocking an account locked after too many failed logins.
+/// The route is only available to the administrators.
+///
+/// The filter extracts the `AccountId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn unlock_account(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "admin" / "accounts" / {AccountId} / "unlock",
+        extract: [authentication::admin(&store)],
+        handler: handlers::unlock_account,
+        trace: "unlock_account request",
+    }
+}
+
+/// GET /admin/maintenance
+///
+/// Creates a filter for a route that handles reading the maintenance mode.
+/// The route is only available to the administrators.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_maintenance(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "admin" / "maintenance",
+        extract: [authentication::admin(&store)],
+        handler: handlers::get_maintenance,
+        trace: "get_maintenance request",
+    }
+}
+
+/// PUT /admin/maintenance
+///
+/// Creates a filter for a route that handles switching the maintenance mode on or off.
+/// The route is only available to the administrators.
+///
+/// The filter extracts the `MaintenanceMode` from the request body and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn set_maintenance(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "admin" / "maintenance",
+        extract: [authentication::admin(&store), codec::body()],
+        handler: handlers::set_maintenance,
+        trace: "set_maintenance request",
+    }
+}
+
+/// GET /admin/moderation-log?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles listing the moderation log.
+/// The route is only available to the administrators.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_moderation_log(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "admin" / "moderation-log",
+        extract: [authentication::admin(&store), warp::query()],
+        handler: handlers::get_moderation_log,
+        trace: "get_moderation_log request",
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use tracing::{debug, info, instrument, trace};
+use warp::Rejection;
+
+use crate::error::ServiceError;
+use crate::responses::{JsonResponse, MessageResponse};
+use crate::store::Store;
+use crate::types::authentication::Session;
+use crate::types::notification::{Notification, NotificationId};
+use crate::types::pagination::Pagination;
+
+/// Handler for `GET /accounts/me/notifications?offset={i64}&limit={i64}`
+///
+/// Returns the notifications of the account making the request, the most recent ones first.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/accounts/me/notifications",
+    tag = "notifications",
+    params(Pagination),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Notifications of the account", body = [Notification]),
+        (status = 400, description = "Invalid pagination parameters", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::notifications", skip(store))]
+pub async fn get_notifications(
+    store: Store,
+    params: HashMap<String, String>,
+    session: Session,
+) -> Result<JsonResponse<Vec<Notification>>, Rejection> {
+    trace!("querying notifications");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    let notifications = store.get_notifications(session.account_id, pag).await?;
+    info!("returning {} notifications", notifications.len());
+    Ok(JsonResponse::ok(notifications))
+}
+
+/// Handler for `POST /notifications/{id}/read`
+///
+/// Marks the notification with the given id as read.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `notification_id` - [NotificationId] for the notification to mark as read
+#[utoipa::path(
+    post,
+    path = "/notifications/{id}/read",
+    tag = "notifications",
+    params(("id" = NotificationId, Path, description = "Id of the notification")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Notification marked as read", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "The account has no such notification", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::notifications", skip(store))]
+pub async fn mark_notification_read(
+    store: Store,
+    notification_id: NotificationId,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    trace!("marking the notification with notification_id = {notification_id:?} as read");
+    match store.mark_notification_read(session.account_id, notification_id).await {
+        Ok(true) => {
+            info!("marked the notification with notification_id = {notification_id:?} as read");
+            Ok(MessageResponse::ok("Notification marked as read"))
+        }
+        Ok(false) => Err(ServiceError::NotificationNotFound(notification_id.into()).into()),
+        Err(error) => Err(error.into()),
+    }
+}
@@ -0,0 +1,45 @@
+//! Module for the `Notification` resource.
+//!
+//! When an answer is posted, the owner of the question and its watchers, the accounts that
+//! answered it before, are notified about it. The notifications are created by a job added to the
+//! [queue](crate::jobs) with the answer, so posting the answer does not wait for them.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the `Notification` resource.
+//! - `routes` - Contains the filters for the `Notification` resource.
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Handlers for the `Notification` resource.
+mod handlers;
+/// Routes for the `Notification` resource.
+mod routes;
+
+/// OpenAPI document for the `Notification` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(handlers::get_notifications, handlers::mark_notification_read),
+    tags((name = "notifications", description = "Notifications about the new answers to the questions of the account"))
+)]
+pub struct NotificationsApi;
+
+/// Filter for the `Notification` resource.
+///
+/// Creates a filter that handles requests for the `Notification` resource.
+///
+/// The filter combines the following filters:
+/// - `get_notifications`, for handling `GET /accounts/me/notifications`
+/// - `mark_notification_read`, for handling `POST /notifications/{id}/read`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::get_notifications(store.clone())
+        .or(routes::mark_notification_read(store.clone()))
+        .boxed()
+}
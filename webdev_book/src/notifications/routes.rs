@@ -0,0 +1,43 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::authentication;
+use crate::filters::route;
+use crate::notifications::handlers;
+use crate::store::Store;
+use crate::types::notification::NotificationId;
+
+/// GET /accounts/me/notifications?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles listing the notifications of the account making the request.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_notifications(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / "me" / "notifications",
+        extract: [warp::query(), authentication::auth(&store)],
+        handler: handlers::get_notifications,
+        trace: "get_notifications request",
+    }
+}
+
+/// POST /notifications/{id}/read
+///
+/// Creates a filter for a route that handles marking a notification as read.
+///
+/// The filter extracts the `NotificationId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn mark_notification_read(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "notifications" / {NotificationId} / "read",
+        extract: [authentication::auth(&store)],
+        handler: handlers::mark_notification_read,
+        trace: "mark_notification_read request",
+    }
+}
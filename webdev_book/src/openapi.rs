@@ -0,0 +1,84 @@
+//! Module that generates the OpenAPI document for the API and serves it.
+//!
+//! The document is assembled from the documents of the resource modules, which are generated from
+//! the `utoipa::path` annotations on the handlers.
+
+use std::sync::Arc;
+
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::openapi::OpenApi as OpenApiDocument;
+use utoipa::{Modify, OpenApi};
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::filters::with_trace;
+use crate::{answers, attachments, authentication, badges, jobs, moderation, notifications, questions, tags, webhooks};
+
+/// Swagger UI page, which renders the document served at `/api-docs/openapi.json`.
+const SWAGGER_UI: &str = include_str!("../assets/swagger-ui.html");
+
+/// Adds the security schemes used by the routes that require authentication.
+///
+/// The `token` scheme is the token returned by `POST /login`, sent in the `Authorization` header,
+/// raw or with the `Bearer` scheme. The browsers may send it in the `session` cookie instead.
+/// The tokens of the sessions remembered on login are renewed with `POST /refresh`, once expired.
+/// The `admin_token` scheme is the administrator token, sent in the `X-Admin-Token` header.
+struct TokenSecurity;
+
+impl Modify for TokenSecurity {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "token",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("Authorization"))),
+        );
+        components.add_security_scheme(
+            "admin_token",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Admin-Token"))),
+        );
+    }
+}
+
+/// Root of the OpenAPI document, the resource documents are merged into it.
+#[derive(OpenApi)]
+#[openapi(info(title = "webdev book API"), modifiers(&TokenSecurity))]
+struct ApiDoc;
+
+/// Returns the OpenAPI document for the whole API.
+pub fn openapi() -> OpenApiDocument {
+    let mut openapi = ApiDoc::openapi();
+    openapi.merge(authentication::AuthenticationApi::openapi());
+    openapi.merge(questions::QuestionsApi::openapi());
+    openapi.merge(answers::AnswersApi::openapi());
+    openapi.merge(attachments::AttachmentsApi::openapi());
+    openapi.merge(tags::TagsApi::openapi());
+    openapi.merge(webhooks::WebhooksApi::openapi());
+    openapi.merge(notifications::NotificationsApi::openapi());
+    openapi.merge(badges::BadgesApi::openapi());
+    openapi.merge(jobs::JobsApi::openapi());
+    openapi.merge(moderation::ModerationApi::openapi());
+    openapi
+}
+
+/// Filter for the API documentation.
+///
+/// The filter combines the following routes:
+/// - `GET /api-docs/openapi.json`, serving the OpenAPI document
+/// - `GET /api-docs`, serving the Swagger UI page for the document
+pub fn filter() -> BoxedFilter<(impl Reply,)> {
+    // The document is shared, as warp clones the callback for every request the route is tried on
+    let document = Arc::new(openapi());
+
+    // The path is checked first, so the requests for other paths are not rejected as not allowed
+    let openapi_json = warp::path!("api-docs" / "openapi.json")
+        .and(warp::get())
+        .map(move || warp::reply::json(document.as_ref()))
+        .with(with_trace!("openapi request"));
+
+    let swagger_ui = warp::path!("api-docs")
+        .and(warp::get())
+        .map(|| warp::reply::html(SWAGGER_UI))
+        .with(with_trace!("swagger_ui request"));
+
+    openapi_json.or(swagger_ui).boxed()
+}
@@ -1,113 +1,416 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
 
-use tracing::{debug, info, instrument, trace};
-use warp::http::StatusCode;
-use warp::reply::{json, with_status};
+use chrono::{DateTime, Utc};
+use futures_util::stream;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, instrument, trace, warn};
+use warp::sse;
 use warp::{Rejection, Reply};
+use webdev_core::events::Event;
 
-use crate::types::authentication::Session;
+use crate::answers;
+use crate::api::profanity::censor_or_defer;
+use crate::quotas::{self, Contribution};
+use crate::responses::{EncodedJsonResponse, JsonResponse, MessageResponse};
+use crate::storage::Storage;
+use crate::types::answer::AnswerOrder;
+use crate::types::authentication::{AccountId, Session};
+use crate::types::markdown::ContentFormat;
+use crate::types::moderation::{AccountRole, ModerationAction};
 use crate::{
     error::ServiceError,
     store::Store,
-    types::{pagination::Pagination, question::*},
+    types::{
+        pagination::{Page, Pagination},
+        question::*,
+        quota::QuotaExceeded,
+        sanitize, timestamp,
+        vote::{Score, Vote},
+    },
 };
 
-/// Handler for `GET /questions?start={i32}&limit={i32}`
+/// Handler for `GET /questions?offset={i64}&limit={i64}&since={timestamp}`
 ///
-/// Returns a list of questions, paginated according to the query parameters
+/// Returns a page of questions, paginated according to the query parameters, together with the
+/// total number of questions, see [Page]. The most recent questions come first.
+///
+/// With `since`, only the questions created after the given time are listed and counted, so the
+/// clients polling for new questions don't read the whole listing again. The time is either an
+/// RFC 3339 timestamp or a number of seconds since the Unix epoch.
 ///
 /// Query parameters are consumed from the request and used to paginate the results.
 /// If no query parameters are provided, the default values are used.
 ///
 /// The default values are:
-/// - `start` - 0
-/// - `limit` - no limit
+/// - `offset` - 0
+/// - `limit` - the maximum page size of the store, see [Store::max_page_size]
 ///
+/// Negative values, limits above the maximum page size and unknown parameters are rejected.
 /// Pagination logic is implemented in the [Pagination] struct.
 ///
+/// The serialized listings are cached for a few seconds by the storages with a listing cache, and
+/// the cache is invalidated on any write to the questions, see [Store::LISTING_CACHE_TTL].
+///
 /// # Parameters
-/// - `store` - [Store] instance
+/// - `store` - [Storage] instance, usually the [Store]
 /// - `params` - HashMap of query parameters
-///   - `start` - The starting index for the paginated results
+///   - `offset` - The starting index for the paginated results
 ///   - `limit` - The maximum number of results to return
+///   - `since` - The time after which the listed questions were created
+#[utoipa::path(
+    get,
+    path = "/questions",
+    tag = "questions",
+    params(
+        ("since" = Option<String>, Query, description = "Only the questions created after this time, as an RFC 3339 timestamp or seconds since the Unix epoch"),
+        Pagination
+    ),
+    responses(
+        (status = 200, description = "Page of questions, with the total number of questions", body = Page<Question>),
+        (status = 400, description = "Invalid pagination or since parameters", body = String),
+    )
+)]
 #[instrument(target = "webdev_book::questions", skip(store))]
-pub async fn get_questions(store: Store, params: HashMap<String, String>) -> Result<impl Reply, Rejection> {
+pub async fn get_questions<S: Storage>(
+    store: S,
+    mut params: HashMap<String, String>,
+) -> Result<EncodedJsonResponse, Rejection> {
     trace!("querying questions");
 
-    // Extract the pagination parameters from the query
+    // Extract the filter and the pagination parameters from the query
+    let since = match params.remove("since") {
+        Some(since) => Some(
+            timestamp::parse(&since)
+                .map_err(|error| ServiceError::ValidationError(format!("invalid since: {error}")))?,
+        ),
+        None => None,
+    };
     let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    let pag = store.paginate(pag)?;
 
-    debug!(pagination = ?pag);
+    debug!(pagination = ?pag, ?since);
+
+    // Serve the listing from the cache, if it was served recently
+    let key = listing_key(&pag, since);
+    let listing_cache = store.listing_cache();
+    let cached = match listing_cache {
+        Some(listing_cache) => listing_cache.get(&key).await,
+        None => None,
+    };
+    if let Some(body) = cached {
+        info!("returning cached questions");
+        return Ok(EncodedJsonResponse(body));
+    }
 
     // Read the questions from the store
-    match store.get_questions(pag).await {
-        Ok(questions) => {
-            debug!(questions_found = questions.len());
+    match store.get_questions_with_total(pag, since).await {
+        Ok((questions, total)) => {
+            debug!(questions_found = questions.len(), total);
             info!("returning all questions");
-            Ok(json(&questions))
+            let body: Arc<str> = serde_json::to_string(&Page::new(questions, total, pag))
+                .expect("questions are always serializable")
+                .into();
+            if let Some(listing_cache) = listing_cache {
+                listing_cache.insert(key, body.clone()).await;
+            }
+            Ok(EncodedJsonResponse(body))
         }
         Err(e) => Err(e.into()),
     }
 }
 
-/// Handler for `GET /questions/{id}`
+/// Returns the key of the listing in the listing cache of the [Store].
+fn listing_key(pag: &Pagination, since: Option<DateTime<Utc>>) -> String {
+    let Pagination { offset, limit } = pag;
+    format!("offset={offset}&limit={limit:?}&since={since:?}")
+}
+
+/// Handler for `GET /questions/feed?offset={i64}&limit={i64}`
 ///
-/// Returns the question with the given id.
+/// Returns the feed of the account making the request: the questions using the tags the account
+/// follows, the most recent ones first. The tags are followed with `PUT /accounts/me/tags/{tag}`.
 ///
 /// # Parameters
 /// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/questions/feed",
+    tag = "questions",
+    params(Pagination),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Questions using the tags followed by the account", body = [Question]),
+        (status = 400, description = "Invalid pagination parameters", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn get_feed(
+    store: Store,
+    params: HashMap<String, String>,
+    session: Session,
+) -> Result<JsonResponse<Vec<Question>>, Rejection> {
+    trace!("querying the feed");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    let questions = store.get_feed(session.account_id, pag).await?;
+    info!("returning {} questions of the feed", questions.len());
+    Ok(JsonResponse::ok(questions))
+}
+
+/// Handler for `GET /questions/{id}?include={answers}&format={markdown|html}`
+///
+/// Returns the question with the given id.
+///
+/// With `include=answers`, the answers to the question are embedded in it, in the default order of
+/// the answer listings and at most a page of the maximum size, so the clients need a single
+/// request. The question and the answers are queried concurrently.
+///
+/// With `format=html`, the question and the embedded answers have their content rendered to
+/// sanitized HTML, in the `content_html` fields.
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
 /// - `id` - [QuestionId] for the question to retrieve
+/// - `params` - HashMap of query parameters
+///   - `include` - The related resources to embed in the question
+///   - `format` - The format of the content: `markdown` or `html`
+#[utoipa::path(
+    get,
+    path = "/questions/{id}",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question"), QuestionParams),
+    responses(
+        (status = 200, description = "The question, with the requested related resources", body = QuestionDetail),
+        (status = 400, description = "Invalid question id or query parameters", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
 #[instrument(target = "webdev_book::questions", skip(store))]
-pub async fn get_question(store: Store, question_id: QuestionId) -> Result<impl Reply, Rejection> {
+pub async fn get_question<S: Storage>(
+    store: S,
+    question_id: QuestionId,
+    params: HashMap<String, String>,
+) -> Result<JsonResponse<QuestionDetail>, Rejection> {
     trace!("querying question_id = {question_id:?}");
+    let QuestionParams { include, format } =
+        QuestionParams::extract(&params).map_err(|error| ServiceError::ValidationError(error.to_string()))?;
+    debug!(?include, ?format);
 
-    let question = store.get_question(question_id).await?;
+    let (question, mut answers) = match include {
+        Some(QuestionInclude::Answers) => {
+            let first_page = Pagination { offset: 0, limit: None };
+            let (question, answers) = tokio::try_join!(
+                store.get_question(question_id),
+                store.get_answers(question_id, AnswerOrder::default(), first_page)
+            )?;
+            (question, Some(answers))
+        }
+        None => (store.get_question(question_id).await?, None),
+    };
     debug!(question_found = question.is_some());
 
-    match question {
-        Some(question) => {
-            info!("returning question with question_id = {question_id:?}");
-            Ok(json(&question))
+    let Some(mut question) = question else {
+        return Err(ServiceError::QuestionNotFound(question_id.into()).into());
+    };
+    if format == Some(ContentFormat::Html) {
+        trace!("loading the content rendered to HTML");
+        question.content_html = store.get_question_html(question_id).await?;
+        if let Some(answers) = answers.as_mut() {
+            *answers = answers::with_content_html(&store, std::mem::take(answers)).await?;
         }
-        None => Err(ServiceError::QuestionNotFound(question_id.into()).into()),
     }
+
+    info!("returning question with question_id = {question_id:?}");
+    Ok(JsonResponse::ok(QuestionDetail { question, answers }))
 }
 
-/// Handler for `POST /questions`
+/// Handler for `GET /questions/{id}/revisions?offset={i64}&limit={i64}`
 ///
-/// Creates a new question
+/// Returns the previous versions of the question with the given id, the most recent ones first.
 ///
 /// # Parameters
 /// - `store` - [Store] instance
-/// - `question` - [Question] object containing question details
+/// - `id` - [QuestionId] for the question whose revisions are listed
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/questions/{id}/revisions",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question"), Pagination),
+    responses(
+        (status = 200, description = "Paginated list of the previous versions of the question", body = [QuestionRevision]),
+        (status = 400, description = "Invalid question id or pagination parameters", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
 #[instrument(target = "webdev_book::questions", skip(store))]
-pub async fn add_question(store: Store, question: Question, session: Session) -> Result<impl Reply, Rejection> {
+pub async fn get_question_revisions(
+    store: Store,
+    question_id: QuestionId,
+    params: HashMap<String, String>,
+) -> Result<JsonResponse<Vec<QuestionRevision>>, Rejection> {
+    trace!("querying revisions for question_id = {question_id:?}");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    if store.get_question(question_id).await?.is_none() {
+        return Err(ServiceError::QuestionNotFound(question_id.into()).into());
+    }
+
+    let revisions = store.get_question_revisions(question_id, pag).await?;
+    info!(
+        "returning {} revisions for question_id = {question_id:?}",
+        revisions.len()
+    );
+    Ok(JsonResponse::ok(revisions))
+}
+
+/// Handler for `GET /questions/{id}/events`
+///
+/// Streams the changes to the question with the given id as Server-Sent Events.
+///
+/// An event is sent whenever the question is edited, or an answer to it is added, edited or deleted.
+/// The name of each event is the type of the change, and its data is the JSON encoded change.
+/// The stream ends after the question is deleted.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `id` - [QuestionId] for the question to follow
+#[utoipa::path(
+    get,
+    path = "/questions/{id}/events",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    responses(
+        (status = 200, description = "Stream of the changes to the question", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Invalid question id", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn question_events(store: Store, question_id: QuestionId) -> Result<impl Reply, Rejection> {
+    // Subscribe before checking the question, so no change made in between is missed
+    let receiver = store.events.subscribe();
+
+    trace!("checking if the question with question_id = {question_id:?} exists");
+    if store.get_question(question_id).await?.is_none() {
+        return Err(ServiceError::QuestionNotFound(question_id.into()).into());
+    }
+
+    info!("streaming events for question with question_id = {question_id:?}");
+    let events = stream::unfold(Some(receiver), move |receiver| async move {
+        let mut receiver = receiver?;
+        loop {
+            match receiver.recv().await {
+                Ok(event) if event.question_id() == Some(question_id) => {
+                    let data = serde_json::to_string(&event).expect("events are always serializable");
+                    let sse_event = sse::Event::default().event(event.name()).data(data);
+                    // Nothing more can happen to a deleted question, so the stream ends after it
+                    let receiver = match event {
+                        Event::QuestionDeleted { .. } => None,
+                        _ => Some(receiver),
+                    };
+                    return Some((Ok::<_, Infallible>(sse_event), receiver));
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => warn!("event stream lagged behind, skipped {skipped} events"),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(sse::reply(sse::keep_alive().stream(events)))
+}
+
+/// Handler for `POST /questions?force={bool}`
+///
+/// Creates a new question
+///
+/// The question is rejected with [ServiceError::SimilarQuestions] if questions with similar titles
+/// were already asked, see [Store::get_similar_questions], unless `force` is `true`.
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `params` - HashMap of query parameters
+///   - `force` - Whether the question is asked even if similar questions were already asked
+/// - `question` - [NewQuestion] object containing question details
+#[utoipa::path(
+    post,
+    path = "/questions",
+    tag = "questions",
+    params(NewQuestionParams),
+    request_body = NewQuestion,
+    security(("token" = [])),
+    responses(
+        (status = 201, description = "The created question", body = Question),
+        (status = 400, description = "Empty or too long title or content, or invalid query parameters, or invalid tags, with a FieldError body", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 409, description = "Similar questions were already asked", body = [Question]),
+        (status = 422, description = "Missing or unknown fields in the body", body = String),
+        (status = 429, description = "Daily limit of questions reached", body = QuotaExceeded),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn add_question<S: Storage>(
+    store: S,
+    params: HashMap<String, String>,
+    question: NewQuestion,
+    session: Session,
+) -> Result<JsonResponse<Question>, Rejection> {
     trace!("adding a new question");
-    let Question {
-        title, content, tags, ..
-    } = question;
+    let NewQuestionParams { force } =
+        NewQuestionParams::extract(&params).map_err(|error| ServiceError::ValidationError(error.to_string()))?;
+    quotas::check(&store, session.account_id, Contribution::Question).await?;
+    let NewQuestion { title, content, tags } = question;
+
+    trace!("normalizing title, content and tags...");
+    let title = sanitize::title(&title, store.limits())?;
+    let content = sanitize::content(&content, store.limits())?;
+    let tags = sanitize::tags(tags, store.limits())?;
 
     trace!("censoring title and content...");
-    let (title, content) = tokio::try_join!(store.bad_words_api.censor(title), store.bad_words_api.censor(content))?;
+    let ((title, title_censored), (content, content_censored)) = tokio::try_join!(
+        censor_or_defer(store.profanity_filter(), title),
+        censor_or_defer(store.profanity_filter(), content)
+    )?;
+    let censored = title_censored && content_censored;
 
     debug!("censored title: {title}");
     debug!("censored content: {content}");
 
+    if !force {
+        trace!("looking for similar questions");
+        let similar = store
+            .get_similar_questions(&title, Store::SIMILAR_QUESTIONS_LIMIT)
+            .await?;
+        if !similar.is_empty() {
+            return Err(ServiceError::SimilarQuestions(similar).into());
+        }
+    }
+
+    let censored_question = Question::builder()
+        .title(title)
+        .content(content)
+        .tags(tags)
+        .build()
+        .expect("all required fields are set");
+
     match store
-        .add_question(
-            session.account_id,
-            Question {
-                id: None,
-                title,
-                content,
-                tags,
-            },
-        )
+        .add_question(session.account_id, censored_question, censored)
         .await
     {
         Ok(question) => {
             info!("created a question with question_id = {:?}", question.id);
-            Ok(with_status(json(&question), StatusCode::CREATED))
+            Ok(JsonResponse::created(question))
         }
         Err(error) => Err(error.into()),
     }
@@ -117,74 +420,469 @@ pub async fn add_question(store: Store, question: Question, session: Session) ->
 ///
 /// Updates the question with the given id
 ///
+/// The question can be updated by its owner, or by a moderator, see [authorize_change].
+///
+/// With the `If-Match` header, the question is only updated if it is still at the version the update
+/// is based on, so the concurrent updates don't silently overwrite each other.
+///
 /// # Parameters
-/// - `store` - [Store] instance
+/// - `store` - [Storage] instance, usually the [Store]
 /// - `question_id` - [QuestionId] for the question to update
-/// - `question` - [Question] object containing updated question details
+/// - `question` - [UpdateQuestion] object containing updated question details
+/// - `session` - [Session] of the account updating the question
+/// - `expected_version` - The version of the question the update is based on, if the request carries one
+#[utoipa::path(
+    put,
+    path = "/questions/{id}",
+    tag = "questions",
+    params(
+        ("id" = QuestionId, Path, description = "Id of the question"),
+        ("If-Match" = Option<String>, Header, description = "Version of the question the update is based on, e.g. `\"3\"`"),
+    ),
+    request_body = UpdateQuestion,
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Question updated", body = String),
+        (status = 400, description = "Empty or too long title or content, or invalid tags, with a FieldError body, or malformed `If-Match`", body = String),
+        (status = 401, description = "Not the owner of the question, nor a moderator", body = String),
+        (status = 404, description = "Question not found", body = String),
+        (status = 412, description = "Question was modified since the version in `If-Match`", body = String),
+        (status = 422, description = "Missing or unknown fields in the body", body = String),
+    )
+)]
 #[instrument(target = "webdev_book::questions", skip(store))]
-pub async fn update_question(
-    store: Store,
+pub async fn update_question<S: Storage>(
+    store: S,
     question_id: QuestionId,
-    question: Question,
+    question: UpdateQuestion,
     session: Session,
-) -> Result<impl Reply, Rejection> {
-    let Session { account_id, .. } = session;
-    trace!("checking if the account is the owner of the question");
-    if !store.is_question_owner(question_id, account_id).await? {
-        return Err(ServiceError::Unauthorized.into());
-    }
+    expected_version: Option<i32>,
+) -> Result<MessageResponse, Rejection> {
+    let Session { account_id, role, .. } = session;
+    trace!("checking if the account may change the question");
+    let owner_id = authorize_change(&store, question_id, account_id, role).await?;
+    let moderator_id = overriding_moderator(account_id, ModerationAction::UpdateQuestion, question_id, owner_id);
 
     trace!("updating the question with question_id = {}", question_id.0);
-    let Question {
-        title, content, tags, ..
-    } = question;
+    let UpdateQuestion { title, content, tags } = question;
+
+    trace!("normalizing title, content and tags...");
+    let title = sanitize::title(&title, store.limits())?;
+    let content = sanitize::content(&content, store.limits())?;
+    let tags = sanitize::tags(tags, store.limits())?;
 
     trace!("censoring title and content...");
-    let (title, content) = tokio::try_join!(store.bad_words_api.censor(title), store.bad_words_api.censor(content))?;
+    let ((title, title_censored), (content, content_censored)) = tokio::try_join!(
+        censor_or_defer(store.profanity_filter(), title),
+        censor_or_defer(store.profanity_filter(), content)
+    )?;
+    let censored = title_censored && content_censored;
 
     debug!("censored title: {title}");
     debug!("censored content: {content}");
 
-    let censored_question = Question {
-        id: Some(question_id),
-        title,
-        content,
-        tags,
-    };
+    let censored_question = Question::builder()
+        .id(question_id)
+        .title(title)
+        .content(content)
+        .tags(tags)
+        .build()
+        .expect("all required fields are set");
 
     match store
-        .update_question(session.account_id, censored_question, question_id)
+        .update_question(
+            owner_id,
+            censored_question,
+            question_id,
+            expected_version,
+            censored,
+            moderator_id,
+        )
         .await
     {
         Ok(question) => {
             info!("updated question with question_id = {}", question_id.0);
             debug!(updated_question = ?question);
-            Ok(with_status("Question updated", StatusCode::OK))
+            Ok(MessageResponse::ok("Question updated"))
         }
         Err(error) => Err(error.into()),
     }
 }
 
-/// Handler for `DELETE /questions/{id}`
+/// Checks that the account may update or delete the question, and returns the owner of the question,
+/// which is `None` if the account of the owner was deleted.
 ///
-/// Deletes the question with the given id
+/// The question may be changed by its owner, and by the accounts with a role that can moderate,
+/// see [AccountRole::can_moderate], whose changes are recorded, see [overriding_moderator]. The role is the
+/// one carried by the token of the session, so the account is not read.
+async fn authorize_change<S: Storage>(
+    store: &S,
+    question_id: QuestionId,
+    account_id: AccountId,
+    role: AccountRole,
+) -> Result<Option<AccountId>, ServiceError> {
+    let owner_id = store.get_question_owner(question_id).await?;
+    if owner_id != Some(account_id) && !role.can_moderate() {
+        return Err(ServiceError::Unauthorized);
+    }
+    Ok(owner_id)
+}
+
+/// Returns the account as the moderator of the change, if it changes the question of another
+/// account, see [authorize_change]. The store records the change of the moderator in the moderation
+/// log together with the change, so no change is left unrecorded.
+fn overriding_moderator(
+    account_id: AccountId,
+    action: ModerationAction,
+    question_id: QuestionId,
+    owner_id: Option<AccountId>,
+) -> Option<AccountId> {
+    if owner_id == Some(account_id) {
+        return None;
+    }
+    warn!(
+        "the account with account_id = {} overrides the owner of the question with question_id = {}: {}",
+        account_id.0,
+        question_id.0,
+        action.as_str()
+    );
+    Some(account_id)
+}
+
+/// Checks that the account is the owner of the question, unless the request was made by an
+/// administrator, for whom there is no session.
+async fn authorize_moderation(
+    store: &Store,
+    question_id: QuestionId,
+    session: Option<Session>,
+) -> Result<(), ServiceError> {
+    match session {
+        Some(Session { account_id, .. }) if !store.is_question_owner(question_id, account_id).await? => {
+            Err(ServiceError::Unauthorized)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Handler for `POST /questions/{id}/close`
+///
+/// Closes the question with the given id, so it cannot be answered, optionally as a duplicate of
+/// another question. Closing a closed question replaces the question it duplicates.
+///
+/// The question can be closed by its owner, or by an administrator.
 ///
 /// # Parameters
 /// - `store` - [Store] instance
-/// - `question_id` - [QuestionId] for the question to delete
+/// - `question_id` - [QuestionId] for the question to close
+/// - `close` - [CloseQuestion] object containing the question this question duplicates, if any
+#[utoipa::path(
+    post,
+    path = "/questions/{id}/close",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    request_body = CloseQuestion,
+    security(("token" = []), ("admin_token" = [])),
+    responses(
+        (status = 200, description = "The closed question", body = Question),
+        (status = 400, description = "Missing or same duplicated question", body = String),
+        (status = 401, description = "Not the owner of the question, nor an administrator", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
 #[instrument(target = "webdev_book::questions", skip(store))]
-pub async fn delete_question(store: Store, question_id: QuestionId, session: Session) -> Result<impl Reply, Rejection> {
-    let Session { account_id, .. } = session;
+pub async fn close_question(
+    store: Store,
+    question_id: QuestionId,
+    close: CloseQuestion,
+    session: Option<Session>,
+) -> Result<JsonResponse<Question>, Rejection> {
+    trace!("checking if the question can be closed by the request");
+    authorize_moderation(&store, question_id, session).await?;
+
+    let status = match close.duplicate_of {
+        Some(duplicate_of) if duplicate_of == question_id => {
+            return Err(ServiceError::ValidationError("a question cannot duplicate itself".to_string()).into());
+        }
+        Some(duplicate_of) => {
+            if store.get_question(duplicate_of).await?.is_none() {
+                return Err(
+                    ServiceError::ValidationError(format!("duplicated question {} not found", duplicate_of.0)).into(),
+                );
+            }
+            QuestionStatus::DuplicateOf(duplicate_of)
+        }
+        None => QuestionStatus::Closed,
+    };
+
+    match store.set_question_status(question_id, status).await? {
+        Some(question) => {
+            info!("closed question with question_id = {}", question_id.0);
+            debug!(?status);
+            Ok(JsonResponse::ok(question))
+        }
+        None => Err(ServiceError::QuestionNotFound(question_id.into()).into()),
+    }
+}
+
+/// Handler for `POST /questions/{id}/reopen`
+///
+/// Reopens the question with the given id, so it can be answered again.
+///
+/// The question can be reopened by its owner, or by an administrator.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `question_id` - [QuestionId] for the question to reopen
+#[utoipa::path(
+    post,
+    path = "/questions/{id}/reopen",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    security(("token" = []), ("admin_token" = [])),
+    responses(
+        (status = 200, description = "The reopened question", body = Question),
+        (status = 401, description = "Not the owner of the question, nor an administrator", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn reopen_question(
+    store: Store,
+    question_id: QuestionId,
+    session: Option<Session>,
+) -> Result<JsonResponse<Question>, Rejection> {
+    trace!("checking if the question can be reopened by the request");
+    authorize_moderation(&store, question_id, session).await?;
+
+    match store.set_question_status(question_id, QuestionStatus::Open).await? {
+        Some(question) => {
+            info!("reopened question with question_id = {}", question_id.0);
+            Ok(JsonResponse::ok(question))
+        }
+        None => Err(ServiceError::QuestionNotFound(question_id.into()).into()),
+    }
+}
+
+/// Handler for `POST /questions/{id}/vote`
+///
+/// Votes on the question with the given id, up or down. Voting in the other direction changes the
+/// vote, and voting again in the same direction is a conflict, see [Store::vote_question]. The vote
+/// is retracted with `DELETE /questions/{id}/vote`.
+///
+/// The owner of the question cannot vote on it.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `question_id` - [QuestionId] for the question to vote on
+/// - `vote` - [Vote] object containing the direction of the vote
+#[utoipa::path(
+    post,
+    path = "/questions/{id}/vote",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    request_body = Vote,
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The score of the question, with the vote of the account", body = Score),
+        (status = 400, description = "Vote on the own question", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Question not found", body = String),
+        (status = 409, description = "Already voted in this direction", body = Object),
+        (status = 422, description = "Missing or unknown direction", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn vote_question(
+    store: Store,
+    question_id: QuestionId,
+    vote: Vote,
+    session: Session,
+) -> Result<JsonResponse<Score>, Rejection> {
     trace!("checking if the account is the owner of the question");
-    if !store.is_question_owner(question_id, account_id).await? {
-        return Err(ServiceError::Unauthorized.into());
+    if store.is_question_owner(question_id, session.account_id).await? {
+        return Err(ServiceError::ValidationError("cannot vote on the own question".to_string()).into());
+    }
+
+    let score = store
+        .vote_question(session.account_id, question_id, vote.direction)
+        .await?;
+    info!("voted on question with question_id = {}", question_id.0);
+    debug!(?score);
+    Ok(JsonResponse::ok(score))
+}
+
+/// Handler for `DELETE /questions/{id}/vote`
+///
+/// Retracts the vote of the account on the question with the given id. Retracting is idempotent, the
+/// score is returned whether the account has voted or not, see [Store::retract_question_vote].
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `question_id` - [QuestionId] for the question to retract the vote from
+#[utoipa::path(
+    delete,
+    path = "/questions/{id}/vote",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "The score of the question, without a vote of the account", body = Score),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn retract_question_vote(
+    store: Store,
+    question_id: QuestionId,
+    session: Session,
+) -> Result<JsonResponse<Score>, Rejection> {
+    let score = store.retract_question_vote(session.account_id, question_id).await?;
+    info!("retracted the vote on question with question_id = {}", question_id.0);
+    debug!(?score);
+    Ok(JsonResponse::ok(score))
+}
+
+/// Handler for `PUT /questions/{id}/bookmark`
+///
+/// Bookmarks the question with the given id for the account making the request, so it is listed
+/// at `GET /accounts/me/bookmarks`. Bookmarking a question again does nothing.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `question_id` - [QuestionId] for the question to bookmark
+#[utoipa::path(
+    put,
+    path = "/questions/{id}/bookmark",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Question bookmarked", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn bookmark_question(
+    store: Store,
+    question_id: QuestionId,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    if !store.add_bookmark(session.account_id, question_id).await? {
+        return Err(ServiceError::QuestionNotFound(question_id.into()).into());
     }
+    info!("bookmarked question with question_id = {}", question_id.0);
+    Ok(MessageResponse::ok("Question bookmarked"))
+}
+
+/// Handler for `DELETE /questions/{id}/bookmark`
+///
+/// Removes the bookmark of the question with the given id for the account making the request.
+/// Removing a bookmark the account does not have does nothing.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `question_id` - [QuestionId] for the question to remove the bookmark of
+#[utoipa::path(
+    delete,
+    path = "/questions/{id}/bookmark",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Bookmark removed", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn remove_bookmark(
+    store: Store,
+    question_id: QuestionId,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    if !store.remove_bookmark(session.account_id, question_id).await? {
+        return Err(ServiceError::QuestionNotFound(question_id.into()).into());
+    }
+    info!("removed the bookmark of question with question_id = {}", question_id.0);
+    Ok(MessageResponse::ok("Bookmark removed"))
+}
+
+/// Handler for `GET /accounts/me/bookmarks?offset={i64}&limit={i64}`
+///
+/// Returns the questions bookmarked by the account making the request, the most recently
+/// bookmarked ones first.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/accounts/me/bookmarks",
+    tag = "questions",
+    params(Pagination),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Questions bookmarked by the account", body = [Question]),
+        (status = 400, description = "Invalid pagination parameters", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn get_bookmarks(
+    store: Store,
+    params: HashMap<String, String>,
+    session: Session,
+) -> Result<JsonResponse<Vec<Question>>, Rejection> {
+    trace!("querying bookmarks");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    let questions = store.get_bookmarks(session.account_id, pag).await?;
+    info!("returning {} bookmarked questions", questions.len());
+    Ok(JsonResponse::ok(questions))
+}
+
+/// Handler for `DELETE /questions/{id}`
+///
+/// Deletes the question with the given id
+///
+/// The question can be deleted by its owner, or by a moderator, see [authorize_change].
+///
+/// # Parameters
+/// - `store` - [Storage] instance, usually the [Store]
+/// - `question_id` - [QuestionId] for the question to delete
+#[utoipa::path(
+    delete,
+    path = "/questions/{id}",
+    tag = "questions",
+    params(("id" = QuestionId, Path, description = "Id of the question")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Question deleted", body = String),
+        (status = 401, description = "Not the owner of the question, nor a moderator", body = String),
+        (status = 404, description = "Question not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::questions", skip(store))]
+pub async fn delete_question<S: Storage>(
+    store: S,
+    question_id: QuestionId,
+    session: Session,
+) -> Result<MessageResponse, Rejection> {
+    let Session { account_id, role, .. } = session;
+    trace!("checking if the account may change the question");
+    let owner_id = authorize_change(&store, question_id, account_id, role).await?;
+    let moderator_id = overriding_moderator(account_id, ModerationAction::DeleteQuestion, question_id, owner_id);
 
     trace!("deleting the question with question_id = {}", question_id.0);
-    match store.delete_question(session.account_id, question_id).await {
+    match store.delete_question(owner_id, question_id, moderator_id).await {
         Ok(true) => {
             info!("deleted question with question_id = {}", question_id.0);
-            Ok(with_status("Question deleted", StatusCode::OK))
+            Ok(MessageResponse::ok("Question deleted"))
         }
         Ok(false) => Err(ServiceError::QuestionNotFound(question_id.into()).into()),
         Err(error) => Err(error.into()),
@@ -3,9 +3,11 @@
 //! This module contains the following submodules:
 //! - `handlers` - Contains the request handlers for the `Questions` resource.
 //! - `routes` - Contains the filters for the `Questions` resource.
+use utoipa::OpenApi;
 use warp::filters::BoxedFilter;
 use warp::{Filter, Reply};
 
+use crate::storage::Storage;
 use crate::store::Store;
 
 /// Handlers for the `Questions` resource.
@@ -13,20 +15,71 @@ mod handlers;
 /// Routes for the `Questions` resource.
 mod routes;
 
+/// OpenAPI document for the `Questions` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(handlers::get_questions, handlers::get_feed, handlers::get_question, handlers::get_question_revisions, handlers::question_events, handlers::add_question, handlers::update_question, handlers::close_question, handlers::reopen_question, handlers::vote_question, handlers::retract_question_vote, handlers::bookmark_question, handlers::remove_bookmark, handlers::get_bookmarks, handlers::delete_question),
+    tags((name = "questions", description = "Questions asked by the users"))
+)]
+pub struct QuestionsApi;
+
 /// Filter for `Questions` module
 ///
 /// Creates a filter that handles requests for the `Questions` resource.
 ///
 /// The filter combines the following filters:
 /// - `get_questions` for handling `GET /questions`
+/// - `get_feed` for handling `GET /questions/feed`
 /// - `get_question` for handling `GET /questions/{id}`
+/// - `get_question_revisions` for handling `GET /questions/{id}/revisions`
+/// - `question_events` for handling `GET /questions/{id}/events`
 /// - `add_question` for handling `POST /questions`
 /// - `update_question` for handling `PUT /questions/{id}`
+/// - `close_question` for handling `POST /questions/{id}/close`
+/// - `reopen_question` for handling `POST /questions/{id}/reopen`
+/// - `vote_question` for handling `POST /questions/{id}/vote`
+/// - `retract_question_vote` for handling `DELETE /questions/{id}/vote`
+/// - `bookmark_question` for handling `PUT /questions/{id}/bookmark`
+/// - `remove_bookmark` for handling `DELETE /questions/{id}/bookmark`
+/// - `get_bookmarks` for handling `GET /accounts/me/bookmarks`
 /// - `delete_question` for handling `DELETE /questions/{id}`
 ///
 /// # Parameters
 /// - `store` - The [Store] to use for handling requests.
 pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::get_questions(store.clone())
+        .or(routes::get_feed(store.clone()))
+        .or(routes::get_question(store.clone()))
+        .or(routes::get_question_revisions(store.clone()))
+        .or(routes::question_events(store.clone()))
+        .or(routes::add_question(store.clone()))
+        .or(routes::update_question(store.clone()))
+        .or(routes::close_question(store.clone()))
+        .or(routes::reopen_question(store.clone()))
+        .or(routes::vote_question(store.clone()))
+        .or(routes::retract_question_vote(store.clone()))
+        .or(routes::bookmark_question(store.clone()))
+        .or(routes::remove_bookmark(store.clone()))
+        .or(routes::get_bookmarks(store.clone()))
+        .or(routes::delete_question(store.clone()))
+        .boxed()
+}
+
+/// Filter for the `Questions` resource, served from any [Storage].
+///
+/// Combines the routes of [filter] that only depend on the [Storage], so they can be served from
+/// another backend than the [Store], e.g. the in-memory one of the tests:
+/// - `get_questions` for handling `GET /questions`
+/// - `get_question` for handling `GET /questions/{id}`
+/// - `add_question` for handling `POST /questions`
+/// - `update_question` for handling `PUT /questions/{id}`
+/// - `delete_question` for handling `DELETE /questions/{id}`
+///
+/// # Parameters
+/// - `store` - The [Storage] to use for handling requests.
+pub fn storage_filter<S: Storage>(store: &S) -> BoxedFilter<(impl Reply,)> {
     routes::get_questions(store.clone())
         .or(routes::get_question(store.clone()))
         .or(routes::add_question(store.clone()))
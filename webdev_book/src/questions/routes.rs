@@ -1,82 +1,234 @@
 use std::collections::HashMap;
 
-use warp::{filters::BoxedFilter, Filter, Reply};
+use warp::{Filter, Rejection, Reply};
 
-use crate::filters::{store_filter, with_trace};
+use crate::codec;
+use crate::filters::{if_match, route};
+use crate::storage::Storage;
 use crate::store::Store;
 use crate::types::question::QuestionId;
 use crate::{authentication, questions::*};
 
-/// GET /questions?start={i32}&limit={i32}
+/// GET /questions?offset={i64}&limit={i64}&since={timestamp}
 ///
 /// Creates a filter for a route that handles fetching a list of questions.
 ///
 /// The filter parses the query parameters, and passes them to the handler.
 ///
 /// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_questions<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "questions",
+        extract: [warp::query::<HashMap<String, String>>()],
+        handler: handlers::get_questions,
+        trace: "get_questions request",
+    }
+}
+
+/// GET /questions/feed?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles fetching the feed of the account making the request.
+///
+/// # Parameters
 /// - `store` - [Store] object available to the route handler
-pub fn get_questions(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::get())
-        .and(warp::path!("questions"))
-        .and(warp::query::<HashMap<String, String>>())
-        .and_then(handlers::get_questions)
-        .with(with_trace!("get_questions request"))
-        .boxed()
+pub fn get_feed(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "questions" / "feed",
+        extract: [warp::query::<HashMap<String, String>>(), authentication::auth(&store)],
+        handler: handlers::get_feed,
+        trace: "get_feed request",
+    }
 }
 
-/// GET /questions/{id}
+/// GET /questions/{id}?include={answers}&format={markdown|html}
 ///
 /// Creates a filter for a route that handles fetching a single question.
+/// The filter extracts the `QuestionId` from the URL path, parses the query parameters, and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn get_question<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "questions" / {QuestionId},
+        extract: [warp::query::<HashMap<String, String>>()],
+        handler: handlers::get_question,
+        trace: "get_question request",
+    }
+}
+
+/// GET /questions/{id}/revisions?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles fetching the previous versions of a question.
+///
+/// The filter extracts the `QuestionId` from the URL path, parses the query parameters, and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_question_revisions(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "questions" / {QuestionId} / "revisions",
+        extract: [warp::query::<HashMap<String, String>>()],
+        handler: handlers::get_question_revisions,
+        trace: "get_question_revisions request",
+    }
+}
+
+/// GET /questions/{id}/events
+///
+/// Creates a filter for a route that streams the changes to a single question as Server-Sent Events.
 /// The filter extracts the `QuestionId` from the URL path and passes it to the handler.
 ///
 /// # Parameters
 /// - `store` - [Store] object available to the route handler
-pub fn get_question(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::get())
-        .and(warp::path!("questions" / QuestionId))
-        .and_then(handlers::get_question)
-        .with(with_trace!("get_question request"))
-        .boxed()
+pub fn question_events(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "questions" / {QuestionId} / "events",
+        handler: handlers::question_events,
+        trace: "question_events request",
+    }
 }
 
-/// POST /questions
+/// POST /questions?force={bool}
 ///
 /// Creates a filter for a route that handles creating a new question.
 ///
-/// The filter extracts the `Question` from the request body as JSON and passes it to the handler.
+/// The filter parses the query parameters, extracts the `Question` from the request body as JSON and passes them to the handler.
 ///
 /// # Parameters
-/// - `store` - [Store] object available to the route handler
-pub fn add_question(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::post())
-        .and(warp::path!("questions"))
-        .and(warp::body::json())
-        .and(authentication::auth())
-        .and_then(handlers::add_question)
-        .with(with_trace!("add_question request"))
-        .boxed()
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn add_question<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions",
+        extract: [warp::query::<HashMap<String, String>>(), codec::body(), authentication::auth(&store)],
+        handler: handlers::add_question,
+        trace: "add_question request",
+    }
 }
 
 /// PUT /questions/{id}
 ///
 /// Creates a filter for a route that handles updating a question.
 ///
-/// The filter extracts the `QuestionId` from the URL path and the `Question` from the request body as JSON and passes them to the handler.
+/// The filter extracts the `QuestionId` from the URL path, the `Question` from the request body as JSON
+/// and the version the update is based on from the `If-Match` header, see [if_match], and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn update_question<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "questions" / {QuestionId},
+        extract: [codec::body(), authentication::auth(&store), if_match()],
+        handler: handlers::update_question,
+        trace: "update_questions request",
+    }
+}
+
+/// POST /questions/{id}/vote
+///
+/// Creates a filter for a route that handles voting on a question.
+///
+/// The filter extracts the `QuestionId` from the URL path and the `Vote` from the request body and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn vote_question(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions" / {QuestionId} / "vote",
+        extract: [codec::body(), authentication::auth(&store)],
+        handler: handlers::vote_question,
+        trace: "vote_question request",
+    }
+}
+
+/// DELETE /questions/{id}/vote
+///
+/// Creates a filter for a route that handles retracting the vote on a question.
+///
+/// The filter extracts the `QuestionId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn retract_question_vote(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "questions" / {QuestionId} / "vote",
+        extract: [authentication::auth(&store)],
+        handler: handlers::retract_question_vote,
+        trace: "retract_question_vote request",
+    }
+}
+
+/// PUT /questions/{id}/bookmark
+///
+/// Creates a filter for a route that handles bookmarking a question.
+///
+/// The filter extracts the `QuestionId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn bookmark_question(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "questions" / {QuestionId} / "bookmark",
+        extract: [authentication::auth(&store)],
+        handler: handlers::bookmark_question,
+        trace: "bookmark_question request",
+    }
+}
+
+/// DELETE /questions/{id}/bookmark
+///
+/// Creates a filter for a route that handles removing the bookmark of a question.
+///
+/// The filter extracts the `QuestionId` from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn remove_bookmark(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "questions" / {QuestionId} / "bookmark",
+        extract: [authentication::auth(&store)],
+        handler: handlers::remove_bookmark,
+        trace: "remove_bookmark request",
+    }
+}
+
+/// GET /accounts/me/bookmarks?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles listing the questions bookmarked by the account making the request.
 ///
 /// # Parameters
 /// - `store` - [Store] object available to the route handler
-pub fn update_question(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::put())
-        .and(warp::path!("questions" / QuestionId))
-        .and(warp::body::json())
-        .and(authentication::auth())
-        .and_then(handlers::update_question)
-        .with(with_trace!("update_questions request"))
-        .boxed()
+pub fn get_bookmarks(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "accounts" / "me" / "bookmarks",
+        extract: [warp::query::<HashMap<String, String>>(), authentication::auth(&store)],
+        handler: handlers::get_bookmarks,
+        trace: "get_bookmarks request",
+    }
 }
 
 /// DELETE /questions/{id}
@@ -86,13 +238,54 @@ pub fn update_question(store: Store) -> BoxedFilter<(impl Reply,)> {
 /// The filter extracts the `QuestionId` from the URL path and passes it to the handler.
 ///
 /// # Parameters
+/// - `store` - [Storage] available to the route handler, usually the [Store]
+pub fn delete_question<S: Storage>(store: S) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "questions" / {QuestionId},
+        extract: [authentication::auth(&store)],
+        handler: handlers::delete_question,
+        trace: "delete_question request",
+    }
+}
+
+/// POST /questions/{id}/close
+///
+/// Creates a filter for a route that handles closing a question.
+/// The route is available to the owner of the question, and to the administrators.
+///
+/// The filter extracts the `QuestionId` from the URL path and the `CloseQuestion` from the request body and passes them to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn close_question(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions" / {QuestionId} / "close",
+        extract: [codec::body(), authentication::auth_or_admin(&store)],
+        handler: handlers::close_question,
+        trace: "close_question request",
+    }
+}
+
+/// POST /questions/{id}/reopen
+///
+/// Creates a filter for a route that handles reopening a closed question.
+/// The route is available to the owner of the question, and to the administrators.
+///
+/// The filter extracts the `QuestionId` from the URL path and passes it to the handler.
+///
+/// # Parameters
 /// - `store` - [Store] object available to the route handler
-pub fn delete_question(store: Store) -> BoxedFilter<(impl Reply,)> {
-    store_filter(store)
-        .and(warp::delete())
-        .and(warp::path!("questions" / QuestionId))
-        .and(authentication::auth())
-        .and_then(handlers::delete_question)
-        .with(with_trace!("delete_question request"))
-        .boxed()
+pub fn reopen_question(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "questions" / {QuestionId} / "reopen",
+        extract: [authentication::auth_or_admin(&store)],
+        handler: handlers::reopen_question,
+        trace: "reopen_question request",
+    }
 }
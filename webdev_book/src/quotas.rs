@@ -0,0 +1,53 @@
+//! Module enforcing the daily limits on the contributions of the accounts.
+//!
+//! The limits are set in the configuration, and kept in the [Quotas](crate::types::quota::Quotas) of the store. They are
+//! checked by the handlers before a contribution is censored, and the contributions over the limit
+//! are rejected with [ServiceError::QuotaExceeded], whose response states the limit, the usage
+//! and the time the usage is reset. The usage counts the contributions made since the start of the
+//! day, including the deleted ones, so deleting a contribution does not allow another one.
+//!
+//! The check of the handlers only rejects early. The limit is strict: the store checks it again in
+//! the transaction adding the contribution, with the account locked, so concurrent contributions of
+//! an account cannot go over it.
+
+use tracing::{debug, instrument};
+
+use crate::error::ServiceError;
+use crate::storage::Storage;
+use crate::types::authentication::AccountId;
+use crate::types::quota::start_of_day;
+
+pub use crate::types::quota::Contribution;
+
+/// Checks that the account can make another contribution today.
+///
+/// # Parameters
+/// - `store` - The [Storage] whose quotas, clock and contributions are used.
+/// - `account_id` - The account making the contribution.
+/// - `contribution` - The kind of the contribution.
+///
+/// # Returns
+/// - [ServiceError::QuotaExceeded] if the account reached the limit for today.
+#[instrument(target = "webdev_book::quotas", skip(store))]
+pub async fn check<S: Storage>(
+    store: &S,
+    account_id: AccountId,
+    contribution: Contribution,
+) -> Result<(), ServiceError> {
+    let quotas = store.quotas();
+    let Some(limit) = quotas.limit(contribution) else {
+        return Ok(());
+    };
+
+    let now = store.clock().now();
+    let today = start_of_day(now);
+    let usage = match contribution {
+        Contribution::Question => store.count_questions_since(account_id, today).await?,
+        Contribution::Answer => store.count_answers_since(account_id, today).await?,
+    };
+    debug!(usage, limit);
+
+    quotas
+        .check(contribution, usage, now)
+        .map_err(ServiceError::QuotaExceeded)
+}
@@ -0,0 +1,199 @@
+//! Module that implements the typed responses returned by the handlers.
+//!
+//! The handlers return these types instead of the replies built with `warp::reply`, so their
+//! output can be inspected before it is turned into a response, and the wire format of all the
+//! responses is defined in one place:
+//! - [JsonResponse], a value encoded in the format negotiated by [codec](crate::codec), JSON by default
+//! - [EncodedJsonResponse], a value already encoded as JSON, e.g. a cached listing, which
+//!   [codec](crate::codec) re-encodes
+//! - [MessageResponse], a plain text message confirming an operation
+//! - [RedirectResponse], a redirect to another location, e.g. to an OAuth provider
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use warp::http::header::{HeaderValue, CONTENT_TYPE, LOCATION, SET_COOKIE};
+use warp::http::StatusCode;
+use warp::reply::Response;
+use warp::Reply;
+
+use crate::codec;
+
+/// Response with a value encoded in the [response_format](crate::codec::response_format), JSON
+/// unless another format was negotiated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonResponse<T> {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The value sent in the body of the response.
+    pub body: T,
+    /// The path of the resource, sent in the `Location` header, if set.
+    pub location: Option<String>,
+    /// The cookies set on the client, each sent in a `Set-Cookie` header.
+    pub cookies: Vec<String>,
+}
+
+impl<T> JsonResponse<T> {
+    /// Creates a `200 OK` response with the value.
+    pub fn ok(body: T) -> Self {
+        Self {
+            status: StatusCode::OK,
+            body,
+            location: None,
+            cookies: Vec::new(),
+        }
+    }
+
+    /// Creates a `201 Created` response with the created resource.
+    pub fn created(body: T) -> Self {
+        Self {
+            status: StatusCode::CREATED,
+            body,
+            location: None,
+            cookies: Vec::new(),
+        }
+    }
+
+    /// Sets the path of the resource, sent in the `Location` header.
+    pub fn with_location(self, location: impl Into<String>) -> Self {
+        Self {
+            location: Some(location.into()),
+            ..self
+        }
+    }
+
+    /// Adds a cookie, sent in its own `Set-Cookie` header, e.g. `name=value; HttpOnly`.
+    pub fn with_cookie(mut self, cookie: impl Into<String>) -> Self {
+        self.cookies.push(cookie.into());
+        self
+    }
+}
+
+impl<T: Serialize + Send> Reply for JsonResponse<T> {
+    fn into_response(self) -> Response {
+        let format = codec::response_format();
+        let mut response = warp::reply::with_status(format.encode(&self.body), self.status).into_response();
+        let content_type = HeaderValue::from_static(format.media_type());
+        response.headers_mut().insert(CONTENT_TYPE, content_type);
+        if let Some(location) = self.location {
+            response = warp::reply::with_header(response, LOCATION, location).into_response();
+        }
+        set_cookies(response, self.cookies.iter().map(String::as_str))
+    }
+}
+
+/// Response with a value that is already encoded as JSON, sent with `200 OK`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedJsonResponse(pub Arc<str>);
+
+impl Reply for EncodedJsonResponse {
+    fn into_response(self) -> Response {
+        warp::reply::with_header(self.0.to_string(), CONTENT_TYPE, "application/json").into_response()
+    }
+}
+
+/// Response with a plain text message confirming an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageResponse {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The message sent in the body of the response.
+    pub message: &'static str,
+    /// The cookies set on the client, each sent in a `Set-Cookie` header.
+    pub cookies: &'static [&'static str],
+}
+
+impl MessageResponse {
+    /// Creates a `200 OK` response with the message.
+    pub fn ok(message: &'static str) -> Self {
+        Self {
+            status: StatusCode::OK,
+            message,
+            cookies: &[],
+        }
+    }
+
+    /// Creates a `201 Created` response with the message.
+    pub fn created(message: &'static str) -> Self {
+        Self {
+            status: StatusCode::CREATED,
+            message,
+            cookies: &[],
+        }
+    }
+
+    /// Creates a `204 No Content` response, without a body.
+    pub fn no_content() -> Self {
+        Self {
+            status: StatusCode::NO_CONTENT,
+            message: "",
+            cookies: &[],
+        }
+    }
+
+    /// Sets the cookies, each sent in a `Set-Cookie` header, e.g. ones clearing the cookies of the client.
+    pub fn with_cookies(self, cookies: &'static [&'static str]) -> Self {
+        Self { cookies, ..self }
+    }
+}
+
+impl Reply for MessageResponse {
+    fn into_response(self) -> Response {
+        let response = warp::reply::with_status(self.message, self.status).into_response();
+        set_cookies(response, self.cookies.iter().copied())
+    }
+}
+
+/// Response redirecting the client to another location, with `303 See Other`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectResponse {
+    /// The location the client is redirected to, sent in the `Location` header.
+    pub location: String,
+    /// The cookie set on the client, sent in the `Set-Cookie` header, if any.
+    pub cookie: Option<String>,
+}
+
+impl RedirectResponse {
+    /// Creates a `303 See Other` response redirecting to the location.
+    pub fn see_other(location: impl Into<String>) -> Self {
+        Self {
+            location: location.into(),
+            cookie: None,
+        }
+    }
+
+    /// Sets the cookie, sent in the `Set-Cookie` header, e.g. `name=value; HttpOnly`.
+    pub fn with_cookie(self, cookie: impl Into<String>) -> Self {
+        Self {
+            cookie: Some(cookie.into()),
+            ..self
+        }
+    }
+}
+
+impl Reply for RedirectResponse {
+    fn into_response(self) -> Response {
+        let response = warp::reply::with_status(
+            warp::reply::with_header(warp::reply(), LOCATION, self.location),
+            StatusCode::SEE_OTHER,
+        );
+        match self.cookie {
+            Some(cookie) => warp::reply::with_header(response, SET_COOKIE, cookie).into_response(),
+            None => response.into_response(),
+        }
+    }
+}
+
+/// Adds the cookies to the response, each in its own `Set-Cookie` header.
+///
+/// The responses with a cookie that is not a valid header value are replaced with a
+/// `500 Internal Server Error`, like the ones built with `warp::reply::with_header`.
+fn set_cookies<'a>(mut response: Response, cookies: impl IntoIterator<Item = &'a str>) -> Response {
+    for cookie in cookies {
+        match HeaderValue::from_str(cookie) {
+            Ok(cookie) => response.headers_mut().append(SET_COOKIE, cookie),
+            Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        };
+    }
+    response
+}
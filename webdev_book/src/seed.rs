@@ -0,0 +1,184 @@
+//! Module that fills the database with a generated dataset, for the demos, the screenshots and the
+//! load tests.
+//!
+//! The dataset is generated from a fixed seed, with a random number generator whose output does
+//! not depend on the platform, so the same profile always produces the same accounts, questions
+//! and answers, inserted in the same order. Seeded into an empty database, they also get the same ids.
+//!
+//! ```text
+//! webdev_book seed --profile demo
+//! ```
+
+use std::str::FromStr;
+
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use tracing::{info, instrument};
+
+use crate::error::ServiceError;
+use crate::store::Store;
+use crate::types::authentication::Account;
+use crate::types::question::Question;
+
+/// Seed of the random number generator, shared by all the profiles.
+const SEED: u64 = 0x5EED_DA7A;
+
+/// Password of all the seeded accounts.
+pub const PASSWORD: &str = "demo password";
+
+const TOPICS: [&str; 10] = [
+    "async closures",
+    "lifetimes",
+    "trait objects",
+    "error handling",
+    "database migrations",
+    "connection pools",
+    "request routing",
+    "websockets",
+    "serialization",
+    "integration tests",
+];
+const TAGS: [&str; 8] = ["rust", "warp", "tokio", "sqlx", "postgres", "serde", "testing", "web"];
+const QUESTION_TEMPLATES: [&str; 5] = [
+    "How do I use {} with {}?",
+    "Why does {} not work with {}?",
+    "What is the idiomatic way to combine {} and {}?",
+    "Is there a simpler alternative to {} for {}?",
+    "How to debug {} in a {} project?",
+];
+const ANSWER_OPENINGS: [&str; 5] = [
+    "I ran into the same problem last week.",
+    "Short answer: it depends on your setup.",
+    "The documentation covers this, but it is easy to miss.",
+    "This is a common source of confusion.",
+    "You are almost there.",
+];
+const ANSWER_ADVICE: [&str; 5] = [
+    "Try moving the shared state behind an Arc.",
+    "Check the versions of the crates first, they have to match.",
+    "Split the work into smaller functions and test them one by one.",
+    "Enable the debug logs, the error is usually printed there.",
+    "A minimal example usually shows where the problem is.",
+];
+
+/// Profile of the generated dataset, selecting its size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// A small dataset, large enough to fill a few pages.
+    Demo,
+}
+
+impl Profile {
+    /// Returns the number of accounts, the number of questions, and the maximum number of answers
+    /// per question, for the profile.
+    fn size(self) -> (usize, usize, usize) {
+        match self {
+            Profile::Demo => (20, 60, 5),
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(profile: &str) -> Result<Self, Self::Err> {
+        match profile {
+            "demo" => Ok(Profile::Demo),
+            _ => Err(format!("unknown profile {profile:?}, expected \"demo\"")),
+        }
+    }
+}
+
+/// Numbers of the resources inserted by [seed].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub accounts: usize,
+    pub questions: usize,
+    pub answers: usize,
+}
+
+/// Returns the email of the n-th seeded account.
+pub fn email(n: usize) -> String {
+    format!("demo{n}@example.com")
+}
+
+/// Picks one of the values.
+fn pick<'a>(rng: &mut ChaCha8Rng, values: &[&'a str]) -> &'a str {
+    values.choose(rng).expect("the value lists are not empty")
+}
+
+/// Fills the database with the dataset of the profile.
+///
+/// The texts are inserted as they are, without the profanity filter, so seeding needs neither the
+/// network nor an API key. The accounts all have the password [PASSWORD].
+///
+/// # Errors
+/// - [ServiceError::Conflict] if the database was already seeded
+/// - Any error returned by the [Store] while inserting the dataset
+#[instrument(target = "webdev_book::seed", skip(store))]
+pub async fn seed(store: &Store, profile: Profile) -> Result<Summary, ServiceError> {
+    if store.get_account(&email(1)).await.is_ok() {
+        return Err(ServiceError::Conflict("the database is already seeded".to_string()));
+    }
+
+    let (accounts, questions, max_answers) = profile.size();
+    let mut rng = ChaCha8Rng::seed_from_u64(SEED);
+    let mut summary = Summary::default();
+
+    let mut account_ids = Vec::with_capacity(accounts);
+    for n in 1..=accounts {
+        let salt = rng.gen::<[u8; 32]>();
+        let password = argon2::hash_encoded(PASSWORD.as_bytes(), &salt, &argon2::Config::default())?;
+        let account = Account::builder()
+            .email(email(n))
+            .password(password)
+            .build()
+            .expect("all required fields are set");
+        let account = store.add_account(account).await?;
+        account_ids.push(account.id);
+        summary.accounts += 1;
+    }
+    info!("seeded {} accounts", summary.accounts);
+
+    for _ in 0..questions {
+        let (first, second) = (pick(&mut rng, &TOPICS), pick(&mut rng, &TOPICS));
+        let title = pick(&mut rng, &QUESTION_TEMPLATES)
+            .replacen("{}", first, 1)
+            .replacen("{}", second, 1);
+        let content = format!("I am working on {first} and got stuck on {second}. {title}");
+        let tag_count = rng.gen_range(1..=3);
+        let tags = TAGS
+            .choose_multiple(&mut rng, tag_count)
+            .map(|tag| tag.to_string())
+            .collect::<Vec<_>>();
+        let author = *account_ids.choose(&mut rng).expect("accounts are seeded first");
+
+        let question = Question::builder()
+            .title(title)
+            .content(content)
+            .tags(tags)
+            .build()
+            .expect("all required fields are set");
+        let question_id = store
+            .add_question(author, question, true)
+            .await?
+            .id
+            .expect("stored questions have an id");
+        summary.questions += 1;
+
+        for _ in 0..rng.gen_range(0..=max_answers) {
+            let content = format!(
+                "{} {}",
+                pick(&mut rng, &ANSWER_OPENINGS),
+                pick(&mut rng, &ANSWER_ADVICE)
+            );
+            let author = *account_ids.choose(&mut rng).expect("accounts are seeded first");
+            store.add_answer(author, question_id, content, true).await?;
+            summary.answers += 1;
+        }
+    }
+    info!("seeded {} questions and {} answers", summary.questions, summary.answers);
+
+    Ok(summary)
+}
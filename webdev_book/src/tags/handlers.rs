@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use percent_encoding::percent_decode_str;
+use tracing::{debug, info, instrument, trace};
+use warp::Rejection;
+
+use crate::error::ServiceError;
+use crate::responses::{JsonResponse, MessageResponse};
+use crate::store::Store;
+use crate::types::authentication::Session;
+use crate::types::pagination::Pagination;
+use crate::types::sanitize;
+use crate::types::tag::{PopularTagsParams, Tag};
+
+/// Handler for `GET /tags?offset={i64}&limit={i64}`
+///
+/// Returns the tags, with the number of the questions using them, the most used ones first.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `offset` - The starting index for the paginated results
+///   - `limit` - The maximum number of results to return
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "tags",
+    params(Pagination),
+    responses(
+        (status = 200, description = "Tags, with the number of the questions using them", body = [Tag]),
+        (status = 400, description = "Invalid pagination parameters", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::tags", skip(store))]
+pub async fn get_tags(store: Store, params: HashMap<String, String>) -> Result<JsonResponse<Vec<Tag>>, Rejection> {
+    trace!("querying tags");
+    let pag = Pagination::extract(&params).map_err(ServiceError::PaginationError)?;
+    debug!(pagination = ?pag);
+
+    let tags = store.get_tags(pag).await?;
+    info!("returning {} tags", tags.len());
+    Ok(JsonResponse::ok(tags))
+}
+
+/// Handler for `GET /tags/popular?window={window}`
+///
+/// Returns the tags used by the most questions asked within the recent window, e.g. `7d`, with the
+/// number of those questions, for rendering a tag cloud, see [Store::get_popular_tags].
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `params` - HashMap of query parameters
+///   - `window` - The recent window the questions are counted over, `7d` by default
+#[utoipa::path(
+    get,
+    path = "/tags/popular",
+    tag = "tags",
+    params(PopularTagsParams),
+    responses(
+        (status = 200, description = "Popular tags, with the number of the recent questions using them", body = [Tag]),
+        (status = 400, description = "Invalid window", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::tags", skip(store))]
+pub async fn get_popular_tags(
+    store: Store,
+    params: HashMap<String, String>,
+) -> Result<JsonResponse<Vec<Tag>>, Rejection> {
+    let PopularTagsParams { window } =
+        PopularTagsParams::extract(&params).map_err(|error| ServiceError::ValidationError(error.to_string()))?;
+    debug!(?window);
+
+    let tags = store.get_popular_tags(window).await?;
+    info!("returning {} popular tags", tags.len());
+    Ok(JsonResponse::ok(tags))
+}
+
+/// Handler for `PUT /accounts/me/tags/{tag}`
+///
+/// Makes the account making the request follow the tag, so the questions using it are listed in
+/// the feed of the account. Following a tag again does nothing.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `tag` - Name of the tag, percent-encoded
+#[utoipa::path(
+    put,
+    path = "/accounts/me/tags/{tag}",
+    tag = "tags",
+    params(("tag" = String, Path, description = "Name of the tag")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Tag followed", body = String),
+        (status = 400, description = "Invalid tag name", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Tag not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::tags", skip(store))]
+pub async fn follow_tag(store: Store, tag: String, session: Session) -> Result<MessageResponse, Rejection> {
+    let name = decode_tag_name(&tag)?;
+    trace!("following the tag {name:?}");
+    if !store.follow_tag(session.account_id, &name).await? {
+        return Err(ServiceError::TagNotFound(name).into());
+    }
+    info!("followed the tag {name:?}");
+    Ok(MessageResponse::ok("Tag followed"))
+}
+
+/// Handler for `DELETE /accounts/me/tags/{tag}`
+///
+/// Makes the account making the request stop following the tag. Unfollowing a tag the account does
+/// not follow does nothing.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `tag` - Name of the tag, percent-encoded
+#[utoipa::path(
+    delete,
+    path = "/accounts/me/tags/{tag}",
+    tag = "tags",
+    params(("tag" = String, Path, description = "Name of the tag")),
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Tag unfollowed", body = String),
+        (status = 400, description = "Invalid tag name", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+        (status = 404, description = "Tag not found", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::tags", skip(store))]
+pub async fn unfollow_tag(store: Store, tag: String, session: Session) -> Result<MessageResponse, Rejection> {
+    let name = decode_tag_name(&tag)?;
+    trace!("unfollowing the tag {name:?}");
+    if !store.unfollow_tag(session.account_id, &name).await? {
+        return Err(ServiceError::TagNotFound(name).into());
+    }
+    info!("unfollowed the tag {name:?}");
+    Ok(MessageResponse::ok("Tag unfollowed"))
+}
+
+/// Decodes the name of the tag from the path segment, and normalizes it like the tags of the
+/// questions, so `Web%20Dev` names the `web dev` tag.
+fn decode_tag_name(segment: &str) -> Result<String, ServiceError> {
+    let name = percent_decode_str(segment)
+        .decode_utf8()
+        .map_err(|error| ServiceError::InvalidId(error.to_string()))?;
+    Ok(sanitize::tag_name(&name))
+}
@@ -0,0 +1,55 @@
+//! Module for the `Tag` resource.
+//!
+//! The tags are not created through the API, but by the [Store] when the questions using them are
+//! asked or updated, so the module only lists them, with the number of the questions using them,
+//! and the popular ones, with the number of the recent questions using them. The accounts follow
+//! the tags, to list the questions using them in their feed, `GET /questions/feed`.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the `Tag` resource.
+//! - `routes` - Contains the filters for the `Tag` resource.
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Handlers for the `Tag` resource.
+mod handlers;
+/// Routes for the `Tag` resource.
+mod routes;
+
+/// OpenAPI document for the `Tag` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::get_tags,
+        handlers::get_popular_tags,
+        handlers::follow_tag,
+        handlers::unfollow_tag
+    ),
+    tags((name = "tags", description = "Tags of the questions"))
+)]
+pub struct TagsApi;
+
+/// Filter for the `Tag` resource.
+///
+/// Creates a filter that handles requests for the `Tag` resource.
+///
+/// The filter combines the following filters:
+/// - `get_tags`, for handling `GET /tags`
+/// - `get_popular_tags`, for handling `GET /tags/popular`
+/// - `follow_tag`, for handling `PUT /accounts/me/tags/{tag}`
+/// - `unfollow_tag`, for handling `DELETE /accounts/me/tags/{tag}`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::get_tags(store.clone())
+        .or(routes::get_popular_tags(store.clone()))
+        .or(routes::follow_tag(store.clone()))
+        .or(routes::unfollow_tag(store.clone()))
+        .boxed()
+}
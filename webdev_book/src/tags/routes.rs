@@ -0,0 +1,78 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::authentication;
+use crate::filters::route;
+use crate::store::Store;
+use crate::tags::handlers;
+
+/// GET /tags?offset={i64}&limit={i64}
+///
+/// Creates a filter for a route that handles listing the tags.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_tags(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "tags",
+        extract: [warp::query()],
+        handler: handlers::get_tags,
+        trace: "get_tags request",
+    }
+}
+
+/// GET /tags/popular?window={window}
+///
+/// Creates a filter for a route that handles listing the popular tags.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn get_popular_tags(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: get,
+        path: "tags" / "popular",
+        extract: [warp::query()],
+        handler: handlers::get_popular_tags,
+        trace: "get_popular_tags request",
+    }
+}
+
+/// PUT /accounts/me/tags/{tag}
+///
+/// Creates a filter for a route that handles following a tag by the account making the request.
+///
+/// The filter extracts the name of the tag from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn follow_tag(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: put,
+        path: "accounts" / "me" / "tags" / {String},
+        extract: [authentication::auth(&store)],
+        handler: handlers::follow_tag,
+        trace: "follow_tag request",
+    }
+}
+
+/// DELETE /accounts/me/tags/{tag}
+///
+/// Creates a filter for a route that handles unfollowing a tag by the account making the request.
+///
+/// The filter extracts the name of the tag from the URL path and passes it to the handler.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn unfollow_tag(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: delete,
+        path: "accounts" / "me" / "tags" / {String},
+        extract: [authentication::auth(&store)],
+        handler: handlers::unfollow_tag,
+        trace: "unfollow_tag request",
+    }
+}
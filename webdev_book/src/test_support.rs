@@ -0,0 +1,127 @@
+//! Helpers for testing the routes with [warp::test], for the crates embedding the service.
+//!
+//! Available with the `test-util` feature. Besides the factories from
+//! [webdev_core::test_support], which are re-exported here, the module provides the router
+//! wired the same way as in `main.rs`, and the requests authenticated as an account, without
+//! going through `POST /login`.
+//!
+//! The routes of the questions, the answers and the accounts can be tried without a database, on
+//! a [MemStore](crate::storage::MemStore) served by their `storage_filter`s, see
+//! [Storage](crate::storage::Storage). The store verifies the tokens of [authenticated] with the
+//! [test_auth_keys]:
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use serde_json::json;
+//! use warp::http::StatusCode;
+//! use warp::Filter;
+//! use webdev_book::error::return_error;
+//! use webdev_book::questions;
+//! use webdev_book::storage::{MemStore, Storage};
+//! use webdev_book::test_support::{authenticated, test_auth_keys};
+//! use webdev_book::types::authentication::Account;
+//!
+//! let store = MemStore::default().with_auth_keys(test_auth_keys());
+//! let account = Account::builder().email("alice@example.com".to_string()).password("hash".to_string());
+//! let alice = store.add_account(account.build().unwrap()).await.unwrap();
+//! let routes = questions::storage_filter(&store).recover(return_error);
+//!
+//! let response = authenticated(alice.id)
+//!     .method("POST")
+//!     .path("/questions")
+//!     .json(&json!({ "title": "How?", "content": "Like this." }))
+//!     .reply(&routes)
+//!     .await;
+//! assert_eq!(response.status(), StatusCode::CREATED);
+//! let response = warp::test::request().path("/questions").reply(&routes).await;
+//! assert_eq!(response.status(), StatusCode::OK);
+//! # }
+//! ```
+//!
+//! The other routes need the [Store], connected to a database, with the resources inserted by the
+//! factories:
+//!
+//! ```ignore
+//! let router = test_router(&store);
+//! let alice = an_account().insert(&store).await;
+//! let response = authenticated(alice.id.unwrap())
+//!     .method("POST")
+//!     .path("/questions")
+//!     .json(&json!({ "title": "How?", "content": "Like this." }))
+//!     .reply(&router)
+//!     .await;
+//! ```
+
+use chrono::{DateTime, Utc};
+use warp::filters::BoxedFilter;
+use warp::test::RequestBuilder;
+use warp::Reply;
+
+use crate::authentication;
+use crate::clock::SystemClock;
+use crate::store::Store;
+use crate::tokens::PasetoSigner;
+use crate::types::authentication::{AccountId, AuthKeys};
+
+pub use webdev_core::test_support::*;
+
+/// Key the test tokens are encrypted with.
+pub const TEST_PASETO_KEY: &[u8; AuthKeys::PASETO_KEY_LENGTH] = b"TEST KEY FOR THE WEBDEV BOOK API";
+
+/// Returns the keys the test tokens are encrypted with, with the [TEST_PASETO_KEY].
+pub fn test_auth_keys() -> AuthKeys {
+    AuthKeys::new("test", TEST_PASETO_KEY).expect("the test key is valid")
+}
+
+/// Returns the filter serving the whole API, as served by the service.
+///
+/// The frontend is not served, and the attachments are stored in a new directory in the
+/// temporary directory of the system. The tokens are verified with the [test_auth_keys], which
+/// the tokens of [token_for] are encrypted with.
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn test_router(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    let store = store.clone().with_auth_keys(test_auth_keys());
+    let attachments_dir = std::env::temp_dir().join(format!("webdev_book_{}", uuid::Uuid::new_v4().simple()));
+    crate::routes(&store, None, attachments_dir)
+}
+
+/// Returns a token for the account, the same one `POST /login` returns with the [SystemClock].
+///
+/// # Parameters
+/// - `account_id` - The id of the account the token is issued for.
+pub fn token_for(account_id: AccountId) -> String {
+    authentication::issue_token(&PasetoSigner::new(test_auth_keys()), &SystemClock, account_id)
+}
+
+/// Returns a token for the account, valid only between the given dates.
+///
+/// The dates can be in the past or in the future, to test the requests with expired tokens,
+/// or with tokens that are not valid yet.
+///
+/// # Parameters
+/// - `account_id` - The id of the account the token is issued for.
+/// - `not_before` - The date the token becomes valid.
+/// - `expiration` - The date the token expires.
+pub fn token_valid_between(account_id: AccountId, not_before: DateTime<Utc>, expiration: DateTime<Utc>) -> String {
+    authentication::issue_token_valid_between(&PasetoSigner::new(test_auth_keys()), account_id, not_before, expiration)
+}
+
+/// Returns a token for the account, encrypted with the given keys instead of the [test_auth_keys].
+///
+/// # Parameters
+/// - `keys` - The keys, whose current one encrypts the token.
+/// - `account_id` - The id of the account the token is issued for.
+pub fn token_with_keys(keys: &AuthKeys, account_id: AccountId) -> String {
+    authentication::issue_token(&PasetoSigner::new(keys.clone()), &SystemClock, account_id)
+}
+
+/// Returns a test request authenticated as the account.
+///
+/// # Parameters
+/// - `account_id` - The id of the account making the request.
+pub fn authenticated(account_id: AccountId) -> RequestBuilder {
+    warp::test::request().header("Authorization", token_for(account_id))
+}
@@ -1,10 +0,0 @@
-//! Module containing types used for resources and other helper types.
-
-/// Module containing types used for `Answer` resource.
-pub mod answer;
-/// Module containing types used for authentication.
-pub mod authentication;
-/// Module contaitning [Pagination](pagination::Pagination) type.
-pub mod pagination;
-/// Module containing types used for `Question` resource.
-pub mod question;
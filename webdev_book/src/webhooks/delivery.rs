@@ -0,0 +1,50 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{debug, instrument};
+
+use crate::types::webhook::Webhook;
+
+/// Header containing the name of the delivered event.
+const EVENT_HEADER: &str = "X-Webhook-Event";
+/// Header containing the signature of the delivered payload.
+const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Delivers the payload to a single webhook, for the [Task::DeliverWebhook](crate::jobs::Task::DeliverWebhook) jobs.
+///
+/// The payload is signed with HMAC-SHA256, using the secret of the webhook as the key.
+/// The signature is sent hex encoded in the `X-Webhook-Signature` header, as `sha256=<signature>`.
+///
+/// Returns the reason of the failure if the request fails, or the webhook responds with an error status.
+#[instrument(target = "webdev_book::webhooks", skip(client, webhook, payload), fields(id = ?webhook.id, url = %webhook.url))]
+pub(crate) async fn deliver(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    event: &str,
+    payload: String,
+) -> Result<(), String> {
+    let signature = sign(&webhook.secret, &payload);
+    let response = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event)
+        .header(SIGNATURE_HEADER, format!("sha256={signature}"))
+        .body(payload)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            debug!("event delivered");
+            Ok(())
+        }
+        Ok(response) => Err(format!("event rejected with status {}", response.status())),
+        Err(error) => Err(format!("event could not be delivered: {error}")),
+    }
+}
+
+/// Signs the payload with HMAC-SHA256 and returns the hex encoded signature.
+fn sign(secret: &str, payload: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
@@ -0,0 +1,71 @@
+use reqwest::Url;
+use tracing::{debug, info, instrument, trace};
+use warp::Rejection;
+
+use crate::error::ServiceError;
+use crate::responses::JsonResponse;
+use crate::store::Store;
+use crate::types::authentication::Session;
+use crate::types::webhook::Webhook;
+
+/// Checks that the webhook can be registered.
+///
+/// The URL has to be an absolute `http` or `https` URL, the webhook has to subscribe to at least
+/// one event, all of which have to be known, and the secret must not be empty.
+fn validate(webhook: &Webhook) -> Result<(), ServiceError> {
+    match Url::parse(&webhook.url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+        _ => {
+            return Err(ServiceError::ValidationError(format!(
+                "invalid webhook url: {:?}",
+                webhook.url
+            )))
+        }
+    }
+    if webhook.events.is_empty() {
+        return Err(ServiceError::ValidationError("webhook has no events".to_string()));
+    }
+    if let Some(event) = webhook
+        .events
+        .iter()
+        .find(|event| !Webhook::EVENTS.contains(&event.as_str()))
+    {
+        return Err(ServiceError::ValidationError(format!(
+            "unknown webhook event: {event:?}"
+        )));
+    }
+    if webhook.secret.is_empty() {
+        return Err(ServiceError::ValidationError("webhook secret is empty".to_string()));
+    }
+    Ok(())
+}
+
+/// Handler for `POST /webhooks`
+///
+/// Registers a new webhook for the account making the request.
+///
+/// # Parameters
+/// - `store` - [Store] instance
+/// - `webhook` - [Webhook] object containing the URL, the events and the secret
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = Webhook,
+    security(("token" = [])),
+    responses(
+        (status = 201, description = "Webhook registered", body = Webhook),
+        (status = 400, description = "Invalid URL, events or secret", body = String),
+        (status = 401, description = "Missing or invalid token", body = String),
+    )
+)]
+#[instrument(target = "webdev_book::webhooks", skip(store, webhook), fields(url = %webhook.url))]
+pub async fn add_webhook(store: Store, webhook: Webhook, session: Session) -> Result<JsonResponse<Webhook>, Rejection> {
+    trace!("validating the webhook");
+    validate(&webhook)?;
+
+    let webhook = store.add_webhook(session.account_id, webhook).await?;
+    info!("registered the webhook with id = {:?}", webhook.id);
+    debug!("registered the webhook: {:?}", webhook);
+    Ok(JsonResponse::created(webhook))
+}
@@ -0,0 +1,46 @@
+//! Module for the `Webhook` resource.
+//!
+//! Webhooks let the clients register a URL, to which the chosen events are delivered as they happen,
+//! by the jobs added to the [queue](crate::jobs) with the writes causing the events.
+//!
+//! This module contains the following submodules:
+//! - `handlers` - Contains the request handlers for the `Webhook` resource.
+//! - `routes` - Contains the filters for the `Webhook` resource.
+//! - `delivery` - Contains the delivery of the events to the webhooks.
+use utoipa::OpenApi;
+use warp::filters::BoxedFilter;
+use warp::{Filter, Reply};
+
+use crate::store::Store;
+
+/// Delivery of the events.
+mod delivery;
+/// Handlers for the `Webhook` resource.
+mod handlers;
+/// Routes for the `Webhook` resource.
+mod routes;
+
+pub(crate) use delivery::deliver;
+
+/// OpenAPI document for the `Webhook` resource.
+///
+/// It is merged into the document of the whole API by [openapi](crate::openapi::openapi).
+#[derive(OpenApi)]
+#[openapi(
+    paths(handlers::add_webhook),
+    tags((name = "webhooks", description = "Notifications about the events, delivered to the registered URLs"))
+)]
+pub struct WebhooksApi;
+
+/// Filter for the `Webhook` resource.
+///
+/// Creates a filter that handles requests for the `Webhook` resource.
+///
+/// The filter combines the following filters:
+/// - `add_webhook`, for handling `POST /webhooks`
+///
+/// # Parameters
+/// - `store` - The [Store] to use for handling requests.
+pub fn filter(store: &Store) -> BoxedFilter<(impl Reply,)> {
+    routes::add_webhook(store.clone()).boxed()
+}
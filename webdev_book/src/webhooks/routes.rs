@@ -0,0 +1,25 @@
+use warp::{Filter, Rejection, Reply};
+
+use crate::authentication;
+use crate::codec;
+use crate::filters::route;
+use crate::store::Store;
+use crate::webhooks::handlers;
+
+/// POST /webhooks
+///
+/// Creates a filter for a route that handles registering new webhooks.
+/// The filter expects a JSON payload containing the URL, the events and the secret of the webhook.
+///
+/// # Parameters
+/// - `store` - [Store] object available to the route handler
+pub fn add_webhook(store: Store) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    route! {
+        store: store,
+        method: post,
+        path: "webhooks",
+        extract: [codec::body(), authentication::auth(&store)],
+        handler: handlers::add_webhook,
+        trace: "add_webhook request",
+    }
+}
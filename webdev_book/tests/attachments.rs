@@ -0,0 +1,53 @@
+//! Tests of the [Storage] of the attached files, which writes the bodies to disk chunk by chunk.
+use futures_util::stream;
+use tokio::io::AsyncReadExt;
+use warp::hyper::body::Bytes;
+use webdev_book::attachments::Storage;
+use webdev_book::error::ServiceError;
+use webdev_book::filters::BodyStream;
+
+/// Returns a new storage directory, in the temporary directory of the system.
+fn storage_dir() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("webdev_book_attachments_{}", uuid::Uuid::new_v4().simple()))
+}
+
+/// Returns a body made of the chunks.
+fn body(chunks: Vec<&'static [u8]>) -> BodyStream {
+    Box::pin(stream::iter(
+        chunks.into_iter().map(|chunk| Ok(Bytes::from_static(chunk))),
+    ))
+}
+
+#[tokio::test]
+async fn the_chunks_are_stored_as_one_file() {
+    let dir = storage_dir();
+    let storage = Storage::new(&dir);
+
+    let (key, size) = storage
+        .save(body(vec![b"first ", b"second ", b"third"]), 100)
+        .await
+        .unwrap();
+    assert_eq!(size, 18);
+    let mut content = String::new();
+    storage
+        .open(&key)
+        .await
+        .unwrap()
+        .read_to_string(&mut content)
+        .await
+        .unwrap();
+    assert_eq!(content, "first second third");
+
+    storage.remove(&key).await.unwrap();
+    assert!(storage.open(&key).await.is_err());
+}
+
+#[tokio::test]
+async fn the_bodies_over_the_limit_are_not_stored() {
+    let dir = storage_dir();
+    let storage = Storage::new(&dir);
+
+    let error = storage.save(body(vec![b"first ", b"second"]), 10).await.unwrap_err();
+    assert!(matches!(error, ServiceError::PayloadTooLarge(10)), "{error:?}");
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+}
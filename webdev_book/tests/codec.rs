@@ -0,0 +1,126 @@
+//! Tests of the codecs of the bodies, on a route echoing the body of the request.
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use warp::{Filter, Rejection, Reply};
+use webdev_book::codec::{self, Format, MAX_BODY_SIZE};
+use webdev_book::error::return_error;
+use webdev_book::responses::JsonResponse;
+
+/// Returns a route echoing the body of the request, encoded as the service encodes its responses.
+fn echo() -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+    let route = warp::post()
+        .and(codec::body::<Value>())
+        .map(|body: Value| JsonResponse::ok(body));
+    warp::header::optional::<String>("accept")
+        .and(route)
+        .and_then(codec::encode)
+        .recover(return_error)
+}
+
+#[test]
+fn the_supported_accepted_media_type_of_the_highest_quality_is_used() {
+    assert_eq!(Format::from_accept(None), Format::Json);
+    assert_eq!(Format::from_accept(Some("text/html, image/png")), Format::Json);
+    assert_eq!(
+        Format::from_accept(Some("text/html, application/cbor;q=0.9, application/msgpack")),
+        Format::MessagePack
+    );
+    assert_eq!(
+        Format::from_accept(Some("application/cbor, application/msgpack")),
+        Format::Cbor
+    );
+    assert_eq!(
+        Format::from_accept(Some("application/json;q=0.5, application/cbor ; q=0.8")),
+        Format::Cbor
+    );
+    assert_eq!(
+        Format::from_accept(Some("application/msgpack;q=0, application/cbor;q=0")),
+        Format::Json
+    );
+    assert_eq!(
+        Format::from_media_type("Application/X-MsgPack; charset=utf-8"),
+        Some(Format::MessagePack)
+    );
+}
+
+#[tokio::test]
+async fn the_bodies_are_decoded_and_encoded_in_the_negotiated_formats() {
+    let body = json!({ "title": "How do I shrink the bodies?", "tags": ["msgpack", "cbor"] });
+
+    let response = warp::test::request()
+        .method("POST")
+        .header("content-type", "application/msgpack")
+        .header("accept", "application/cbor")
+        .body(Format::MessagePack.encode(&body))
+        .reply(&echo())
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/cbor");
+    assert_eq!(response.headers()["vary"], "accept");
+    assert_eq!(Format::Cbor.decode::<Value>(response.body()).unwrap(), body);
+
+    let response = warp::test::request()
+        .method("POST")
+        .header("content-type", "application/cbor")
+        .body(Format::Cbor.encode(&body))
+        .reply(&echo())
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert_eq!(serde_json::from_slice::<Value>(response.body()).unwrap(), body);
+
+    // Without a content type, the body is JSON
+    let response = warp::test::request()
+        .method("POST")
+        .header("accept", "application/msgpack")
+        .body(body.to_string())
+        .reply(&echo())
+        .await;
+    assert_eq!(response.headers()["content-type"], "application/msgpack");
+    assert_eq!(Format::MessagePack.decode::<Value>(response.body()).unwrap(), body);
+}
+
+#[tokio::test]
+async fn the_unsupported_or_undecodable_bodies_are_rejected() {
+    let response = warp::test::request()
+        .method("POST")
+        .header("content-type", "application/xml")
+        .body("<question/>")
+        .reply(&echo())
+        .await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let response = warp::test::request()
+        .method("POST")
+        .header("content-type", "application/msgpack")
+        // 0xc1 is the only byte never used by MessagePack
+        .body([0xc1].as_slice())
+        .reply(&echo())
+        .await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn the_bodies_encoded_as_json_beforehand_are_re_encoded() {
+    let body = json!({ "title": "How do I shrink the bodies?" });
+    let encoded = body.to_string();
+    let route = warp::header::optional::<String>("accept")
+        .and(warp::any().map(move || warp::reply::with_header(encoded.clone(), "content-type", "application/json")))
+        .and_then(codec::encode);
+
+    let response = warp::test::request()
+        .header("accept", "application/msgpack")
+        .reply(&route)
+        .await;
+    assert_eq!(response.headers()["content-type"], "application/msgpack");
+    assert_eq!(response.headers()["vary"], "accept");
+    assert_eq!(Format::MessagePack.decode::<Value>(response.body()).unwrap(), body);
+}
+
+#[tokio::test]
+async fn the_bodies_over_the_limit_are_rejected() {
+    let large = json!({ "content": "a".repeat(MAX_BODY_SIZE as usize) }).to_string();
+
+    let response = warp::test::request().method("POST").body(large).reply(&echo()).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -0,0 +1,277 @@
+//! Snapshot tests of the responses [return_error] builds for every kind of rejection.
+//!
+//! The status and the body of each response are part of the public contract of the API, so they
+//! are stored in `tests/snapshots`, and any change to them shows up in review. The variants of
+//! [ServiceError] that are never returned by the API, e.g. the startup errors, are not covered.
+use chrono::{TimeZone, Utc};
+use insta::assert_snapshot;
+use warp::http::StatusCode;
+use warp::hyper::body::to_bytes;
+use warp::{Filter, Rejection, Reply};
+use webdev_book::error::{
+    return_error, APILayerError, FieldError, MissingAccount, MissingAnswer, MissingAttachment, MissingNotification,
+    MissingQuestion, MissingSession, ReqwestMiddlewareError, ServiceError, SqlxError,
+};
+use webdev_book::filters;
+use webdev_book::types::answer::AnswerId;
+use webdev_book::types::attachment::AttachmentId;
+use webdev_book::types::authentication::{AccountId, SessionId};
+use webdev_book::types::moderation::AccountBan;
+use webdev_book::types::notification::NotificationId;
+use webdev_book::types::pagination::PaginationParsingError;
+use webdev_book::types::question::{Question, QuestionId};
+use webdev_book::types::quota::QuotaExceeded;
+
+/// Renders the status and the body of the response returned for the rejection.
+async fn render(rejection: Rejection) -> String {
+    let response = return_error(rejection)
+        .await
+        .expect("every rejection is recovered")
+        .into_response();
+    let status = response.status();
+    let body = to_bytes(response.into_body()).await.unwrap();
+    format!("{status}\n{}", String::from_utf8_lossy(&body))
+}
+
+/// Returns an error of the HTTP client, which cannot be constructed directly.
+fn reqwest_error() -> reqwest::Error {
+    reqwest::Client::new().get("http://[").build().unwrap_err()
+}
+
+/// Returns an error returned by the Bad Words API.
+fn api_layer_error(status: StatusCode) -> APILayerError {
+    APILayerError {
+        status,
+        message: "quota exceeded".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn service_errors() {
+    let errors = [
+        (
+            "parse_error",
+            ServiceError::ParseError("ten".parse::<i32>().unwrap_err()),
+        ),
+        (
+            "invalid_id",
+            ServiceError::InvalidId("invalid QuestionId format: \"abc\"".to_string()),
+        ),
+        (
+            "validation_error",
+            ServiceError::ValidationError("webhook has no events".to_string()),
+        ),
+        (
+            "invalid_field",
+            ServiceError::InvalidField(FieldError::new("tags[1]", "tag is longer than 64 bytes")),
+        ),
+        (
+            "body_decode_error",
+            ServiceError::BodyDecodeError("EOF while parsing".to_string()),
+        ),
+        (
+            "unsupported_media_type",
+            ServiceError::UnsupportedMediaType("text/plain".to_string()),
+        ),
+        ("payload_too_large", ServiceError::PayloadTooLarge(16)),
+        (
+            "pagination_error",
+            ServiceError::PaginationError(PaginationParsingError::Unknown {
+                parameter: "page".to_string(),
+            }),
+        ),
+        (
+            "question_not_found",
+            ServiceError::QuestionNotFound(MissingQuestion(QuestionId(1))),
+        ),
+        (
+            "answer_not_found",
+            ServiceError::AnswerNotFound(MissingAnswer(AnswerId(1))),
+        ),
+        (
+            "attachment_not_found",
+            ServiceError::AttachmentNotFound(MissingAttachment(AttachmentId(1))),
+        ),
+        (
+            "notification_not_found",
+            ServiceError::NotificationNotFound(MissingNotification(NotificationId(1))),
+        ),
+        (
+            "account_not_found",
+            ServiceError::AccountNotFound(MissingAccount(AccountId(1))),
+        ),
+        (
+            "session_not_found",
+            ServiceError::SessionNotFound(MissingSession(SessionId(1))),
+        ),
+        ("tag_not_found", ServiceError::TagNotFound("rust".to_string())),
+        (
+            "oauth_provider_not_found",
+            ServiceError::OAuthProviderNotFound("gitlab".to_string()),
+        ),
+        (
+            "storage_error",
+            ServiceError::StorageError(std::io::Error::other("disk full")),
+        ),
+        (
+            "database_query_error",
+            ServiceError::DatabaseQueryError(SqlxError::RowNotFound),
+        ),
+        (
+            "argon_library_error",
+            ServiceError::ArgonLibraryError(argon2::Error::PwdTooLong),
+        ),
+        ("reqwest_api_error", ServiceError::ReqwestAPIError(reqwest_error())),
+        (
+            "middleware_reqwest_api_error",
+            ServiceError::MiddlewareReqwestAPIError(ReqwestMiddlewareError::Reqwest(reqwest_error())),
+        ),
+        (
+            "client_error",
+            ServiceError::ClientError(api_layer_error(StatusCode::TOO_MANY_REQUESTS)),
+        ),
+        (
+            "server_error",
+            ServiceError::ServerError(api_layer_error(StatusCode::BAD_GATEWAY)),
+        ),
+        ("wrong_password", ServiceError::WrongPassword),
+        ("cannot_decrypt_token", ServiceError::CannotDecryptToken),
+        ("token_revoked", ServiceError::TokenRevoked),
+        ("outdated_token", ServiceError::OutdatedToken),
+        ("invalid_refresh_token", ServiceError::InvalidRefreshToken),
+        (
+            "oauth_failed",
+            ServiceError::OAuthFailed("the login was denied".to_string()),
+        ),
+        ("unauthorized", ServiceError::Unauthorized),
+        (
+            "account_banned",
+            ServiceError::AccountBanned(AccountBan {
+                account_id: AccountId(1),
+                until: Some(Utc.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap()),
+                reason: "spam".to_string(),
+            }),
+        ),
+        (
+            "quota_exceeded",
+            ServiceError::QuotaExceeded(QuotaExceeded {
+                resource: "questions".to_string(),
+                limit: 20,
+                usage: 20,
+                resets_at: Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap(),
+            }),
+        ),
+        ("too_many_login_attempts", ServiceError::TooManyLoginAttempts(900)),
+        (
+            "account_locked",
+            ServiceError::AccountLocked(Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()),
+        ),
+        (
+            "conflict",
+            ServiceError::Conflict("question was modified concurrently".to_string()),
+        ),
+        (
+            "version_mismatch",
+            ServiceError::VersionMismatch {
+                current: 3,
+                expected: 2,
+            },
+        ),
+        (
+            "similar_questions",
+            ServiceError::SimilarQuestions(vec![Question::builder()
+                .id(QuestionId(1))
+                .title("How do I test warp?")
+                .content("With warp::test.")
+                .build()
+                .unwrap()]),
+        ),
+    ];
+
+    for (name, error) in errors {
+        assert_snapshot!(name, render(warp::reject::custom(error)).await);
+    }
+}
+
+#[tokio::test]
+async fn missing_header() {
+    let rejection = warp::test::request()
+        .filter(&warp::header::<String>("authorization"))
+        .await
+        .unwrap_err();
+    assert_snapshot!(render(rejection).await);
+}
+
+#[tokio::test]
+async fn cors_forbidden() {
+    let route = warp::any().map(warp::reply).with(filters::cors());
+    let rejection = warp::test::request()
+        .method("OPTIONS")
+        .header("origin", "https://example.com")
+        .header("access-control-request-method", "GET")
+        .header("access-control-request-headers", "x-forbidden")
+        .filter(&route)
+        .await
+        .err()
+        .expect("the header is not allowed");
+    assert_snapshot!(render(rejection).await);
+}
+
+#[tokio::test]
+async fn body_deserialize_error() {
+    let rejection = warp::test::request()
+        .body("{\"title\": ")
+        .filter(&warp::body::json::<serde_json::Value>())
+        .await
+        .unwrap_err();
+    assert_snapshot!(render(rejection).await);
+}
+
+#[tokio::test]
+async fn unknown_rejection() {
+    assert_snapshot!(render(warp::reject::not_found()).await);
+}
+
+#[tokio::test]
+async fn invalid_ids_rank_below_the_rejections_of_the_other_routes() {
+    let routes = warp::path!("accounts" / "me")
+        .and(warp::get())
+        .and(warp::header::<String>("authorization"))
+        .map(|_| warp::reply())
+        .or(warp::path("accounts")
+            .and(filters::id::<AccountId>())
+            .and(warp::path::end())
+            .map(|_| warp::reply()));
+
+    // `me` is not an id, but the route of `/accounts/me` tells what is missing
+    let rejection = warp::test::request()
+        .path("/accounts/me")
+        .filter(&routes)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        render(rejection).await,
+        "400 Bad Request\nmissing request header: \"authorization\""
+    );
+
+    let rejection = warp::test::request()
+        .method("DELETE")
+        .path("/accounts/me")
+        .filter(&routes)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(render(rejection).await, "405 Method Not Allowed\nmethod not allowed");
+
+    let rejection = warp::test::request()
+        .path("/accounts/abc")
+        .filter(&routes)
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        render(rejection).await,
+        "400 Bad Request\ninvalid AccountId format: \"abc\""
+    );
+}
@@ -0,0 +1,114 @@
+//! Tests of the files of the frontend, served without the API.
+//!
+//! The fallback to `index.html` behind the API is tested with a database, in the `it` crate.
+use warp::http::StatusCode;
+
+/// Content of the `index.html` of the frontend.
+const INDEX: &str = "<!doctype html><title>webdev book</title>";
+
+/// Returns a frontend directory, with only its `index.html`.
+fn frontend_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("webdev_book_frontend_{}", uuid::Uuid::new_v4().simple()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("index.html"), INDEX).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn the_precompressed_assets_are_served_to_the_clients_accepting_them() {
+    let dir = frontend_dir();
+    std::fs::create_dir_all(dir.join("assets")).unwrap();
+    for (extension, content) in [("", "original"), (".br", "brotli"), (".gz", "gzip")] {
+        std::fs::write(dir.join(format!("assets/index-4f2a9c1b.js{extension}")), content).unwrap();
+    }
+    let routes = webdev_book::frontend::filter(Some(dir));
+
+    for (accept_encoding, encoding, content) in [
+        (Some("gzip, br"), Some("br"), "brotli"),
+        (Some("gzip"), Some("gzip"), "gzip"),
+        (Some("br;q=0, *"), Some("gzip"), "gzip"),
+        (Some("identity"), None, "original"),
+        (None, None, "original"),
+    ] {
+        let mut request = warp::test::request().path("/assets/index-4f2a9c1b.js");
+        if let Some(accept_encoding) = accept_encoding {
+            request = request.header("accept-encoding", accept_encoding);
+        }
+        let response = request.reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK, "{accept_encoding:?}");
+        assert_eq!(response.body(), content, "{accept_encoding:?}");
+        assert_eq!(
+            response
+                .headers()
+                .get("content-encoding")
+                .map(|value| value.to_str().unwrap()),
+            encoding,
+            "{accept_encoding:?}"
+        );
+        assert_eq!(response.headers()["content-type"], "application/javascript");
+        assert_eq!(
+            response.headers()["cache-control"],
+            "public, max-age=31536000, immutable"
+        );
+        assert_eq!(response.headers()["vary"], "accept-encoding");
+    }
+
+    // Without a precompressed version, the file is served as is
+    let response = warp::test::request()
+        .path("/")
+        .header("accept-encoding", "br, gzip")
+        .reply(&routes)
+        .await;
+    assert_eq!(response.body(), INDEX);
+    assert_eq!(response.headers().get("content-encoding"), None);
+    assert_eq!(response.headers()["cache-control"], "no-cache");
+}
+
+#[tokio::test]
+async fn the_precompressed_assets_answer_head_and_range_requests() {
+    let dir = frontend_dir();
+    std::fs::create_dir_all(dir.join("assets")).unwrap();
+    std::fs::write(dir.join("assets/index-4f2a9c1b.js"), "original").unwrap();
+    std::fs::write(dir.join("assets/index-4f2a9c1b.js.br"), "0123456789").unwrap();
+    let routes = webdev_book::frontend::filter(Some(dir));
+    let request = |method: &str, range: Option<&str>| {
+        let mut request = warp::test::request()
+            .method(method)
+            .path("/assets/index-4f2a9c1b.js")
+            .header("accept-encoding", "br");
+        if let Some(range) = range {
+            request = request.header("range", range);
+        }
+        request.reply(&routes)
+    };
+
+    let response = request("HEAD", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.body().is_empty());
+    assert_eq!(response.headers()["content-encoding"], "br");
+    assert_eq!(response.headers()["content-length"], "10");
+    assert_eq!(response.headers()["accept-ranges"], "bytes");
+
+    for (range, content, content_range) in [
+        ("bytes=2-5", "2345", "bytes 2-5/10"),
+        ("bytes=7-", "789", "bytes 7-9/10"),
+        ("bytes=-3", "789", "bytes 7-9/10"),
+        ("bytes=8-100", "89", "bytes 8-9/10"),
+    ] {
+        let response = request("GET", Some(range)).await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT, "{range}");
+        assert_eq!(response.body(), content, "{range}");
+        assert_eq!(response.headers()["content-range"], content_range, "{range}");
+        assert_eq!(response.headers()["content-length"], content.len().to_string().as_str());
+        assert_eq!(response.headers()["content-encoding"], "br");
+    }
+
+    let response = request("GET", Some("bytes=10-")).await;
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()["content-range"], "bytes */10");
+
+    // Several ranges are not served in part
+    let response = request("GET", Some("bytes=0-1, 4-5")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "0123456789");
+}
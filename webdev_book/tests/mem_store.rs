@@ -0,0 +1,228 @@
+//! Tests of the routes of the questions, the answers and the accounts, served from the [MemStore]
+//! instead of the database.
+//!
+//! Unlike the tests of the `it` crate, they don't need a database, so they always run.
+use chrono::{Duration, Utc};
+use serde_json::json;
+use warp::http::StatusCode;
+use warp::Filter;
+use webdev_book::error::return_error;
+use webdev_book::storage::{MemStore, Storage};
+use webdev_book::types::answer::AnswerId;
+use webdev_book::types::authentication::{Account, AccountId, Session};
+use webdev_book::types::question::Question;
+use webdev_book::{answers, authentication, questions};
+
+/// Returns the authorization header of a token for the account, signed by the store.
+fn bearer(store: &MemStore, account_id: AccountId) -> String {
+    let now = Utc::now();
+    let token = store.token_signer().sign(&Session {
+        ver: Session::CLAIMS_VERSION,
+        exp: now + Duration::try_hours(1).unwrap(),
+        nbf: now,
+        account_id,
+        email: String::new(),
+        role: Default::default(),
+        jti: "test".to_string(),
+        sid: None,
+    });
+    format!("Bearer {token}")
+}
+
+#[tokio::test]
+async fn the_answers_are_read_from_the_in_memory_storage() {
+    let store = MemStore::default();
+    let account = Account::builder()
+        .email("Alice@Example.com".to_string())
+        .password("hash".to_string())
+        .build()
+        .unwrap();
+    let alice = store.add_account(account).await.unwrap();
+    let question = Question::builder()
+        .title("Title".to_string())
+        .content("Content".to_string())
+        .build()
+        .unwrap();
+    let question_id = store.add_question(alice.id, question, true).await.unwrap().id.unwrap();
+    let first = store
+        .add_answer(alice.id, question_id, "First".to_string(), true)
+        .await
+        .unwrap();
+    let second = store
+        .add_answer(alice.id, question_id, "**Second**".to_string(), true)
+        .await
+        .unwrap();
+    let routes = answers::storage_filter(&store).recover(return_error);
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{}/answers?sort=newest", question_id.0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let listed: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(listed[0]["id"], second.id.unwrap().0);
+    assert_eq!(listed[1]["id"], first.id.unwrap().0);
+    assert_eq!(
+        listed[1]["author"],
+        serde_json::json!({ "id": alice.id.0, "display_name": null })
+    );
+
+    let response = warp::test::request()
+        .path(&format!("/answers/{}?format=html", second.id.unwrap().0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let answer: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert!(answer["content_html"]
+        .as_str()
+        .unwrap()
+        .contains("<strong>Second</strong>"));
+
+    assert!(store.delete_question(Some(alice.id), question_id, None).await.unwrap());
+    let response = warp::test::request()
+        .path(&format!("/answers/{}", first.id.unwrap().0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = warp::test::request()
+        .path(&format!("/questions/{}/answers", question_id.0))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn the_questions_and_the_answers_are_written_to_the_in_memory_storage() {
+    let store = MemStore::default();
+    let routes = questions::storage_filter(&store)
+        .or(answers::storage_filter(&store))
+        .or(authentication::storage_filter(&store))
+        .recover(return_error);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/register")
+        .json(&json!({ "email": "alice@example.com", "password": "password" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let alice: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    let alice = AccountId(alice["id"].as_i64().unwrap() as i32);
+    let bob = store
+        .add_account(
+            Account::builder()
+                .email("bob@example.com".to_string())
+                .password("hash".to_string())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .id;
+
+    let response = warp::test::request()
+        .method("POST")
+        .path("/questions")
+        .header("authorization", bearer(&store, alice))
+        .json(&json!({ "title": "Title", "content": "Content", "tags": ["rust"] }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let question: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    let question_id = question["id"].as_i64().unwrap();
+
+    // The similar questions are the ones with the same title
+    let response = warp::test::request()
+        .method("POST")
+        .path("/questions")
+        .header("authorization", bearer(&store, bob))
+        .json(&json!({ "title": "title", "content": "Other content" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&format!("/questions/{question_id}"))
+        .header("authorization", bearer(&store, alice))
+        .header("if-match", "\"2\"")
+        .json(&json!({ "title": "Edited", "content": "Content" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&format!("/questions/{question_id}"))
+        .header("authorization", bearer(&store, bob))
+        .json(&json!({ "title": "Edited", "content": "Content" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = warp::test::request()
+        .method("PUT")
+        .path(&format!("/questions/{question_id}"))
+        .header("authorization", bearer(&store, alice))
+        .header("if-match", "\"1\"")
+        .json(&json!({ "title": "Edited", "content": "Content" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&format!("/questions/{question_id}/answers"))
+        .header("authorization", bearer(&store, alice))
+        .json(&json!({ "content": "Answer" }))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let answer: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    let answer_id = answer["id"].as_i64().unwrap();
+    assert_eq!(answer["author"], json!({ "id": alice.0, "display_name": null }));
+
+    let response = warp::test::request()
+        .method("POST")
+        .path(&format!("/questions/{question_id}/answers/{answer_id}/accept"))
+        .header("authorization", bearer(&store, alice))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = warp::test::request()
+        .path(&format!("/questions/{question_id}?include=answers"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let detail: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(detail["title"], "Edited");
+    assert_eq!(detail["version"], 2);
+    assert_eq!(detail["answers"][0]["accepted"], true);
+
+    let response = warp::test::request().path("/questions").reply(&routes).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let page: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["answer_count"], 1);
+
+    let response = warp::test::request()
+        .path(&format!("/accounts/{}", alice.0))
+        .reply(&routes)
+        .await;
+    let profile: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+    assert_eq!(profile["question_count"], 1);
+    assert_eq!(profile["answer_count"], 1);
+
+    let response = warp::test::request()
+        .method("DELETE")
+        .path(&format!("/questions/{question_id}"))
+        .header("authorization", bearer(&store, alice))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(store.get_answer(AnswerId(answer_id as i32)).await.unwrap().is_none());
+
+    // The deleted question and answer still count toward the quotas
+    let yesterday = Utc::now() - Duration::try_days(1).unwrap();
+    assert_eq!(store.count_questions_since(alice, yesterday).await.unwrap(), 1);
+    assert_eq!(store.count_answers_since(alice, yesterday).await.unwrap(), 1);
+}
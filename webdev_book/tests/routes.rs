@@ -0,0 +1,72 @@
+//! Tests of the matching of the routes generated by the `route!` macro, on the routes of the
+//! answers, served from the [MemStore].
+use warp::http::StatusCode;
+use warp::Filter;
+use webdev_book::answers;
+use webdev_book::error::return_error;
+use webdev_book::storage::{MemStore, Storage};
+use webdev_book::types::authentication::Account;
+use webdev_book::types::question::Question;
+
+/// Body of the response to the requests that do not match any route.
+const ROUTE_NOT_FOUND: &str = "route not found";
+
+#[tokio::test]
+async fn the_routes_match_only_their_method_and_their_whole_path() {
+    let store = MemStore::default();
+    let account = Account::builder()
+        .email("alice@example.com".to_string())
+        .password("hash".to_string())
+        .build()
+        .unwrap();
+    let alice = store.add_account(account).await.unwrap();
+    let question = Question::builder()
+        .title("Title".to_string())
+        .content("Content".to_string())
+        .build()
+        .unwrap();
+    let question_id = store.add_question(alice.id, question, true).await.unwrap().id.unwrap();
+    let answer = store
+        .add_answer(alice.id, question_id, "Answer".to_string(), true)
+        .await
+        .unwrap();
+    let answer_id = answer.id.unwrap().0;
+    let routes = answers::storage_filter(&store).recover(return_error);
+
+    let response = warp::test::request()
+        .path(&format!("/answers/{answer_id}"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // The id is parsed from its segment, and the answer extracted by the handler from the store
+    let response = warp::test::request()
+        .path(&format!("/answers/{}", answer_id + 1))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_ne!(response.body(), ROUTE_NOT_FOUND);
+
+    // The malformed ids are rejected, instead of the request falling through the route
+    for path in ["/answers/one", "/answers/-1"] {
+        let response = warp::test::request().path(path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{path}");
+    }
+
+    for (method, path) in [
+        ("GET", format!("/answers/{answer_id}/extra")),
+        ("GET", "/answers".to_string()),
+    ] {
+        let response = warp::test::request().method(method).path(&path).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{method} {path}");
+        assert_eq!(response.body(), ROUTE_NOT_FOUND, "{method} {path}");
+    }
+
+    // The path matches the route, but not the method
+    let response = warp::test::request()
+        .method("PATCH")
+        .path(&format!("/answers/{answer_id}"))
+        .reply(&routes)
+        .await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
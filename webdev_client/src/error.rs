@@ -0,0 +1,69 @@
+//! Module that implements the errors returned by the [Client](crate::Client).
+
+use reqwest::StatusCode;
+
+/// Error type for all errors returned by the client
+///
+/// The variants for the error responses mirror the status codes of the
+/// [ServiceError](webdev_core::error::ServiceError) returned by the service, and contain
+/// the message sent by the service.
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    /// The base URL of the service is not a valid URL
+    #[error("invalid base url: {0:?}")]
+    InvalidUrl(String),
+    /// The request is malformed, e.g. an invalid id or invalid pagination parameters
+    #[error("bad request: {0}")]
+    BadRequest(String),
+    /// The credentials are wrong, the token is missing or invalid,
+    /// or the account does not own the resource
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+    /// The resource does not exist
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// The request conflicts with the current state of the resource
+    #[error("conflict: {0}")]
+    Conflict(String),
+    /// The data sent to the service is invalid or duplicate
+    #[error("unprocessable entity: {0}")]
+    Unprocessable(String),
+    /// Any other error response returned by the service
+    #[error("service error ({status}): {message}")]
+    Service { status: StatusCode, message: String },
+    /// The request could not be sent, or the response could not be read
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+}
+
+impl ClientError {
+    /// Creates the error for an error response from the service.
+    ///
+    /// # Parameters
+    /// - `status` - The status code of the response
+    /// - `message` - The body of the response
+    pub fn from_response(status: StatusCode, message: String) -> Self {
+        match status {
+            StatusCode::BAD_REQUEST => ClientError::BadRequest(message),
+            StatusCode::UNAUTHORIZED => ClientError::Unauthorized(message),
+            StatusCode::NOT_FOUND => ClientError::NotFound(message),
+            StatusCode::CONFLICT => ClientError::Conflict(message),
+            StatusCode::UNPROCESSABLE_ENTITY => ClientError::Unprocessable(message),
+            status => ClientError::Service { status, message },
+        }
+    }
+
+    /// Returns the status code of the error response, if the error is one.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        use ClientError::*;
+        match self {
+            BadRequest(_) => Some(StatusCode::BAD_REQUEST),
+            Unauthorized(_) => Some(StatusCode::UNAUTHORIZED),
+            NotFound(_) => Some(StatusCode::NOT_FOUND),
+            Conflict(_) => Some(StatusCode::CONFLICT),
+            Unprocessable(_) => Some(StatusCode::UNPROCESSABLE_ENTITY),
+            Service { status, .. } => Some(*status),
+            InvalidUrl(_) | Http(_) => None,
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Typed client for the REST API of the webdev book service.
+//!
+//! The client uses the resource types from [webdev_core], the same ones the service uses,
+//! so the requests and responses are always in sync with the service.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), webdev_client::ClientError> {
+//! use webdev_client::Client;
+//! use webdev_client::types::pagination::Pagination;
+//!
+//! let client = Client::new("http://localhost:8080")?;
+//! client.login("user@example.com", "password").await?;
+//! let questions = client.get_questions(Pagination { offset: 0, limit: Some(10) }).await?;
+//! # Ok(())
+//! # }
+//! ```
+#![warn(clippy::all)]
+
+use std::sync::{Arc, RwLock};
+
+use reqwest::{Method, RequestBuilder, Response, Url};
+use serde::de::DeserializeOwned;
+
+pub mod error;
+
+pub use error::ClientError;
+pub use webdev_core::types;
+
+use types::answer::{Answer, AnswerId};
+use types::authentication::{Account, AccountId, AccountProfile};
+use types::moderation::{AccountBan, Ban, MaintenanceMode};
+use types::pagination::{Page, Pagination};
+use types::question::{NewQuestion, Question, QuestionDetail, QuestionId, UpdateQuestion};
+
+/// Client for the REST API of the webdev book service.
+///
+/// The client stores the token returned by [Client::login], and sends it with every request
+/// that requires authentication. The administrative requests are sent with the administrator
+/// token instead, see [Client::set_admin_token]. Clones of the client share the tokens.
+#[derive(Debug, Clone)]
+pub struct Client {
+    /// Base URL of the service, e.g. `http://localhost:8080/`
+    base_url: Url,
+    /// HTTP client used for the requests
+    http: reqwest::Client,
+    /// Token of the logged in account
+    token: Arc<RwLock<Option<String>>>,
+    /// Token of the administrators, sent in the `X-Admin-Token` header
+    admin_token: Arc<RwLock<Option<String>>>,
+}
+
+impl Client {
+    /// Creates a new client for the service at the given URL.
+    ///
+    /// # Parameters
+    /// - `base_url` - URL at which the service is served, e.g. `http://localhost:8080`
+    pub fn new(base_url: &str) -> Result<Self, ClientError> {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    /// Creates a new client for the service at the given URL, which sends the requests with the given HTTP client.
+    ///
+    /// # Parameters
+    /// - `base_url` - URL at which the service is served, e.g. `http://localhost:8080`
+    /// - `http` - HTTP client used for the requests
+    pub fn with_http_client(base_url: &str, http: reqwest::Client) -> Result<Self, ClientError> {
+        // Without the trailing slash, joining the paths would replace the last segment of the URL
+        let base_url = match base_url.ends_with('/') {
+            true => Url::parse(base_url),
+            false => Url::parse(&format!("{base_url}/")),
+        }
+        .map_err(|_| ClientError::InvalidUrl(base_url.to_string()))?;
+
+        Ok(Self {
+            base_url,
+            http,
+            token: Arc::new(RwLock::new(None)),
+            admin_token: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Returns the token of the logged in account, if any.
+    pub fn token(&self) -> Option<String> {
+        self.token.read().expect("token lock is not poisoned").clone()
+    }
+
+    /// Sets the token sent with the requests that require authentication.
+    ///
+    /// Can be used to reuse a token obtained earlier, instead of logging in again.
+    pub fn set_token(&self, token: impl Into<Option<String>>) {
+        *self.token.write().expect("token lock is not poisoned") = token.into();
+    }
+
+    /// Sets the administrator token, sent with the administrative requests.
+    pub fn set_admin_token(&self, admin_token: impl Into<Option<String>>) {
+        *self.admin_token.write().expect("token lock is not poisoned") = admin_token.into();
+    }
+
+    /// Forgets the token of the logged in account.
+    pub fn logout(&self) {
+        self.set_token(None);
+    }
+
+    /// Registers a new account.
+    ///
+    /// `POST /register`
+    ///
+    /// # Returns
+    /// - The profile of the created account
+    pub async fn register(&self, email: &str, password: &str) -> Result<AccountProfile, ClientError> {
+        let account = Self::account(email, password);
+        self.json(self.request(Method::POST, "register")?.json(&account)).await
+    }
+
+    /// Logs in to an account, and stores the returned token in the client.
+    ///
+    /// `POST /login`
+    ///
+    /// # Returns
+    /// - The token for the account
+    pub async fn login(&self, email: &str, password: &str) -> Result<String, ClientError> {
+        let account = Self::account(email, password);
+        let token: String = self.json(self.request(Method::POST, "login")?.json(&account)).await?;
+        self.set_token(token.clone());
+        Ok(token)
+    }
+
+    /// Returns a page of questions, with the total number of questions.
+    ///
+    /// `GET /questions?offset={offset}&limit={limit}`
+    pub async fn get_questions(&self, pagination: Pagination) -> Result<Page<Question>, ClientError> {
+        let Pagination { offset, limit } = pagination;
+        let mut request = self.request(Method::GET, "questions")?.query(&[("offset", offset)]);
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)]);
+        }
+        self.json(request).await
+    }
+
+    /// Returns the question with the given id.
+    ///
+    /// `GET /questions/{id}`
+    pub async fn get_question(&self, QuestionId(id): QuestionId) -> Result<Question, ClientError> {
+        self.json(self.request(Method::GET, &format!("questions/{id}"))?).await
+    }
+
+    /// Returns the question with the given id, with its answers embedded.
+    ///
+    /// `GET /questions/{id}?include=answers`
+    pub async fn get_question_with_answers(&self, QuestionId(id): QuestionId) -> Result<QuestionDetail, ClientError> {
+        self.json(
+            self.request(Method::GET, &format!("questions/{id}"))?
+                .query(&[("include", "answers")]),
+        )
+        .await
+    }
+
+    /// Creates a new question, and returns it as stored by the service.
+    ///
+    /// Unless `force` is set, the service rejects the question with `409 Conflict` if questions
+    /// with similar titles were already asked.
+    ///
+    /// `POST /questions?force={force}`, requires authentication
+    pub async fn add_question(&self, question: &NewQuestion, force: bool) -> Result<Question, ClientError> {
+        self.json(
+            self.authorized(Method::POST, "questions")?
+                .query(&[("force", force)])
+                .json(question),
+        )
+        .await
+    }
+
+    /// Updates the question with the given id.
+    ///
+    /// `PUT /questions/{id}`, requires authentication
+    pub async fn update_question(
+        &self,
+        QuestionId(id): QuestionId,
+        question: &UpdateQuestion,
+    ) -> Result<(), ClientError> {
+        self.send(self.authorized(Method::PUT, &format!("questions/{id}"))?.json(question))
+            .await?;
+        Ok(())
+    }
+
+    /// Deletes the question with the given id.
+    ///
+    /// `DELETE /questions/{id}`, requires authentication
+    pub async fn delete_question(&self, QuestionId(id): QuestionId) -> Result<(), ClientError> {
+        self.send(self.authorized(Method::DELETE, &format!("questions/{id}"))?)
+            .await?;
+        Ok(())
+    }
+
+    /// Adds an answer to the question with the given id, and returns the created answer.
+    ///
+    /// `POST /questions/{id}/answers`, requires authentication
+    pub async fn add_answer(&self, QuestionId(id): QuestionId, content: &str) -> Result<Answer, ClientError> {
+        let answer = Answer {
+            id: None,
+            content: content.to_string(),
+            content_html: None,
+            question_id: None,
+            accepted: false,
+            score: None,
+            author: None,
+        };
+        self.json(
+            self.authorized(Method::POST, &format!("questions/{id}/answers"))?
+                .json(&answer),
+        )
+        .await
+    }
+
+    /// Returns the answer with the given id.
+    ///
+    /// `GET /answers/{id}`
+    pub async fn get_answer(&self, AnswerId(id): AnswerId) -> Result<Answer, ClientError> {
+        self.json(self.request(Method::GET, &format!("answers/{id}"))?).await
+    }
+
+    /// Updates the answer with the given id, and returns the updated answer.
+    ///
+    /// `PUT /answers/{id}`, requires authentication
+    pub async fn update_answer(&self, AnswerId(id): AnswerId, content: &str) -> Result<Answer, ClientError> {
+        let answer = Answer {
+            id: None,
+            content: content.to_string(),
+            content_html: None,
+            question_id: None,
+            accepted: false,
+            score: None,
+            author: None,
+        };
+        self.json(self.authorized(Method::PUT, &format!("answers/{id}"))?.json(&answer))
+            .await
+    }
+
+    /// Deletes the answer with the given id.
+    ///
+    /// `DELETE /answers/{id}`, requires authentication
+    pub async fn delete_answer(&self, AnswerId(id): AnswerId) -> Result<(), ClientError> {
+        self.send(self.authorized(Method::DELETE, &format!("answers/{id}"))?)
+            .await?;
+        Ok(())
+    }
+
+    /// Bans the account with the given id, and returns the ban.
+    ///
+    /// `POST /admin/accounts/{id}/ban`, requires the administrator token
+    pub async fn ban_account(&self, AccountId(id): AccountId, ban: &Ban) -> Result<AccountBan, ClientError> {
+        self.json(self.admin(Method::POST, &format!("admin/accounts/{id}/ban"))?.json(ban))
+            .await
+    }
+
+    /// Unlocks the account with the given id, locked after too many failed logins.
+    ///
+    /// `POST /admin/accounts/{id}/unlock`, requires the administrator token
+    pub async fn unlock_account(&self, AccountId(id): AccountId) -> Result<(), ClientError> {
+        self.send(self.admin(Method::POST, &format!("admin/accounts/{id}/unlock"))?)
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the maintenance mode of the service.
+    ///
+    /// `GET /admin/maintenance`, requires the administrator token
+    pub async fn get_maintenance(&self) -> Result<MaintenanceMode, ClientError> {
+        self.json(self.admin(Method::GET, "admin/maintenance")?).await
+    }
+
+    /// Switches the maintenance mode of the service on or off, and returns the new mode.
+    ///
+    /// `PUT /admin/maintenance`, requires the administrator token
+    pub async fn set_maintenance(&self, enabled: bool) -> Result<MaintenanceMode, ClientError> {
+        self.json(
+            self.admin(Method::PUT, "admin/maintenance")?
+                .json(&MaintenanceMode { enabled }),
+        )
+        .await
+    }
+
+    /// Creates the account sent to `/register` and `/login`.
+    fn account(email: &str, password: &str) -> Account {
+        Account::builder()
+            .email(email)
+            .password(password)
+            .build()
+            .expect("all required fields are set")
+    }
+
+    /// Creates a request to the given path, relative to the base URL.
+    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, ClientError> {
+        let url = self
+            .base_url
+            .join(path)
+            .map_err(|_| ClientError::InvalidUrl(format!("{}{path}", self.base_url)))?;
+        Ok(self.http.request(method, url))
+    }
+
+    /// Creates a request to the given path, which carries the token of the logged in account.
+    ///
+    /// The request is sent without the token if no account is logged in,
+    /// and the service responds with [ClientError::BadRequest].
+    fn authorized(&self, method: Method, path: &str) -> Result<RequestBuilder, ClientError> {
+        let request = self.request(method, path)?;
+        Ok(match self.token() {
+            Some(token) => request.header(reqwest::header::AUTHORIZATION, token),
+            None => request,
+        })
+    }
+
+    /// Creates a request to the given path, which carries the administrator token.
+    ///
+    /// The request is sent without the token if none is set,
+    /// and the service responds with [ClientError::Unauthorized].
+    fn admin(&self, method: Method, path: &str) -> Result<RequestBuilder, ClientError> {
+        let request = self.request(method, path)?;
+        let admin_token = self.admin_token.read().expect("token lock is not poisoned").clone();
+        Ok(match admin_token {
+            Some(admin_token) => request.header("X-Admin-Token", admin_token),
+            None => request,
+        })
+    }
+
+    /// Sends the request, and turns the error responses into a [ClientError].
+    async fn send(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(response)
+        } else {
+            let message = response.text().await?;
+            Err(ClientError::from_response(status, message))
+        }
+    }
+
+    /// Sends the request, and deserializes the JSON body of the response.
+    async fn json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, ClientError> {
+        Ok(self.send(request).await?.json().await?)
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! Provides a client for the Bad Words API, which can be used to check the profanity in the text and censor the text.
 
+use async_trait::async_trait;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use serde::{Deserialize, Serialize};
-use tracing::{error, instrument, trace};
+use tracing::{instrument, trace};
 
+use crate::api::profanity::ProfanityFilter;
 use crate::error::{APILayerError, ServiceError};
 
 /// Bad Word object
@@ -78,16 +80,17 @@ impl BadWordsAPI {
     const API_ENDPOINT: &'static str = "https://api.apilayer.com/bad_words";
     /// Builds the URL for the Bad Words API
     ///
-    /// Builds the URL for the Bad Words API using the provided censor character.
+    /// Builds the URL for the Bad Words API at the endpoint, using the provided censor character.
     /// Censor character is used to replace the bad words in the text
     ///
     /// # Parameters
+    /// - `endpoint` - URL of the Bad Words API endpoint
     /// - `censor_char` - character to replace the bad words with
     ///
     /// # Returns
     /// - `String` containing the URL for the Bad Words API
-    fn url(censor_char: char) -> String {
-        format!("{}?censor_character={censor_char}", Self::API_ENDPOINT)
+    fn url(endpoint: &str, censor_char: char) -> String {
+        format!("{endpoint}?censor_character={censor_char}")
     }
 
     //noinspection DuplicatedCode
@@ -106,6 +109,26 @@ impl BadWordsAPI {
     /// # Returns
     /// - `Result` containing the new instance of the BadWordsAPI or an error
     pub fn build(api_key: &str, censor_char: char) -> Result<Self, BadWordsAPIBuildError> {
+        Self::build_with_endpoint(api_key, censor_char, Self::API_ENDPOINT)
+    }
+
+    /// Builds a new instance of the BadWordsAPI, sending the requests to the given endpoint
+    ///
+    /// Same as [build](BadWordsAPI::build), for a service compatible with the Bad Words API
+    /// hosted elsewhere, e.g. a stub used by the tests.
+    ///
+    /// # Parameters
+    /// - `api_key` - API key for the Bad Words API
+    /// - `censor_char` - character to replace the bad words with
+    /// - `endpoint` - URL of the Bad Words API endpoint
+    ///
+    /// # Returns
+    /// - `Result` containing the new instance of the BadWordsAPI or an error
+    pub fn build_with_endpoint(
+        api_key: &str,
+        censor_char: char,
+        endpoint: &str,
+    ) -> Result<Self, BadWordsAPIBuildError> {
         let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
 
         let mut headers = reqwest::header::HeaderMap::new();
@@ -118,7 +141,7 @@ impl BadWordsAPI {
             .build();
 
         Ok(BadWordsAPI {
-            url: Self::url(censor_char),
+            url: Self::url(endpoint, censor_char),
             client,
         })
     }
@@ -166,3 +189,10 @@ impl BadWordsAPI {
         self.check_profanity(text).await.map(|res| res.censored_content)
     }
 }
+
+#[async_trait]
+impl ProfanityFilter for BadWordsAPI {
+    async fn censor(&self, text: String) -> Result<String, ServiceError> {
+        BadWordsAPI::censor(self, text).await
+    }
+}
@@ -0,0 +1,105 @@
+//! Mock of the profanity filter, for the tests.
+//!
+//! Available with the `test-util` feature.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use warp::http::StatusCode;
+
+use crate::api::profanity::ProfanityFilter;
+use crate::error::{APILayerError, ServiceError};
+
+/// State of the mock, shared by its clones.
+#[derive(Debug, Default)]
+struct State {
+    /// Censored texts returned for the given texts.
+    responses: HashMap<String, String>,
+    /// Status of the error returned instead of the censored text, if set.
+    failure: Option<StatusCode>,
+    /// Texts passed to the filter, in order.
+    calls: Vec<String>,
+}
+
+/// Profanity filter with fixed responses, for the tests.
+///
+/// The filter returns the texts unchanged, unless a response is set for them with
+/// [with_response](MockProfanityFilter::with_response). It fails with the error the
+/// [BadWordsAPI](crate::api::bad_words::BadWordsAPI) returns for a status, while
+/// one is set with [fail_with](MockProfanityFilter::fail_with). Every text it is called with is
+/// recorded, and returned by [calls](MockProfanityFilter::calls).
+///
+/// The clones share the state, so the test can keep a clone to inspect the filter it gave to the store.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use webdev_core::api::mock::MockProfanityFilter;
+/// use webdev_core::api::profanity::ProfanityFilter;
+///
+/// let filter = MockProfanityFilter::new().with_response("darn it", "**** it");
+/// assert_eq!(filter.censor("darn it".to_string()).await.unwrap(), "**** it");
+/// assert_eq!(filter.censor("hello".to_string()).await.unwrap(), "hello");
+/// assert_eq!(filter.calls(), ["darn it", "hello"]);
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockProfanityFilter {
+    state: Arc<Mutex<State>>,
+}
+
+impl MockProfanityFilter {
+    /// Creates a filter returning all texts unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the censored text returned for the text.
+    pub fn with_response(self, text: impl Into<String>, censored: impl Into<String>) -> Self {
+        self.state().responses.insert(text.into(), censored.into());
+        self
+    }
+
+    /// Makes the filter fail with the error returned by the API for the status, until [recover](MockProfanityFilter::recover) is called.
+    ///
+    /// Client error statuses produce [ServiceError::ClientError], all others [ServiceError::ServerError].
+    pub fn fail_with(&self, status: StatusCode) {
+        self.state().failure = Some(status);
+    }
+
+    /// Makes the filter censor the texts again, after [fail_with](MockProfanityFilter::fail_with).
+    pub fn recover(&self) {
+        self.state().failure = None;
+    }
+
+    /// Returns the texts the filter was called with, in order.
+    pub fn calls(&self) -> Vec<String> {
+        self.state().calls.clone()
+    }
+
+    /// Locks the state of the filter.
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl ProfanityFilter for MockProfanityFilter {
+    async fn censor(&self, text: String) -> Result<String, ServiceError> {
+        let mut state = self.state();
+        state.calls.push(text.clone());
+
+        if let Some(status) = state.failure {
+            let error = APILayerError {
+                status,
+                message: "mock failure".to_string(),
+            };
+            return Err(match status.is_client_error() {
+                true => ServiceError::ClientError(error),
+                false => ServiceError::ServerError(error),
+            });
+        }
+        Ok(state.responses.get(&text).cloned().unwrap_or(text))
+    }
+}
@@ -6,6 +6,10 @@
 use serde::{Deserialize, Serialize};
 
 pub mod bad_words;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod profanity;
+pub mod wordlist;
 
 /// Wrapper for the response from any of the API endpoints, which are wrapped by this module
 ///
@@ -0,0 +1,45 @@
+//! Interface of the profanity filters used to censor the content posted by the users.
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::error::ServiceError;
+
+/// Filter censoring the profanity in the texts.
+///
+/// The [Store](crate::store::Store) holds the filter used by the services, which is the
+/// [BadWordsAPI](crate::api::bad_words::BadWordsAPI) unless replaced with
+/// [Store::with_profanity_filter](crate::store::Store::with_profanity_filter).
+#[async_trait]
+pub trait ProfanityFilter: Debug + Send + Sync {
+    /// Censors the text
+    ///
+    /// Returns the text with the profanity replaced, or the text unchanged if there is none.
+    async fn censor(&self, text: String) -> Result<String, ServiceError>;
+}
+
+/// Censors the text with the filter, unless the filter is unavailable.
+///
+/// Returns the censored text and `true`, or the text unchanged and `false` if the filter cannot be
+/// reached or fails on its side, so the text can be stored as is and censored later by a
+/// [job](crate::types::job::Task). The texts the filter rejects are still errors.
+///
+/// # Parameters
+/// - `filter` - The filter censoring the text.
+/// - `text` - The text to censor.
+pub async fn censor_or_defer(filter: &dyn ProfanityFilter, text: String) -> Result<(String, bool), ServiceError> {
+    match filter.censor(text.clone()).await {
+        Ok(censored) => Ok((censored, true)),
+        Err(
+            error @ (ServiceError::ServerError(_)
+            | ServiceError::ReqwestAPIError(_)
+            | ServiceError::MiddlewareReqwestAPIError(_)),
+        ) => {
+            warn!("the profanity filter is unavailable, the text is censored later: {error}");
+            Ok((text, false))
+        }
+        Err(error) => Err(error),
+    }
+}
@@ -0,0 +1,121 @@
+//! Profanity filter censoring the words of a fixed list, without calling any external service.
+//!
+//! Used where the Bad Words API is not reachable. The words are matched whole and regardless of
+//! the case, without the deviations the API detects, e.g. the letters replaced with digits.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+
+use crate::api::bad_words::{BadWord, BadWordsResponse};
+use crate::api::profanity::ProfanityFilter;
+use crate::error::ServiceError;
+
+/// List of words bundled with the crate, see `wordlist.txt`.
+const BUNDLED: &str = include_str!("wordlist.txt");
+
+/// Profanity filter censoring the words of a list.
+///
+/// Returns the same [BadWordsResponse] as the [BadWordsAPI](crate::api::bad_words::BadWordsAPI),
+/// so the callers can switch between the two.
+///
+/// ```
+/// use webdev_core::api::wordlist::WordList;
+///
+/// let filter = WordList::new(["darn"], '*');
+/// let response = filter.check_profanity("Darn it, darnit".to_string());
+/// assert_eq!(response.censored_content, "**** it, darnit");
+/// assert_eq!(response.bad_words_total, 1);
+/// assert_eq!(response.bad_words_list[0].original, "Darn");
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordList {
+    /// The censored words, in lowercase
+    words: HashSet<String>,
+    /// Character to replace the bad words with
+    censor_char: char,
+}
+
+impl WordList {
+    /// Creates a filter censoring the given words
+    ///
+    /// # Parameters
+    /// - `words` - words to censor, matched regardless of the case
+    /// - `censor_char` - character to replace the bad words with
+    pub fn new<I, S>(words: I, censor_char: char) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            words: words.into_iter().map(|word| word.as_ref().to_lowercase()).collect(),
+            censor_char,
+        }
+    }
+
+    /// Creates a filter censoring the words of the list bundled with the crate
+    ///
+    /// # Parameters
+    /// - `censor_char` - character to replace the bad words with
+    pub fn bundled(censor_char: char) -> Self {
+        let words = BUNDLED
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        Self::new(words, censor_char)
+    }
+
+    /// Checks the profanity in the text
+    ///
+    /// Finds the words of the list in the text, and replaces each of their characters with the
+    /// censor character.
+    ///
+    /// # Parameters
+    /// - `text` - text to check for bad words
+    pub fn check_profanity(&self, text: String) -> BadWordsResponse {
+        let mut censored_content = String::with_capacity(text.len());
+        let mut bad_words_list = Vec::new();
+        let mut rest = text.as_str();
+
+        while !rest.is_empty() {
+            let word_len = rest.find(|char: char| !char.is_alphanumeric()).unwrap_or(rest.len());
+            if word_len == 0 {
+                let separator = rest.chars().next().expect("the rest is not empty");
+                censored_content.push(separator);
+                rest = &rest[separator.len_utf8()..];
+                continue;
+            }
+
+            let (word, tail) = rest.split_at(word_len);
+            if self.words.contains(&word.to_lowercase()) {
+                let replaced_len = word.chars().count();
+                let censored: String = std::iter::repeat_n(self.censor_char, replaced_len).collect();
+                censored_content.push_str(&censored);
+                bad_words_list.push(BadWord {
+                    original: word.to_string(),
+                    word: censored,
+                    deviations: 0,
+                    info: 2,
+                    replaced_len: replaced_len as i64,
+                });
+            } else {
+                censored_content.push_str(word);
+            }
+            rest = tail;
+        }
+
+        BadWordsResponse {
+            bad_words_total: bad_words_list.len() as i64,
+            content: text,
+            censored_content,
+            bad_words_list,
+        }
+    }
+}
+
+#[async_trait]
+impl ProfanityFilter for WordList {
+    async fn censor(&self, text: String) -> Result<String, ServiceError> {
+        Ok(self.check_profanity(text).censored_content)
+    }
+}
@@ -0,0 +1,153 @@
+//! Module that implements the [RedisCache], a cache for the questions shared by all service instances.
+//!
+//! The module is only available with the `redis-cache` feature. The cache is used by the
+//! [Store](crate::store::Store) when the `REDIS_URL` environment variable is set.
+//!
+//! The cache holds single questions and pages of questions, both expiring after a TTL.
+//! Writes invalidate them explicitly: a question is removed from the cache when it changes,
+//! and all pages are invalidated at once by bumping a generation counter, which is part of their keys.
+//!
+//! The cache is best-effort: errors are logged, and treated as cache misses.
+
+use std::time::Duration;
+
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, RedisError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{instrument, trace, warn};
+
+use crate::types::pagination::Pagination;
+use crate::types::question::{Question, QuestionId};
+
+/// Key of the counter, which is bumped to invalidate all pages.
+const PAGES_GENERATION_KEY: &str = "questions:pages:generation";
+
+/// Cache for the questions, backed by Redis.
+#[derive(Clone)]
+pub struct RedisCache {
+    /// Connection to Redis, which reconnects when the connection is lost
+    connection: ConnectionManager,
+    /// Time after which the cached values expire
+    ttl: Duration,
+}
+
+impl std::fmt::Debug for RedisCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCache").field("ttl", &self.ttl).finish()
+    }
+}
+
+impl RedisCache {
+    /// The default time after which the cached values expire.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+    /// Connects to Redis.
+    ///
+    /// # Parameters
+    /// - `url` - URL of the Redis server, e.g. `redis://localhost:6379`
+    /// - `ttl` - Time after which the cached values expire
+    pub async fn connect(url: &str, ttl: Duration) -> Result<Self, RedisError> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self { connection, ttl })
+    }
+
+    /// Returns the cached question with the given id.
+    #[instrument(target = "cache", level = "debug", skip(self))]
+    pub async fn get_question(&self, QuestionId(id): QuestionId) -> Option<Question> {
+        self.get(&format!("questions:{id}")).await
+    }
+
+    /// Caches the question.
+    #[instrument(target = "cache", level = "debug", skip(self, question), fields(id = ?question.id))]
+    pub async fn set_question(&self, question: &Question) {
+        if let Some(QuestionId(id)) = question.id {
+            self.set(&format!("questions:{id}"), question).await;
+        }
+    }
+
+    /// Removes the question with the given id from the cache.
+    #[instrument(target = "cache", level = "debug", skip(self))]
+    pub async fn invalidate_question(&self, QuestionId(id): QuestionId) {
+        let mut connection = self.connection.clone();
+        if let Err(error) = connection.del::<_, ()>(format!("questions:{id}")).await {
+            warn!("cannot invalidate question: {error}");
+        }
+    }
+
+    /// Returns the cached page of questions.
+    #[instrument(target = "cache", level = "debug", skip(self))]
+    pub async fn get_page(&self, pagination: Pagination) -> Option<Vec<Question>> {
+        let key = self.page_key(pagination).await?;
+        self.get(&key).await
+    }
+
+    /// Caches the page of questions.
+    #[instrument(target = "cache", level = "debug", skip(self, questions))]
+    pub async fn set_page(&self, pagination: Pagination, questions: &[Question]) {
+        if let Some(key) = self.page_key(pagination).await {
+            self.set(&key, &questions).await;
+        }
+    }
+
+    /// Invalidates all cached pages of questions.
+    ///
+    /// The pages cached before are left to expire.
+    #[instrument(target = "cache", level = "debug", skip(self))]
+    pub async fn invalidate_pages(&self) {
+        let mut connection = self.connection.clone();
+        if let Err(error) = connection.incr::<_, _, ()>(PAGES_GENERATION_KEY, 1).await {
+            warn!("cannot invalidate pages: {error}");
+        }
+    }
+
+    /// Returns the key of the page in the current generation.
+    async fn page_key(&self, pagination: Pagination) -> Option<String> {
+        let Pagination { offset, limit } = pagination;
+        let mut connection = self.connection.clone();
+        match connection.get::<_, Option<u64>>(PAGES_GENERATION_KEY).await {
+            Ok(generation) => {
+                let limit = limit.map_or("all".to_string(), |limit| limit.to_string());
+                Some(format!(
+                    "questions:pages:{}:{offset}:{limit}",
+                    generation.unwrap_or_default()
+                ))
+            }
+            Err(error) => {
+                warn!("cannot read pages generation: {error}");
+                None
+            }
+        }
+    }
+
+    /// Reads a JSON encoded value from the cache.
+    async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let mut connection = self.connection.clone();
+        match connection.get::<_, Option<String>>(key).await {
+            Ok(Some(value)) => {
+                trace!("cache hit: {key}");
+                serde_json::from_str(&value)
+                    .map_err(|error| warn!("cannot decode cached value: {error}"))
+                    .ok()
+            }
+            Ok(None) => {
+                trace!("cache miss: {key}");
+                None
+            }
+            Err(error) => {
+                warn!("cannot read from cache: {error}");
+                None
+            }
+        }
+    }
+
+    /// Writes a JSON encoded value to the cache, which expires after the TTL.
+    async fn set<T: Serialize>(&self, key: &str, value: &T) {
+        let value = serde_json::to_string(value).expect("cached values are always serializable");
+        let mut connection = self.connection.clone();
+        if let Err(error) = connection.set_ex::<_, _, ()>(key, value, self.ttl.as_secs()).await {
+            warn!("cannot write to cache: {error}");
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Module for the clock the services read the current time from.
+//!
+//! Everything that depends on the current time, e.g. the validity of the tokens, reads it from a
+//! [Clock] instead of calling [Utc::now] directly, so the tests can control the time with a
+//! [TestClock] instead of waiting for it to pass.
+
+use std::fmt::Debug;
+#[cfg(feature = "test-util")]
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time.
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Clock returning the time of the system, used by the services.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that stands still until it is moved, for the tests.
+///
+/// Available with the `test-util` feature. The clones share the time, so the test can keep a
+/// clone to move the clock it gave to the store.
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use webdev_core::clock::{Clock, TestClock};
+///
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// let clock = TestClock::new(start);
+/// clock.clone().advance(Duration::try_hours(1).unwrap());
+/// assert_eq!(clock.now(), start + Duration::try_hours(1).unwrap());
+/// ```
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+#[cfg(feature = "test-util")]
+impl TestClock {
+    /// Creates a clock showing the given time.
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    /// Sets the time shown by the clock.
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Moves the clock by the given duration, which can be negative.
+    pub fn advance(&self, duration: chrono::Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Default for TestClock {
+    /// Creates a clock showing the time of the system.
+    fn default() -> Self {
+        Self::new(Utc::now())
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Clock for TestClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
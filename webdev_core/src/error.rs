@@ -0,0 +1,398 @@
+//! Module that implements the error types shared by the services.
+pub use argon2::Error as ArgonError;
+use chrono::{DateTime, Utc};
+pub use reqwest::Error as ReqwestError;
+pub use reqwest_middleware::Error as ReqwestMiddlewareError;
+use serde::Serialize;
+pub use sqlx::Error as SqlxError;
+use utoipa::ToSchema;
+use warp::{http::StatusCode, reject::Reject};
+
+use crate::api::bad_words::BadWordsAPIBuildError;
+use crate::types::authentication::{AccountId, SessionId};
+use crate::types::moderation::AccountBan;
+use crate::types::quota::QuotaExceeded;
+use crate::types::{
+    answer::AnswerId,
+    attachment::AttachmentId,
+    notification::NotificationId,
+    question::{Question, QuestionId},
+};
+use crate::{api, types::pagination::PaginationParsingError};
+
+/// Error type for missing questions
+///
+/// This error is used when a question is not found in the database.
+#[derive(thiserror::Error, Debug)]
+#[error("{0:?}")]
+pub struct MissingQuestion(pub QuestionId);
+
+impl From<QuestionId> for MissingQuestion {
+    fn from(id: QuestionId) -> Self {
+        MissingQuestion(id)
+    }
+}
+
+/// Error type for missing answers
+///
+/// This error is used when an answer is not found in the database.
+#[derive(thiserror::Error, Debug)]
+#[error("{0:?}")]
+pub struct MissingAnswer(pub AnswerId);
+
+impl From<AnswerId> for MissingAnswer {
+    fn from(id: AnswerId) -> Self {
+        MissingAnswer(id)
+    }
+}
+
+/// Error type for missing attachments
+///
+/// This error is used when an attachment is not found in the database.
+#[derive(thiserror::Error, Debug)]
+#[error("{0:?}")]
+pub struct MissingAttachment(pub AttachmentId);
+
+impl From<AttachmentId> for MissingAttachment {
+    fn from(id: AttachmentId) -> Self {
+        MissingAttachment(id)
+    }
+}
+
+/// Error type for missing notifications
+///
+/// This error is used when a notification is not found in the database.
+#[derive(thiserror::Error, Debug)]
+#[error("{0:?}")]
+pub struct MissingNotification(pub NotificationId);
+
+impl From<NotificationId> for MissingNotification {
+    fn from(id: NotificationId) -> Self {
+        MissingNotification(id)
+    }
+}
+
+/// Error type for missing accounts
+///
+/// This error is used when an account is not found in the database.
+#[derive(thiserror::Error, Debug)]
+#[error("{0:?}")]
+pub struct MissingAccount(pub AccountId);
+
+impl From<AccountId> for MissingAccount {
+    fn from(id: AccountId) -> Self {
+        MissingAccount(id)
+    }
+}
+
+/// Error type for missing sessions
+///
+/// This error is used when a session of the account is not found in the database.
+#[derive(thiserror::Error, Debug)]
+#[error("{0:?}")]
+pub struct MissingSession(pub SessionId);
+
+impl From<SessionId> for MissingSession {
+    fn from(id: SessionId) -> Self {
+        MissingSession(id)
+    }
+}
+
+/// Error type for the invalid values of a field of the request body
+///
+/// The field is named by its path in the body, e.g. `tags[1]`, so the clients can show the message
+/// next to the value they sent.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[error("{field}: {message}")]
+pub struct FieldError {
+    /// The path of the field in the request body.
+    pub field: String,
+    /// Why the value of the field is invalid.
+    pub message: String,
+}
+
+impl FieldError {
+    /// Creates an error for the field.
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Error type for the API layer
+///
+/// This error is used when the API layer returns an error.
+/// API layer errors are errors that are returned from the external API.
+#[derive(thiserror::Error, Debug, Clone)]
+#[error("status: {status}, message: {message}")]
+pub struct APILayerError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl Reject for APILayerError {}
+
+impl APILayerError {
+    pub async fn transform_error(res: reqwest::Response) -> Self {
+        Self {
+            status: res.status(),
+            message: res.json::<api::APIResponse>().await.unwrap().message,
+        }
+    }
+}
+
+/// Error type for all errors returned by the service
+#[derive(thiserror::Error, Debug)]
+pub enum ServiceError {
+    /// Error for parsing any value from a string
+    #[error("cannot parse value: {0}")]
+    ParseError(#[from] std::num::ParseIntError),
+    /// Error for when migrations fail on startup
+    #[error("cannot run migrations: {0}")]
+    MigrationError(#[from] sqlx::migrate::MigrateError),
+    /// Error for when BadWordsAPI handle cannot be created
+    #[error("cannot create BadWordsAPI handle : {0}")]
+    BadWordsAPIBuildError(#[from] BadWordsAPIBuildError),
+    /// Error for failing to connect to the database
+    #[error("cannot connect to the database, invalid connection string (or credentials)")]
+    DatabaseConnectionError,
+    /// Error for failing to establish the minimum number of database connections on startup
+    #[error("cannot establish {0} database connections on startup: {1}")]
+    DatabaseWarmupError(u32, #[source] SqlxError),
+    /// Error while parsing the configuration file
+    #[error("cannot parse configuration file: {0}")]
+    ConfigParsingError(#[from] config::ConfigError),
+    /// Error for the missing or invalid keys of the tokens, see [AuthKeys](crate::types::authentication::AuthKeys)
+    #[error("invalid auth key: {0}")]
+    InvalidAuthKey(String),
+    /// Error for the invalid parameters of the password hashes, see [PasswordHashing](crate::types::authentication::PasswordHashing)
+    #[error("invalid password hashing parameters: {0}")]
+    InvalidPasswordHashing(String),
+    /// Error for ids that cannot be parsed from the request path
+    #[error("{0}")]
+    InvalidId(String),
+    /// Error for request bodies that are well formed, but contain invalid values
+    #[error("invalid request: {0}")]
+    ValidationError(String),
+    /// Error for request bodies with an invalid value of a single field, see [FieldError]
+    #[error("invalid request: {0}")]
+    InvalidField(FieldError),
+    /// Error for request bodies that cannot be decoded
+    #[error("cannot decode request body: {0}")]
+    BodyDecodeError(String),
+    /// Error for request bodies in an unsupported encoding
+    #[error("unsupported media type: {0:?}")]
+    UnsupportedMediaType(String),
+    /// Error for request bodies larger than the route accepts
+    #[error("request body is larger than {0} bytes")]
+    PayloadTooLarge(u64),
+    /// Error for invalid pagination parameters
+    #[error("pagination error: {0}")]
+    PaginationError(#[from] PaginationParsingError),
+    /// Error for missing questions, used when a question is not found in the database
+    #[error("question {0} not found")]
+    QuestionNotFound(#[from] MissingQuestion),
+    /// Error for missing answers, used when an answer is not found in the database
+    #[error("answer {0} not found")]
+    AnswerNotFound(#[from] MissingAnswer),
+    /// Error for missing attachments, used when an attachment is not found in the database
+    #[error("attachment {0} not found")]
+    AttachmentNotFound(#[from] MissingAttachment),
+    /// Error for missing notifications, used when a notification is not found in the database
+    #[error("notification {0} not found")]
+    NotificationNotFound(#[from] MissingNotification),
+    /// Error for missing accounts, used when an account is not found in the database
+    #[error("account {0} not found")]
+    AccountNotFound(#[from] MissingAccount),
+    /// Error for missing sessions, used when a session of the account is not found in the database
+    #[error("session {0} not found")]
+    SessionNotFound(#[from] MissingSession),
+    /// Error for missing tags, used when a tag is not found in the database by its name
+    #[error("tag {0:?} not found")]
+    TagNotFound(String),
+    /// Error for the unknown OAuth providers, and the ones that are not enabled
+    #[error("OAuth provider {0:?} not found")]
+    OAuthProviderNotFound(String),
+    /// Error for reading or writing the stored files
+    #[error("cannot access the file storage")]
+    StorageError(#[from] std::io::Error),
+    /// Error for invalid database queries
+    #[error("cannot update, invalid data")]
+    DatabaseQueryError(#[from] SqlxError),
+    /// Error returned by the Argon2 hashing library
+    #[error("argon2 error")]
+    ArgonLibraryError(#[from] ArgonError),
+    /// Error for Reqwest errors
+    #[error("external API error:")]
+    ReqwestAPIError(#[from] ReqwestError),
+    /// Error for Reqwest middleware errors
+    #[error("external API error")]
+    MiddlewareReqwestAPIError(#[from] ReqwestMiddlewareError),
+    /// Error for client errors
+    #[error("external client error")]
+    ClientError(APILayerError),
+    /// Error for server errors
+    #[error("external server error")]
+    ServerError(APILayerError),
+    #[error("wrong credentials combination")]
+    WrongPassword,
+    #[error("auth token could not be decyphered")]
+    CannotDecryptToken,
+    /// Error for the tokens revoked before they expire, e.g. on logout
+    #[error("auth token was revoked")]
+    TokenRevoked,
+    /// Error for the tokens issued with other claims, see [Session::CLAIMS_VERSION](crate::types::authentication::Session::CLAIMS_VERSION)
+    #[error("auth token is outdated, log in again")]
+    OutdatedToken,
+    /// Error for the refresh tokens that are missing, unknown, or whose session has expired
+    #[error("refresh token is invalid or expired, log in again")]
+    InvalidRefreshToken,
+    /// Error for the logins with an external provider that were denied, or could not be verified
+    #[error("OAuth login failed: {0}")]
+    OAuthFailed(String),
+    #[error("unauthorized, no premission to modify the resource")]
+    Unauthorized,
+    /// Error for the requests of the banned accounts
+    #[error(
+        "account is banned{}: {}",
+        .0.until.map(|until| format!(" until {}", until.to_rfc3339())).unwrap_or_default(),
+        .0.reason
+    )]
+    AccountBanned(AccountBan),
+    /// Error for the contributions over the daily limit of the account
+    #[error("daily limit of {} {} reached, resets at {}", .0.limit, .0.resource, .0.resets_at.to_rfc3339())]
+    QuotaExceeded(QuotaExceeded),
+    /// Error for the logins after too many failed ones, with the number of seconds to wait for
+    #[error("too many failed logins, retry in {0} seconds")]
+    TooManyLoginAttempts(u64),
+    /// Error for the logins to the accounts locked after too many consecutive failed logins, with
+    /// the time the account was locked at
+    #[error("account is locked since {} after too many failed logins, contact an administrator", .0.to_rfc3339())]
+    AccountLocked(DateTime<Utc>),
+    /// Error for the writes sent while the service is in maintenance mode
+    #[error("service is in maintenance mode, only reads are served, retry later")]
+    Maintenance,
+    /// Error for requests that conflict with the current state of the resource
+    #[error("conflict: {0}")]
+    Conflict(String),
+    /// Error for the updates based on another version of the resource than its current one, sent
+    /// in the `If-Match` header
+    #[error("version mismatch: the resource is at version {current}, but the update is based on version {expected}")]
+    VersionMismatch { current: i32, expected: i32 },
+    /// Error for new questions similar to the ones already asked, which can be asked anyway with `force=true`
+    #[error("similar questions were already asked: {}", question_ids(.0))]
+    SimilarQuestions(Vec<Question>),
+}
+
+/// Lists the ids of the questions, for the error messages.
+fn question_ids(questions: &[Question]) -> String {
+    let ids: Vec<_> = questions
+        .iter()
+        .filter_map(|question| question.id)
+        .map(|QuestionId(id)| id.to_string())
+        .collect();
+    ids.join(", ")
+}
+
+impl ServiceError {
+    /// Returns the status code for the error
+    ///
+    /// This function returns the status code for the error, based on the error type.
+    ///
+    /// # Returns
+    /// - `StatusCode`: The status code for the error
+    ///     - `StatusCode::BAD_REQUEST`: For `InvalidId`, `ValidationError`, `InvalidField` and `PaginationError`
+    ///     - `StatusCode::NOT_FOUND`: For `QuestionNotFound`, `AnswerNotFound`, `AttachmentNotFound`, `NotificationNotFound`, `AccountNotFound`, `SessionNotFound`, `TagNotFound` and `OAuthProviderNotFound`
+    ///     - `StatusCode::FORBIDDEN`: For `AccountBanned`
+    ///     - `StatusCode::CONFLICT`: For `Conflict` and `SimilarQuestions`
+    ///     - `StatusCode::PRECONDITION_FAILED`: For `VersionMismatch`
+    ///     - `StatusCode::LOCKED`: For `AccountLocked`
+    ///     - `StatusCode::TOO_MANY_REQUESTS`: For `QuotaExceeded` and `TooManyLoginAttempts`
+    ///     - `StatusCode::PAYLOAD_TOO_LARGE`: For `PayloadTooLarge`
+    ///     - `StatusCode::UNPROCESSABLE_ENTITY`: For `BodyDecodeError`
+    ///     - `StatusCode::UNSUPPORTED_MEDIA_TYPE`: For `UnsupportedMediaType`
+    ///     - `StatusCode::SERVICE_UNAVAILABLE`: For `Maintenance`
+    ///     - `StatusCode::INTERNAL_SERVER_ERROR`: For all other errors
+    pub fn status_code(&self) -> StatusCode {
+        use ServiceError::*;
+        match self {
+            ParseError(_) => StatusCode::BAD_REQUEST,
+            InvalidId(_) => StatusCode::BAD_REQUEST,
+            ValidationError(_) => StatusCode::BAD_REQUEST,
+            InvalidField(_) => StatusCode::BAD_REQUEST,
+            BodyDecodeError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            PaginationError(_) => StatusCode::BAD_REQUEST,
+            QuestionNotFound(_) => StatusCode::NOT_FOUND,
+            AnswerNotFound(_) => StatusCode::NOT_FOUND,
+            AttachmentNotFound(_) => StatusCode::NOT_FOUND,
+            NotificationNotFound(_) => StatusCode::NOT_FOUND,
+            AccountNotFound(_) => StatusCode::NOT_FOUND,
+            SessionNotFound(_) => StatusCode::NOT_FOUND,
+            TagNotFound(_) => StatusCode::NOT_FOUND,
+            OAuthProviderNotFound(_) => StatusCode::NOT_FOUND,
+            PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            StorageError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            DatabaseQueryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ArgonLibraryError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ReqwestAPIError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            MiddlewareReqwestAPIError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ClientError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            WrongPassword => StatusCode::UNAUTHORIZED,
+            CannotDecryptToken => StatusCode::UNAUTHORIZED,
+            TokenRevoked => StatusCode::UNAUTHORIZED,
+            OutdatedToken => StatusCode::UNAUTHORIZED,
+            InvalidRefreshToken => StatusCode::UNAUTHORIZED,
+            OAuthFailed(_) => StatusCode::UNAUTHORIZED,
+            Unauthorized => StatusCode::UNAUTHORIZED,
+            AccountBanned(_) => StatusCode::FORBIDDEN,
+            QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+            TooManyLoginAttempts(_) => StatusCode::TOO_MANY_REQUESTS,
+            AccountLocked(_) => StatusCode::LOCKED,
+            Maintenance => StatusCode::SERVICE_UNAVAILABLE,
+            Conflict(_) => StatusCode::CONFLICT,
+            SimilarQuestions(_) => StatusCode::CONFLICT,
+            VersionMismatch { .. } => StatusCode::PRECONDITION_FAILED,
+            MigrationError(_) => unreachable!("migration errors are not returned by the API"),
+            ConfigParsingError(_) => unreachable!("config parsing errors are not returned by the API"),
+            InvalidAuthKey(_) => unreachable!("auth key errors are not returned by the API"),
+            InvalidPasswordHashing(_) => unreachable!("password hashing errors are not returned by the API"),
+            BadWordsAPIBuildError(_) => unreachable!("bad words API errors are not returned by the API"),
+            DatabaseConnectionError => unreachable!("database connection errors are not returned by the API"),
+            DatabaseWarmupError(..) => unreachable!("database warmup errors are not returned by the API"),
+        }
+    }
+}
+
+impl Reject for ServiceError {}
+
+/// Error codes for PostgreSQL
+pub mod pg_error_codes {
+    pub const FOREIGN_KEY_VIOLATION: &str = "23503";
+    pub const UNIQUE_VIOLATION: &str = "23505";
+    pub const CHECK_VIOLATION: &str = "23514";
+
+    /// Returns the default error message for the error code
+    pub fn default_error_message(code: &str) -> &'static str {
+        match code {
+            FOREIGN_KEY_VIOLATION => "invalid data: referenced row not found",
+            UNIQUE_VIOLATION => "duplicate data",
+            CHECK_VIOLATION => "invalid data: constraint violation",
+            _ => "cannot update data",
+        }
+    }
+
+    /// Returns the machine-readable code and the message for the unique violation of the constraint
+    pub fn unique_violation(constraint: Option<&str>) -> (&'static str, &'static str) {
+        match constraint {
+            Some("question_votes_pkey" | "answer_votes_pkey") => {
+                ("already_voted", "the account has already voted in this direction")
+            }
+            Some("accounts_email_key") => ("email_taken", "email already registered"),
+            _ => ("duplicate_data", default_error_message(UNIQUE_VIOLATION)),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Module that implements the [EventBus], which notifies listeners about changes to resources.
+//!
+//! The [Store](crate::store::Store) publishes an [Event] after every successful write, so live
+//! update channels (e.g. WebSockets) can forward them to the clients.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::trace;
+
+use crate::types::answer::{Answer, AnswerId};
+use crate::types::question::{Question, QuestionId};
+
+/// An event describing a change to a resource.
+///
+/// Events are serialized with a `type` field naming the event, e.g.
+/// `{"type": "question_created", "question": {...}}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// A question was created.
+    QuestionCreated { question: Question },
+    /// A question was updated.
+    QuestionUpdated { question: Question },
+    /// A question was deleted.
+    QuestionDeleted { question_id: QuestionId },
+    /// An answer was added to a question.
+    AnswerCreated { answer: Answer },
+    /// An answer was updated.
+    AnswerUpdated { answer: Answer },
+    /// An answer was deleted.
+    AnswerDeleted {
+        answer_id: AnswerId,
+        question_id: QuestionId,
+    },
+}
+
+impl Event {
+    /// Returns the name of the event, the same one used for the `type` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Event::QuestionCreated { .. } => "question_created",
+            Event::QuestionUpdated { .. } => "question_updated",
+            Event::QuestionDeleted { .. } => "question_deleted",
+            Event::AnswerCreated { .. } => "answer_created",
+            Event::AnswerUpdated { .. } => "answer_updated",
+            Event::AnswerDeleted { .. } => "answer_deleted",
+        }
+    }
+
+    /// Returns the id of the question the event is about.
+    ///
+    /// For answer events, this is the id of the question the answer belongs to.
+    pub fn question_id(&self) -> Option<QuestionId> {
+        match self {
+            Event::QuestionCreated { question } | Event::QuestionUpdated { question } => question.id,
+            Event::QuestionDeleted { question_id } | Event::AnswerDeleted { question_id, .. } => Some(*question_id),
+            Event::AnswerCreated { answer } | Event::AnswerUpdated { answer } => answer.question_id,
+        }
+    }
+
+    /// Returns the tags of the question the event is about, if the event contains the question.
+    pub fn tags(&self) -> &[String] {
+        match self {
+            Event::QuestionCreated { question } | Event::QuestionUpdated { question } => {
+                question.tags.as_deref().unwrap_or_default()
+            }
+            _ => &[],
+        }
+    }
+}
+
+/// Broadcast channel for [Event]s.
+///
+/// Every subscriber receives every event published after it subscribed. Subscribers that fall
+/// behind by more than [EventBus::CAPACITY] events miss the oldest ones.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Event>,
+}
+
+impl EventBus {
+    /// The number of events kept for subscribers that are lagging behind.
+    pub const CAPACITY: usize = 256;
+
+    /// Creates a new event bus, without any subscribers.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(Self::CAPACITY);
+        Self { sender }
+    }
+
+    /// Publishes an event to all current subscribers.
+    ///
+    /// Publishing never fails, events published while nobody is subscribed are dropped.
+    pub fn publish(&self, event: Event) {
+        trace!(?event, "publishing event");
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribes to the events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,27 @@
+//! Core library of the webdev book services.
+//!
+//! This crate contains everything that is not tied to a particular transport:
+//! - `types` - The resource types and helper types used by the services.
+//! - `error` - The error types returned by the services.
+//! - `store` - The [Store](store::Store), a shared state backed by the database.
+//! - `storage` - The [Storage](storage::Storage) of the questions, the answers and the accounts, implemented by the store and in memory.
+//! - `events` - The [EventBus](events::EventBus), which notifies listeners about changes to resources.
+//! - `api` - Wrappers for the external APIs used by the services.
+//! - `clock` - The [Clock](clock::Clock) the services read the current time from.
+//! - `tokens` - The [TokenSigner](tokens::TokenSigner) the tokens of the sessions are issued with.
+//! - `cache` - The Redis cache used by the store, with the `redis-cache` feature.
+//! - `test_support` - Factories inserting the resources for the tests, with the `test-util` feature.
+#![warn(clippy::all)]
+
+pub mod api;
+#[cfg(feature = "redis-cache")]
+pub mod cache;
+pub mod clock;
+pub mod error;
+pub mod events;
+pub mod storage;
+pub mod store;
+#[cfg(feature = "test-util")]
+pub mod test_support;
+pub mod tokens;
+pub mod types;
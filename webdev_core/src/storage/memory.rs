@@ -0,0 +1,698 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::api::mock::MockProfanityFilter;
+use crate::api::profanity::ProfanityFilter;
+use crate::clock::{Clock, SystemClock};
+use crate::error::ServiceError;
+use crate::storage::Storage;
+use crate::tokens::{PasetoSigner, TokenSigner};
+use crate::types::answer::{AccountAnswer, Answer, AnswerId, AnswerOrder};
+use crate::types::authentication::{
+    normalize_email, Account, AccountId, AccountProfile, AuthKeys, Author, PasswordHashing, ProfileUpdate,
+    PublicProfile, Session,
+};
+use crate::types::markdown;
+use crate::types::moderation::{AccountRole, ModerationAction, ModerationLogEntry};
+use crate::types::pagination::Pagination;
+use crate::types::question::{Question, QuestionId, QuestionStatus};
+use crate::types::quota::{start_of_day, Contribution, Quotas};
+use crate::types::sanitize::Limits;
+
+/// [Storage] keeping the questions, the answers and the accounts in `HashMap`s, for the tests.
+///
+/// Available with the `test-util` feature. The clones share the resources, like the clones of the
+/// [Store](crate::store::Store), so the test can keep a clone to insert the resources the handlers
+/// read. The ids are taken from a single counter, so they are unique across the resources.
+///
+/// The operations behave like the methods of the [Store](crate::store::Store) with the same names,
+/// but without the events, the caches, the jobs, the revisions of the questions and the sessions
+/// of the store, so the content stored uncensored is never censored. The similar questions are the ones with the same title, ignoring the case, and the
+/// tokens are only verified by the token signer, see [MemStore::with_auth_keys]. The content is
+/// censored by a [MockProfanityFilter] by default.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use webdev_core::storage::{MemStore, Storage};
+/// use webdev_core::types::authentication::Account;
+/// use webdev_core::types::question::Question;
+///
+/// let store = MemStore::default();
+/// let account = Account::builder().email("alice@example.com".to_string()).password("hash".to_string());
+/// let alice = store.add_account(account.build().unwrap()).await.unwrap();
+/// let question = Question::builder()
+///     .title("Title".to_string())
+///     .content("Content".to_string())
+///     .build()
+///     .unwrap();
+/// let question = store.add_question(alice.id, question, true).await.unwrap();
+/// let question_id = question.id.unwrap();
+/// assert_eq!(store.get_question(question_id).await.unwrap().unwrap().author.unwrap().id, alice.id);
+/// assert!(store.delete_question(Some(alice.id), question_id, None).await.unwrap());
+/// assert!(store.get_question(question_id).await.unwrap().is_none());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemStore {
+    resources: Arc<Mutex<Resources>>,
+    clock: Arc<dyn Clock>,
+    profanity_filter: Arc<dyn ProfanityFilter>,
+    token_signer: Arc<dyn TokenSigner>,
+    quotas: Quotas,
+    limits: Limits,
+    max_page_size: i64,
+    password_hashing: PasswordHashing,
+}
+
+/// The resources of a [MemStore].
+#[derive(Debug, Default)]
+struct Resources {
+    last_id: i32,
+    questions: HashMap<QuestionId, Entry<Question>>,
+    answers: HashMap<AnswerId, Entry<Answer>>,
+    accounts: HashMap<AccountId, AccountProfile>,
+    moderation_log: Vec<ModerationLogEntry>,
+    /// The owners and the creation times of the questions, kept when they are deleted, for the quotas.
+    asked: Vec<(AccountId, DateTime<Utc>)>,
+    /// The owners and the creation times of the answers, kept when they are deleted, for the quotas.
+    answered: Vec<(AccountId, DateTime<Utc>)>,
+}
+
+/// A question or an answer of a [MemStore], with the account that owns it and its creation time.
+#[derive(Debug)]
+struct Entry<T> {
+    owner: AccountId,
+    created_on: DateTime<Utc>,
+    item: T,
+}
+
+impl Resources {
+    /// Returns the next id, for any resource.
+    fn next_id(&mut self) -> i32 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    /// Returns the author of a question or an answer, if the account exists.
+    fn author(&self, account_id: AccountId) -> Option<Author> {
+        self.accounts.get(&account_id).map(|profile| Author {
+            id: profile.id,
+            display_name: profile.display_name.clone(),
+        })
+    }
+
+    /// Records the change of a moderator to the question of another account.
+    fn log_moderation(
+        &mut self,
+        moderator_id: AccountId,
+        action: ModerationAction,
+        question_id: QuestionId,
+        owner_id: Option<AccountId>,
+        created_on: DateTime<Utc>,
+    ) {
+        self.moderation_log.push(ModerationLogEntry {
+            id: self.moderation_log.len() as i32 + 1,
+            moderator_id: Some(moderator_id),
+            action,
+            question_id,
+            owner_id,
+            created_on,
+        });
+    }
+
+    /// Records a contribution of the account, unless it reached the daily limit on the kind.
+    fn record_contribution(
+        &mut self,
+        quotas: &Quotas,
+        account_id: AccountId,
+        contribution: Contribution,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServiceError> {
+        let contributions = match contribution {
+            Contribution::Question => &mut self.asked,
+            Contribution::Answer => &mut self.answered,
+        };
+        let today = start_of_day(now);
+        let usage = contributions
+            .iter()
+            .filter(|&&(owner, created_on)| owner == account_id && created_on >= today)
+            .count() as i64;
+        quotas
+            .check(contribution, usage, now)
+            .map_err(ServiceError::QuotaExceeded)?;
+        contributions.push((account_id, now));
+        Ok(())
+    }
+
+    /// Returns the answers to the question.
+    fn answers_to(&self, question_id: QuestionId) -> impl Iterator<Item = &Entry<Answer>> {
+        self.answers
+            .values()
+            .filter(move |entry| entry.item.question_id == Some(question_id))
+    }
+
+    /// Returns the question with its author.
+    fn with_author(&self, entry: &Entry<Question>) -> Question {
+        Question {
+            author: self.author(entry.owner),
+            ..entry.item.clone()
+        }
+    }
+}
+
+impl Default for MemStore {
+    fn default() -> Self {
+        Self {
+            resources: Arc::default(),
+            clock: Arc::new(SystemClock),
+            profanity_filter: Arc::new(MockProfanityFilter::new()),
+            token_signer: Arc::new(PasetoSigner::new(AuthKeys::random())),
+            quotas: Quotas::default(),
+            limits: Limits::default(),
+            max_page_size: Pagination::MAX_LIMIT,
+            password_hashing: PasswordHashing::default(),
+        }
+    }
+}
+
+impl MemStore {
+    /// Sets the clock the creation times of the resources are read from.
+    pub fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
+    /// Sets the filter censoring the content posted by the users.
+    pub fn with_profanity_filter(self, profanity_filter: impl ProfanityFilter + 'static) -> Self {
+        Self {
+            profanity_filter: Arc::new(profanity_filter),
+            ..self
+        }
+    }
+
+    /// Sets the keys the tokens of the sessions are verified with.
+    pub fn with_auth_keys(self, auth_keys: AuthKeys) -> Self {
+        Self {
+            token_signer: Arc::new(PasetoSigner::new(auth_keys)),
+            ..self
+        }
+    }
+
+    /// Sets the daily limits on the contributions of every account.
+    pub fn with_quotas(self, quotas: Quotas) -> Self {
+        Self { quotas, ..self }
+    }
+
+    /// Sets the largest number of items a listing returns at once.
+    pub fn with_max_page_size(self, max_page_size: i64) -> Self {
+        Self { max_page_size, ..self }
+    }
+
+    /// Returns the resources, which stay usable if another test thread panicked holding them.
+    fn resources(&self) -> MutexGuard<'_, Resources> {
+        self.resources.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Returns the page of the items.
+fn page<T>(items: impl IntoIterator<Item = T>, pag: Pagination) -> Vec<T> {
+    let Pagination { offset, limit } = pag;
+    items
+        .into_iter()
+        .skip(usize::try_from(offset).unwrap_or_default())
+        .take(limit.map_or(usize::MAX, |limit| usize::try_from(limit).unwrap_or_default()))
+        .collect()
+}
+
+#[async_trait]
+impl Storage for MemStore {
+    fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    fn profanity_filter(&self) -> &dyn ProfanityFilter {
+        self.profanity_filter.as_ref()
+    }
+
+    fn quotas(&self) -> &Quotas {
+        &self.quotas
+    }
+
+    fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    fn password_hashing(&self) -> &PasswordHashing {
+        &self.password_hashing
+    }
+
+    fn token_signer(&self) -> &dyn TokenSigner {
+        self.token_signer.as_ref()
+    }
+
+    fn listing_cache(&self) -> Option<&moka::future::Cache<String, Arc<str>>> {
+        None
+    }
+
+    fn paginate(&self, pag: Pagination) -> Result<Pagination, ServiceError> {
+        Ok(pag.bounded(self.max_page_size)?)
+    }
+
+    async fn validate_session(&self, session: &Session) -> Result<(), ServiceError> {
+        // The tokens of the deleted accounts are rejected, like by the store
+        match self.resources().accounts.contains_key(&session.account_id) {
+            true => Ok(()),
+            false => Err(ServiceError::TokenRevoked),
+        }
+    }
+
+    async fn get_questions_with_total(
+        &self,
+        pag: Pagination,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<Question>, i64), ServiceError> {
+        let pag = self.paginate(pag)?;
+        let resources = self.resources();
+        let mut questions: Vec<_> = resources
+            .questions
+            .values()
+            .filter(|entry| since.is_none_or(|since| entry.created_on > since))
+            .collect();
+        questions.sort_by_key(|entry| std::cmp::Reverse((entry.created_on, entry.item.id.map(|id| id.0))));
+        let total = questions.len() as i64;
+        let questions = page(questions, pag)
+            .into_iter()
+            .map(|entry| Question {
+                answer_count: entry.item.id.map(|id| resources.answers_to(id).count() as i64),
+                ..resources.with_author(entry)
+            })
+            .collect();
+        Ok((questions, total))
+    }
+
+    async fn get_question(&self, question_id: QuestionId) -> Result<Option<Question>, ServiceError> {
+        let resources = self.resources();
+        Ok(resources
+            .questions
+            .get(&question_id)
+            .map(|entry| resources.with_author(entry)))
+    }
+
+    async fn get_question_html(&self, question_id: QuestionId) -> Result<Option<String>, ServiceError> {
+        Ok(self
+            .resources()
+            .questions
+            .get(&question_id)
+            .map(|entry| markdown::to_html(&entry.item.content)))
+    }
+
+    async fn get_similar_questions(&self, title: &str, limit: i64) -> Result<Vec<Question>, ServiceError> {
+        let resources = self.resources();
+        let mut questions: Vec<_> = resources
+            .questions
+            .values()
+            .filter(|entry| entry.item.title.to_lowercase() == title.to_lowercase())
+            .collect();
+        questions.sort_by_key(|entry| entry.item.id.map(|id| id.0));
+        Ok(page(
+            questions,
+            Pagination {
+                offset: 0,
+                limit: Some(limit),
+            },
+        )
+        .into_iter()
+        .map(|entry| resources.with_author(entry))
+        .collect())
+    }
+
+    async fn add_question(
+        &self,
+        account_id: AccountId,
+        question: Question,
+        _censored: bool,
+    ) -> Result<Question, ServiceError> {
+        let mut resources = self.resources();
+        let now = self.clock.now();
+        resources.record_contribution(&self.quotas, account_id, Contribution::Question, now)?;
+        let question_id = QuestionId(resources.next_id());
+        let question = Question {
+            id: Some(question_id),
+            content_html: None,
+            tags: question.tags.filter(|tags| !tags.is_empty()),
+            version: 1,
+            status: QuestionStatus::Open,
+            answer_count: None,
+            score: Some(0),
+            author: None,
+            ..question
+        };
+        let entry = Entry {
+            owner: account_id,
+            created_on: now,
+            item: question.clone(),
+        };
+        resources.questions.insert(question_id, entry);
+        Ok(question)
+    }
+
+    async fn update_question(
+        &self,
+        account_id: Option<AccountId>,
+        question: Question,
+        question_id: QuestionId,
+        expected_version: Option<i32>,
+        _censored: bool,
+        moderator_id: Option<AccountId>,
+    ) -> Result<Question, ServiceError> {
+        let mut resources = self.resources();
+        let Some(entry) = resources.questions.get_mut(&question_id) else {
+            return Err(ServiceError::QuestionNotFound(question_id.into()));
+        };
+        let current = entry.item.version;
+        match expected_version {
+            Some(expected) if expected != current => return Err(ServiceError::VersionMismatch { current, expected }),
+            _ if Some(entry.owner) != account_id => return Err(ServiceError::QuestionNotFound(question_id.into())),
+            _ => {}
+        }
+        entry.item = Question {
+            title: question.title,
+            content: question.content,
+            tags: question.tags.filter(|tags| !tags.is_empty()),
+            version: current + 1,
+            ..entry.item.clone()
+        };
+        let question = entry.item.clone();
+        if let Some(moderator_id) = moderator_id {
+            let now = self.clock.now();
+            resources.log_moderation(
+                moderator_id,
+                ModerationAction::UpdateQuestion,
+                question_id,
+                account_id,
+                now,
+            );
+        }
+        Ok(question)
+    }
+
+    async fn delete_question(
+        &self,
+        account_id: Option<AccountId>,
+        question_id: QuestionId,
+        moderator_id: Option<AccountId>,
+    ) -> Result<bool, ServiceError> {
+        let mut resources = self.resources();
+        if !matches!(resources.questions.get(&question_id), Some(entry) if Some(entry.owner) == account_id) {
+            return Ok(false);
+        }
+        if let Some(moderator_id) = moderator_id {
+            let now = self.clock.now();
+            resources.log_moderation(
+                moderator_id,
+                ModerationAction::DeleteQuestion,
+                question_id,
+                account_id,
+                now,
+            );
+        }
+        // The answers are deleted with the question, like by the foreign key of the table `answers`
+        resources.questions.remove(&question_id);
+        resources
+            .answers
+            .retain(|_, entry| entry.item.question_id != Some(question_id));
+        Ok(true)
+    }
+
+    async fn is_question_owner(&self, question_id: QuestionId, account_id: AccountId) -> Result<bool, ServiceError> {
+        Ok(self.get_question_owner(question_id).await? == Some(account_id))
+    }
+
+    async fn get_question_owner(&self, question_id: QuestionId) -> Result<Option<AccountId>, ServiceError> {
+        match self.resources().questions.get(&question_id) {
+            Some(entry) => Ok(Some(entry.owner)),
+            None => Err(ServiceError::QuestionNotFound(question_id.into())),
+        }
+    }
+
+    async fn count_questions_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError> {
+        Ok(self
+            .resources()
+            .asked
+            .iter()
+            .filter(|&&(owner, created_on)| owner == account_id && created_on >= since)
+            .count() as i64)
+    }
+
+    async fn add_answer(
+        &self,
+        account_id: AccountId,
+        question_id: QuestionId,
+        content: String,
+        _censored: bool,
+    ) -> Result<Answer, ServiceError> {
+        let mut resources = self.resources();
+        match resources.questions.get(&question_id) {
+            Some(entry) if entry.item.status == QuestionStatus::Open => {}
+            Some(_) => {
+                return Err(ServiceError::Conflict(format!(
+                    "question {} is closed to new answers",
+                    question_id.0
+                )))
+            }
+            None => return Err(ServiceError::QuestionNotFound(question_id.into())),
+        }
+        let now = self.clock.now();
+        resources.record_contribution(&self.quotas, account_id, Contribution::Answer, now)?;
+        let answer_id = AnswerId(resources.next_id());
+        let answer = Answer {
+            id: Some(answer_id),
+            content,
+            content_html: None,
+            question_id: Some(question_id),
+            accepted: false,
+            score: Some(0),
+            author: None,
+        };
+        let entry = Entry {
+            owner: account_id,
+            created_on: now,
+            item: answer.clone(),
+        };
+        resources.answers.insert(answer_id, entry);
+        Ok(answer)
+    }
+
+    async fn get_answer(&self, answer_id: AnswerId) -> Result<Option<Answer>, ServiceError> {
+        let resources = self.resources();
+        Ok(resources.answers.get(&answer_id).map(|entry| Answer {
+            author: resources.author(entry.owner),
+            ..entry.item.clone()
+        }))
+    }
+
+    async fn get_answers(
+        &self,
+        question_id: QuestionId,
+        order: AnswerOrder,
+        pag: Pagination,
+    ) -> Result<Vec<Answer>, ServiceError> {
+        let pag = self.paginate(pag)?;
+        let resources = self.resources();
+        let mut answers: Vec<_> = resources
+            .answers_to(question_id)
+            .map(|entry| Answer {
+                author: resources.author(entry.owner),
+                ..entry.item.clone()
+            })
+            .collect();
+        match order {
+            AnswerOrder::Newest => answers.sort_by_key(|answer| std::cmp::Reverse(answer.id.map(|id| id.0))),
+            AnswerOrder::Oldest => answers.sort_by_key(|answer| answer.id.map(|id| id.0)),
+            AnswerOrder::Score => answers.sort_by_key(|answer| {
+                (
+                    !answer.accepted,
+                    std::cmp::Reverse(answer.score),
+                    answer.id.map(|id| id.0),
+                )
+            }),
+        }
+        Ok(page(answers, pag))
+    }
+
+    async fn get_answers_html(&self, answer_ids: Vec<AnswerId>) -> Result<HashMap<AnswerId, String>, ServiceError> {
+        let resources = self.resources();
+        Ok(answer_ids
+            .into_iter()
+            .filter_map(|answer_id| {
+                let entry = resources.answers.get(&answer_id)?;
+                Some((answer_id, markdown::to_html(&entry.item.content)))
+            })
+            .collect())
+    }
+
+    async fn get_account_answers(
+        &self,
+        account_id: AccountId,
+        pag: Pagination,
+    ) -> Result<Vec<AccountAnswer>, ServiceError> {
+        let pag = self.paginate(pag)?;
+        let resources = self.resources();
+        let mut answers: Vec<_> = resources
+            .answers
+            .iter()
+            .filter(|(_, entry)| entry.owner == account_id)
+            .filter_map(|(answer_id, entry)| {
+                let question_id = entry.item.question_id?;
+                Some(AccountAnswer {
+                    id: *answer_id,
+                    content: entry.item.content.clone(),
+                    question_id,
+                    question_title: resources.questions.get(&question_id)?.item.title.clone(),
+                    accepted: entry.item.accepted,
+                })
+            })
+            .collect();
+        answers.sort_by_key(|answer| std::cmp::Reverse(answer.id.0));
+        Ok(page(answers, pag))
+    }
+
+    async fn update_answer(
+        &self,
+        account_id: AccountId,
+        answer_id: AnswerId,
+        content: String,
+        _censored: bool,
+    ) -> Result<Answer, ServiceError> {
+        let mut resources = self.resources();
+        match resources.answers.get_mut(&answer_id) {
+            Some(entry) if entry.owner == account_id => {
+                entry.item.content = content;
+                Ok(entry.item.clone())
+            }
+            _ => Err(ServiceError::AnswerNotFound(answer_id.into())),
+        }
+    }
+
+    async fn accept_answer(
+        &self,
+        question_id: QuestionId,
+        answer_id: AnswerId,
+    ) -> Result<Option<Answer>, ServiceError> {
+        let mut resources = self.resources();
+        if !matches!(resources.answers.get(&answer_id), Some(entry) if entry.item.question_id == Some(question_id)) {
+            return Ok(None);
+        }
+        for (id, entry) in resources.answers.iter_mut() {
+            if entry.item.question_id == Some(question_id) {
+                entry.item.accepted = *id == answer_id;
+            }
+        }
+        Ok(resources.answers.get(&answer_id).map(|entry| entry.item.clone()))
+    }
+
+    async fn delete_answer(&self, account_id: AccountId, answer_id: AnswerId) -> Result<bool, ServiceError> {
+        let mut resources = self.resources();
+        if !matches!(resources.answers.get(&answer_id), Some(entry) if entry.owner == account_id) {
+            return Ok(false);
+        }
+        resources.answers.remove(&answer_id);
+        Ok(true)
+    }
+
+    async fn is_answer_owner(&self, answer_id: AnswerId, account_id: AccountId) -> Result<bool, ServiceError> {
+        match self.resources().answers.get(&answer_id) {
+            Some(entry) => Ok(entry.owner == account_id),
+            None => Err(ServiceError::AnswerNotFound(answer_id.into())),
+        }
+    }
+
+    async fn count_answers_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError> {
+        Ok(self
+            .resources()
+            .answered
+            .iter()
+            .filter(|&&(owner, created_on)| owner == account_id && created_on >= since)
+            .count() as i64)
+    }
+
+    async fn get_authors(&self, account_ids: Vec<AccountId>) -> Result<HashMap<AccountId, Author>, ServiceError> {
+        let resources = self.resources();
+        Ok(account_ids
+            .into_iter()
+            .filter_map(|account_id| Some((account_id, resources.author(account_id)?)))
+            .collect())
+    }
+
+    async fn add_account(&self, account: Account) -> Result<AccountProfile, ServiceError> {
+        let email = normalize_email(&account.email);
+        let mut resources = self.resources();
+        if resources.accounts.values().any(|profile| profile.email == email) {
+            return Err(ServiceError::Conflict("email already registered".to_string()));
+        }
+        let account_id = AccountId(resources.next_id());
+        let profile = AccountProfile {
+            id: account_id,
+            email,
+            created_at: self.clock.now(),
+            display_name: None,
+            bio: None,
+            website: None,
+        };
+        resources.accounts.insert(account_id, profile.clone());
+        Ok(profile)
+    }
+
+    async fn get_account_by_id(&self, account_id: AccountId) -> Result<AccountProfile, ServiceError> {
+        match self.resources().accounts.get(&account_id) {
+            Some(profile) => Ok(profile.clone()),
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    async fn get_account_role(&self, account_id: AccountId) -> Result<AccountRole, ServiceError> {
+        // The roles are not kept, every account is a user
+        match self.resources().accounts.contains_key(&account_id) {
+            true => Ok(AccountRole::default()),
+            false => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    async fn update_profile(
+        &self,
+        account_id: AccountId,
+        profile: &ProfileUpdate,
+    ) -> Result<AccountProfile, ServiceError> {
+        let mut resources = self.resources();
+        let Some(account) = resources.accounts.get_mut(&account_id) else {
+            return Err(ServiceError::AccountNotFound(account_id.into()));
+        };
+        account.display_name = profile.display_name.clone();
+        account.bio = profile.bio.clone();
+        account.website = profile.website.clone();
+        Ok(account.clone())
+    }
+
+    async fn get_public_profile(&self, account_id: AccountId) -> Result<PublicProfile, ServiceError> {
+        let resources = self.resources();
+        let Some(account) = resources.accounts.get(&account_id) else {
+            return Err(ServiceError::AccountNotFound(account_id.into()));
+        };
+        let count = |owners: Vec<AccountId>| owners.into_iter().filter(|owner| *owner == account_id).count() as i64;
+        Ok(PublicProfile {
+            id: account.id,
+            display_name: account.display_name.clone(),
+            bio: account.bio.clone(),
+            website: account.website.clone(),
+            created_at: account.created_at,
+            question_count: count(resources.questions.values().map(|entry| entry.owner).collect()),
+            answer_count: count(resources.answers.values().map(|entry| entry.owner).collect()),
+        })
+    }
+}
@@ -0,0 +1,404 @@
+//! Module for the storage of the questions, the answers and the accounts.
+//!
+//! The [Storage] trait is the part of the [Store] the handlers of these resources depend on, see
+//! `questions::storage_filter`, `answers::storage_filter` and `authentication::storage_filter`, so
+//! they can be served from another backend than the database:
+//! - [Store], which keeps them in PostgreSQL, and is the one the services use
+//! - [MemStore], which keeps them in memory, for the tests, with the `test-util` feature
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::api::profanity::ProfanityFilter;
+use crate::clock::Clock;
+use crate::error::ServiceError;
+use crate::store::Store;
+use crate::tokens::TokenSigner;
+use crate::types::answer::{AccountAnswer, Answer, AnswerId, AnswerOrder};
+use crate::types::authentication::{
+    Account, AccountId, AccountProfile, Author, PasswordHashing, ProfileUpdate, PublicProfile, Session,
+};
+use crate::types::moderation::AccountRole;
+use crate::types::pagination::Pagination;
+use crate::types::question::{Question, QuestionId};
+use crate::types::quota::Quotas;
+use crate::types::sanitize::Limits;
+
+#[cfg(feature = "test-util")]
+mod memory;
+
+#[cfg(feature = "test-util")]
+pub use self::memory::MemStore;
+
+/// Storage of the questions, the answers and the accounts.
+///
+/// The operations behave like the methods and the fields of the [Store] with the same names, which
+/// document them, and return the same errors for the missing resources.
+#[async_trait]
+pub trait Storage: Clone + Send + Sync + 'static {
+    /// Returns the clock the current time is read from, see [Store::clock].
+    fn clock(&self) -> &dyn Clock;
+
+    /// Returns the filter censoring the content posted by the users, see [Store::profanity_filter].
+    fn profanity_filter(&self) -> &dyn ProfanityFilter;
+
+    /// Returns the daily limits on the contributions of every account, see [Store::quotas].
+    fn quotas(&self) -> &Quotas;
+
+    /// Returns the maximum lengths of the text posted by the users, see [Store::limits].
+    fn limits(&self) -> &Limits;
+
+    /// Returns the parameters the passwords are hashed with, see [Store::password_hashing].
+    fn password_hashing(&self) -> &PasswordHashing;
+
+    /// Returns the signer the tokens of the sessions are verified with, see [Store::token_signer].
+    fn token_signer(&self) -> &dyn TokenSigner;
+
+    /// Returns the cache of the serialized listings of the questions, see [Store::listing_cache],
+    /// or `None` if the listings are not cached.
+    fn listing_cache(&self) -> Option<&moka::future::Cache<String, Arc<str>>>;
+
+    /// Bounds the pagination of a listing by the maximum page size, see [Store::paginate].
+    fn paginate(&self, pag: Pagination) -> Result<Pagination, ServiceError>;
+
+    /// Checks that the session of a verified token is still valid, see [Store::validate_session].
+    async fn validate_session(&self, session: &Session) -> Result<(), ServiceError>;
+
+    /// Returns a page of the questions and their total number, see [Store::get_questions_with_total].
+    async fn get_questions_with_total(
+        &self,
+        pag: Pagination,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<Question>, i64), ServiceError>;
+
+    /// Returns the question, with its author, or `None` if it does not exist, see [Store::get_question].
+    async fn get_question(&self, question_id: QuestionId) -> Result<Option<Question>, ServiceError>;
+
+    /// Returns the content of the question rendered to HTML, see [Store::get_question_html].
+    async fn get_question_html(&self, question_id: QuestionId) -> Result<Option<String>, ServiceError>;
+
+    /// Returns the questions whose titles are similar to the title, see [Store::get_similar_questions].
+    async fn get_similar_questions(&self, title: &str, limit: i64) -> Result<Vec<Question>, ServiceError>;
+
+    /// Adds the question of the account, and returns it with its id, see [Store::add_question].
+    ///
+    /// The question is censored later if `censored` is false, e.g. as the profanity filter is unavailable.
+    async fn add_question(
+        &self,
+        account_id: AccountId,
+        question: Question,
+        censored: bool,
+    ) -> Result<Question, ServiceError>;
+
+    /// Updates the question of the owner, recording the change of a moderator in the moderation
+    /// log, see [Store::update_question].
+    async fn update_question(
+        &self,
+        account_id: Option<AccountId>,
+        question: Question,
+        question_id: QuestionId,
+        expected_version: Option<i32>,
+        censored: bool,
+        moderator_id: Option<AccountId>,
+    ) -> Result<Question, ServiceError>;
+
+    /// Deletes the question if the account asked it, recording the change of a moderator in the
+    /// moderation log, see [Store::delete_question].
+    async fn delete_question(
+        &self,
+        account_id: Option<AccountId>,
+        question_id: QuestionId,
+        moderator_id: Option<AccountId>,
+    ) -> Result<bool, ServiceError>;
+
+    /// Checks if the account asked the question, see [Store::is_question_owner].
+    async fn is_question_owner(&self, question_id: QuestionId, account_id: AccountId) -> Result<bool, ServiceError>;
+
+    /// Returns the account that asked the question, see [Store::get_question_owner].
+    async fn get_question_owner(&self, question_id: QuestionId) -> Result<Option<AccountId>, ServiceError>;
+
+    /// Counts the questions asked by the account since the time, including the deleted ones, see
+    /// [Store::count_questions_since].
+    async fn count_questions_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError>;
+
+    /// Adds the answer of the account to the open question, see [Store::add_answer].
+    async fn add_answer(
+        &self,
+        account_id: AccountId,
+        question_id: QuestionId,
+        content: String,
+        censored: bool,
+    ) -> Result<Answer, ServiceError>;
+
+    /// Returns the answer, with its author, or `None` if it does not exist, see [Store::get_answer].
+    async fn get_answer(&self, answer_id: AnswerId) -> Result<Option<Answer>, ServiceError>;
+
+    /// Returns a page of the answers to the question, in the order, see [Store::get_answers].
+    async fn get_answers(
+        &self,
+        question_id: QuestionId,
+        order: AnswerOrder,
+        pag: Pagination,
+    ) -> Result<Vec<Answer>, ServiceError>;
+
+    /// Returns the contents of the answers rendered to HTML, see [Store::get_answers_html].
+    async fn get_answers_html(&self, answer_ids: Vec<AnswerId>) -> Result<HashMap<AnswerId, String>, ServiceError>;
+
+    /// Returns a page of the answers of the account, see [Store::get_account_answers].
+    async fn get_account_answers(
+        &self,
+        account_id: AccountId,
+        pag: Pagination,
+    ) -> Result<Vec<AccountAnswer>, ServiceError>;
+
+    /// Updates the answer of the account, see [Store::update_answer].
+    async fn update_answer(
+        &self,
+        account_id: AccountId,
+        answer_id: AnswerId,
+        content: String,
+        censored: bool,
+    ) -> Result<Answer, ServiceError>;
+
+    /// Marks the answer to the question as accepted, see [Store::accept_answer].
+    async fn accept_answer(&self, question_id: QuestionId, answer_id: AnswerId)
+        -> Result<Option<Answer>, ServiceError>;
+
+    /// Deletes the answer if the account wrote it, see [Store::delete_answer].
+    async fn delete_answer(&self, account_id: AccountId, answer_id: AnswerId) -> Result<bool, ServiceError>;
+
+    /// Checks if the account wrote the answer, see [Store::is_answer_owner].
+    async fn is_answer_owner(&self, answer_id: AnswerId, account_id: AccountId) -> Result<bool, ServiceError>;
+
+    /// Counts the answers posted by the account since the time, including the deleted ones, see
+    /// [Store::count_answers_since].
+    async fn count_answers_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError>;
+
+    /// Returns the authors of the accounts, skipping the missing ones, see [Store::get_authors].
+    async fn get_authors(&self, account_ids: Vec<AccountId>) -> Result<HashMap<AccountId, Author>, ServiceError>;
+
+    /// Adds the account, with its password already hashed, see [Store::add_account].
+    async fn add_account(&self, account: Account) -> Result<AccountProfile, ServiceError>;
+
+    /// Returns the profile of the account, see [Store::get_account_by_id].
+    async fn get_account_by_id(&self, account_id: AccountId) -> Result<AccountProfile, ServiceError>;
+
+    /// Returns the role of the account, see [Store::get_account_role].
+    async fn get_account_role(&self, account_id: AccountId) -> Result<AccountRole, ServiceError>;
+
+    /// Replaces the profile of the account, see [Store::update_profile].
+    async fn update_profile(
+        &self,
+        account_id: AccountId,
+        profile: &ProfileUpdate,
+    ) -> Result<AccountProfile, ServiceError>;
+
+    /// Returns the public profile of the account, see [Store::get_public_profile].
+    async fn get_public_profile(&self, account_id: AccountId) -> Result<PublicProfile, ServiceError>;
+}
+
+#[async_trait]
+impl Storage for Store {
+    fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    fn profanity_filter(&self) -> &dyn ProfanityFilter {
+        self.profanity_filter.as_ref()
+    }
+
+    fn quotas(&self) -> &Quotas {
+        &self.quotas
+    }
+
+    fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    fn password_hashing(&self) -> &PasswordHashing {
+        &self.password_hashing
+    }
+
+    fn token_signer(&self) -> &dyn TokenSigner {
+        self.token_signer.as_ref()
+    }
+
+    fn listing_cache(&self) -> Option<&moka::future::Cache<String, Arc<str>>> {
+        Some(&self.listing_cache)
+    }
+
+    fn paginate(&self, pag: Pagination) -> Result<Pagination, ServiceError> {
+        Store::paginate(self, pag)
+    }
+
+    async fn validate_session(&self, session: &Session) -> Result<(), ServiceError> {
+        Store::validate_session(self, session).await
+    }
+
+    async fn get_questions_with_total(
+        &self,
+        pag: Pagination,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<Question>, i64), ServiceError> {
+        Store::get_questions_with_total(self, pag, since).await
+    }
+
+    async fn get_question(&self, question_id: QuestionId) -> Result<Option<Question>, ServiceError> {
+        Store::get_question(self, question_id).await
+    }
+
+    async fn get_question_html(&self, question_id: QuestionId) -> Result<Option<String>, ServiceError> {
+        Store::get_question_html(self, question_id).await
+    }
+
+    async fn get_similar_questions(&self, title: &str, limit: i64) -> Result<Vec<Question>, ServiceError> {
+        Store::get_similar_questions(self, title, limit).await
+    }
+
+    async fn add_question(
+        &self,
+        account_id: AccountId,
+        question: Question,
+        censored: bool,
+    ) -> Result<Question, ServiceError> {
+        Store::add_question(self, account_id, question, censored).await
+    }
+
+    async fn update_question(
+        &self,
+        account_id: Option<AccountId>,
+        question: Question,
+        question_id: QuestionId,
+        expected_version: Option<i32>,
+        censored: bool,
+        moderator_id: Option<AccountId>,
+    ) -> Result<Question, ServiceError> {
+        Store::update_question(
+            self,
+            account_id,
+            question,
+            question_id,
+            expected_version,
+            censored,
+            moderator_id,
+        )
+        .await
+    }
+
+    async fn delete_question(
+        &self,
+        account_id: Option<AccountId>,
+        question_id: QuestionId,
+        moderator_id: Option<AccountId>,
+    ) -> Result<bool, ServiceError> {
+        Store::delete_question(self, account_id, question_id, moderator_id).await
+    }
+
+    async fn is_question_owner(&self, question_id: QuestionId, account_id: AccountId) -> Result<bool, ServiceError> {
+        Store::is_question_owner(self, question_id, account_id).await
+    }
+
+    async fn get_question_owner(&self, question_id: QuestionId) -> Result<Option<AccountId>, ServiceError> {
+        Store::get_question_owner(self, question_id).await
+    }
+
+    async fn count_questions_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError> {
+        Store::count_questions_since(self, account_id, since).await
+    }
+
+    async fn add_answer(
+        &self,
+        account_id: AccountId,
+        question_id: QuestionId,
+        content: String,
+        censored: bool,
+    ) -> Result<Answer, ServiceError> {
+        Store::add_answer(self, account_id, question_id, content, censored).await
+    }
+
+    async fn get_answer(&self, answer_id: AnswerId) -> Result<Option<Answer>, ServiceError> {
+        Store::get_answer(self, answer_id).await
+    }
+
+    async fn get_answers(
+        &self,
+        question_id: QuestionId,
+        order: AnswerOrder,
+        pag: Pagination,
+    ) -> Result<Vec<Answer>, ServiceError> {
+        Store::get_answers(self, question_id, order, pag).await
+    }
+
+    async fn get_answers_html(&self, answer_ids: Vec<AnswerId>) -> Result<HashMap<AnswerId, String>, ServiceError> {
+        Store::get_answers_html(self, answer_ids).await
+    }
+
+    async fn get_account_answers(
+        &self,
+        account_id: AccountId,
+        pag: Pagination,
+    ) -> Result<Vec<AccountAnswer>, ServiceError> {
+        Store::get_account_answers(self, account_id, pag).await
+    }
+
+    async fn update_answer(
+        &self,
+        account_id: AccountId,
+        answer_id: AnswerId,
+        content: String,
+        censored: bool,
+    ) -> Result<Answer, ServiceError> {
+        Store::update_answer(self, account_id, answer_id, content, censored).await
+    }
+
+    async fn accept_answer(
+        &self,
+        question_id: QuestionId,
+        answer_id: AnswerId,
+    ) -> Result<Option<Answer>, ServiceError> {
+        Store::accept_answer(self, question_id, answer_id).await
+    }
+
+    async fn delete_answer(&self, account_id: AccountId, answer_id: AnswerId) -> Result<bool, ServiceError> {
+        Store::delete_answer(self, account_id, answer_id).await
+    }
+
+    async fn is_answer_owner(&self, answer_id: AnswerId, account_id: AccountId) -> Result<bool, ServiceError> {
+        Store::is_answer_owner(self, answer_id, account_id).await
+    }
+
+    async fn count_answers_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError> {
+        Store::count_answers_since(self, account_id, since).await
+    }
+
+    async fn get_authors(&self, account_ids: Vec<AccountId>) -> Result<HashMap<AccountId, Author>, ServiceError> {
+        Store::get_authors(self, account_ids).await
+    }
+
+    async fn add_account(&self, account: Account) -> Result<AccountProfile, ServiceError> {
+        Store::add_account(self, account).await
+    }
+
+    async fn get_account_by_id(&self, account_id: AccountId) -> Result<AccountProfile, ServiceError> {
+        Store::get_account_by_id(self, account_id).await
+    }
+
+    async fn get_account_role(&self, account_id: AccountId) -> Result<AccountRole, ServiceError> {
+        Store::get_account_role(self, account_id).await
+    }
+
+    async fn update_profile(
+        &self,
+        account_id: AccountId,
+        profile: &ProfileUpdate,
+    ) -> Result<AccountProfile, ServiceError> {
+        Store::update_profile(self, account_id, profile).await
+    }
+
+    async fn get_public_profile(&self, account_id: AccountId) -> Result<PublicProfile, ServiceError> {
+        Store::get_public_profile(self, account_id).await
+    }
+}
@@ -0,0 +1,4425 @@
+//! Module that implements the [Store], a shared state for the application.
+
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::postgres::{PgArguments, PgConnectOptions, PgPool, PgPoolOptions, PgRow};
+use sqlx::query::Query;
+use sqlx::{PgConnection, Postgres, Row};
+use tracing::{error, info, instrument, trace, warn};
+
+use crate::api::bad_words::BadWordsAPI;
+use crate::api::profanity::ProfanityFilter;
+use crate::clock::{Clock, SystemClock};
+use crate::error::{pg_error_codes, ServiceError};
+use crate::events::{Event, EventBus};
+use crate::tokens::{PasetoSigner, TokenSigner};
+use crate::types::answer::{AccountAnswer, AnswerId, AnswerOrder};
+use crate::types::attachment::{Attachment, AttachmentId};
+use crate::types::authentication::{
+    normalize_email, Account, AccountContent, AccountId, AccountProfile, ActiveSession, AuthKeys, Author, FailedLogins,
+    OAuthConfig, PasswordHashing, ProfileUpdate, PublicProfile, Session, SessionId, SessionLifetimes,
+};
+use crate::types::badge::{AwardedBadge, Badge};
+use crate::types::job::{Job, JobId, JobStatus, Task};
+use crate::types::markdown;
+use crate::types::moderation::{AccountBan, AccountRole, ModerationAction, ModerationLogEntry};
+use crate::types::notification::{Notification, NotificationId};
+use crate::types::question::{QuestionId, QuestionRevision, QuestionStatus};
+use crate::types::quota::{start_of_day, Contribution, Quotas};
+use crate::types::sanitize::Limits;
+use crate::types::tag::{Tag, TagId, TagWindow};
+use crate::types::vote::{Score, VoteDirection};
+use crate::types::webhook::{Webhook, WebhookId};
+use crate::types::{answer::Answer, pagination::Pagination, question::Question};
+
+/// Options for the database connection pool of the [Store].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// The number of connections kept open, which are established before the store is built.
+    pub min_connections: u32,
+    /// The maximum number of connections open at the same time.
+    pub max_connections: u32,
+    /// Whether the connections are established only when they are first needed.
+    ///
+    /// A lazy pool does not fail when the database is not available yet, the queries fail until it is.
+    /// The connections are not established upfront, so `min_connections` is ignored.
+    pub lazy: bool,
+    /// The number of prepared statements cached by each connection.
+    ///
+    /// The queries of the store are static, so each of them is prepared once per connection,
+    /// and reused afterwards, as long as the cache is large enough to hold all of them.
+    /// At least one statement is cached, as the statements are only closed when they are evicted
+    /// from the cache, so without it every query would leave its statement prepared on the connection.
+    pub statement_cache_capacity: usize,
+    /// Whether a connection is checked to be alive before it is taken from the pool.
+    ///
+    /// Disabling the check saves a round trip per request, at the cost of the queries failing on
+    /// connections closed by the database in the meantime.
+    pub test_before_acquire: bool,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            min_connections: 0,
+            max_connections: 5,
+            lazy: false,
+            statement_cache_capacity: 100,
+            test_before_acquire: true,
+        }
+    }
+}
+
+/// The tags of the question in `questions.id`, in the order they were written, or NULL if it has none.
+///
+/// The tags are kept in the tables `tags` and `question_tags`, so every query reading the questions
+/// selects them with this subquery, into the `tags` column the [Question] is read from.
+const QUESTION_TAGS: &str = "(SELECT array_agg(tags.name ORDER BY question_tags.position) FROM question_tags \
+    JOIN tags ON tags.id = question_tags.tag_id WHERE question_tags.question_id = questions.id) AS tags";
+
+/// The kinds of items the accounts vote on, see [Store::record_vote].
+#[derive(Debug, Copy, Clone)]
+enum VotedItem {
+    Question,
+    Answer,
+}
+
+impl VotedItem {
+    /// Returns the table of the items, the table of the votes on the items, and the column of the
+    /// votes with the ID of the item.
+    fn tables(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            VotedItem::Question => ("questions", "question_votes", "question_id"),
+            VotedItem::Answer => ("answers", "answer_votes", "answer_id"),
+        }
+    }
+}
+
+/// This struct represents the store, which keeps the resources in a PostgreSQL database.
+///
+/// The store is cheap to clone, the clones share the connection pool, the caches and the event bus.
+/// The examples in the documentation use the `MemStore` of the `test-util` feature instead, which
+/// serves the questions, the answers and the accounts without a database, see [Storage](crate::storage::Storage).
+#[derive(Clone)]
+pub struct Store {
+    pub connection: PgPool,
+    /// Filter censoring the content posted by the users, the [BadWordsAPI] by default.
+    pub profanity_filter: Arc<dyn ProfanityFilter>,
+    /// Clock the current time is read from, the [SystemClock] by default.
+    pub clock: Arc<dyn Clock>,
+    /// Daily limits on the contributions of every account, none by default.
+    pub quotas: Quotas,
+    /// Maximum lengths of the text posted by the users, see [sanitize](crate::types::sanitize).
+    pub limits: Limits,
+    /// Largest number of items a listing returns at once, [Pagination::MAX_LIMIT] by default.
+    pub max_page_size: i64,
+    /// Keys the tokens of the sessions and the OAuth states are encrypted with, random ones by default.
+    pub auth_keys: AuthKeys,
+    /// Signer the tokens of the sessions are issued and verified with, a [PasetoSigner] with the
+    /// [Store::auth_keys] by default.
+    pub token_signer: Arc<dyn TokenSigner>,
+    /// Clients of the providers the accounts can log in with, none by default.
+    pub oauth: OAuthConfig,
+    /// Token the administrators authenticate with, in the `X-Admin-Token` header, none by default,
+    /// so the administrative requests are rejected.
+    pub admin_token: Option<String>,
+    /// Whether the instance is in maintenance mode, see [Store::set_maintenance], off by default.
+    pub maintenance: Arc<AtomicBool>,
+    /// Parameters the passwords are hashed with, the ones recommended by OWASP by default.
+    pub password_hashing: PasswordHashing,
+    /// How long the sessions started by the logins last, see [SessionLifetimes].
+    pub session_lifetimes: SessionLifetimes,
+    /// Bus on which an [Event] is published after every successful write.
+    pub events: EventBus,
+    /// In-process cache for the single questions, see [Store::QUESTION_CACHE_TTL].
+    pub question_cache: moka::future::Cache<QuestionId, Question>,
+    /// In-process cache for the serialized listings of questions, keyed by the query parameters,
+    /// see [Store::LISTING_CACHE_TTL].
+    pub listing_cache: moka::future::Cache<String, Arc<str>>,
+    /// In-process cache for the popular tags, keyed by the window they are counted over,
+    /// see [Store::POPULAR_TAGS_CACHE_TTL].
+    pub popular_tags_cache: moka::future::Cache<TagWindow, Vec<Tag>>,
+    /// In-process counters of the failed logins, keyed by the email and by the client address,
+    /// see [Store::check_login_attempts].
+    pub failed_logins: moka::future::Cache<String, FailedLogins>,
+    /// Cache for the questions, used when `REDIS_URL` is set.
+    #[cfg(feature = "redis-cache")]
+    pub cache: Option<crate::cache::RedisCache>,
+    /// The duration after which the read queries are explained, see [Store::explain_if_slow].
+    #[cfg(feature = "explain-slow-queries")]
+    pub slow_query_threshold: std::time::Duration,
+}
+
+impl std::fmt::Debug for Store {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Store").field("connection", &self.connection).finish()
+    }
+}
+
+impl std::fmt::Display for Store {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Store {
+    /// The maximum number of questions kept in the in-process question cache.
+    pub const QUESTION_CACHE_CAPACITY: u64 = 1_000;
+    /// The time after which the questions in the in-process question cache expire.
+    ///
+    /// The cache is invalidated by the writes of this instance, so the TTL bounds how long
+    /// the writes made by other instances can go unnoticed.
+    pub const QUESTION_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(10);
+    /// The maximum number of listings kept in the listing cache.
+    pub const LISTING_CACHE_CAPACITY: u64 = 256;
+    /// The time after which the listings in the listing cache expire.
+    ///
+    /// The whole cache is invalidated by any write to the questions made by this instance.
+    pub const LISTING_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+    /// The maximum number of windows kept in the popular tags cache.
+    pub const POPULAR_TAGS_CACHE_CAPACITY: u64 = 16;
+    /// The time after which the popular tags in the popular tags cache expire.
+    ///
+    /// The cache is not invalidated by the writes, the popular tags are only recounted once expired.
+    pub const POPULAR_TAGS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+    /// The maximum number of emails and client addresses whose failed logins are counted.
+    pub const FAILED_LOGINS_CAPACITY: u64 = 100_000;
+    /// The number of failed logins after which the logins to an account are rejected.
+    pub const FAILED_LOGINS_PER_ACCOUNT: u32 = 5;
+    /// The number of failed logins after which the logins from a client address are rejected.
+    ///
+    /// It is higher than the limit of the accounts, as many clients can share an address.
+    pub const FAILED_LOGINS_PER_ADDRESS: u32 = 20;
+    /// The time the failed logins are counted for, from the first one.
+    ///
+    /// The logins over the limits are rejected until the time has passed, and the counters expire
+    /// from the cache after it.
+    pub const FAILED_LOGINS_WINDOW: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+    /// The number of consecutive failed logins after which an account is locked, until an
+    /// administrator unlocks it, see [Store::record_failed_password].
+    pub const LOCKOUT_FAILED_LOGINS: i32 = 10;
+    /// The maximum number of popular tags returned, see [Store::get_popular_tags].
+    pub const POPULAR_TAGS_LIMIT: i64 = 50;
+    /// The maximum number of similar questions returned when a new question is asked.
+    pub const SIMILAR_QUESTIONS_LIMIT: i64 = 5;
+    /// The duration after which the read queries are explained, if `SLOW_QUERY_THRESHOLD_MS` is not set.
+    #[cfg(feature = "explain-slow-queries")]
+    pub const DEFAULT_SLOW_QUERY_THRESHOLD: std::time::Duration = std::time::Duration::from_millis(100);
+
+    /// This function creates a new store.
+    ///
+    /// # Arguments
+    /// - `db_url`: A string slice that contains the URL for the database.
+    /// - `api_layer_key`: The key for the Bad Words API, which censors the content.
+    /// - `pool_config`: The options for the database connection pool.
+    ///
+    /// # Returns
+    /// - A store if the database connection was established successfully.
+    /// - [ServiceError::DatabaseConnectionError] if the database cannot be reached, or the URL is invalid.
+    /// - [ServiceError::DatabaseWarmupError] if the minimum number of connections cannot be established.
+    #[instrument(target = "store", level = "debug", skip(db_url, api_layer_key))]
+    pub async fn build(db_url: &str, api_layer_key: &str, pool_config: PoolConfig) -> Result<Self, ServiceError> {
+        trace!("creating store object");
+
+        let PoolConfig {
+            min_connections,
+            max_connections,
+            lazy,
+            statement_cache_capacity,
+            test_before_acquire,
+        } = pool_config;
+        let connect_options = PgConnectOptions::from_str(db_url)
+            .map_err(|_| ServiceError::DatabaseConnectionError)?
+            // The uncached statements are never closed, see `PoolConfig::statement_cache_capacity`
+            .statement_cache_capacity(statement_cache_capacity.max(1));
+        let pool_options = PgPoolOptions::new()
+            .min_connections(min_connections)
+            .max_connections(max_connections)
+            .test_before_acquire(test_before_acquire);
+
+        let db_pool = if lazy {
+            trace!("creating lazy connection pool");
+            pool_options.connect_lazy_with(connect_options)
+        } else {
+            trace!("creating connection pool");
+            let pool = match pool_options.connect_with(connect_options).await {
+                Ok(pool) => {
+                    info!("DB connection established successfully");
+                    Ok(pool)
+                }
+                Err(_) => Err(ServiceError::DatabaseConnectionError),
+            }?;
+            Self::warm_up(&pool, min_connections).await?;
+            pool
+        };
+
+        trace!("building BadWordsAPI object");
+        let bad_words_api = match std::env::var("API_LAYER_URL") {
+            Ok(endpoint) => BadWordsAPI::build_with_endpoint(api_layer_key, '*', &endpoint)?,
+            Err(_) => BadWordsAPI::build(api_layer_key, '*')?,
+        };
+
+        #[allow(unused_mut)]
+        let mut store = Self::from_parts(db_pool, Arc::new(bad_words_api));
+        #[cfg(feature = "redis-cache")]
+        {
+            store.cache = Self::connect_cache().await;
+        }
+
+        trace!("store object created successfully");
+        Ok(store)
+    }
+
+    /// This function creates a store on top of the connection pool, with empty caches.
+    fn from_parts(connection: PgPool, profanity_filter: Arc<dyn ProfanityFilter>) -> Self {
+        let auth_keys = AuthKeys::random();
+        Store {
+            connection,
+            profanity_filter,
+            clock: Arc::new(SystemClock),
+            quotas: Quotas::default(),
+            limits: Limits::default(),
+            max_page_size: Pagination::MAX_LIMIT,
+            token_signer: Arc::new(PasetoSigner::new(auth_keys.clone())),
+            auth_keys,
+            oauth: OAuthConfig::default(),
+            admin_token: None,
+            maintenance: Arc::new(AtomicBool::new(false)),
+            password_hashing: PasswordHashing::default(),
+            session_lifetimes: SessionLifetimes::default(),
+            events: EventBus::new(),
+            question_cache: moka::future::Cache::builder()
+                .max_capacity(Self::QUESTION_CACHE_CAPACITY)
+                .time_to_live(Self::QUESTION_CACHE_TTL)
+                .build(),
+            listing_cache: moka::future::Cache::builder()
+                .max_capacity(Self::LISTING_CACHE_CAPACITY)
+                .time_to_live(Self::LISTING_CACHE_TTL)
+                .build(),
+            popular_tags_cache: moka::future::Cache::builder()
+                .max_capacity(Self::POPULAR_TAGS_CACHE_CAPACITY)
+                .time_to_live(Self::POPULAR_TAGS_CACHE_TTL)
+                .build(),
+            failed_logins: moka::future::Cache::builder()
+                .max_capacity(Self::FAILED_LOGINS_CAPACITY)
+                .time_to_live(Self::FAILED_LOGINS_WINDOW)
+                .build(),
+            #[cfg(feature = "redis-cache")]
+            cache: None,
+            #[cfg(feature = "explain-slow-queries")]
+            slow_query_threshold: std::env::var("SLOW_QUERY_THRESHOLD_MS")
+                .ok()
+                .and_then(|threshold| threshold.parse().ok())
+                .map_or(Self::DEFAULT_SLOW_QUERY_THRESHOLD, std::time::Duration::from_millis),
+        }
+    }
+
+    /// This function replaces the profanity filter of the store.
+    ///
+    /// Used by the tests, to censor the content without calling the Bad Words API.
+    ///
+    /// # Arguments
+    /// - `profanity_filter`: The filter censoring the content posted by the users.
+    pub fn with_profanity_filter(self, profanity_filter: impl ProfanityFilter + 'static) -> Self {
+        Self {
+            profanity_filter: Arc::new(profanity_filter),
+            ..self
+        }
+    }
+
+    /// This function replaces the clock of the store.
+    ///
+    /// Used by the tests, to control the time instead of waiting for it to pass.
+    ///
+    /// # Arguments
+    /// - `clock`: The clock the current time is read from.
+    pub fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
+    /// This function sets the daily limits on the contributions of every account.
+    ///
+    /// # Arguments
+    /// - `quotas`: The limits, read from the configuration.
+    pub fn with_quotas(self, quotas: Quotas) -> Self {
+        Self { quotas, ..self }
+    }
+
+    /// This function sets the maximum lengths of the text posted by the users.
+    ///
+    /// # Arguments
+    /// - `limits`: The limits, read from the configuration.
+    pub fn with_limits(self, limits: Limits) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// This function sets the largest number of items a listing returns at once.
+    ///
+    /// # Arguments
+    /// - `max_page_size`: The maximum page size, read from the configuration.
+    pub fn with_max_page_size(self, max_page_size: i64) -> Self {
+        Self { max_page_size, ..self }
+    }
+
+    /// This function bounds the pagination of a listing by the maximum page size.
+    ///
+    /// # Arguments
+    /// - `pag`: A `Pagination` struct that contains the requested offset and limit.
+    ///
+    /// # Returns
+    /// - The pagination with the maximum page size as the limit, if the limit was missing.
+    /// - An error if the requested limit is larger than the maximum page size.
+    pub fn paginate(&self, pag: Pagination) -> Result<Pagination, ServiceError> {
+        Ok(pag.bounded(self.max_page_size)?)
+    }
+
+    /// This function sets the keys the tokens of the sessions are encrypted with.
+    ///
+    /// The tokens are issued as PASETO tokens encrypted with these keys, see [PasetoSigner], until
+    /// the signer is replaced with [Store::with_token_signer].
+    ///
+    /// # Arguments
+    /// - `auth_keys`: The keys, loaded at startup.
+    pub fn with_auth_keys(self, auth_keys: AuthKeys) -> Self {
+        Self {
+            token_signer: Arc::new(PasetoSigner::new(auth_keys.clone())),
+            auth_keys,
+            ..self
+        }
+    }
+
+    /// This function sets the signer the tokens of the sessions are issued and verified with.
+    ///
+    /// # Arguments
+    /// - `token_signer`: The signer, selected by the configuration.
+    pub fn with_token_signer(self, token_signer: Arc<dyn TokenSigner>) -> Self {
+        Self { token_signer, ..self }
+    }
+
+    /// This function sets the clients of the providers the accounts can log in with.
+    ///
+    /// # Arguments
+    /// - `oauth`: The configuration, read at startup.
+    pub fn with_oauth(self, oauth: OAuthConfig) -> Self {
+        Self { oauth, ..self }
+    }
+
+    /// This function sets the token the administrators authenticate with.
+    ///
+    /// # Arguments
+    /// - `admin_token`: The token, read at startup, or `None` to reject the administrative requests.
+    pub fn with_admin_token(self, admin_token: Option<String>) -> Self {
+        Self { admin_token, ..self }
+    }
+
+    /// This function sets the parameters the passwords are hashed with.
+    ///
+    /// # Arguments
+    /// - `password_hashing`: The parameters, read from the configuration.
+    pub fn with_password_hashing(self, password_hashing: PasswordHashing) -> Self {
+        Self {
+            password_hashing,
+            ..self
+        }
+    }
+
+    /// This function sets how long the sessions started by the logins last.
+    ///
+    /// # Arguments
+    /// - `session_lifetimes`: The lifetimes, read from the configuration.
+    pub fn with_session_lifetimes(self, session_lifetimes: SessionLifetimes) -> Self {
+        Self {
+            session_lifetimes,
+            ..self
+        }
+    }
+
+    /// This function establishes the given number of connections in the pool.
+    ///
+    /// The pool keeps `min_connections` open on its own, but only in the background, so the
+    /// connections are acquired here to make sure they can be established before serving requests.
+    ///
+    /// # Arguments
+    /// - `pool`: The pool to warm up.
+    /// - `connections`: The number of connections to establish.
+    async fn warm_up(pool: &PgPool, connections: u32) -> Result<(), ServiceError> {
+        trace!("establishing {connections} connections");
+        let mut acquired = Vec::with_capacity(connections as usize);
+        for _ in 0..connections {
+            match pool.acquire().await {
+                Ok(connection) => acquired.push(connection),
+                Err(error) => {
+                    error!("{error}");
+                    return Err(ServiceError::DatabaseWarmupError(connections, error));
+                }
+            }
+        }
+        info!("{} DB connections established", acquired.len());
+        Ok(())
+    }
+
+    /// This function connects to the Redis cache at `REDIS_URL`, if it is set.
+    ///
+    /// The values expire after `REDIS_CACHE_TTL` seconds, or after
+    /// [DEFAULT_TTL](crate::cache::RedisCache::DEFAULT_TTL) if it is not set.
+    ///
+    /// # Returns
+    /// - The cache if the connection was established successfully.
+    /// - `None` if `REDIS_URL` is not set, or the connection failed, in which case the store works without the cache.
+    #[cfg(feature = "redis-cache")]
+    async fn connect_cache() -> Option<crate::cache::RedisCache> {
+        use crate::cache::RedisCache;
+
+        let url = std::env::var("REDIS_URL").ok()?;
+        let ttl = std::env::var("REDIS_CACHE_TTL")
+            .ok()
+            .and_then(|ttl| ttl.parse().ok())
+            .map_or(RedisCache::DEFAULT_TTL, std::time::Duration::from_secs);
+
+        trace!("connecting to the cache at {url}");
+        match RedisCache::connect(&url, ttl).await {
+            Ok(cache) => {
+                info!("cache connection established successfully");
+                Some(cache)
+            }
+            Err(error) => {
+                warn!("cannot connect to the cache, continuing without it: {error}");
+                None
+            }
+        }
+    }
+
+    /// This function runs the read query built by `build`, and returns all rows.
+    ///
+    /// With the `explain-slow-queries` feature, the query is explained if it is slow, see
+    /// [Store::explain_if_slow]. Explaining runs the query again, so only the queries without side
+    /// effects can be run through this function.
+    async fn fetch_all<'q, F>(&self, build: F) -> Result<Vec<PgRow>, sqlx::Error>
+    where
+        F: Fn() -> Query<'q, Postgres, PgArguments>,
+    {
+        #[cfg(feature = "explain-slow-queries")]
+        let started = std::time::Instant::now();
+        let rows = build().fetch_all(&self.connection).await?;
+        #[cfg(feature = "explain-slow-queries")]
+        self.explain_if_slow(started.elapsed(), &build).await;
+        Ok(rows)
+    }
+
+    /// This function runs the read query built by `build`, and returns the row, if there is one.
+    ///
+    /// Same as [Store::fetch_all], for the queries returning at most one row.
+    async fn fetch_optional<'q, F>(&self, build: F) -> Result<Option<PgRow>, sqlx::Error>
+    where
+        F: Fn() -> Query<'q, Postgres, PgArguments>,
+    {
+        #[cfg(feature = "explain-slow-queries")]
+        let started = std::time::Instant::now();
+        let row = build().fetch_optional(&self.connection).await?;
+        #[cfg(feature = "explain-slow-queries")]
+        self.explain_if_slow(started.elapsed(), &build).await;
+        Ok(row)
+    }
+
+    /// This function logs the plan of the query, if it took longer than the slow query threshold.
+    ///
+    /// The query is built again, and run with `EXPLAIN (ANALYZE, FORMAT JSON)`, so the logged plan
+    /// has the actual timings and row counts. The plan is logged as a warning, with the
+    /// `store::slow_queries` target.
+    ///
+    /// Meant for finding the missing indexes during development, as every slow query runs twice.
+    #[cfg(feature = "explain-slow-queries")]
+    async fn explain_if_slow<'q, F>(&self, elapsed: std::time::Duration, build: &F)
+    where
+        F: Fn() -> Query<'q, Postgres, PgArguments>,
+    {
+        use sqlx::Execute;
+
+        if elapsed < self.slow_query_threshold {
+            return;
+        }
+
+        let mut query = build();
+        let sql = query.sql();
+        let arguments = query.take_arguments().unwrap_or_default();
+        let explain = format!("EXPLAIN (ANALYZE, FORMAT JSON) {sql}");
+        match sqlx::query_scalar_with::<_, serde_json::Value, _>(&explain, arguments)
+            .fetch_one(&self.connection)
+            .await
+        {
+            Ok(plan) => warn!(target: "store::slow_queries", ?elapsed, %plan, "slow query: {sql}"),
+            Err(error) => warn!(target: "store::slow_queries", ?elapsed, "slow query: {sql}, cannot explain: {error}"),
+        }
+    }
+
+    /// This function invalidates the cached copies of the question and of the pages of questions.
+    ///
+    /// # Arguments
+    /// - `question_id`: The ID of the changed question, or `None` if a question was added, or
+    ///   only the number of answers in the pages changed.
+    async fn invalidate_cache(&self, question_id: Option<QuestionId>) {
+        if let Some(question_id) = question_id {
+            self.question_cache.invalidate(&question_id).await;
+        }
+        self.listing_cache.invalidate_all();
+
+        #[cfg(feature = "redis-cache")]
+        if let Some(cache) = &self.cache {
+            if let Some(question_id) = question_id {
+                cache.invalidate_question(question_id).await;
+            }
+            cache.invalidate_pages().await;
+        }
+    }
+
+    /// This function returns the authors of the given accounts, from the table `accounts`.
+    ///
+    /// The authors are loaded with a single query, no matter how many ids are given, so the
+    /// authors of a whole page of questions or answers don't take a query per row.
+    ///
+    /// # Arguments
+    /// - `account_ids`: The IDs of the accounts, which may contain duplicates.
+    ///
+    /// # Returns
+    /// - A map from the account ID to the author, which skips the accounts that don't exist.
+    /// - An error if the authors could not be loaded.
+    #[instrument(target = "store", level = "debug", skip_all)]
+    pub async fn get_authors(
+        &self,
+        account_ids: impl IntoIterator<Item = AccountId>,
+    ) -> Result<HashMap<AccountId, Author>, ServiceError> {
+        let account_ids: HashSet<i32> = account_ids.into_iter().map(|AccountId(id)| id).collect();
+        if account_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        trace!("fetching {} authors from the database", account_ids.len());
+        let account_ids: Vec<_> = account_ids.into_iter().collect();
+        match self
+            .fetch_all(|| {
+                sqlx::query("SELECT id, display_name FROM accounts WHERE id = ANY($1)").bind(account_ids.clone())
+            })
+            .await?
+            .into_iter()
+            .map(|row| Author::try_from(row).map(|author| (author.id, author)))
+            .collect::<Result<HashMap<_, _>, _>>()
+        {
+            Ok(authors) => {
+                trace!("authors fetched successfully");
+                Ok(authors)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function reads the ID of the author from a row of the `questions` or `answers` table,
+    /// which is `None` if the account of the author was deleted.
+    fn author_id(row: &PgRow) -> Result<Option<AccountId>, sqlx::Error> {
+        Ok(row.try_get::<Option<i32>, _>("account_id")?.map(AccountId))
+    }
+
+    /// This function reads the content rendered to HTML from a row of the `questions` or `answers` table.
+    ///
+    /// The content written before the rendered HTML was stored with it is rendered now.
+    fn content_html(row: &PgRow) -> Result<String, sqlx::Error> {
+        match row.try_get::<Option<String>, _>("content_html")? {
+            Some(content_html) => Ok(content_html),
+            None => Ok(markdown::to_html(row.try_get("content")?)),
+        }
+    }
+
+    /// This function returns the content of a question rendered to HTML, from the table `questions`.
+    ///
+    /// # Arguments
+    /// - `question_id`: An integer that represents the ID of the question.
+    ///
+    /// # Returns
+    /// - The rendered content, or `None` if the question does not exist.
+    /// - An error if the content could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_question_html(&self, question_id: QuestionId) -> Result<Option<String>, ServiceError> {
+        let QuestionId(question_id) = question_id;
+        trace!("fetching the rendered content of the question with id={question_id}");
+        match self
+            .fetch_optional(|| {
+                sqlx::query("SELECT content, content_html FROM questions WHERE id = $1").bind(question_id)
+            })
+            .await?
+            .as_ref()
+            .map(Self::content_html)
+            .transpose()
+        {
+            Ok(content_html) => Ok(content_html),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the contents of the given answers rendered to HTML, from the table `answers`.
+    ///
+    /// The contents are loaded with a single query, like the authors in [Store::get_authors].
+    ///
+    /// # Arguments
+    /// - `answer_ids`: The IDs of the answers, which may contain duplicates.
+    ///
+    /// # Returns
+    /// - A map from the answer ID to the rendered content, which skips the answers that don't exist.
+    /// - An error if the contents could not be read.
+    #[instrument(target = "store", level = "debug", skip_all)]
+    pub async fn get_answers_html(
+        &self,
+        answer_ids: impl IntoIterator<Item = AnswerId>,
+    ) -> Result<HashMap<AnswerId, String>, ServiceError> {
+        let answer_ids: HashSet<i32> = answer_ids.into_iter().map(|AnswerId(id)| id).collect();
+        if answer_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        trace!("fetching the rendered content of {} answers", answer_ids.len());
+        let answer_ids: Vec<_> = answer_ids.into_iter().collect();
+        match self
+            .fetch_all(|| {
+                sqlx::query("SELECT id, content, content_html FROM answers WHERE id = ANY($1)").bind(answer_ids.clone())
+            })
+            .await?
+            .iter()
+            .map(|row| Ok((AnswerId(row.try_get("id")?), Self::content_html(row)?)))
+            .collect::<Result<HashMap<_, _>, sqlx::Error>>()
+        {
+            Ok(contents) => Ok(contents),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns all questions from the table `questions`, with the number of their answers.
+    ///
+    /// The questions are ordered from the most recent to the oldest, and by their IDs among the
+    /// questions created at the same time, so the pages don't overlap or skip questions.
+    ///
+    /// # Arguments
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    /// - `since`: If set, only the questions created after this time are returned.
+    ///
+    /// # Returns
+    /// - A vector of questions if the questions were found successfully.
+    /// - An error if the questions could not be found.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_questions(
+        &self,
+        pag: Pagination,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Question>, ServiceError> {
+        let pag = self.paginate(pag)?;
+        // Only the full listing is cached, as the polling clients rarely ask for the same `since` twice
+        #[cfg(feature = "redis-cache")]
+        if let (Some(cache), None) = (&self.cache, since) {
+            if let Some(questions) = cache.get_page(pag).await {
+                return Ok(questions);
+            }
+        }
+
+        let Pagination { offset, limit } = pag;
+
+        trace!("fetching questions from the database");
+        let sql = format!(
+            "SELECT questions.*, {QUESTION_TAGS}, \
+            (SELECT COUNT(*) FROM answers WHERE answers.question_id = questions.id) AS answer_count \
+            FROM questions WHERE ($3::TIMESTAMPTZ IS NULL OR created_on > $3) \
+            ORDER BY created_on DESC, id DESC LIMIT $1 OFFSET $2"
+        );
+        match self
+            .fetch_all(|| sqlx::query(&sql).bind(limit).bind(offset).bind(since))
+            .await?
+            .into_iter()
+            .map(|row| Ok((Self::author_id(&row)?, Question::try_from(row)?)))
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+        {
+            Ok(rows) => {
+                trace!("questions fetched successfully");
+                let authors = self
+                    .get_authors(rows.iter().filter_map(|(author_id, _)| *author_id))
+                    .await?;
+                let questions: Vec<_> = rows
+                    .into_iter()
+                    .map(|(author_id, question)| Question {
+                        author: author_id.and_then(|author_id| authors.get(&author_id).cloned()),
+                        ..question
+                    })
+                    .collect();
+                #[cfg(feature = "redis-cache")]
+                if let (Some(cache), None) = (&self.cache, since) {
+                    cache.set_page(pag, &questions).await;
+                }
+                Ok(questions)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the number of questions in the table `questions`.
+    ///
+    /// # Arguments
+    /// - `since`: If set, only the questions created after this time are counted.
+    ///
+    /// # Returns
+    /// - The total number of questions.
+    /// - An error if the questions could not be counted.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn count_questions(&self, since: Option<DateTime<Utc>>) -> Result<i64, ServiceError> {
+        trace!("counting questions in the database");
+        match sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM questions WHERE ($1::TIMESTAMPTZ IS NULL OR created_on > $1)",
+        )
+        .bind(since)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(total) => {
+                trace!("questions counted successfully");
+                Ok(total)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns a page of questions, together with the total number of questions.
+    ///
+    /// The page and the total are queried concurrently, on separate connections from the pool,
+    /// so the total doesn't add the latency of a second query to the listing.
+    ///
+    /// # Arguments
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    /// - `since`: If set, only the questions created after this time are listed and counted.
+    ///
+    /// # Returns
+    /// - The page of questions and the total number of questions.
+    /// - An error if either query failed.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_questions_with_total(
+        &self,
+        pag: Pagination,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<Question>, i64), ServiceError> {
+        tokio::try_join!(self.get_questions(pag, since), self.count_questions(since))
+    }
+
+    /// This function returns the questions whose titles are similar to the given title, the most
+    /// similar ones first.
+    ///
+    /// The titles are compared by their trigrams, with the `%` operator of the `pg_trgm` extension,
+    /// so the questions are similar if the similarity of the titles is above `pg_trgm.similarity_threshold`.
+    ///
+    /// # Arguments
+    /// - `title`: The title the titles of the questions are compared to.
+    /// - `limit`: The maximum number of questions to return.
+    ///
+    /// # Returns
+    /// - A vector of questions, empty if no question is similar.
+    /// - An error if the questions could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_similar_questions(&self, title: &str, limit: i64) -> Result<Vec<Question>, ServiceError> {
+        trace!("fetching similar questions from the database");
+        let sql = format!(
+            "SELECT questions.*, {QUESTION_TAGS} FROM questions WHERE title % $1 \
+            ORDER BY similarity(title, $1) DESC, id LIMIT $2"
+        );
+        match self
+            .fetch_all(|| sqlx::query(&sql).bind(title).bind(limit))
+            .await?
+            .into_iter()
+            .map(|row| Ok((Self::author_id(&row)?, Question::try_from(row)?)))
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+        {
+            Ok(rows) => {
+                trace!("{} similar questions fetched successfully", rows.len());
+                let authors = self
+                    .get_authors(rows.iter().filter_map(|(author_id, _)| *author_id))
+                    .await?;
+                Ok(rows
+                    .into_iter()
+                    .map(|(author_id, question)| Question {
+                        author: author_id.and_then(|author_id| authors.get(&author_id).cloned()),
+                        ..question
+                    })
+                    .collect())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns a question from the table `questions` by its ID.
+    ///
+    /// # Arguments
+    /// - `id`: An integer that represents the ID of the question.
+    ///
+    /// # Returns
+    /// - A Question if the question was found successfully.
+    /// - An error if the question could not be found.
+    pub async fn get_question(&self, question_id: QuestionId) -> Result<Option<Question>, ServiceError> {
+        if let Some(question) = self.question_cache.get(&question_id).await {
+            trace!("question found in the in-process cache");
+            return Ok(Some(question));
+        }
+
+        #[cfg(feature = "redis-cache")]
+        if let Some(cache) = &self.cache {
+            if let Some(question) = cache.get_question(question_id).await {
+                self.question_cache.insert(question_id, question.clone()).await;
+                return Ok(Some(question));
+            }
+        }
+
+        let QuestionId(question_id) = question_id;
+
+        let sql = format!("SELECT questions.*, {QUESTION_TAGS} FROM questions WHERE id = $1");
+        let pg_row = self.fetch_optional(|| sqlx::query(&sql).bind(question_id)).await?;
+
+        let Some(pg_row) = pg_row else {
+            trace!("question not found");
+            return Ok(None);
+        };
+
+        let author_id = Self::author_id(&pg_row)?;
+        match Question::try_from(pg_row) {
+            Ok(question) => {
+                let question = Question {
+                    author: self.get_authors(author_id).await?.into_values().next(),
+                    ..question
+                };
+                self.question_cache
+                    .insert(QuestionId(question_id), question.clone())
+                    .await;
+                #[cfg(feature = "redis-cache")]
+                if let Some(cache) = &self.cache {
+                    cache.set_question(&question).await;
+                }
+                Ok(Some(question))
+            }
+            Err(error) => {
+                tracing::event!(target:"webdev_book", tracing::Level::ERROR, "{:?}", error);
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    pub async fn is_question_owner(
+        &self,
+        question_id: QuestionId,
+        account_id: AccountId,
+    ) -> Result<bool, ServiceError> {
+        let QuestionId(q_id) = question_id;
+        let AccountId(acc_id) = account_id;
+
+        match sqlx::query("SELECT account_id FROM questions WHERE id = $1")
+            .bind(q_id)
+            .fetch_optional(&self.connection)
+            .await
+        {
+            Ok(Some(row)) => Ok({
+                let id: Option<i32> = row.try_get("account_id")?;
+                id == Some(acc_id)
+            }),
+            Ok(None) => Err(ServiceError::QuestionNotFound(question_id.into())),
+            Err(error) => Err(ServiceError::DatabaseQueryError(error)),
+        }
+    }
+
+    /// This function returns the owner of the question, from the table `questions`.
+    ///
+    /// # Arguments
+    /// - `question_id`: The ID of the question.
+    ///
+    /// # Returns
+    /// - The ID of the account that asked the question, or `None` if the account was deleted.
+    /// - [ServiceError::QuestionNotFound] if the question does not exist.
+    /// - An error if the question could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_question_owner(&self, question_id: QuestionId) -> Result<Option<AccountId>, ServiceError> {
+        match sqlx::query_scalar::<_, Option<i32>>("SELECT account_id FROM questions WHERE id = $1")
+            .bind(question_id.0)
+            .fetch_optional(&self.connection)
+            .await
+        {
+            Ok(Some(account_id)) => Ok(account_id.map(AccountId)),
+            Ok(None) => Err(ServiceError::QuestionNotFound(question_id.into())),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function will insert a question into the table `questions`
+    ///
+    /// The tags of the question are linked to it in the table `question_tags`, in the same transaction,
+    /// see [Store::set_question_tags], as is the contribution counted by the quotas, which is checked
+    /// against the daily limit first, see [Store::count_questions_since], and so are the jobs delivering it to the webhooks, see
+    /// [Store::enqueue_event_jobs], and the job censoring it if it is not censored yet.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account asking the question.
+    /// - `question`: The question to add.
+    /// - `censored`: Whether the title and the content are censored, otherwise a [Task::CensorQuestion] job is added.
+    ///
+    /// # Returns
+    /// - A new Question if the question was added successfully.
+    /// - [ServiceError::QuotaExceeded] if the account reached its daily limit on questions.
+    /// - An error if the question could not be added.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_question(
+        &self,
+        account_id: AccountId,
+        question: Question,
+        censored: bool,
+    ) -> Result<Question, ServiceError> {
+        trace!("adding a question to the database");
+        let Question {
+            title, content, tags, ..
+        } = question;
+        let AccountId(account_id) = account_id;
+        let content_html = markdown::to_html(&content);
+        let tags = tags.filter(|tags| !tags.is_empty());
+
+        let mut transaction = self.connection.begin().await?;
+        Self::record_contribution(
+            &mut transaction,
+            &self.quotas,
+            AccountId(account_id),
+            Contribution::Question,
+            self.clock.now(),
+        )
+        .await?;
+        let res = sqlx::query(
+            "INSERT INTO questions (title, content, account_id, content_html)\
+            VALUES ($1, $2, $3, $4)\
+            RETURNING *",
+        )
+        .bind(title)
+        .bind(content)
+        .bind(account_id)
+        .bind(content_html)
+        .map(Question::try_from)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        match res {
+            Ok(question) => {
+                let question_id = question.id.expect("inserted questions have an id");
+                Self::set_question_tags(&mut transaction, question_id, tags.as_deref().unwrap_or_default()).await?;
+                let question = Question { tags, ..question };
+                let event = Event::QuestionCreated {
+                    question: question.clone(),
+                };
+                Self::enqueue_event_jobs(&mut transaction, &event).await?;
+                if !censored {
+                    Self::enqueue_task(&mut transaction, &Task::CensorQuestion { question_id }).await?;
+                }
+                transaction.commit().await?;
+                trace!("question added successfully with id={:?}", question.id);
+                self.invalidate_cache(None).await;
+                self.events.publish(event);
+                Ok(question)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function will update a question in the table `questions` by its ID
+    ///
+    /// Every update increments the `version` of the question. If `expected_version` is provided,
+    /// the question is only updated if its current version matches it, which prevents concurrent
+    /// updates from silently overwriting each other.
+    ///
+    /// The replaced version is recorded in the table `question_revisions`, in the same transaction
+    /// as the update, see [Store::get_question_revisions], as are the new tags, see [Store::set_question_tags],
+    /// the job censoring the question if it is not censored yet, and the entry of the moderation log
+    /// if a moderator updates the question of another account, see [Store::get_moderation_log].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the owner of the question, which is only updated if it is still its owner,
+    ///   or `None` for a question whose account was deleted.
+    /// - `question`: A `Question` struct that contains the new data for the question.
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `expected_version`: The version of the question the update is based on, if known.
+    /// - `censored`: Whether the title and the content are censored, otherwise a [Task::CensorQuestion] job is added.
+    /// - `moderator_id`: The ID of the moderator updating the question of another account, if it is not its owner.
+    ///
+    /// # Returns
+    /// - An updated Question if the question was updated successfully.
+    /// - [ServiceError::VersionMismatch] if the question was modified since `expected_version`.
+    /// - [ServiceError::QuestionNotFound] if the question does not exist.
+    /// - An error if the question could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn update_question(
+        &self,
+        account_id: Option<AccountId>,
+        question: Question,
+        question_id: QuestionId,
+        expected_version: Option<i32>,
+        censored: bool,
+        moderator_id: Option<AccountId>,
+    ) -> Result<Question, ServiceError> {
+        let QuestionId(q_id) = question_id;
+        let owner_id = account_id;
+        let account_id = account_id.map(|AccountId(account_id)| account_id);
+        trace!("updating question in the database; id={q_id}");
+        let Question {
+            title, content, tags, ..
+        } = question;
+        let content_html = markdown::to_html(&content);
+        let tags = tags.filter(|tags| !tags.is_empty());
+
+        // The row is locked until the update is committed, so concurrent updates record every version once
+        let mut transaction = self.connection.begin().await?;
+        sqlx::query(&format!(
+            "INSERT INTO question_revisions (question_id, version, title, content, tags) \
+            SELECT id, version, title, content, {QUESTION_TAGS} FROM questions \
+            WHERE id = $1 AND account_id IS NOT DISTINCT FROM $2 AND ($3::INTEGER IS NULL OR version = $3) \
+            FOR UPDATE"
+        ))
+        .bind(q_id)
+        .bind(account_id)
+        .bind(expected_version)
+        .execute(&mut *transaction)
+        .await?;
+        let res = sqlx::query(
+            "UPDATE questions \
+            SET title = $1, content = $2, content_html = $6, version = version + 1 \
+            WHERE id = $3 AND account_id IS NOT DISTINCT FROM $4 AND ($5::INTEGER IS NULL OR version = $5) \
+            RETURNING *",
+        )
+        .bind(title)
+        .bind(content)
+        .bind(q_id)
+        .bind(account_id)
+        .bind(expected_version)
+        .bind(content_html)
+        .map(Question::try_from)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        match res {
+            Some(Ok(question)) => {
+                Self::set_question_tags(&mut transaction, question_id, tags.as_deref().unwrap_or_default()).await?;
+                if !censored {
+                    Self::enqueue_task(&mut transaction, &Task::CensorQuestion { question_id }).await?;
+                }
+                if let Some(moderator_id) = moderator_id {
+                    let (action, now) = (ModerationAction::UpdateQuestion, self.clock.now());
+                    Self::log_moderation(&mut transaction, moderator_id, action, question_id, owner_id, now).await?;
+                }
+                transaction.commit().await?;
+                let question = Question { tags, ..question };
+                trace!("question updated successfully");
+                self.invalidate_cache(question.id).await;
+                self.events.publish(Event::QuestionUpdated {
+                    question: question.clone(),
+                });
+                Ok(question)
+            }
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => {
+                let current_version: Option<i32> = sqlx::query_scalar("SELECT version FROM questions WHERE id = $1")
+                    .bind(q_id)
+                    .fetch_optional(&self.connection)
+                    .await?;
+
+                match (current_version, expected_version) {
+                    (Some(current), Some(expected)) if current != expected => {
+                        trace!("stale update; current version={current}, expected version={expected}");
+                        Err(ServiceError::VersionMismatch { current, expected })
+                    }
+                    _ => {
+                        trace!("question not found");
+                        Err(ServiceError::QuestionNotFound(question_id.into()))
+                    }
+                }
+            }
+        }
+    }
+
+    /// This function censors the title and the content of a question in the table `questions`, for
+    /// the [Task::CensorQuestion] jobs.
+    ///
+    /// The question is only replaced if it was not updated while it was censored, as the updates
+    /// are censored on their own.
+    ///
+    /// # Arguments
+    /// - `question_id`: The ID of the question.
+    ///
+    /// # Returns
+    /// - An Ok(true) if the question was censored.
+    /// - An Ok(false) if the question was deleted or updated in the meantime.
+    /// - An error if the profanity filter failed, or the question could not be read or updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn censor_question(&self, question_id: QuestionId) -> Result<bool, ServiceError> {
+        let QuestionId(q_id) = question_id;
+        let row: Option<(String, String, i32)> =
+            sqlx::query_as("SELECT title, content, version FROM questions WHERE id = $1")
+                .bind(q_id)
+                .fetch_optional(&self.connection)
+                .await?;
+        let Some((title, content, version)) = row else {
+            trace!("question not found");
+            return Ok(false);
+        };
+
+        let (title, content) = tokio::try_join!(
+            self.profanity_filter.censor(title),
+            self.profanity_filter.censor(content)
+        )?;
+        let content_html = markdown::to_html(&content);
+        let censored = sqlx::query(
+            "UPDATE questions SET title = $1, content = $2, content_html = $3 \
+            WHERE id = $4 AND version = $5",
+        )
+        .bind(title)
+        .bind(content)
+        .bind(content_html)
+        .bind(q_id)
+        .bind(version)
+        .execute(&self.connection)
+        .await?
+        .rows_affected()
+            > 0;
+
+        trace!("question censored={censored}");
+        if censored {
+            self.invalidate_cache(Some(question_id)).await;
+        }
+        Ok(censored)
+    }
+
+    /// This function replaces the tags of a question in the table `question_tags`, in the transaction
+    /// writing the question.
+    ///
+    /// The tags that don't exist yet are added to the table `tags`, the ones the question stops using
+    /// are kept, with one question less.
+    ///
+    /// # Arguments
+    /// - `transaction`: The transaction writing the question.
+    /// - `question_id`: The ID of the question.
+    /// - `tags`: The new tags of the question, in the order they were written.
+    async fn set_question_tags(
+        transaction: &mut PgConnection,
+        question_id: QuestionId,
+        tags: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let QuestionId(question_id) = question_id;
+        sqlx::query("DELETE FROM question_tags WHERE question_id = $1")
+            .bind(question_id)
+            .execute(&mut *transaction)
+            .await?;
+        if tags.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query("INSERT INTO tags (name) SELECT unnest($1::TEXT[]) ON CONFLICT (name) DO NOTHING")
+            .bind(tags)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query(
+            "INSERT INTO question_tags (question_id, tag_id, position) \
+            SELECT $1, tags.id, question_tag.position::SMALLINT \
+            FROM unnest($2::TEXT[]) WITH ORDINALITY AS question_tag (name, position) \
+            JOIN tags ON tags.name = question_tag.name \
+            ON CONFLICT (question_id, tag_id) DO NOTHING",
+        )
+        .bind(question_id)
+        .bind(tags)
+        .execute(&mut *transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// This function records a contribution of the account in the table `contributions`, in the
+    /// transaction adding it. The contributions are kept when the questions and the answers are
+    /// deleted, so the quotas count them.
+    ///
+    /// The account is locked until the transaction ends, and its contributions of the kind made
+    /// today are counted before the new one is recorded, so concurrent contributions of the account
+    /// cannot go over the daily limit, see [Quotas::check].
+    ///
+    /// # Arguments
+    /// - `transaction`: The transaction adding the contribution.
+    /// - `quotas`: The daily limits on the contributions.
+    /// - `account_id`: The ID of the account making the contribution.
+    /// - `contribution`: The kind of the contribution.
+    /// - `now`: The current time, from which the start of the day is computed.
+    ///
+    /// # Returns
+    /// - [ServiceError::QuotaExceeded] if the account reached the limit for today.
+    async fn record_contribution(
+        transaction: &mut PgConnection,
+        quotas: &Quotas,
+        account_id: AccountId,
+        contribution: Contribution,
+        now: DateTime<Utc>,
+    ) -> Result<(), ServiceError> {
+        if quotas.limit(contribution).is_some() {
+            // NO KEY UPDATE does not conflict with the locks the foreign keys take on the account
+            sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR NO KEY UPDATE")
+                .bind(account_id.0)
+                .execute(&mut *transaction)
+                .await?;
+            // created_on is stored in the time zone of the session, as it is set by NOW()
+            let usage: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM contributions \
+                WHERE account_id = $1 AND kind = $2 AND created_on >= $3::TIMESTAMPTZ::TIMESTAMP",
+            )
+            .bind(account_id.0)
+            .bind(contribution.kind())
+            .bind(start_of_day(now))
+            .fetch_one(&mut *transaction)
+            .await?;
+            if let Err(exceeded) = quotas.check(contribution, usage, now) {
+                trace!("quota exceeded; usage={usage}");
+                return Err(ServiceError::QuotaExceeded(exceeded));
+            }
+        }
+        sqlx::query("INSERT INTO contributions (account_id, kind) VALUES ($1, $2)")
+            .bind(account_id.0)
+            .bind(contribution.kind())
+            .execute(&mut *transaction)
+            .await?;
+        Ok(())
+    }
+
+    /// This function adds a job for the task to the table `jobs`, in the transaction of the write
+    /// causing it, so the job is queued if and only if the write is committed.
+    ///
+    /// # Arguments
+    /// - `transaction`: The transaction of the write causing the job.
+    /// - `task`: The work described by the job.
+    async fn enqueue_task(transaction: &mut PgConnection, task: &Task) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO jobs (kind, payload) VALUES ($1, $2)")
+            .bind(task.kind())
+            .bind(task.payload())
+            .execute(&mut *transaction)
+            .await?;
+        Ok(())
+    }
+
+    /// This function adds the jobs caused by an event to the table `jobs`, in the transaction of the
+    /// write causing the event.
+    ///
+    /// The event is delivered to the webhooks subscribed to it, see [Task::DeliverWebhook], and the
+    /// new answers are notified to the watchers of their questions, see [Task::NotifyAnswer].
+    ///
+    /// # Arguments
+    /// - `transaction`: The transaction of the write causing the event.
+    /// - `event`: The event caused by the write.
+    async fn enqueue_event_jobs(transaction: &mut PgConnection, event: &Event) -> Result<(), sqlx::Error> {
+        if let Event::AnswerCreated { answer } = event {
+            let answer_id = answer.id.expect("inserted answers have an id");
+            Self::enqueue_task(transaction, &Task::NotifyAnswer { answer_id }).await?;
+        }
+
+        let name = event.name();
+        if !Webhook::EVENTS.contains(&name) {
+            return Ok(());
+        }
+        let webhook_ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM webhooks WHERE $1 = ANY(events)")
+            .bind(name)
+            .fetch_all(&mut *transaction)
+            .await?;
+        let payload = serde_json::to_string(event).expect("events are always serializable");
+        for webhook_id in webhook_ids {
+            let task = Task::DeliverWebhook {
+                webhook_id: WebhookId(webhook_id),
+                event: name.to_string(),
+                payload: payload.clone(),
+            };
+            Self::enqueue_task(transaction, &task).await?;
+        }
+        Ok(())
+    }
+
+    /// This function returns the previous versions of a question from the table `question_revisions`,
+    /// the most recent ones first.
+    ///
+    /// # Arguments
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of revisions, empty if the question was never updated.
+    /// - An error if the revisions could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_question_revisions(
+        &self,
+        question_id: QuestionId,
+        pag: Pagination,
+    ) -> Result<Vec<QuestionRevision>, ServiceError> {
+        let QuestionId(question_id) = question_id;
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        trace!("fetching the revisions of the question with id={question_id}");
+        match self
+            .fetch_all(|| {
+                sqlx::query(
+                    "SELECT * FROM question_revisions WHERE question_id = $1 \
+                    ORDER BY version DESC LIMIT $2 OFFSET $3",
+                )
+                .bind(question_id)
+                .bind(limit)
+                .bind(offset)
+            })
+            .await?
+            .into_iter()
+            .map(QuestionRevision::try_from)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(revisions) => {
+                trace!("revisions fetched successfully");
+                Ok(revisions)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the tags from the table `tags`, with the number of the questions using
+    /// them, the most used ones first.
+    ///
+    /// # Arguments
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of tags.
+    /// - An error if the tags could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_tags(&self, pag: Pagination) -> Result<Vec<Tag>, ServiceError> {
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        trace!("fetching tags from the database");
+        match self
+            .fetch_all(|| {
+                sqlx::query(
+                    "SELECT tags.id, tags.name, COUNT(question_tags.question_id) AS question_count FROM tags \
+                    LEFT JOIN question_tags ON question_tags.tag_id = tags.id GROUP BY tags.id \
+                    ORDER BY question_count DESC, tags.name LIMIT $1 OFFSET $2",
+                )
+                .bind(limit)
+                .bind(offset)
+            })
+            .await?
+            .into_iter()
+            .map(Tag::try_from)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(tags) => {
+                trace!("{} tags fetched successfully", tags.len());
+                Ok(tags)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the tags used by the most questions asked within the recent window, with
+    /// the number of those questions, for rendering a tag cloud.
+    ///
+    /// At most [Store::POPULAR_TAGS_LIMIT] tags are returned. They are counted once per window and
+    /// kept in the popular tags cache, see [Store::POPULAR_TAGS_CACHE_TTL].
+    ///
+    /// # Arguments
+    /// - `window`: The recent window the questions are counted over.
+    ///
+    /// # Returns
+    /// - A vector of tags, the most used ones first, without the tags unused within the window.
+    /// - An error if the tags could not be counted.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_popular_tags(&self, window: TagWindow) -> Result<Vec<Tag>, ServiceError> {
+        if let Some(tags) = self.popular_tags_cache.get(&window).await {
+            trace!("popular tags found in the cache");
+            return Ok(tags);
+        }
+
+        let since = self.clock.now() - window.duration();
+        trace!("counting the popular tags since {since}");
+        match self
+            .fetch_all(|| {
+                sqlx::query(
+                    "SELECT tags.id, tags.name, COUNT(*) AS question_count FROM question_tags \
+                    JOIN tags ON tags.id = question_tags.tag_id \
+                    JOIN questions ON questions.id = question_tags.question_id \
+                    WHERE questions.created_on > $1 GROUP BY tags.id \
+                    ORDER BY question_count DESC, tags.name LIMIT $2",
+                )
+                .bind(since)
+                .bind(Self::POPULAR_TAGS_LIMIT)
+            })
+            .await?
+            .into_iter()
+            .map(Tag::try_from)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(tags) => {
+                trace!("{} popular tags counted successfully", tags.len());
+                self.popular_tags_cache.insert(window, tags.clone()).await;
+                Ok(tags)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns a tag from the table `tags` by its ID, with the number of the questions
+    /// using it.
+    ///
+    /// # Arguments
+    /// - `tag_id`: The ID of the tag.
+    ///
+    /// # Returns
+    /// - The tag, or `None` if the tag was not found.
+    /// - An error if the tag could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_tag(&self, tag_id: TagId) -> Result<Option<Tag>, ServiceError> {
+        let TagId(tag_id) = tag_id;
+        let row = self
+            .fetch_optional(|| {
+                sqlx::query(
+                    "SELECT id, name, (SELECT COUNT(*) FROM question_tags WHERE tag_id = tags.id) AS question_count \
+                    FROM tags WHERE id = $1",
+                )
+                .bind(tag_id)
+            })
+            .await?;
+        match row.map(Tag::try_from).transpose() {
+            Ok(tag) => Ok(tag),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function inserts a tag into the table `tags`, before any question uses it.
+    ///
+    /// # Arguments
+    /// - `name`: The name of the tag.
+    ///
+    /// # Returns
+    /// - The new tag, used by no question.
+    /// - An error if the tag could not be added, e.g. a tag with the same name exists.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_tag(&self, name: &str) -> Result<Tag, ServiceError> {
+        trace!("adding a tag to the database");
+        match sqlx::query("INSERT INTO tags (name) VALUES ($1) RETURNING id, name, 0::BIGINT AS question_count")
+            .bind(name)
+            .map(Tag::try_from)
+            .fetch_one(&self.connection)
+            .await?
+        {
+            Ok(tag) => {
+                trace!("tag added successfully with id={}", tag.id.0);
+                Ok(tag)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function renames a tag in the table `tags`, and so in every question using it.
+    ///
+    /// # Arguments
+    /// - `tag_id`: The ID of the tag.
+    /// - `name`: The new name of the tag.
+    ///
+    /// # Returns
+    /// - The renamed tag, or `None` if the tag was not found.
+    /// - An error if the tag could not be renamed, e.g. a tag with the new name exists.
+    #[instrument(target = "store", skip(self))]
+    pub async fn rename_tag(&self, tag_id: TagId, name: &str) -> Result<Option<Tag>, ServiceError> {
+        trace!("renaming the tag with id={}", tag_id.0);
+        let row = sqlx::query(
+            "WITH renamed AS (UPDATE tags SET name = $2 WHERE id = $1 RETURNING *) \
+            SELECT id, name, (SELECT COUNT(*) FROM question_tags WHERE tag_id = renamed.id) AS question_count \
+            FROM renamed",
+        )
+        .bind(tag_id.0)
+        .bind(name)
+        .fetch_optional(&self.connection)
+        .await?;
+
+        match row.map(Tag::try_from).transpose() {
+            Ok(Some(tag)) => {
+                trace!("tag renamed successfully");
+                let question_ids = self.get_tagged_question_ids(tag_id).await?;
+                self.invalidate_questions(question_ids).await;
+                Ok(Some(tag))
+            }
+            Ok(None) => {
+                trace!("tag not found");
+                Ok(None)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function deletes a tag from the table `tags`, and so from every question using it.
+    ///
+    /// # Arguments
+    /// - `tag_id`: The ID of the tag.
+    ///
+    /// # Returns
+    /// - An Ok(true) if the tag was deleted successfully.
+    /// - An Ok(false) if the tag was not found.
+    /// - An error if the tag could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn delete_tag(&self, tag_id: TagId) -> Result<bool, ServiceError> {
+        trace!("deleting the tag with id={}", tag_id.0);
+        // The questions are read before the tag is unlinked from them
+        let question_ids = self.get_tagged_question_ids(tag_id).await?;
+        match sqlx::query("DELETE FROM tags WHERE id = $1")
+            .bind(tag_id.0)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(result) => {
+                self.invalidate_questions(question_ids).await;
+                Ok(result.rows_affected() > 0)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the IDs of the questions using the tag, from the table `question_tags`.
+    async fn get_tagged_question_ids(&self, tag_id: TagId) -> Result<Vec<QuestionId>, ServiceError> {
+        let question_ids: Vec<i32> = sqlx::query_scalar("SELECT question_id FROM question_tags WHERE tag_id = $1")
+            .bind(tag_id.0)
+            .fetch_all(&self.connection)
+            .await?;
+        Ok(question_ids.into_iter().map(QuestionId).collect())
+    }
+
+    /// This function invalidates the cached copies of the questions, and of the pages of questions,
+    /// see [Store::invalidate_cache].
+    async fn invalidate_questions(&self, question_ids: Vec<QuestionId>) {
+        for question_id in question_ids {
+            self.invalidate_cache(Some(question_id)).await;
+        }
+        self.invalidate_cache(None).await;
+    }
+
+    /// This function makes the account follow the tag, in the table `tag_subscriptions`, so the
+    /// questions using it are listed in the feed of the account, see [Store::get_feed].
+    ///
+    /// Following a tag the account already follows does nothing.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `name`: The name of the tag, normalized as by [tag_name](crate::types::sanitize::tag_name).
+    ///
+    /// # Returns
+    /// - `true` if the account follows the tag, `false` if the tag does not exist.
+    /// - An error if the subscription could not be added.
+    #[instrument(target = "store", skip(self))]
+    pub async fn follow_tag(&self, account_id: AccountId, name: &str) -> Result<bool, ServiceError> {
+        let AccountId(account_id) = account_id;
+        trace!("following the tag {name:?} by the account with id={account_id}");
+        match sqlx::query_scalar(
+            "WITH tag AS (SELECT id FROM tags WHERE name = $2), \
+            followed AS (INSERT INTO tag_subscriptions (account_id, tag_id) SELECT $1, id FROM tag \
+            ON CONFLICT (account_id, tag_id) DO NOTHING) \
+            SELECT EXISTS (SELECT 1 FROM tag)",
+        )
+        .bind(account_id)
+        .bind(name)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(found) => Ok(found),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function makes the account stop following the tag, in the table `tag_subscriptions`.
+    ///
+    /// Unfollowing a tag the account does not follow does nothing.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `name`: The name of the tag, normalized as by [tag_name](crate::types::sanitize::tag_name).
+    ///
+    /// # Returns
+    /// - `true` if the account does not follow the tag anymore, `false` if the tag does not exist.
+    /// - An error if the subscription could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn unfollow_tag(&self, account_id: AccountId, name: &str) -> Result<bool, ServiceError> {
+        let AccountId(account_id) = account_id;
+        trace!("unfollowing the tag {name:?} by the account with id={account_id}");
+        match sqlx::query_scalar(
+            "WITH tag AS (SELECT id FROM tags WHERE name = $2), \
+            unfollowed AS (DELETE FROM tag_subscriptions WHERE account_id = $1 AND tag_id IN (SELECT id FROM tag)) \
+            SELECT EXISTS (SELECT 1 FROM tag)",
+        )
+        .bind(account_id)
+        .bind(name)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(found) => Ok(found),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the feed of the account: the questions using the tags the account
+    /// follows, the most recent ones first.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of questions, empty if the account follows no tags.
+    /// - An error if the questions could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_feed(&self, account_id: AccountId, pag: Pagination) -> Result<Vec<Question>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        let Pagination { offset, limit } = self.paginate(pag)?;
+
+        trace!("fetching the feed of the account with id={account_id}");
+        let sql = format!(
+            "SELECT questions.*, {QUESTION_TAGS}, \
+            (SELECT COUNT(*) FROM answers WHERE answers.question_id = questions.id) AS answer_count \
+            FROM questions WHERE EXISTS (SELECT 1 FROM question_tags \
+            JOIN tag_subscriptions ON tag_subscriptions.tag_id = question_tags.tag_id \
+            WHERE question_tags.question_id = questions.id AND tag_subscriptions.account_id = $1) \
+            ORDER BY created_on DESC, id DESC LIMIT $2 OFFSET $3"
+        );
+        match self
+            .fetch_all(|| sqlx::query(&sql).bind(account_id).bind(limit).bind(offset))
+            .await?
+            .into_iter()
+            .map(|row| Ok((Self::author_id(&row)?, Question::try_from(row)?)))
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+        {
+            Ok(rows) => {
+                trace!("feed fetched successfully");
+                let authors = self
+                    .get_authors(rows.iter().filter_map(|(author_id, _)| *author_id))
+                    .await?;
+                Ok(rows
+                    .into_iter()
+                    .map(|(author_id, question)| Question {
+                        author: author_id.and_then(|author_id| authors.get(&author_id).cloned()),
+                        ..question
+                    })
+                    .collect())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function bookmarks the question for the account, in the table `bookmarks`, so it is
+    /// listed in the bookmarks of the account, see [Store::get_bookmarks].
+    ///
+    /// Bookmarking a question the account has already bookmarked does nothing.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `question_id`: An integer that represents the ID of the question.
+    ///
+    /// # Returns
+    /// - `true` if the question is bookmarked, `false` if the question does not exist.
+    /// - An error if the bookmark could not be added.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_bookmark(&self, account_id: AccountId, question_id: QuestionId) -> Result<bool, ServiceError> {
+        let (AccountId(account_id), QuestionId(question_id)) = (account_id, question_id);
+        trace!("bookmarking the question with id={question_id} for the account with id={account_id}");
+        match sqlx::query_scalar(
+            "WITH question AS (SELECT id FROM questions WHERE id = $2), \
+            bookmarked AS (INSERT INTO bookmarks (account_id, question_id) SELECT $1, id FROM question \
+            ON CONFLICT (account_id, question_id) DO NOTHING) \
+            SELECT EXISTS (SELECT 1 FROM question)",
+        )
+        .bind(account_id)
+        .bind(question_id)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(found) => Ok(found),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function removes the bookmark of the question for the account, from the table `bookmarks`.
+    ///
+    /// Removing a bookmark the account does not have does nothing.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `question_id`: An integer that represents the ID of the question.
+    ///
+    /// # Returns
+    /// - `true` if the question is not bookmarked anymore, `false` if the question does not exist.
+    /// - An error if the bookmark could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn remove_bookmark(&self, account_id: AccountId, question_id: QuestionId) -> Result<bool, ServiceError> {
+        let (AccountId(account_id), QuestionId(question_id)) = (account_id, question_id);
+        trace!("removing the bookmark of the question with id={question_id} for the account with id={account_id}");
+        match sqlx::query_scalar(
+            "WITH question AS (SELECT id FROM questions WHERE id = $2), \
+            removed AS (DELETE FROM bookmarks WHERE account_id = $1 AND question_id IN (SELECT id FROM question)) \
+            SELECT EXISTS (SELECT 1 FROM question)",
+        )
+        .bind(account_id)
+        .bind(question_id)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(found) => Ok(found),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the questions bookmarked by the account, the most recently bookmarked
+    /// ones first.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of questions.
+    /// - An error if the questions could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_bookmarks(&self, account_id: AccountId, pag: Pagination) -> Result<Vec<Question>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        let Pagination { offset, limit } = self.paginate(pag)?;
+
+        trace!("fetching the bookmarks of the account with id={account_id}");
+        let sql = format!(
+            "SELECT questions.*, {QUESTION_TAGS}, \
+            (SELECT COUNT(*) FROM answers WHERE answers.question_id = questions.id) AS answer_count \
+            FROM bookmarks JOIN questions ON questions.id = bookmarks.question_id WHERE bookmarks.account_id = $1 \
+            ORDER BY bookmarks.created_on DESC, questions.id DESC LIMIT $2 OFFSET $3"
+        );
+        match self
+            .fetch_all(|| sqlx::query(&sql).bind(account_id).bind(limit).bind(offset))
+            .await?
+            .into_iter()
+            .map(|row| Ok((Self::author_id(&row)?, Question::try_from(row)?)))
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+        {
+            Ok(rows) => {
+                trace!("bookmarks fetched successfully");
+                let authors = self
+                    .get_authors(rows.iter().filter_map(|(author_id, _)| *author_id))
+                    .await?;
+                Ok(rows
+                    .into_iter()
+                    .map(|(author_id, question)| Question {
+                        author: author_id.and_then(|author_id| authors.get(&author_id).cloned()),
+                        ..question
+                    })
+                    .collect())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function records the vote of an account on a question in the table `question_votes`.
+    ///
+    /// Voting in the other direction changes the vote, and voting again in the same direction fails
+    /// with the unique violation of the table, see [Store::record_vote].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account that votes.
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `direction`: The direction of the vote.
+    ///
+    /// # Returns
+    /// - The new score of the question, with the vote of the account.
+    /// - [ServiceError::QuestionNotFound] if the question does not exist.
+    /// - An error if the vote could not be recorded, e.g. the account has already voted in the direction.
+    #[instrument(target = "store", skip(self))]
+    pub async fn vote_question(
+        &self,
+        account_id: AccountId,
+        question_id: QuestionId,
+        direction: VoteDirection,
+    ) -> Result<Score, ServiceError> {
+        trace!("voting {direction:?} on the question with id={}", question_id.0);
+        match self
+            .record_vote(VotedItem::Question, question_id.0, account_id, Some(direction))
+            .await
+        {
+            Ok(Some(score)) => {
+                trace!("question voted successfully; score={}", score.score);
+                self.invalidate_cache(Some(question_id)).await;
+                Ok(score)
+            }
+            Ok(None) => Err(ServiceError::QuestionNotFound(question_id.into())),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function records the vote of an account on an answer in the table `answer_votes`.
+    ///
+    /// Voting in the other direction changes the vote, and voting again in the same direction fails
+    /// with the unique violation of the table, see [Store::record_vote].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account that votes.
+    /// - `answer_id`: An integer that represents the ID of the answer.
+    /// - `direction`: The direction of the vote.
+    ///
+    /// # Returns
+    /// - The new score of the answer, with the vote of the account.
+    /// - [ServiceError::AnswerNotFound] if the answer does not exist.
+    /// - An error if the vote could not be recorded, e.g. the account has already voted in the direction.
+    #[instrument(target = "store", skip(self))]
+    pub async fn vote_answer(
+        &self,
+        account_id: AccountId,
+        answer_id: AnswerId,
+        direction: VoteDirection,
+    ) -> Result<Score, ServiceError> {
+        trace!("voting {direction:?} on the answer with id={}", answer_id.0);
+        match self
+            .record_vote(VotedItem::Answer, answer_id.0, account_id, Some(direction))
+            .await
+        {
+            Ok(Some(score)) => {
+                trace!("answer voted successfully; score={}", score.score);
+                // The answers are embedded in the cached question details
+                self.invalidate_cache(None).await;
+                Ok(score)
+            }
+            Ok(None) => Err(ServiceError::AnswerNotFound(answer_id.into())),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function retracts the vote of an account on a question, from the table `question_votes`.
+    ///
+    /// Retracting is idempotent, the score is returned whether the account has voted or not.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account that retracts the vote.
+    /// - `question_id`: An integer that represents the ID of the question.
+    ///
+    /// # Returns
+    /// - The new score of the question, without a vote of the account.
+    /// - [ServiceError::QuestionNotFound] if the question does not exist.
+    /// - An error if the vote could not be retracted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn retract_question_vote(
+        &self,
+        account_id: AccountId,
+        question_id: QuestionId,
+    ) -> Result<Score, ServiceError> {
+        trace!("retracting the vote on the question with id={}", question_id.0);
+        match self
+            .record_vote(VotedItem::Question, question_id.0, account_id, None)
+            .await
+        {
+            Ok(Some(score)) => {
+                trace!("question vote retracted successfully; score={}", score.score);
+                self.invalidate_cache(Some(question_id)).await;
+                Ok(score)
+            }
+            Ok(None) => Err(ServiceError::QuestionNotFound(question_id.into())),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function retracts the vote of an account on an answer, from the table `answer_votes`.
+    ///
+    /// Retracting is idempotent, the score is returned whether the account has voted or not.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account that retracts the vote.
+    /// - `answer_id`: An integer that represents the ID of the answer.
+    ///
+    /// # Returns
+    /// - The new score of the answer, without a vote of the account.
+    /// - [ServiceError::AnswerNotFound] if the answer does not exist.
+    /// - An error if the vote could not be retracted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn retract_answer_vote(&self, account_id: AccountId, answer_id: AnswerId) -> Result<Score, ServiceError> {
+        trace!("retracting the vote on the answer with id={}", answer_id.0);
+        match self.record_vote(VotedItem::Answer, answer_id.0, account_id, None).await {
+            Ok(Some(score)) => {
+                trace!("answer vote retracted successfully; score={}", score.score);
+                // The answers are embedded in the cached question details
+                self.invalidate_cache(None).await;
+                Ok(score)
+            }
+            Ok(None) => Err(ServiceError::AnswerNotFound(answer_id.into())),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function records the vote of an account on a question or an answer, in one transaction,
+    /// and returns the new score of the item.
+    ///
+    /// The score is kept in the `score` column of the item, and changed by the difference between
+    /// the new and the previous vote of the account, so the listings read it without aggregating
+    /// the votes. The item is locked until the vote is committed, so the concurrent votes on the
+    /// same item are applied one after the other, and the item cannot be deleted in the meantime.
+    ///
+    /// Without a direction, the vote of the account is retracted. A vote in the same direction as
+    /// the previous one is inserted anyway, so the primary key of the votes rejects it with the
+    /// unique violation, which the API reports as a conflict.
+    ///
+    /// # Returns
+    /// - The new score of the item, with the vote of the account.
+    /// - `None` if the item does not exist.
+    /// - An error if the vote could not be recorded.
+    async fn record_vote(
+        &self,
+        item: VotedItem,
+        item_id: i32,
+        account_id: AccountId,
+        direction: Option<VoteDirection>,
+    ) -> Result<Option<Score>, sqlx::Error> {
+        let AccountId(account_id) = account_id;
+        let (items, votes, column) = item.tables();
+
+        let mut transaction = self.connection.begin().await?;
+        let locked: Option<i32> = sqlx::query_scalar(&format!("SELECT id FROM {items} WHERE id = $1 FOR UPDATE"))
+            .bind(item_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+        if locked.is_none() {
+            trace!("voted item not found");
+            return Ok(None);
+        }
+
+        let previous: Option<i16> = sqlx::query_scalar(&format!(
+            "SELECT value FROM {votes} WHERE {column} = $1 AND account_id = $2"
+        ))
+        .bind(item_id)
+        .bind(account_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let vote = match (direction, previous) {
+            (None, None) => None,
+            (None, Some(_)) => {
+                trace!("retracting the vote");
+                sqlx::query(&format!("DELETE FROM {votes} WHERE {column} = $1 AND account_id = $2"))
+                    .bind(item_id)
+                    .bind(account_id)
+                    .execute(&mut *transaction)
+                    .await?;
+                None
+            }
+            (Some(direction), Some(value)) if value != direction.value() => {
+                trace!("changing the vote");
+                sqlx::query(&format!(
+                    "UPDATE {votes} SET value = $3 WHERE {column} = $1 AND account_id = $2"
+                ))
+                .bind(item_id)
+                .bind(account_id)
+                .bind(direction.value())
+                .execute(&mut *transaction)
+                .await?;
+                Some(direction)
+            }
+            (Some(direction), _) => {
+                sqlx::query(&format!(
+                    "INSERT INTO {votes} ({column}, account_id, value) VALUES ($1, $2, $3)"
+                ))
+                .bind(item_id)
+                .bind(account_id)
+                .bind(direction.value())
+                .execute(&mut *transaction)
+                .await?;
+                Some(direction)
+            }
+        };
+
+        let change = vote.map_or(0, |vote| i64::from(vote.value())) - previous.map_or(0, i64::from);
+        let score: i64 = sqlx::query_scalar(&format!(
+            "UPDATE {items} SET score = score + $2 WHERE id = $1 RETURNING score"
+        ))
+        .bind(item_id)
+        .bind(change)
+        .fetch_one(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+        Ok(Some(Score { score, vote }))
+    }
+
+    /// This function sets the status of a question in the table `questions`, e.g. closes it as a
+    /// duplicate of another question.
+    ///
+    /// Unlike [Store::update_question], the version of the question is not incremented, and no
+    /// revision is recorded, as the title, content and tags are not changed.
+    ///
+    /// # Arguments
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `status`: The new status of the question.
+    ///
+    /// # Returns
+    /// - The updated Question, or `None` if the question was not found.
+    /// - An error if the status could not be set.
+    #[instrument(target = "store", skip(self))]
+    pub async fn set_question_status(
+        &self,
+        question_id: QuestionId,
+        status: QuestionStatus,
+    ) -> Result<Option<Question>, ServiceError> {
+        trace!("setting the status of the question with id={}", question_id.0);
+        let row = sqlx::query(&format!(
+            "WITH updated AS (UPDATE questions SET status = $1, duplicate_of = $2 WHERE id = $3 RETURNING *) \
+            SELECT questions.*, {QUESTION_TAGS} FROM updated AS questions"
+        ))
+        .bind(status.as_str())
+        .bind(status.duplicate_of().map(|QuestionId(id)| id))
+        .bind(question_id.0)
+        .fetch_optional(&self.connection)
+        .await?;
+
+        let Some(row) = row else {
+            trace!("question not found");
+            return Ok(None);
+        };
+
+        let author_id = Self::author_id(&row)?;
+        match Question::try_from(row) {
+            Ok(question) => {
+                trace!("question status set successfully");
+                let question = Question {
+                    author: self.get_authors(author_id).await?.into_values().next(),
+                    ..question
+                };
+                self.invalidate_cache(question.id).await;
+                self.events.publish(Event::QuestionUpdated {
+                    question: question.clone(),
+                });
+                Ok(Some(question))
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function will delete a question from the table `questions` by its ID
+    ///
+    /// If a moderator deletes the question of another account, the entry of the moderation log is
+    /// added in the same transaction, see [Store::get_moderation_log].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the owner of the question, which is only deleted if it is still its owner,
+    ///   or `None` for a question whose account was deleted.
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `moderator_id`: The ID of the moderator deleting the question of another account, if it is not its owner.
+    ///
+    /// # Returns
+    /// - An Ok(true) if the question was deleted successfully.
+    /// - An Ok(false) if the question was not found.
+    /// - An error if the question could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn delete_question(
+        &self,
+        account_id: Option<AccountId>,
+        question_id: QuestionId,
+        moderator_id: Option<AccountId>,
+    ) -> Result<bool, ServiceError> {
+        let owner_id = account_id;
+        let QuestionId(question_id) = question_id;
+        let account_id = account_id.map(|AccountId(account_id)| account_id);
+        trace!("deleting question from the database; id={question_id}");
+        let mut transaction = self.connection.begin().await?;
+        match sqlx::query("DELETE FROM questions WHERE id = $1 AND account_id IS NOT DISTINCT FROM $2")
+            .bind(question_id)
+            .bind(account_id)
+            .execute(&mut *transaction)
+            .await
+        {
+            Ok(res) => {
+                if res.rows_affected() == 0 {
+                    trace!("question not found");
+                    Ok(false)
+                } else {
+                    if let Some(moderator_id) = moderator_id {
+                        let (action, now) = (ModerationAction::DeleteQuestion, self.clock.now());
+                        let question_id = QuestionId(question_id);
+                        Self::log_moderation(&mut transaction, moderator_id, action, question_id, owner_id, now)
+                            .await?;
+                    }
+                    transaction.commit().await?;
+                    trace!("question deleted successfully");
+                    self.invalidate_cache(Some(QuestionId(question_id))).await;
+                    self.events.publish(Event::QuestionDeleted {
+                        question_id: QuestionId(question_id),
+                    });
+                    Ok(true)
+                }
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function adds an answer to the table `answers` for a given question ID.
+    ///
+    /// The contribution counted by the quotas is checked against the daily limit and recorded in the
+    /// same transaction, see [Store::count_answers_since], and so are the jobs notifying the answer and delivering it to
+    /// the webhooks, see [Store::enqueue_event_jobs], and the job censoring it if it is not censored yet.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account answering the question.
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `content`: A string slice that contains the content of the answer.
+    /// - `censored`: Whether the content is censored, otherwise a [Task::CensorAnswer] job is added.
+    ///
+    /// # Returns
+    /// - An Answer if the answer was added successfully.
+    /// - [ServiceError::QuestionNotFound] if there is no question with the given ID.
+    /// - [ServiceError::Conflict] if the question is closed.
+    /// - [ServiceError::QuotaExceeded] if the account reached its daily limit on answers.
+    /// - An error if the answer could not be added.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_answer(
+        &self,
+        account_id: AccountId,
+        question_id: QuestionId,
+        content: String,
+        censored: bool,
+    ) -> Result<Answer, ServiceError> {
+        let AccountId(account_id) = account_id;
+        trace!("adding an answer for the question with id={}", question_id.0);
+        let content_html = markdown::to_html(&content);
+        let mut transaction = self.connection.begin().await?;
+        Self::record_contribution(
+            &mut transaction,
+            &self.quotas,
+            AccountId(account_id),
+            Contribution::Answer,
+            self.clock.now(),
+        )
+        .await?;
+        // The question is locked while it is checked, so it cannot be closed before the answer is added
+        let answer = match sqlx::query(
+            "INSERT INTO answers (content, question_id, account_id, content_html) \
+            SELECT $1, id, $3, $4 FROM questions WHERE id = $2 AND status = 'open' FOR SHARE \
+            RETURNING *",
+        )
+        .bind(content)
+        .bind(question_id.0)
+        .bind(account_id)
+        .bind(content_html)
+        .map(Answer::try_from)
+        .fetch_optional(&mut *transaction)
+        .await
+        {
+            Ok(Some(answer)) => answer,
+            Ok(None) => {
+                let status: Option<String> = sqlx::query_scalar("SELECT status FROM questions WHERE id = $1")
+                    .bind(question_id.0)
+                    .fetch_optional(&self.connection)
+                    .await?;
+                return match status {
+                    Some(status) => {
+                        trace!("question is not open; status={status}");
+                        Err(ServiceError::Conflict(format!(
+                            "question {} is closed to new answers",
+                            question_id.0
+                        )))
+                    }
+                    None => Err(ServiceError::QuestionNotFound(question_id.into())),
+                };
+            }
+            // The question was deleted, or never existed
+            Err(sqlx::Error::Database(error))
+                if error.code().as_deref() == Some(pg_error_codes::FOREIGN_KEY_VIOLATION) =>
+            {
+                return Err(ServiceError::QuestionNotFound(question_id.into()));
+            }
+            Err(error) => return Err(ServiceError::DatabaseQueryError(error)),
+        };
+        match answer {
+            Ok(answer) => {
+                let answer_id = answer.id.expect("inserted answers have an id");
+                let event = Event::AnswerCreated { answer: answer.clone() };
+                Self::enqueue_event_jobs(&mut transaction, &event).await?;
+                if !censored {
+                    Self::enqueue_task(&mut transaction, &Task::CensorAnswer { answer_id }).await?;
+                }
+                transaction.commit().await?;
+                trace!("answer added successfully with id={:?}", answer.id);
+                self.invalidate_cache(None).await;
+                self.events.publish(event);
+                Ok(answer)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns an answer from the table `answers` by its ID.
+    ///
+    /// # Arguments
+    /// - `answer_id`: An integer that represents the ID of the answer.
+    ///
+    /// # Returns
+    /// - An Answer if the answer was found successfully.
+    /// - An error if the answer could not be found.
+    #[instrument(target = "store", skip(self))]
+    pub async fn get_answer(&self, answer_id: AnswerId) -> Result<Option<Answer>, ServiceError> {
+        let AnswerId(answer_id) = answer_id;
+
+        let pg_row = self
+            .fetch_optional(|| sqlx::query("SELECT * FROM answers WHERE id = $1").bind(answer_id))
+            .await?;
+
+        let Some(pg_row) = pg_row else {
+            trace!("answer not found");
+            return Ok(None);
+        };
+
+        let author_id = Self::author_id(&pg_row)?;
+        match Answer::try_from(pg_row) {
+            Ok(answer) => Ok(Some(Answer {
+                author: self.get_authors(author_id).await?.into_values().next(),
+                ..answer
+            })),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the answers to a question from the table `answers`, in the given order.
+    ///
+    /// # Arguments
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `order`: The order of the answers.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of answers, empty if the question has none.
+    /// - An error if the answers could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_answers(
+        &self,
+        question_id: QuestionId,
+        order: AnswerOrder,
+        pag: Pagination,
+    ) -> Result<Vec<Answer>, ServiceError> {
+        let QuestionId(question_id) = question_id;
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        let order_by = match order {
+            AnswerOrder::Newest => "id DESC",
+            AnswerOrder::Oldest => "id",
+            AnswerOrder::Score => "accepted DESC, score DESC, id",
+        };
+        let sql = format!("SELECT * FROM answers WHERE question_id = $1 ORDER BY {order_by} LIMIT $2 OFFSET $3");
+
+        trace!("fetching answers from the database");
+        match self
+            .fetch_all(|| sqlx::query(&sql).bind(question_id).bind(limit).bind(offset))
+            .await?
+            .into_iter()
+            .map(|row| Ok((Self::author_id(&row)?, Answer::try_from(row)?)))
+            .collect::<Result<Vec<_>, sqlx::Error>>()
+        {
+            Ok(rows) => {
+                trace!("answers fetched successfully");
+                let authors = self
+                    .get_authors(rows.iter().filter_map(|(author_id, _)| *author_id))
+                    .await?;
+                Ok(rows
+                    .into_iter()
+                    .map(|(author_id, answer)| Answer {
+                        author: author_id.and_then(|author_id| authors.get(&author_id).cloned()),
+                        ..answer
+                    })
+                    .collect())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the answers of the account from the table `answers`, with the titles
+    /// of the questions they answer, the most recent ones first.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account that wrote the answers.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of answers, empty if the account has none.
+    /// - An error if the answers could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_account_answers(
+        &self,
+        account_id: AccountId,
+        pag: Pagination,
+    ) -> Result<Vec<AccountAnswer>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        trace!("fetching the answers of the account with id={account_id}");
+        match sqlx::query(
+            "SELECT answers.id, answers.content, answers.question_id, answers.accepted, \
+            questions.title AS question_title FROM answers \
+            JOIN questions ON questions.id = answers.question_id \
+            WHERE answers.account_id = $1 ORDER BY answers.id DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(account_id)
+        .bind(limit)
+        .bind(offset)
+        .map(AccountAnswer::try_from)
+        .fetch_all(&self.connection)
+        .await?
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(answers) => {
+                trace!("answers fetched successfully");
+                Ok(answers)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function checks if the account is the author of the answer.
+    ///
+    /// # Arguments
+    /// - `answer_id`: An integer that represents the ID of the answer.
+    /// - `account_id`: An integer that represents the ID of the account.
+    ///
+    /// # Returns
+    /// - A boolean indicating whether the account is the author of the answer.
+    /// - [ServiceError::AnswerNotFound] if the answer does not exist.
+    #[instrument(target = "store", skip(self))]
+    pub async fn is_answer_owner(&self, answer_id: AnswerId, account_id: AccountId) -> Result<bool, ServiceError> {
+        let AnswerId(a_id) = answer_id;
+        let AccountId(acc_id) = account_id;
+
+        match sqlx::query("SELECT account_id FROM answers WHERE id = $1")
+            .bind(a_id)
+            .fetch_optional(&self.connection)
+            .await
+        {
+            Ok(Some(row)) => Ok({
+                let id: Option<i32> = row.try_get("account_id")?;
+                id == Some(acc_id)
+            }),
+            Ok(None) => Err(ServiceError::AnswerNotFound(answer_id.into())),
+            Err(error) => Err(ServiceError::DatabaseQueryError(error)),
+        }
+    }
+
+    /// This function will update an answer in the table `answers` by its ID
+    ///
+    /// The job censoring the answer, if it is not censored yet, is added in the same transaction.
+    ///
+    /// # Arguments
+    /// - `account_id`: An integer that represents the ID of the author of the answer.
+    /// - `answer_id`: An integer that represents the ID of the answer.
+    /// - `content`: A string that contains the new content of the answer.
+    /// - `censored`: Whether the content is censored, otherwise a [Task::CensorAnswer] job is added.
+    ///
+    /// # Returns
+    /// - An updated Answer if the answer was updated successfully.
+    /// - [ServiceError::AnswerNotFound] if the account has no answer with the given ID.
+    /// - An error if the answer could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn update_answer(
+        &self,
+        account_id: AccountId,
+        answer_id: AnswerId,
+        content: String,
+        censored: bool,
+    ) -> Result<Answer, ServiceError> {
+        let AnswerId(a_id) = answer_id;
+        let AccountId(account_id) = account_id;
+        trace!("updating answer in the database; id={a_id}");
+        let content_html = markdown::to_html(&content);
+
+        let mut transaction = self.connection.begin().await?;
+        match sqlx::query(
+            "UPDATE answers \
+            SET content = $1, content_html = $4 \
+            WHERE id = $2 AND account_id = $3 \
+            RETURNING *",
+        )
+        .bind(content)
+        .bind(a_id)
+        .bind(account_id)
+        .bind(content_html)
+        .map(Answer::try_from)
+        .fetch_optional(&mut *transaction)
+        .await?
+        {
+            Some(Ok(answer)) => {
+                if !censored {
+                    Self::enqueue_task(&mut transaction, &Task::CensorAnswer { answer_id }).await?;
+                }
+                transaction.commit().await?;
+                trace!("answer updated successfully");
+                self.events.publish(Event::AnswerUpdated { answer: answer.clone() });
+                Ok(answer)
+            }
+            // The answer was deleted, or its owner changed, since the owner was checked
+            None => {
+                trace!("answer not found");
+                Err(ServiceError::AnswerNotFound(answer_id.into()))
+            }
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function censors the content of an answer in the table `answers`, for the
+    /// [Task::CensorAnswer] jobs.
+    ///
+    /// The answer is only replaced if it was not updated while it was censored, as the updates are
+    /// censored on their own.
+    ///
+    /// # Arguments
+    /// - `answer_id`: The ID of the answer.
+    ///
+    /// # Returns
+    /// - An Ok(true) if the answer was censored.
+    /// - An Ok(false) if the answer was deleted or updated in the meantime.
+    /// - An error if the profanity filter failed, or the answer could not be read or updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn censor_answer(&self, answer_id: AnswerId) -> Result<bool, ServiceError> {
+        let AnswerId(a_id) = answer_id;
+        let content: Option<String> = sqlx::query_scalar("SELECT content FROM answers WHERE id = $1")
+            .bind(a_id)
+            .fetch_optional(&self.connection)
+            .await?;
+        let Some(content) = content else {
+            trace!("answer not found");
+            return Ok(false);
+        };
+
+        let censored_content = self.profanity_filter.censor(content.clone()).await?;
+        let content_html = markdown::to_html(&censored_content);
+        let censored = sqlx::query(
+            "UPDATE answers SET content = $1, content_html = $2 \
+            WHERE id = $3 AND content = $4",
+        )
+        .bind(censored_content)
+        .bind(content_html)
+        .bind(a_id)
+        .bind(content)
+        .execute(&self.connection)
+        .await?
+        .rows_affected()
+            > 0;
+
+        trace!("answer censored={censored}");
+        if censored {
+            self.invalidate_cache(None).await;
+        }
+        Ok(censored)
+    }
+
+    /// This function will delete an answer from the table `answers` by its ID
+    ///
+    /// # Arguments
+    /// - `account_id`: An integer that represents the ID of the author of the answer.
+    /// - `answer_id`: An integer that represents the ID of the answer.
+    ///
+    /// # Returns
+    /// - An Ok(true) if the answer was deleted successfully.
+    /// - An Ok(false) if the answer was not found.
+    /// - An error if the answer could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn delete_answer(&self, account_id: AccountId, answer_id: AnswerId) -> Result<bool, ServiceError> {
+        let AnswerId(a_id) = answer_id;
+        let AccountId(account_id) = account_id;
+        trace!("deleting answer from the database; id={a_id}");
+        match sqlx::query_scalar::<_, i32>(
+            "DELETE FROM answers WHERE id = $1 AND account_id = $2 RETURNING question_id",
+        )
+        .bind(a_id)
+        .bind(account_id)
+        .fetch_optional(&self.connection)
+        .await
+        {
+            Ok(Some(question_id)) => {
+                trace!("answer deleted successfully");
+                self.invalidate_cache(None).await;
+                self.events.publish(Event::AnswerDeleted {
+                    answer_id,
+                    question_id: QuestionId(question_id),
+                });
+                Ok(true)
+            }
+            Ok(None) => {
+                trace!("answer not found");
+                Ok(false)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function marks an answer to the question as accepted, in place of the answer that
+    /// was accepted before, if any.
+    ///
+    /// # Arguments
+    /// - `question_id`: An integer that represents the ID of the question.
+    /// - `answer_id`: An integer that represents the ID of the answer to accept.
+    ///
+    /// # Returns
+    /// - The accepted answer, or `None` if the question has no such answer.
+    /// - An error if the answer could not be accepted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn accept_answer(
+        &self,
+        question_id: QuestionId,
+        answer_id: AnswerId,
+    ) -> Result<Option<Answer>, ServiceError> {
+        let QuestionId(question_id) = question_id;
+        let AnswerId(answer_id) = answer_id;
+        trace!("accepting the answer with id={answer_id} to the question with id={question_id}");
+
+        // The previously accepted answer is cleared first, as the unique index allows a single
+        // accepted answer per question at any moment, even within a statement
+        let mut transaction = self.connection.begin().await?;
+        sqlx::query("UPDATE answers SET accepted = FALSE WHERE question_id = $1 AND accepted AND id <> $2")
+            .bind(question_id)
+            .bind(answer_id)
+            .execute(&mut *transaction)
+            .await?;
+        let answer = match sqlx::query(
+            "UPDATE answers SET accepted = TRUE \
+            WHERE id = $1 AND question_id = $2 \
+            RETURNING *",
+        )
+        .bind(answer_id)
+        .bind(question_id)
+        .map(Answer::try_from)
+        .fetch_optional(&mut *transaction)
+        .await?
+        {
+            Some(Ok(answer)) => answer,
+            Some(Err(error)) => {
+                error!("{error}");
+                return Err(ServiceError::DatabaseQueryError(error));
+            }
+            None => {
+                trace!("answer not found");
+                return Ok(None);
+            }
+        };
+        transaction.commit().await?;
+
+        trace!("answer accepted successfully");
+        self.events.publish(Event::AnswerUpdated { answer: answer.clone() });
+        Ok(Some(answer))
+    }
+
+    /// This function creates a new account in the table `accounts`.
+    ///
+    /// It is expected that the password is already hashed before calling this function. The email
+    /// is stored normalized, see [normalize_email], and is unique regardless of its case.
+    ///
+    /// # Arguments
+    /// - `account`: An `Account` struct that contains the email and password of the account.
+    ///
+    /// # Returns
+    /// - The profile of the created account, without its password.
+    /// - [ServiceError::DatabaseQueryError] with the unique violation of `accounts_email_key` if
+    ///   the email is already registered, see [pg_error_codes::unique_violation].
+    /// - An error if the account could not be created.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_account(&self, account: Account) -> Result<AccountProfile, ServiceError> {
+        match sqlx::query(
+            "INSERT INTO accounts (email, password) VALUES ($1, $2) \
+            RETURNING id, email, created_at, display_name, bio, website",
+        )
+        .bind(normalize_email(&account.email))
+        .bind(account.password)
+        .map(AccountProfile::try_from)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(Ok(profile)) => Ok(profile),
+            Ok(Err(error)) | Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// Get an account from the table `accounts` by its email.
+    ///
+    /// # Arguments
+    /// - `email`: A string slice that contains the email of the account.
+    ///
+    /// # Returns
+    /// - An `Account` if the account was found successfully.
+    #[instrument(target = "store", skip(self))]
+    pub async fn get_account(&self, email: &str) -> Result<Account, ServiceError> {
+        let pg_row = sqlx::query("SELECT * FROM accounts WHERE email = $1")
+            .bind(normalize_email(email))
+            .fetch_one(&self.connection)
+            .await?;
+
+        match Account::try_from(pg_row) {
+            Ok(account) => Ok(account),
+            Err(error) => {
+                tracing::event!(target:"webdev_book", tracing::Level::ERROR, "{:?}", error);
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the account with the email, creating it if there is none, for the
+    /// accounts logging in with an external provider, which verified the email.
+    ///
+    /// An existing account is linked by its email, so it can log in with both its password and the
+    /// provider. A new account gets the given password, expected to be the hash of a random one, so
+    /// it logs in only with the provider.
+    ///
+    /// # Arguments
+    /// - `email`: The email verified by the provider.
+    /// - `password`: The hashed password of the account, if it is created.
+    ///
+    /// # Returns
+    /// - The ID of the existing or the created account.
+    /// - [ServiceError::ValidationError] if the email is not accepted for the accounts.
+    /// - An error if the account could not be read or created.
+    #[instrument(target = "store", skip(self, password))]
+    pub async fn get_or_add_external_account(&self, email: &str, password: &str) -> Result<AccountId, ServiceError> {
+        // The no-op update makes the existing row returned, so the lookup and the insert are atomic
+        match sqlx::query_scalar(
+            "INSERT INTO accounts (email, password) VALUES ($1, $2) \
+            ON CONFLICT (email) DO UPDATE SET email = accounts.email RETURNING id",
+        )
+        .bind(normalize_email(email))
+        .bind(password)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(account_id) => Ok(AccountId(account_id)),
+            Err(error)
+                if error
+                    .as_database_error()
+                    .and_then(|db_error| db_error.code())
+                    .is_some_and(|code| code == pg_error_codes::CHECK_VIOLATION) =>
+            {
+                Err(ServiceError::ValidationError(format!(
+                    "the email {email:?} is not accepted"
+                )))
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the profile of the account from the table `accounts`, by its ID.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - The profile of the account, without its password.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_account_by_id(&self, account_id: AccountId) -> Result<AccountProfile, ServiceError> {
+        match sqlx::query("SELECT id, email, created_at, display_name, bio, website FROM accounts WHERE id = $1")
+            .bind(account_id.0)
+            .map(AccountProfile::try_from)
+            .fetch_optional(&self.connection)
+            .await?
+        {
+            Some(Ok(profile)) => Ok(profile),
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    /// This function replaces the profile of the account in the table `accounts`.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `profile`: The new profile, already normalized and validated.
+    ///
+    /// # Returns
+    /// - The updated profile of the account.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn update_profile(
+        &self,
+        account_id: AccountId,
+        profile: &ProfileUpdate,
+    ) -> Result<AccountProfile, ServiceError> {
+        trace!("updating the profile of the account with id={}", account_id.0);
+        match sqlx::query(
+            "UPDATE accounts SET display_name = $2, bio = $3, website = $4 WHERE id = $1 \
+            RETURNING id, email, created_at, display_name, bio, website",
+        )
+        .bind(account_id.0)
+        .bind(&profile.display_name)
+        .bind(&profile.bio)
+        .bind(&profile.website)
+        .map(AccountProfile::try_from)
+        .fetch_optional(&self.connection)
+        .await?
+        {
+            Some(Ok(profile)) => {
+                trace!("profile updated successfully");
+                Ok(profile)
+            }
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    /// This function returns the public profile of the account, with the number of the questions
+    /// and the answers it posted.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - The public profile of the account.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_public_profile(&self, account_id: AccountId) -> Result<PublicProfile, ServiceError> {
+        match sqlx::query(
+            "SELECT id, display_name, bio, website, created_at, \
+            (SELECT COUNT(*) FROM questions WHERE questions.account_id = accounts.id) AS question_count, \
+            (SELECT COUNT(*) FROM answers WHERE answers.account_id = accounts.id) AS answer_count \
+            FROM accounts WHERE id = $1",
+        )
+        .bind(account_id.0)
+        .map(PublicProfile::try_from)
+        .fetch_optional(&self.connection)
+        .await?
+        {
+            Some(Ok(profile)) => Ok(profile),
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    /// This function bans the account, replacing its previous ban, if any.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `until`: The time the ban ends, or `None` to ban the account for good.
+    /// - `reason`: The reason of the ban.
+    ///
+    /// # Returns
+    /// - The ban if the account was banned.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn ban_account(
+        &self,
+        account_id: AccountId,
+        until: Option<DateTime<Utc>>,
+        reason: &str,
+    ) -> Result<AccountBan, ServiceError> {
+        trace!("banning the account with id={}", account_id.0);
+        match sqlx::query(
+            "UPDATE accounts SET banned_until = $2, ban_reason = $3 WHERE id = $1 \
+            RETURNING id, banned_until, ban_reason",
+        )
+        .bind(account_id.0)
+        .bind(until)
+        .bind(reason)
+        .map(AccountBan::try_from)
+        .fetch_optional(&self.connection)
+        .await?
+        {
+            Some(Ok(ban)) => Ok(ban),
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    /// This function returns the role of the account, from the table `accounts`.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - The role of the account.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_account_role(&self, account_id: AccountId) -> Result<AccountRole, ServiceError> {
+        let role: Option<String> = sqlx::query_scalar("SELECT role FROM accounts WHERE id = $1")
+            .bind(account_id.0)
+            .fetch_optional(&self.connection)
+            .await?;
+        match role.map(|role| role.parse::<AccountRole>()) {
+            Some(Ok(role)) => Ok(role),
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(sqlx::Error::ColumnDecode {
+                    index: "role".to_string(),
+                    source: error.into(),
+                }))
+            }
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    /// This function sets the role of the account, in the table `accounts`.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `role`: The new role of the account.
+    ///
+    /// # Returns
+    /// - An empty result if the role was set.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn set_account_role(&self, account_id: AccountId, role: AccountRole) -> Result<(), ServiceError> {
+        trace!("setting the role of the account with id={} to {role:?}", account_id.0);
+        match sqlx::query("UPDATE accounts SET role = $2 WHERE id = $1")
+            .bind(account_id.0)
+            .bind(role.as_str())
+            .execute(&self.connection)
+            .await
+        {
+            Ok(res) if res.rows_affected() == 0 => Err(ServiceError::AccountNotFound(account_id.into())),
+            Ok(_) => {
+                trace!("role set successfully");
+                Ok(())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function records the change a moderator made to the question of another account in the
+    /// table `moderation_log`, in the transaction making the change, so the change is committed if
+    /// and only if it is recorded.
+    ///
+    /// # Arguments
+    /// - `transaction`: The transaction making the change.
+    /// - `moderator_id`: The ID of the moderator.
+    /// - `action`: The change made by the moderator.
+    /// - `question_id`: The ID of the question.
+    /// - `owner_id`: The ID of the owner of the question, or `None` if the account was deleted.
+    /// - `created_on`: The time the change was made at.
+    async fn log_moderation(
+        transaction: &mut PgConnection,
+        moderator_id: AccountId,
+        action: ModerationAction,
+        question_id: QuestionId,
+        owner_id: Option<AccountId>,
+        created_on: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        trace!("recording the moderation of the question with id={}", question_id.0);
+        sqlx::query(
+            "INSERT INTO moderation_log (moderator_id, action, question_id, owner_id, created_on) \
+            VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(moderator_id.0)
+        .bind(action.as_str())
+        .bind(question_id.0)
+        .bind(owner_id.map(|AccountId(owner_id)| owner_id))
+        .bind(created_on)
+        .execute(&mut *transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// This function returns a page of the moderation log, the newest entries first.
+    ///
+    /// # Arguments
+    /// - `pag`: The pagination of the entries.
+    ///
+    /// # Returns
+    /// - The entries of the page.
+    /// - An error if the entries could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_moderation_log(&self, pag: Pagination) -> Result<Vec<ModerationLogEntry>, ServiceError> {
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        match sqlx::query("SELECT * FROM moderation_log ORDER BY id DESC LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
+            .map(ModerationLogEntry::try_from)
+            .fetch_all(&self.connection)
+            .await?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(entries) => Ok(entries),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the ban of the account, if it is banned at the given time.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `now`: The time the ban is checked at.
+    ///
+    /// # Returns
+    /// - The ban if the account is banned, `None` otherwise.
+    /// - [ServiceError::AccountNotFound] if the account does not exist, e.g. it was deleted.
+    /// - An error if the account could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_active_ban(
+        &self,
+        account_id: AccountId,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AccountBan>, ServiceError> {
+        match sqlx::query(
+            "SELECT id, banned_until, ban_reason, \
+            ban_reason IS NOT NULL AND (banned_until IS NULL OR banned_until > $2) AS banned \
+            FROM accounts WHERE id = $1",
+        )
+        .bind(account_id.0)
+        .bind(now)
+        .map(|row: PgRow| match row.try_get("banned")? {
+            true => AccountBan::try_from(row).map(Some),
+            false => Ok(None),
+        })
+        .fetch_optional(&self.connection)
+        .await?
+        {
+            Some(Ok(ban)) => Ok(ban),
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Err(ServiceError::AccountNotFound(account_id.into())),
+        }
+    }
+
+    /// Returns the keys the failed logins to the account with the email, and from the client
+    /// address, are counted under, with the number of failures each key is limited to.
+    fn failed_login_keys(email: &str, address: Option<IpAddr>) -> Vec<(String, u32)> {
+        let mut keys = vec![(
+            format!("email:{}", normalize_email(email)),
+            Self::FAILED_LOGINS_PER_ACCOUNT,
+        )];
+        if let Some(address) = address {
+            keys.push((format!("address:{address}"), Self::FAILED_LOGINS_PER_ADDRESS));
+        }
+        keys
+    }
+
+    /// This function checks that a login to the account with the email, from the client address,
+    /// is accepted, as neither has failed too many times within [Store::FAILED_LOGINS_WINDOW].
+    ///
+    /// It is checked before the password is verified, so the guesses over the limits do not cost
+    /// the hashing of the password. The failures are counted in memory, by every instance on its own.
+    ///
+    /// # Arguments
+    /// - `email`: The email the login is for.
+    /// - `address`: The address of the client, if it is known.
+    ///
+    /// # Returns
+    /// - An empty result if the login is accepted.
+    /// - [ServiceError::TooManyLoginAttempts] with the number of seconds until the oldest of the
+    ///   counted failures expires, otherwise.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn check_login_attempts(&self, email: &str, address: Option<IpAddr>) -> Result<(), ServiceError> {
+        let now = self.clock.now();
+        let window = chrono::Duration::from_std(Self::FAILED_LOGINS_WINDOW).expect("the window is short");
+        let mut retry_after = None;
+        for (key, limit) in Self::failed_login_keys(email, address) {
+            if let Some(FailedLogins { count, since }) = self.failed_logins.get(&key).await {
+                let resets_at = since + window;
+                if count >= limit && resets_at > now {
+                    let seconds = (resets_at - now).num_seconds().max(1) as u64;
+                    retry_after = retry_after.max(Some(seconds));
+                }
+            }
+        }
+        match retry_after {
+            Some(seconds) => {
+                warn!("login rejected after too many failures, retry in {seconds} seconds");
+                Err(ServiceError::TooManyLoginAttempts(seconds))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// This function counts a failed login to the account with the email, from the client address,
+    /// see [Store::check_login_attempts].
+    ///
+    /// The counting restarts once [Store::FAILED_LOGINS_WINDOW] has passed since the first failure.
+    ///
+    /// # Arguments
+    /// - `email`: The email the login was for.
+    /// - `address`: The address of the client, if it is known.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn record_failed_login(&self, email: &str, address: Option<IpAddr>) {
+        let now = self.clock.now();
+        let window = chrono::Duration::from_std(Self::FAILED_LOGINS_WINDOW).expect("the window is short");
+        for (key, _) in Self::failed_login_keys(email, address) {
+            self.failed_logins
+                .entry(key)
+                .and_upsert_with(|entry| {
+                    let failures = match entry.map(|entry| entry.into_value()) {
+                        Some(FailedLogins { count, since }) if since + window > now => FailedLogins {
+                            count: count.saturating_add(1),
+                            since,
+                        },
+                        _ => FailedLogins { count: 1, since: now },
+                    };
+                    std::future::ready(failures)
+                })
+                .await;
+        }
+    }
+
+    /// This function forgets the failed logins to the account with the email, after it logged in.
+    ///
+    /// The failures of the client address are kept, so an address guessing the passwords of many
+    /// accounts is limited even if it knows the password of one of them.
+    ///
+    /// # Arguments
+    /// - `email`: The email of the account.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn clear_failed_logins(&self, email: &str) {
+        for (key, _) in Self::failed_login_keys(email, None) {
+            self.failed_logins.invalidate(&key).await;
+        }
+    }
+
+    /// This function replaces the hash of the password of the account, in the table `accounts`,
+    /// e.g. after the password was hashed again with the current [PasswordHashing].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `password`: The new hash of the password.
+    ///
+    /// # Returns
+    /// - An empty result if the hash was replaced.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", skip(self, password))]
+    pub async fn update_password(&self, account_id: AccountId, password: &str) -> Result<(), ServiceError> {
+        match sqlx::query("UPDATE accounts SET password = $2 WHERE id = $1")
+            .bind(account_id.0)
+            .bind(password)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(res) if res.rows_affected() == 0 => Err(ServiceError::AccountNotFound(account_id.into())),
+            Ok(_) => {
+                trace!("password updated successfully");
+                Ok(())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function checks that the account is not locked, in the table `accounts`, see
+    /// [Store::record_failed_password].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - An empty result if the account is not locked.
+    /// - [ServiceError::AccountLocked] with the time the account was locked at, otherwise.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn check_account_lock(&self, account_id: AccountId) -> Result<(), ServiceError> {
+        match sqlx::query_scalar::<_, Option<DateTime<Utc>>>("SELECT locked_on FROM accounts WHERE id = $1")
+            .bind(account_id.0)
+            .fetch_optional(&self.connection)
+            .await
+        {
+            Ok(Some(Some(locked_on))) => Err(ServiceError::AccountLocked(locked_on)),
+            Ok(Some(None)) => Ok(()),
+            Ok(None) => Err(ServiceError::AccountNotFound(account_id.into())),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function counts a wrong password given for the account, in the table `accounts`, and
+    /// locks the account once [Store::LOCKOUT_FAILED_LOGINS] consecutive ones were given.
+    ///
+    /// The count is reset by the next login, see [Store::reset_failed_passwords], and the lock is
+    /// only lifted by [Store::unlock_account].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - The time the account was locked at, if it is locked.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn record_failed_password(&self, account_id: AccountId) -> Result<Option<DateTime<Utc>>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        match sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "UPDATE accounts
+             SET failed_logins = failed_logins + 1,
+                 locked_on = CASE WHEN failed_logins + 1 >= $2 THEN COALESCE(locked_on, $3) ELSE locked_on END
+             WHERE id = $1
+             RETURNING locked_on",
+        )
+        .bind(account_id)
+        .bind(Self::LOCKOUT_FAILED_LOGINS)
+        .bind(self.clock.now())
+        .fetch_optional(&self.connection)
+        .await
+        {
+            Ok(locked_on) => {
+                let locked_on = locked_on.flatten();
+                if locked_on.is_some() {
+                    warn!("the account with id={account_id} is locked after too many failed logins");
+                }
+                Ok(locked_on)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function resets the count of the wrong passwords given for the account, in the table
+    /// `accounts`, after it logged in, see [Store::record_failed_password].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - An empty result if the count was reset.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn reset_failed_passwords(&self, account_id: AccountId) -> Result<(), ServiceError> {
+        let AccountId(account_id) = account_id;
+        match sqlx::query("UPDATE accounts SET failed_logins = 0 WHERE id = $1 AND failed_logins > 0")
+            .bind(account_id)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function unlocks the account, in the table `accounts`, and resets the count of the
+    /// wrong passwords given for it, see [Store::record_failed_password].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - An empty result if the account was unlocked, or was not locked.
+    /// - [ServiceError::AccountNotFound] if the account does not exist.
+    /// - An error if the account could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn unlock_account(&self, account_id: AccountId) -> Result<(), ServiceError> {
+        trace!("unlocking the account with id={}", account_id.0);
+        match sqlx::query("UPDATE accounts SET failed_logins = 0, locked_on = NULL WHERE id = $1")
+            .bind(account_id.0)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(res) if res.rows_affected() == 0 => Err(ServiceError::AccountNotFound(account_id.into())),
+            Ok(_) => {
+                trace!("account unlocked successfully");
+                Ok(())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns whether the instance is in maintenance mode, see [Store::set_maintenance].
+    pub fn in_maintenance(&self) -> bool {
+        self.maintenance.load(Ordering::Relaxed)
+    }
+
+    /// This function switches the maintenance mode of the instance on or off. While it is on, the
+    /// writes of the accounts are rejected with [ServiceError::Maintenance], and only the reads and
+    /// the administrative requests are served.
+    ///
+    /// Like the counters of the failed logins, the mode is kept in the process, and shared by the
+    /// clones of the store, so with several instances of the service it is switched on each of them.
+    ///
+    /// # Arguments
+    /// - `enabled`: Whether the maintenance mode is on.
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.maintenance.store(enabled, Ordering::Relaxed);
+        info!("maintenance mode switched {}", if enabled { "on" } else { "off" });
+    }
+
+    /// This function revokes the token, in the table `revoked_tokens`, so it is rejected before it
+    /// expires, see [Store::is_token_revoked]. The session of the token is ended as well.
+    ///
+    /// The revocations of the tokens expired by now are deleted, as the expired tokens are rejected anyway.
+    ///
+    /// # Arguments
+    /// - `jti`: The unique id of the token, its `jti` claim.
+    /// - `expires_on`: The time the token expires.
+    ///
+    /// # Returns
+    /// - An empty result if the token was revoked, or had already been revoked.
+    /// - An error if the revocation could not be recorded.
+    #[instrument(target = "store", skip(self))]
+    pub async fn revoke_token(&self, jti: &str, expires_on: DateTime<Utc>) -> Result<(), ServiceError> {
+        trace!("revoking the token with jti={jti}");
+        let mut transaction = self.connection.begin().await?;
+        sqlx::query("DELETE FROM revoked_tokens WHERE expires_on < $1")
+            .bind(self.clock.now())
+            .execute(&mut *transaction)
+            .await?;
+        match sqlx::query("INSERT INTO revoked_tokens (jti, expires_on) VALUES ($1, $2) ON CONFLICT (jti) DO NOTHING")
+            .bind(jti)
+            .bind(expires_on)
+            .execute(&mut *transaction)
+            .await
+        {
+            Ok(_) => {
+                sqlx::query("DELETE FROM sessions WHERE jti = $1")
+                    .bind(jti)
+                    .execute(&mut *transaction)
+                    .await?;
+                transaction.commit().await?;
+                trace!("token revoked successfully");
+                Ok(())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function checks if the token was revoked, in the table `revoked_tokens`, or if its
+    /// session was deleted from the table `sessions`, see [Store::delete_session].
+    ///
+    /// The tokens without a session, e.g. the ones issued before the sessions were recorded, are
+    /// only checked against the revocations.
+    ///
+    /// # Arguments
+    /// - `jti`: The unique id of the token, its `jti` claim.
+    /// - `session_id`: The ID of the session of the token, its `sid` claim, if it has one.
+    ///
+    /// # Returns
+    /// - A boolean indicating whether the token was revoked.
+    /// - An error if the revocations could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn is_token_revoked(&self, jti: &str, session_id: Option<SessionId>) -> Result<bool, ServiceError> {
+        match sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM revoked_tokens WHERE jti = $1)
+                 OR ($2::INTEGER IS NOT NULL AND NOT EXISTS (SELECT 1 FROM sessions WHERE id = $2 AND jti = $1))",
+        )
+        .bind(jti)
+        .bind(session_id.map(|SessionId(id)| id))
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(revoked) => Ok(revoked),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function checks that the session of a verified token is still valid, and extends it by
+    /// its use if it is remembered, see [Store::slide_session].
+    ///
+    /// # Arguments
+    /// - `session`: The session the token was issued for.
+    ///
+    /// # Returns
+    /// - An empty result if the session is valid.
+    /// - [ServiceError::TokenRevoked] if the token was revoked, see [Store::is_token_revoked], or
+    ///   its account was deleted.
+    /// - [ServiceError::AccountBanned] if the account is banned, see [Store::get_active_ban].
+    /// - An error if the session could not be checked.
+    #[instrument(target = "store", level = "debug", skip_all)]
+    pub async fn validate_session(&self, session: &Session) -> Result<(), ServiceError> {
+        if self.is_token_revoked(&session.jti, session.sid).await? {
+            return Err(ServiceError::TokenRevoked);
+        }
+        match self.get_active_ban(session.account_id, self.clock.now()).await {
+            Ok(Some(ban)) => Err(ServiceError::AccountBanned(ban)),
+            Ok(None) => {
+                if let Some(session_id) = session.sid {
+                    self.slide_session(session_id).await?;
+                }
+                Ok(())
+            }
+            Err(ServiceError::AccountNotFound(_)) => Err(ServiceError::TokenRevoked),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// This function starts a session of the account, in the table `sessions`, for the token with
+    /// the given `jti`.
+    ///
+    /// The sessions of the account expired by now are deleted, as their tokens are rejected anyway.
+    /// The remembered sessions are stored with the hash of their refresh token, see [Store::refresh_session].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `jti`: The unique id of the token of the session, its `jti` claim.
+    /// - `user_agent`: The `User-Agent` of the client that logged in, if it sent one.
+    /// - `expires_on`: The time the session expires.
+    /// - `refresh_token_hash`: The hash of the refresh token of a remembered session.
+    ///
+    /// # Returns
+    /// - The ID of the session.
+    /// - An error if the session could not be added.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_session(
+        &self,
+        account_id: AccountId,
+        jti: &str,
+        user_agent: Option<&str>,
+        expires_on: DateTime<Utc>,
+        refresh_token_hash: Option<&str>,
+    ) -> Result<SessionId, ServiceError> {
+        let AccountId(account_id) = account_id;
+        let mut transaction = self.connection.begin().await?;
+        sqlx::query("DELETE FROM sessions WHERE account_id = $1 AND expires_on < $2")
+            .bind(account_id)
+            .bind(self.clock.now())
+            .execute(&mut *transaction)
+            .await?;
+        match sqlx::query_scalar(
+            "INSERT INTO sessions (account_id, jti, user_agent, created_on, expires_on, refresh_token_hash)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING id",
+        )
+        .bind(account_id)
+        .bind(jti)
+        .bind(user_agent)
+        .bind(self.clock.now())
+        .bind(expires_on)
+        .bind(refresh_token_hash)
+        .fetch_one(&mut *transaction)
+        .await
+        {
+            Ok(id) => {
+                transaction.commit().await?;
+                trace!("session {id} started");
+                Ok(SessionId(id))
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function refreshes the remembered session with the given refresh token, in the table
+    /// `sessions`, if it has not expired.
+    ///
+    /// The session gets the new token and the new refresh token, so the previous ones are rejected
+    /// from now on, see [Store::is_token_revoked]. Its expiration slides to
+    /// [SessionLifetimes::remember_me] from now, but not past [SessionLifetimes::remember_me_max]
+    /// after the session was started.
+    ///
+    /// # Arguments
+    /// - `refresh_token_hash`: The hash of the refresh token of the session.
+    /// - `jti`: The unique id of the new token of the session, its `jti` claim.
+    /// - `new_refresh_token_hash`: The hash of the new refresh token of the session.
+    ///
+    /// # Returns
+    /// - The ID of the session, the ID of its account and its new expiration, if the session was refreshed.
+    /// - `None` if no session has the refresh token, or the session has expired.
+    /// - An error if the session could not be refreshed.
+    #[instrument(target = "store", skip_all)]
+    pub async fn refresh_session(
+        &self,
+        refresh_token_hash: &str,
+        jti: &str,
+        new_refresh_token_hash: &str,
+    ) -> Result<Option<(SessionId, AccountId, DateTime<Utc>)>, ServiceError> {
+        let now = self.clock.now();
+        let SessionLifetimes {
+            remember_me,
+            remember_me_max,
+            ..
+        } = self.session_lifetimes;
+        match sqlx::query(
+            "UPDATE sessions
+             SET jti = $2, refresh_token_hash = $3, expires_on = LEAST($4, created_on + $5)
+             WHERE refresh_token_hash = $1 AND expires_on >= $6
+             RETURNING id, account_id, expires_on",
+        )
+        .bind(refresh_token_hash)
+        .bind(jti)
+        .bind(new_refresh_token_hash)
+        .bind(now + remember_me)
+        .bind(remember_me_max)
+        .bind(now)
+        .map(|row: PgRow| -> Result<_, sqlx::Error> {
+            Ok((
+                SessionId(row.try_get("id")?),
+                AccountId(row.try_get("account_id")?),
+                row.try_get("expires_on")?,
+            ))
+        })
+        .fetch_optional(&self.connection)
+        .await?
+        {
+            Some(Ok(session)) => {
+                trace!("session refreshed successfully");
+                Ok(Some(session))
+            }
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// This function extends the remembered session in the table `sessions` on its use, if it has
+    /// not expired.
+    ///
+    /// The expiration slides like on a refresh, see [Store::refresh_session], but only once
+    /// [SessionLifetimes::remember_me_slide] has passed since the last extension, so the session is
+    /// not updated on every request. The sessions that are not remembered are left alone.
+    ///
+    /// # Arguments
+    /// - `session_id`: The ID of the session.
+    ///
+    /// # Returns
+    /// - The new expiration of the session, if the session was extended.
+    /// - `None` if the session was not extended.
+    /// - An error if the session could not be extended.
+    #[instrument(target = "store")]
+    pub async fn slide_session(&self, session_id: SessionId) -> Result<Option<DateTime<Utc>>, ServiceError> {
+        let now = self.clock.now();
+        let SessionLifetimes {
+            remember_me,
+            remember_me_max,
+            remember_me_slide,
+            ..
+        } = self.session_lifetimes;
+        match sqlx::query(
+            "UPDATE sessions
+             SET expires_on = LEAST($2, created_on + $3)
+             WHERE id = $1 AND refresh_token_hash IS NOT NULL AND expires_on >= $4
+               AND expires_on + $5 <= LEAST($2, created_on + $3)
+             RETURNING expires_on",
+        )
+        .bind(session_id.0)
+        .bind(now + remember_me)
+        .bind(remember_me_max)
+        .bind(now)
+        .bind(remember_me_slide)
+        .map(|row: PgRow| row.try_get("expires_on"))
+        .fetch_optional(&self.connection)
+        .await?
+        {
+            Some(Ok(expires_on)) => {
+                trace!("session extended successfully");
+                Ok(Some(expires_on))
+            }
+            Some(Err(error)) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// This function returns the sessions of the account from the table `sessions` that have not
+    /// expired, the most recent ones first.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `current`: The ID of the session of the request, which is marked as current, if it has one.
+    ///
+    /// # Returns
+    /// - A vector of sessions.
+    /// - An error if the sessions could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_sessions(
+        &self,
+        account_id: AccountId,
+        current: Option<SessionId>,
+    ) -> Result<Vec<ActiveSession>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        match sqlx::query(
+            "SELECT id, user_agent, created_on, expires_on, id IS NOT DISTINCT FROM $2 AS current
+             FROM sessions
+             WHERE account_id = $1 AND expires_on >= $3
+             ORDER BY created_on DESC, id DESC",
+        )
+        .bind(account_id)
+        .bind(current.map(|SessionId(id)| id))
+        .bind(self.clock.now())
+        .map(ActiveSession::try_from)
+        .fetch_all(&self.connection)
+        .await?
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(sessions) => Ok(sessions),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function deletes the session of the account from the table `sessions`, so the token
+    /// of the session is rejected from now on, see [Store::is_token_revoked].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `session_id`: The ID of the session.
+    ///
+    /// # Returns
+    /// - `true` if the session was deleted, `false` if the account has no such session.
+    /// - An error if the session could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn delete_session(&self, account_id: AccountId, session_id: SessionId) -> Result<bool, ServiceError> {
+        let (AccountId(account_id), SessionId(session_id)) = (account_id, session_id);
+        match sqlx::query("DELETE FROM sessions WHERE id = $1 AND account_id = $2")
+            .bind(session_id)
+            .bind(account_id)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(result) => Ok(result.rows_affected() > 0),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function deletes all the sessions of the account from the table `sessions`, so the
+    /// tokens issued by logging in are rejected from now on, see [Store::is_token_revoked].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - The number of the deleted sessions.
+    /// - An error if the sessions could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn delete_sessions(&self, account_id: AccountId) -> Result<u64, ServiceError> {
+        let AccountId(account_id) = account_id;
+        match sqlx::query("DELETE FROM sessions WHERE account_id = $1")
+            .bind(account_id)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(result) => Ok(result.rows_affected()),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function deletes the account from the table `accounts`, in a single transaction with
+    /// its questions and answers, and revokes the token of the request, see [Store::revoke_token].
+    ///
+    /// The votes of the account are deleted with it, and subtracted from the scores they were counted
+    /// in. With [AccountContent::Anonymize] the questions and the answers of the account are kept
+    /// without an owner, and listed without an author, while with [AccountContent::Delete] they are
+    /// deleted, the questions together with all their answers. The other tokens of the account are
+    /// rejected as well, as their account no longer exists, see [Store::get_active_ban].
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `content`: What happens to the questions and the answers of the account.
+    /// - `jti`: The unique id of the token of the request, its `jti` claim.
+    /// - `expires_on`: The time the token of the request expires.
+    ///
+    /// # Returns
+    /// - An Ok(true) if the account was deleted successfully.
+    /// - An Ok(false) if the account was not found.
+    /// - An error if the account could not be deleted.
+    #[instrument(target = "store", skip(self))]
+    pub async fn delete_account(
+        &self,
+        account_id: AccountId,
+        content: AccountContent,
+        jti: &str,
+        expires_on: DateTime<Utc>,
+    ) -> Result<bool, ServiceError> {
+        let AccountId(account_id) = account_id;
+        trace!("deleting the account with id={account_id}, content={content:?}");
+        let mut transaction = self.connection.begin().await?;
+        let question_ids: Vec<i32> = sqlx::query_scalar(
+            "SELECT id FROM questions WHERE account_id = $1 \
+            UNION SELECT question_id FROM answers WHERE account_id = $1 \
+            UNION SELECT question_id FROM question_votes WHERE account_id = $1 \
+            UNION SELECT answers.question_id FROM answers \
+            JOIN answer_votes ON answer_votes.answer_id = answers.id WHERE answer_votes.account_id = $1",
+        )
+        .bind(account_id)
+        .fetch_all(&mut *transaction)
+        .await?;
+        sqlx::query(
+            "UPDATE questions SET score = questions.score - question_votes.value FROM question_votes \
+            WHERE question_votes.account_id = $1 AND question_votes.question_id = questions.id",
+        )
+        .bind(account_id)
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query(
+            "UPDATE answers SET score = answers.score - answer_votes.value FROM answer_votes \
+            WHERE answer_votes.account_id = $1 AND answer_votes.answer_id = answers.id",
+        )
+        .bind(account_id)
+        .execute(&mut *transaction)
+        .await?;
+        let (deleted_questions, deleted_answers): (Vec<i32>, Vec<(i32, i32)>) = match content {
+            AccountContent::Anonymize => (Vec::new(), Vec::new()),
+            AccountContent::Delete => {
+                let questions = sqlx::query_scalar("DELETE FROM questions WHERE account_id = $1 RETURNING id")
+                    .bind(account_id)
+                    .fetch_all(&mut *transaction)
+                    .await?;
+                let answers = sqlx::query_as("DELETE FROM answers WHERE account_id = $1 RETURNING id, question_id")
+                    .bind(account_id)
+                    .fetch_all(&mut *transaction)
+                    .await?;
+                (questions, answers)
+            }
+        };
+        sqlx::query("INSERT INTO revoked_tokens (jti, expires_on) VALUES ($1, $2) ON CONFLICT (jti) DO NOTHING")
+            .bind(jti)
+            .bind(expires_on)
+            .execute(&mut *transaction)
+            .await?;
+        match sqlx::query("DELETE FROM accounts WHERE id = $1")
+            .bind(account_id)
+            .execute(&mut *transaction)
+            .await
+        {
+            Ok(res) if res.rows_affected() == 0 => {
+                trace!("account not found");
+                Ok(false)
+            }
+            Ok(_) => {
+                transaction.commit().await?;
+                trace!("account deleted successfully");
+                self.invalidate_questions(question_ids.into_iter().map(QuestionId).collect())
+                    .await;
+                for &question_id in &deleted_questions {
+                    self.events.publish(Event::QuestionDeleted {
+                        question_id: QuestionId(question_id),
+                    });
+                }
+                for (answer_id, question_id) in deleted_answers {
+                    self.events.publish(Event::AnswerDeleted {
+                        answer_id: AnswerId(answer_id),
+                        question_id: QuestionId(question_id),
+                    });
+                }
+                Ok(true)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function counts the questions asked by the account since the given time, from the table
+    /// `contributions`.
+    ///
+    /// The deleted questions are still counted, so deleting a question does not allow asking another one.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `since`: The time from which the questions are counted.
+    ///
+    /// # Returns
+    /// - The number of questions.
+    /// - An error if the questions could not be counted.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn count_questions_since(
+        &self,
+        account_id: AccountId,
+        since: DateTime<Utc>,
+    ) -> Result<i64, ServiceError> {
+        self.count_contributions_since("question", account_id, since).await
+    }
+
+    /// This function counts the answers posted by the account since the given time, from the table
+    /// `contributions`.
+    ///
+    /// The deleted answers are still counted, so deleting an answer does not allow posting another one.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    /// - `since`: The time from which the answers are counted.
+    ///
+    /// # Returns
+    /// - The number of answers.
+    /// - An error if the answers could not be counted.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn count_answers_since(&self, account_id: AccountId, since: DateTime<Utc>) -> Result<i64, ServiceError> {
+        self.count_contributions_since("answer", account_id, since).await
+    }
+
+    /// This function counts the contributions of the kind made by the account since the given time.
+    async fn count_contributions_since(
+        &self,
+        kind: &str,
+        account_id: AccountId,
+        since: DateTime<Utc>,
+    ) -> Result<i64, ServiceError> {
+        // created_on is stored in the time zone of the session, as it is set by NOW()
+        match sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM contributions \
+            WHERE account_id = $1 AND kind = $2 AND created_on >= $3::TIMESTAMPTZ::TIMESTAMP",
+        )
+        .bind(account_id.0)
+        .bind(kind)
+        .bind(since)
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(count) => Ok(count),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function registers a new webhook in the table `webhooks`.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account registering the webhook.
+    /// - `webhook`: A `Webhook` struct that contains the URL, the events and the secret of the webhook.
+    ///
+    /// # Returns
+    /// - A Webhook if the webhook was registered successfully.
+    /// - An error if the webhook could not be registered.
+    #[instrument(target = "store", skip(self, webhook), fields(url = %webhook.url))]
+    pub async fn add_webhook(&self, account_id: AccountId, webhook: Webhook) -> Result<Webhook, ServiceError> {
+        let AccountId(account_id) = account_id;
+        trace!("adding a webhook for the account with id={account_id}");
+        match sqlx::query(
+            "INSERT INTO webhooks (url, events, secret, account_id) \
+            VALUES ($1, $2, $3, $4) \
+            RETURNING *",
+        )
+        .bind(webhook.url)
+        .bind(webhook.events)
+        .bind(webhook.secret)
+        .bind(account_id)
+        .map(Webhook::try_from)
+        .fetch_one(&self.connection)
+        .await?
+        {
+            Ok(webhook) => {
+                trace!("webhook added successfully with id={:?}", webhook.id);
+                Ok(webhook)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function adds the description of a file attached to a question to the table `attachments`.
+    ///
+    /// # Arguments
+    /// - `question_id`: The ID of the question the file is attached to.
+    /// - `file_name`: The name of the file, as given by the client.
+    /// - `content_type`: The media type of the file.
+    /// - `size`: The size of the file in bytes.
+    /// - `storage_key`: The name of the file on disk.
+    ///
+    /// # Returns
+    /// - The attachment if it was added successfully.
+    /// - An error if the attachment could not be added, e.g. if the question does not exist.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_attachment(
+        &self,
+        question_id: QuestionId,
+        file_name: &str,
+        content_type: &str,
+        size: i64,
+        storage_key: &str,
+    ) -> Result<Attachment, ServiceError> {
+        let QuestionId(question_id) = question_id;
+        trace!("adding an attachment to the question with id={question_id}");
+        match sqlx::query(
+            "INSERT INTO attachments (question_id, file_name, content_type, size, storage_key) \
+            VALUES ($1, $2, $3, $4, $5) \
+            RETURNING *",
+        )
+        .bind(question_id)
+        .bind(file_name)
+        .bind(content_type)
+        .bind(size)
+        .bind(storage_key)
+        .map(Attachment::try_from)
+        .fetch_one(&self.connection)
+        .await?
+        {
+            Ok(attachment) => {
+                trace!("attachment added successfully with id={:?}", attachment.id);
+                Ok(attachment)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns an attachment from the table `attachments` by its ID.
+    ///
+    /// # Arguments
+    /// - `attachment_id`: The ID of the attachment.
+    ///
+    /// # Returns
+    /// - The attachment if it exists, `None` otherwise.
+    /// - An error if the attachment could not be read.
+    #[instrument(target = "store", skip(self))]
+    pub async fn get_attachment(&self, attachment_id: AttachmentId) -> Result<Option<Attachment>, ServiceError> {
+        let AttachmentId(attachment_id) = attachment_id;
+        match sqlx::query("SELECT * FROM attachments WHERE id = $1")
+            .bind(attachment_id)
+            .map(Attachment::try_from)
+            .fetch_optional(&self.connection)
+            .await?
+            .transpose()
+        {
+            Ok(attachment) => Ok(attachment),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns a webhook from the table `webhooks` by its ID.
+    ///
+    /// # Arguments
+    /// - `webhook_id`: The ID of the webhook.
+    ///
+    /// # Returns
+    /// - The webhook if it exists, `None` otherwise.
+    /// - An error if the webhook could not be read.
+    #[instrument(target = "store", skip(self))]
+    pub async fn get_webhook(&self, webhook_id: WebhookId) -> Result<Option<Webhook>, ServiceError> {
+        let WebhookId(webhook_id) = webhook_id;
+        match sqlx::query("SELECT * FROM webhooks WHERE id = $1")
+            .bind(webhook_id)
+            .map(Webhook::try_from)
+            .fetch_optional(&self.connection)
+            .await?
+            .transpose()
+        {
+            Ok(webhook) => Ok(webhook),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function adds a job to the queue in the table `jobs`.
+    ///
+    /// # Arguments
+    /// - `kind`: The kind of the job, which tells the worker how to run it.
+    /// - `payload`: The description of the work to do.
+    ///
+    /// # Returns
+    /// - The job if it was added successfully.
+    /// - An error if the job could not be added.
+    #[instrument(target = "store", skip(self, payload))]
+    pub async fn enqueue_job(&self, kind: &str, payload: serde_json::Value) -> Result<Job, ServiceError> {
+        trace!("adding a job to the queue");
+        match sqlx::query("INSERT INTO jobs (kind, payload) VALUES ($1, $2) RETURNING *")
+            .bind(kind)
+            .bind(payload)
+            .map(Job::try_from)
+            .fetch_one(&self.connection)
+            .await?
+        {
+            Ok(job) => {
+                trace!("job added successfully with id={:?}", job.id);
+                Ok(job)
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function takes the next due job from the queue, and marks it as running.
+    ///
+    /// The job is locked while it is being taken, so concurrent workers, even in other
+    /// service instances, never take the same job.
+    ///
+    /// # Returns
+    /// - The job if there is one due, `None` otherwise.
+    /// - An error if the queue could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn claim_job(&self) -> Result<Option<Job>, ServiceError> {
+        match sqlx::query(
+            "UPDATE jobs SET status = 'running', attempts = attempts + 1, updated_on = NOW() \
+            WHERE id = (\
+                SELECT id FROM jobs WHERE status = 'pending' AND run_at <= NOW() \
+                ORDER BY run_at LIMIT 1 FOR UPDATE SKIP LOCKED\
+            ) \
+            RETURNING *",
+        )
+        .map(Job::try_from)
+        .fetch_optional(&self.connection)
+        .await?
+        .transpose()
+        {
+            Ok(job) => Ok(job),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function marks the job as done.
+    ///
+    /// # Arguments
+    /// - `job_id`: The ID of the job.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn complete_job(&self, job_id: JobId) -> Result<(), ServiceError> {
+        let JobId(job_id) = job_id;
+        match sqlx::query("UPDATE jobs SET status = 'done', last_error = NULL, updated_on = NOW() WHERE id = $1")
+            .bind(job_id)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function records a failed attempt of the job.
+    ///
+    /// The job is scheduled to be retried after the given delay, unless it ran out of attempts,
+    /// in which case it is marked as failed.
+    ///
+    /// # Arguments
+    /// - `job_id`: The ID of the job.
+    /// - `error`: The error of the attempt.
+    /// - `retry_delay`: The time to wait before the retry.
+    ///
+    /// # Returns
+    /// - The new status of the job.
+    /// - An error if the job could not be updated.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn fail_job(
+        &self,
+        job_id: JobId,
+        error: &str,
+        retry_delay: std::time::Duration,
+    ) -> Result<JobStatus, ServiceError> {
+        let JobId(job_id) = job_id;
+        match sqlx::query_scalar::<_, String>(
+            "UPDATE jobs SET \
+                status = CASE WHEN attempts < max_attempts THEN 'pending' ELSE 'failed' END, \
+                run_at = NOW() + make_interval(secs => $3), \
+                last_error = $2, \
+                updated_on = NOW() \
+            WHERE id = $1 \
+            RETURNING status",
+        )
+        .bind(job_id)
+        .bind(error)
+        .bind(retry_delay.as_secs_f64())
+        .fetch_one(&self.connection)
+        .await
+        {
+            Ok(status) => Ok(status.parse().unwrap_or(JobStatus::Failed)),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the jobs left running, e.g. by a crashed instance, back to the queue.
+    ///
+    /// Only the jobs that were taken long enough ago are returned, so the jobs still being run
+    /// by the other instances are left alone.
+    ///
+    /// # Arguments
+    /// - `older_than`: The time after which a running job is considered abandoned.
+    ///
+    /// # Returns
+    /// - The number of jobs returned to the queue.
+    /// - An error if the jobs could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn requeue_stale_jobs(&self, older_than: std::time::Duration) -> Result<u64, ServiceError> {
+        match sqlx::query(
+            "UPDATE jobs SET status = 'pending', updated_on = NOW() \
+            WHERE status = 'running' AND updated_on < NOW() - make_interval(secs => $1)",
+        )
+        .bind(older_than.as_secs_f64())
+        .execute(&self.connection)
+        .await
+        {
+            Ok(result) => Ok(result.rows_affected()),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the jobs from the table `jobs`, the most recent ones first.
+    ///
+    /// # Arguments
+    /// - `status`: The status of the returned jobs, or `None` for all jobs.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of jobs.
+    /// - An error if the jobs could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_jobs(&self, status: Option<JobStatus>, pag: Pagination) -> Result<Vec<Job>, ServiceError> {
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        match sqlx::query(
+            "SELECT * FROM jobs WHERE ($1::TEXT IS NULL OR status = $1) \
+            ORDER BY id DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(status.map(|status| status.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .map(Job::try_from)
+        .fetch_all(&self.connection)
+        .await?
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(jobs) => Ok(jobs),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function notifies the owner and the watchers of the question about a new answer.
+    ///
+    /// The watchers are the accounts that answered the question before. A notification is added
+    /// to the table `notifications` for each of them, except for the author of the answer and the
+    /// deleted accounts, and the accounts already notified about the answer are skipped, so the
+    /// function can be retried.
+    ///
+    /// # Arguments
+    /// - `answer_id`: The ID of the new answer.
+    ///
+    /// # Returns
+    /// - The number of notifications added, 0 if the answer was deleted in the meantime.
+    /// - An error if the notifications could not be added.
+    #[instrument(target = "store", skip(self))]
+    pub async fn add_answer_notifications(&self, answer_id: AnswerId) -> Result<u64, ServiceError> {
+        let AnswerId(answer_id) = answer_id;
+        trace!("notifying about the answer with id={answer_id}");
+        match sqlx::query(
+            "INSERT INTO notifications (account_id, question_id, answer_id) \
+            SELECT watchers.account_id, answers.question_id, answers.id FROM answers \
+            JOIN (\
+                SELECT id AS question_id, account_id FROM questions \
+                UNION SELECT question_id, account_id FROM answers WHERE id < $1\
+            ) AS watchers ON watchers.question_id = answers.question_id \
+            JOIN accounts ON accounts.id = watchers.account_id \
+            WHERE answers.id = $1 AND watchers.account_id IS DISTINCT FROM answers.account_id \
+            ON CONFLICT (account_id, answer_id) DO NOTHING",
+        )
+        .bind(answer_id)
+        .execute(&self.connection)
+        .await
+        {
+            Ok(result) => {
+                trace!("added {} notifications", result.rows_affected());
+                Ok(result.rows_affected())
+            }
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function returns the notifications of the account from the table `notifications`,
+    /// the most recent ones first.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account the notifications are for.
+    /// - `pag`: A `Pagination` struct that contains the offset and limit for the query.
+    ///
+    /// # Returns
+    /// - A vector of notifications.
+    /// - An error if the notifications could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_notifications(
+        &self,
+        account_id: AccountId,
+        pag: Pagination,
+    ) -> Result<Vec<Notification>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        let Pagination { offset, limit } = self.paginate(pag)?;
+        match sqlx::query("SELECT * FROM notifications WHERE account_id = $1 ORDER BY id DESC LIMIT $2 OFFSET $3")
+            .bind(account_id)
+            .bind(limit)
+            .bind(offset)
+            .map(Notification::try_from)
+            .fetch_all(&self.connection)
+            .await?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(notifications) => Ok(notifications),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function marks the notification of the account as read.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account the notification is for.
+    /// - `notification_id`: The ID of the notification.
+    ///
+    /// # Returns
+    /// - `true` if the notification was marked as read, `false` if the account has no such notification.
+    /// - An error if the notification could not be updated.
+    #[instrument(target = "store", skip(self))]
+    pub async fn mark_notification_read(
+        &self,
+        account_id: AccountId,
+        notification_id: NotificationId,
+    ) -> Result<bool, ServiceError> {
+        let (AccountId(account_id), NotificationId(notification_id)) = (account_id, notification_id);
+        match sqlx::query("UPDATE notifications SET read = TRUE WHERE id = $1 AND account_id = $2")
+            .bind(notification_id)
+            .bind(account_id)
+            .execute(&self.connection)
+            .await
+        {
+            Ok(result) => Ok(result.rows_affected() > 0),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+
+    /// This function awards the badges earned by the accounts since the last evaluation.
+    ///
+    /// The badges are added to the table `badges`, the badges the accounts already have are skipped,
+    /// and the content kept from the deleted accounts earns no badge.
+    ///
+    /// # Returns
+    /// - The number of badges awarded.
+    /// - An error if the badges could not be awarded.
+    #[instrument(target = "store", skip(self))]
+    pub async fn award_badges(&self) -> Result<u64, ServiceError> {
+        let mut awarded = 0;
+        for badge in Badge::ALL {
+            // The accounts that earned the badge, whose count of contributions reaches the threshold
+            let (earned, threshold) = match badge {
+                Badge::FirstQuestion => (
+                    "SELECT account_id FROM questions GROUP BY account_id HAVING COUNT(*) >= $2",
+                    1,
+                ),
+                Badge::TenAnswers => (
+                    "SELECT account_id FROM answers GROUP BY account_id HAVING COUNT(*) >= $2",
+                    Badge::TEN_ANSWERS,
+                ),
+                Badge::PopularQuestion => (
+                    "SELECT DISTINCT questions.account_id FROM questions \
+                    JOIN answers ON answers.question_id = questions.id \
+                    AND answers.account_id IS DISTINCT FROM questions.account_id \
+                    GROUP BY questions.id HAVING COUNT(*) >= $2",
+                    Badge::POPULAR_QUESTION_ANSWERS,
+                ),
+            };
+            let query = format!(
+                "INSERT INTO badges (account_id, badge) SELECT earned.account_id, $1 FROM ({earned}) AS earned \
+                JOIN accounts ON accounts.id = earned.account_id \
+                ON CONFLICT (account_id, badge) DO NOTHING"
+            );
+            match sqlx::query(&query)
+                .bind(badge.as_str())
+                .bind(threshold)
+                .execute(&self.connection)
+                .await
+            {
+                Ok(result) => {
+                    trace!("awarded {} {badge} badges", result.rows_affected());
+                    awarded += result.rows_affected();
+                }
+                Err(error) => {
+                    error!("{error}");
+                    return Err(ServiceError::DatabaseQueryError(error));
+                }
+            }
+        }
+        Ok(awarded)
+    }
+
+    /// This function returns the badges awarded to the account, in the order they were awarded.
+    ///
+    /// # Arguments
+    /// - `account_id`: The ID of the account.
+    ///
+    /// # Returns
+    /// - A vector of the awarded badges, empty if the account does not exist.
+    /// - An error if the badges could not be read.
+    #[instrument(target = "store", level = "debug", skip(self))]
+    pub async fn get_badges(&self, account_id: AccountId) -> Result<Vec<AwardedBadge>, ServiceError> {
+        let AccountId(account_id) = account_id;
+        match sqlx::query("SELECT * FROM badges WHERE account_id = $1 ORDER BY awarded_on, badge")
+            .bind(account_id)
+            .map(AwardedBadge::try_from)
+            .fetch_all(&self.connection)
+            .await?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(badges) => Ok(badges),
+            Err(error) => {
+                error!("{error}");
+                Err(ServiceError::DatabaseQueryError(error))
+            }
+        }
+    }
+}
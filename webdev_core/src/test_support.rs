@@ -0,0 +1,249 @@
+//! Factories inserting the resources through the [Store], for the tests.
+//!
+//! Available with the `test-util` feature. Every factory fills in the values a test does not care
+//! about, and inserts the resource with [insert](QuestionFactory::insert):
+//!
+//! ```ignore
+//! let alice = an_account().insert(&store).await;
+//! let question = a_question()
+//!     .with_tags(["rust", "warp"])
+//!     .owned_by(alice.id.unwrap())
+//!     .insert(&store)
+//!     .await;
+//! let answer = an_answer().to(question.id.unwrap()).insert(&store).await;
+//! ```
+//!
+//! The factories panic when the resource cannot be inserted, as the test cannot go on without it.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::store::Store;
+use crate::types::answer::Answer;
+use crate::types::authentication::{Account, AccountId, ProfileUpdate};
+use crate::types::question::{Question, QuestionId};
+
+/// Password of the accounts created by [an_account], unless set with [AccountFactory::with_password].
+pub const DEFAULT_PASSWORD: &str = "correct horse battery staple";
+
+/// Returns a number not returned before by this process, used to make the default values unique.
+fn next_sequence() -> usize {
+    static SEQUENCE: AtomicUsize = AtomicUsize::new(1);
+    SEQUENCE.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Returns a factory for an account.
+pub fn an_account() -> AccountFactory {
+    AccountFactory {
+        email: format!("user{}@example.com", next_sequence()),
+        password: DEFAULT_PASSWORD.to_string(),
+        display_name: None,
+    }
+}
+
+/// Returns a factory for a question.
+pub fn a_question() -> QuestionFactory {
+    let sequence = next_sequence();
+    QuestionFactory {
+        title: format!("Question {sequence}"),
+        content: format!("Content of the question {sequence}"),
+        tags: None,
+        owner: None,
+    }
+}
+
+/// Returns a factory for an answer.
+pub fn an_answer() -> AnswerFactory {
+    AnswerFactory {
+        content: format!("Answer {}", next_sequence()),
+        question: None,
+        owner: None,
+    }
+}
+
+/// Factory for an [Account], created by [an_account].
+#[derive(Debug, Clone)]
+pub struct AccountFactory {
+    email: String,
+    password: String,
+    display_name: Option<String>,
+}
+
+impl AccountFactory {
+    /// Sets the email of the account, a unique `user<n>@example.com` by default.
+    pub fn with_email(self, email: impl Into<String>) -> Self {
+        Self {
+            email: email.into(),
+            ..self
+        }
+    }
+
+    /// Sets the password of the account, [DEFAULT_PASSWORD] by default.
+    pub fn with_password(self, password: impl Into<String>) -> Self {
+        Self {
+            password: password.into(),
+            ..self
+        }
+    }
+
+    /// Sets the name the account is shown with, none by default.
+    pub fn with_display_name(self, display_name: impl Into<String>) -> Self {
+        Self {
+            display_name: Some(display_name.into()),
+            ..self
+        }
+    }
+
+    /// Inserts the account, with the password hashed as on registration.
+    ///
+    /// The returned account holds the plain password, so the test can log in with it.
+    pub async fn insert(self, store: &Store) -> Account {
+        let salt = format!("test_support_salt_{}", next_sequence());
+        let hashed = argon2::hash_encoded(self.password.as_bytes(), salt.as_bytes(), &argon2::Config::default())
+            .expect("cannot hash the password");
+        let account = Account::builder()
+            .email(self.email.clone())
+            .password(hashed)
+            .build()
+            .expect("all required fields are set");
+        let account = store.add_account(account).await.expect("cannot insert the account");
+        if let Some(display_name) = self.display_name {
+            let profile = ProfileUpdate {
+                display_name: Some(display_name),
+                bio: None,
+                website: None,
+            };
+            store
+                .update_profile(account.id, &profile)
+                .await
+                .expect("cannot set the profile of the account");
+        }
+        Account {
+            id: Some(account.id),
+            email: account.email,
+            password: self.password,
+        }
+    }
+}
+
+/// Factory for a [Question], created by [a_question].
+#[derive(Debug, Clone)]
+pub struct QuestionFactory {
+    title: String,
+    content: String,
+    tags: Option<Vec<String>>,
+    owner: Option<AccountId>,
+}
+
+impl QuestionFactory {
+    /// Sets the title of the question.
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..self
+        }
+    }
+
+    /// Sets the content of the question.
+    pub fn with_content(self, content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..self
+        }
+    }
+
+    /// Sets the tags of the question, which has none by default.
+    pub fn with_tags<T: Into<String>>(self, tags: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            tags: Some(tags.into_iter().map(Into::into).collect()),
+            ..self
+        }
+    }
+
+    /// Sets the author of the question, a new account by default.
+    pub fn owned_by(self, account_id: AccountId) -> Self {
+        Self {
+            owner: Some(account_id),
+            ..self
+        }
+    }
+
+    /// Inserts the question, and the account of its author if it was not set.
+    pub async fn insert(self, store: &Store) -> Question {
+        let owner = match self.owner {
+            Some(owner) => owner,
+            None => an_account()
+                .insert(store)
+                .await
+                .id
+                .expect("inserted accounts have an id"),
+        };
+        let question = Question::builder()
+            .title(self.title)
+            .content(self.content)
+            .tags(self.tags)
+            .build()
+            .expect("all required fields are set");
+        store
+            .add_question(owner, question, true)
+            .await
+            .expect("cannot insert the question")
+    }
+}
+
+/// Factory for an [Answer], created by [an_answer].
+#[derive(Debug, Clone)]
+pub struct AnswerFactory {
+    content: String,
+    question: Option<QuestionId>,
+    owner: Option<AccountId>,
+}
+
+impl AnswerFactory {
+    /// Sets the content of the answer.
+    pub fn with_content(self, content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            ..self
+        }
+    }
+
+    /// Sets the question the answer is associated with, a new question by default.
+    pub fn to(self, question_id: QuestionId) -> Self {
+        Self {
+            question: Some(question_id),
+            ..self
+        }
+    }
+
+    /// Sets the author of the answer, a new account by default.
+    pub fn owned_by(self, account_id: AccountId) -> Self {
+        Self {
+            owner: Some(account_id),
+            ..self
+        }
+    }
+
+    /// Inserts the answer, and the question and the account of its author if they were not set.
+    pub async fn insert(self, store: &Store) -> Answer {
+        let question = match self.question {
+            Some(question) => question,
+            None => a_question()
+                .insert(store)
+                .await
+                .id
+                .expect("inserted questions have an id"),
+        };
+        let owner = match self.owner {
+            Some(owner) => owner,
+            None => an_account()
+                .insert(store)
+                .await
+                .id
+                .expect("inserted accounts have an id"),
+        };
+        store
+            .add_answer(owner, question, self.content, true)
+            .await
+            .expect("cannot insert the answer")
+    }
+}
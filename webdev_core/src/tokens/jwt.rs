@@ -0,0 +1,121 @@
+//! Signing of the tokens, as JSON Web Tokens.
+//!
+//! The tokens are signed with [jsonwebtoken], with HS256 or RS256, and their `exp` and `nbf`
+//! claims are written as the number of seconds since the Unix epoch, as the gateways expect. Only
+//! the algorithm of the signer is accepted, so a token cannot choose how it is verified.
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+
+use crate::error::ServiceError;
+use crate::tokens::TokenSigner;
+use crate::types::authentication::Session;
+
+/// Signer issuing the tokens as JSON Web Tokens, see the [module](self) documentation.
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use webdev_core::tokens::{JwtSigner, TokenSigner};
+/// use webdev_core::types::authentication::{AccountId, Session};
+/// use webdev_core::types::moderation::AccountRole;
+///
+/// let signer = JwtSigner::hs256(b"A SECRET OF AT LEAST THIRTY-TWO BYTES").unwrap();
+/// let now = Utc::now();
+/// let session = Session {
+///     ver: Session::CLAIMS_VERSION,
+///     exp: now + Duration::try_hours(1).unwrap(),
+///     nbf: now,
+///     account_id: AccountId(1),
+///     email: "alice@example.com".to_string(),
+///     role: AccountRole::User,
+///     jti: "jti".to_string(),
+///     sid: None,
+/// };
+///
+/// let token = signer.sign(&session);
+/// let claims = signer.verify(&token, now).unwrap();
+/// assert_eq!(claims["account_id"], 1);
+/// assert_eq!(claims["exp"], session.exp.timestamp());
+///
+/// let other = JwtSigner::hs256(b"ANOTHER SECRET, THIRTY-TWO BYTES LONG").unwrap();
+/// assert!(other.verify(&token, now).is_err());
+/// assert!(JwtSigner::hs256(b"too short").is_err());
+/// ```
+#[derive(Clone)]
+pub struct JwtSigner {
+    /// The algorithm the tokens are signed and verified with.
+    algorithm: Algorithm,
+    /// The key signing the tokens.
+    encoding_key: EncodingKey,
+    /// The key verifying the tokens.
+    decoding_key: DecodingKey,
+}
+
+impl JwtSigner {
+    /// The minimum length of the HS256 secrets, in bytes.
+    pub const MIN_SECRET_LENGTH: usize = 32;
+
+    /// Returns the signer signing the tokens with HS256 and the shared secret.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidAuthKey] if the secret is shorter than [JwtSigner::MIN_SECRET_LENGTH].
+    pub fn hs256(secret: &[u8]) -> Result<Self, ServiceError> {
+        if secret.len() < Self::MIN_SECRET_LENGTH {
+            return Err(ServiceError::InvalidAuthKey(format!(
+                "the JWT secret is {} bytes long, it has to be at least {} bytes long",
+                secret.len(),
+                Self::MIN_SECRET_LENGTH
+            )));
+        }
+        Ok(Self {
+            algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+        })
+    }
+
+    /// Returns the signer signing the tokens with RS256, with the RSA private key, and verifying
+    /// them with the public key, both PEM encoded.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidAuthKey] if either of the keys is not a PEM encoded RSA key.
+    pub fn rs256(private_key_pem: &[u8], public_key_pem: &[u8]) -> Result<Self, ServiceError> {
+        let invalid = |key: &str, error: jsonwebtoken::errors::Error| {
+            ServiceError::InvalidAuthKey(format!("the JWT {key} key is not a PEM encoded RSA key: {error}"))
+        };
+        Ok(Self {
+            algorithm: Algorithm::RS256,
+            encoding_key: EncodingKey::from_rsa_pem(private_key_pem).map_err(|error| invalid("private", error))?,
+            decoding_key: DecodingKey::from_rsa_pem(public_key_pem).map_err(|error| invalid("public", error))?,
+        })
+    }
+}
+
+impl TokenSigner for JwtSigner {
+    fn sign(&self, session: &Session) -> String {
+        let mut claims = serde_json::json!(session);
+        claims["exp"] = serde_json::json!(session.exp.timestamp());
+        claims["nbf"] = serde_json::json!(session.nbf.timestamp());
+        jsonwebtoken::encode(&Header::new(self.algorithm), &claims, &self.encoding_key)
+            .expect("Failed to construct the JWT")
+    }
+
+    fn verify(&self, token: &str, _now: DateTime<Utc>) -> Result<serde_json::Value, ServiceError> {
+        let mut validation = Validation::new(self.algorithm);
+        // The times are checked by the services, against their clock
+        validation.validate_exp = false;
+        validation.validate_nbf = false;
+        validation.required_spec_claims.clear();
+        jsonwebtoken::decode::<serde_json::Value>(token, &self.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|_| ServiceError::CannotDecryptToken)
+    }
+}
+
+impl std::fmt::Debug for JwtSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The keys are never logged
+        f.debug_struct("JwtSigner")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,46 @@
+//! Module for the formats the tokens of the sessions are issued in.
+//!
+//! The tokens are issued and verified by a [TokenSigner], kept in the [Store](crate::store::Store):
+//! - [PasetoSigner], the PASETO v4 local tokens, encrypted with the [AuthKeys](crate::types::authentication::AuthKeys),
+//!   used by default
+//! - [JwtSigner], the JSON Web Tokens signed with HS256 or RS256, for the deployments behind
+//!   gateways that only read JWTs
+//!
+//! The signers only check the encryption or the signature of the tokens. Their claims, e.g. their
+//! expiration, are checked by the services, against the time of their [Clock](crate::clock::Clock).
+
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::ServiceError;
+use crate::types::authentication::Session;
+
+/// The JSON Web Tokens.
+pub mod jwt;
+/// The PASETO v4 local tokens.
+pub mod paseto;
+
+pub use self::jwt::JwtSigner;
+pub use self::paseto::PasetoSigner;
+
+/// Format the tokens of the sessions are issued and verified in.
+///
+/// The [Store](crate::store::Store) holds the signer used by the services, which is a
+/// [PasetoSigner] unless replaced with [Store::with_token_signer](crate::store::Store::with_token_signer).
+pub trait TokenSigner: Debug + Send + Sync {
+    /// Returns the token holding the claims of the session.
+    ///
+    /// # Panics
+    /// - If the token cannot be constructed.
+    fn sign(&self, session: &Session) -> String;
+
+    /// Returns the claims of the token, if it was issued by this signer.
+    ///
+    /// The claims are not checked, only the encryption or the signature of the token. The current
+    /// time decides whether the tokens of the previous formats are still accepted.
+    ///
+    /// # Errors
+    /// - [ServiceError::CannotDecryptToken] if the token was not issued by this signer.
+    fn verify(&self, token: &str, now: DateTime<Utc>) -> Result<serde_json::Value, ServiceError>;
+}
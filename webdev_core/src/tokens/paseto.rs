@@ -0,0 +1,123 @@
+//! Encryption of the tokens, as PASETO v4 local tokens.
+//!
+//! The tokens are encrypted and decrypted with [rusty_paseto], while their claims are written and
+//! checked by the services.
+//!
+//! The tokens issued before, which are PASETO v2 local tokens, are decrypted with the `paseto`
+//! crate during the transition, see [AuthKeys::accepts_v2_tokens].
+use chrono::{DateTime, Utc};
+use rusty_paseto::core::{Footer, Key, Local, Paseto, PasetoNonce, PasetoSymmetricKey, Payload, V4};
+
+use crate::error::ServiceError;
+use crate::tokens::TokenSigner;
+use crate::types::authentication::{AuthKeys, Session};
+
+/// The prefix of the PASETO v4 local tokens.
+const V4_LOCAL: &str = "v4.local.";
+/// The prefix of the PASETO v2 local tokens, issued before the v4 ones.
+pub const V2_LOCAL: &str = "v2.local.";
+
+/// Signer issuing the tokens as PASETO v4 local tokens, encrypted with the current key of the
+/// [AuthKeys], and accepting the tokens of any of the keys.
+///
+/// ```
+/// use chrono::{Duration, Utc};
+/// use webdev_core::tokens::{PasetoSigner, TokenSigner};
+/// use webdev_core::types::authentication::{AccountId, AuthKeys, Session};
+/// use webdev_core::types::moderation::AccountRole;
+///
+/// let keys = AuthKeys::new("2024-06", b"32 BYTES LONG KEY FOR THE TOKENS").unwrap();
+/// let signer = PasetoSigner::new(keys);
+/// let now = Utc::now();
+/// let session = Session {
+///     ver: Session::CLAIMS_VERSION,
+///     exp: now + Duration::try_hours(1).unwrap(),
+///     nbf: now,
+///     account_id: AccountId(1),
+///     email: "alice@example.com".to_string(),
+///     role: AccountRole::User,
+///     jti: "jti".to_string(),
+///     sid: None,
+/// };
+///
+/// let token = signer.sign(&session);
+/// assert!(token.starts_with("v4.local."));
+/// assert_eq!(signer.verify(&token, now).unwrap()["account_id"], 1);
+/// assert!(signer.verify("v4.local.forged", now).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PasetoSigner {
+    keys: AuthKeys,
+}
+
+impl PasetoSigner {
+    /// Returns the signer encrypting the tokens with the keys.
+    pub fn new(keys: AuthKeys) -> Self {
+        Self { keys }
+    }
+}
+
+impl TokenSigner for PasetoSigner {
+    fn sign(&self, session: &Session) -> String {
+        let (key_id, key) = self.keys.current_key();
+        encrypt(key, &key_footer(key_id), &serde_json::json!(session))
+    }
+
+    fn verify(&self, token: &str, now: DateTime<Utc>) -> Result<serde_json::Value, ServiceError> {
+        // The footer names the key the token was encrypted with. The tokens without the footer were
+        // encrypted with the current key, before the keys had ids.
+        let accept_v2 = self.keys.accepts_v2_tokens(now);
+        let claims = self
+            .keys
+            .keys()
+            .find_map(|(id, key)| decrypt(token, key, Some(&key_footer(id)), accept_v2))
+            .or_else(|| decrypt(token, self.keys.current_key().1, None, accept_v2))
+            .ok_or(ServiceError::CannotDecryptToken)?;
+        serde_json::from_str(&claims).map_err(|_| ServiceError::CannotDecryptToken)
+    }
+}
+
+/// Returns the footer of the tokens encrypted with the key with the given id, e.g. `{"kid":"2024-06"}`.
+pub fn key_footer(id: &str) -> String {
+    serde_json::json!({ "kid": id }).to_string()
+}
+
+/// Returns the symmetric v4 key from one of the [AuthKeys].
+fn v4_key(key: &[u8]) -> PasetoSymmetricKey<V4, Local> {
+    let key = <&[u8; AuthKeys::PASETO_KEY_LENGTH]>::try_from(key).expect("the keys are validated by AuthKeys");
+    PasetoSymmetricKey::from(Key::from(key))
+}
+
+/// Encrypts the claims as a PASETO v4 local token, with the key and the footer.
+///
+/// # Panics
+/// - If the nonce cannot be generated, or the token cannot be constructed.
+pub fn encrypt(key: &[u8], footer: &str, claims: &serde_json::Value) -> String {
+    let nonce = Key::<32>::try_new_random().expect("Failed to generate the nonce of the token");
+    let payload = claims.to_string();
+    Paseto::<V4, Local>::builder()
+        .set_payload(Payload::from(payload.as_str()))
+        .set_footer(Footer::from(footer))
+        .try_encrypt(&v4_key(key), &PasetoNonce::<V4, Local>::from(&nonce))
+        .expect("Failed to construct paseto token")
+}
+
+/// Decrypts a token with the key, and returns its claims, as JSON.
+///
+/// The v4 tokens are checked to have the footer, if any. The v2 tokens are only decrypted if
+/// `accept_v2` is set, see [AuthKeys::accepts_v2_tokens], and `None` is returned for the tokens
+/// that cannot be decrypted.
+pub fn decrypt(token: &str, key: &[u8], footer: Option<&str>, accept_v2: bool) -> Option<String> {
+    if token.starts_with(V4_LOCAL) {
+        let key = v4_key(key);
+        match footer {
+            Some(footer) => Paseto::<V4, Local>::try_decrypt(token, &key, Footer::from(footer), None),
+            None => Paseto::<V4, Local>::try_decrypt(token, &key, None, None),
+        }
+        .ok()
+    } else if token.starts_with(V2_LOCAL) && accept_v2 {
+        ::paseto::v2::local::decrypt_paseto(token, footer, key).ok()
+    } else {
+        None
+    }
+}
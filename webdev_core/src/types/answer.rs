@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use macros::{DbObjectId, QueryParams};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::types::authentication::Author;
+use crate::types::markdown::ContentFormat;
+use crate::types::question::QuestionId;
+
+/// Represents an answer id.
+///
+/// `AnswerId` is a wrapper around an i32. It represents the id of an answer.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct AnswerId(pub i32);
+
+/// Represents an answer.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Answer {
+    /// The id of the answer.
+    pub id: Option<AnswerId>,
+    /// The content of the answer, in markdown.
+    pub content: String,
+    /// The content of the answer, rendered to sanitized HTML.
+    ///
+    /// It is loaded by the [Store](crate::store::Store) only when requested with [ContentFormat::Html].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(skip)]
+    #[schema(read_only)]
+    pub content_html: Option<String>,
+    /// The id of the question this answer is associated with.
+    pub question_id: Option<QuestionId>,
+    /// Whether the owner of the question accepted this answer.
+    #[serde(default)]
+    #[schema(read_only)]
+    pub accepted: bool,
+    /// The score of the answer, from the votes of the accounts, see [Score](crate::types::vote::Score).
+    ///
+    /// It is kept with the answer by the [Store](crate::store::Store), and changed whenever the answer is voted on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(skip)]
+    #[schema(read_only)]
+    pub score: Option<i64>,
+    /// The author of the answer.
+    ///
+    /// It is loaded by the [Store](crate::store::Store) only when the answer is read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(skip)]
+    #[schema(read_only)]
+    pub author: Option<Author>,
+}
+
+impl TryFrom<PgRow> for Answer {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(AnswerId(row.try_get("id")?)),
+            content: row.try_get("content")?,
+            content_html: None,
+            question_id: Some(QuestionId(row.try_get("question_id")?)),
+            accepted: row.try_get("accepted")?,
+            score: match row.try_get("score") {
+                Ok(score) => Some(score),
+                Err(sqlx::Error::ColumnNotFound(_)) => None,
+                Err(error) => return Err(error),
+            },
+            author: None,
+        })
+    }
+}
+
+/// Query parameters of the requests reading an answer.
+///
+/// The parameters are extracted with [AnswerParams::extract], generated by the [QueryParams] derive.
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy, Default)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct AnswerParams {
+    /// The format of the content of the answer
+    pub format: Option<ContentFormat>,
+}
+
+/// Represents an answer in the listing of the answers of an account.
+///
+/// It is listed with the title of the question it answers, so the account can tell its answers
+/// apart without fetching the questions.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountAnswer {
+    /// The id of the answer.
+    pub id: AnswerId,
+    /// The content of the answer.
+    pub content: String,
+    /// The id of the question this answer is associated with.
+    pub question_id: QuestionId,
+    /// The title of the question this answer is associated with.
+    pub question_title: String,
+    /// Whether the owner of the question accepted this answer.
+    pub accepted: bool,
+}
+
+impl TryFrom<PgRow> for AccountAnswer {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: AnswerId(row.try_get("id")?),
+            content: row.try_get("content")?,
+            question_id: QuestionId(row.try_get("question_id")?),
+            question_title: row.try_get("question_title")?,
+            accepted: row.try_get("accepted")?,
+        })
+    }
+}
+
+/// Order of the answers in the listings, chosen with the `sort` query parameter.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnswerOrder {
+    /// The most recent answers first.
+    Newest,
+    /// The oldest answers first.
+    Oldest,
+    /// The best answers first, and the oldest ones among equally good answers.
+    ///
+    /// The accepted answer comes first, and the other answers follow by their score.
+    #[default]
+    Score,
+}
+
+impl FromStr for AnswerOrder {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "newest" => Ok(Self::Newest),
+            "oldest" => Ok(Self::Oldest),
+            "score" => Ok(Self::Score),
+            _ => Err(format!(
+                "unknown answer order \"{value}\", expected one of \"newest\", \"oldest\" or \"score\""
+            )),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use macros::DbObjectId;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+use crate::types::question::QuestionId;
+
+/// Represents an attachment id.
+///
+/// `AttachmentId` is a wrapper around an i32. It represents the id of a file attached to a question.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct AttachmentId(pub i32);
+
+/// Represents a file attached to a question.
+///
+/// Only the description of the file is stored in the database, the content is stored on disk
+/// under the `storage_key` of the attachment.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Attachment {
+    /// The id of the attachment.
+    pub id: AttachmentId,
+    /// The id of the question the file is attached to.
+    pub question_id: QuestionId,
+    /// The name of the file, as given by the client.
+    pub file_name: String,
+    /// The media type of the file, as given by the client.
+    pub content_type: String,
+    /// The size of the file in bytes.
+    pub size: i64,
+    /// The name of the file on disk.
+    ///
+    /// It is never sent to the client.
+    #[serde(skip)]
+    pub storage_key: String,
+}
+
+impl TryFrom<PgRow> for Attachment {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: AttachmentId(row.try_get("id")?),
+            question_id: QuestionId(row.try_get("question_id")?),
+            file_name: row.try_get("file_name")?,
+            content_type: row.try_get("content_type")?,
+            size: row.try_get("size")?,
+            storage_key: row.try_get("storage_key")?,
+        })
+    }
+}
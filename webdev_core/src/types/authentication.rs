@@ -0,0 +1,805 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use macros::{Builder, DbObjectId, QueryParams};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::error::ServiceError;
+use crate::types::moderation::AccountRole;
+
+/// Represents an account id.
+///
+/// `AccountId` is a wrapper around a i32. It represents the id of an account.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct AccountId(pub i32);
+
+/// Represents an account.
+///
+/// `Account` is a struct that represents an account. It contains the id, email, and password of the account.
+/// Accounts can be constructed with [Account::builder], generated by the [Builder] derive.
+#[derive(Builder, Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Account {
+    /// The id of the account.
+    ///
+    /// Is an `Option` because the id is not known when creating a new account.
+    pub id: Option<AccountId>,
+    /// The email of the account.
+    pub email: String,
+    /// The password of the account.
+    ///
+    /// Password can be plain text or hashed,
+    /// depending if the account is being created or retrieved from the database.
+    pub password: String,
+}
+
+impl TryFrom<PgRow> for Account {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(AccountId(row.try_get("id")?)),
+            email: row.try_get("email")?,
+            password: row.try_get("password")?,
+        })
+    }
+}
+
+/// Returns the email in the form the accounts are stored and looked up with: trimmed, and in
+/// lowercase.
+///
+/// The emails are unique regardless of their case, as the column is `citext`, so the same address
+/// typed in another case is rejected on registration, and logs in to the same account.
+///
+/// ```
+/// use webdev_core::types::authentication::normalize_email;
+///
+/// assert_eq!(normalize_email(" Alice@Example.COM "), "alice@example.com");
+/// ```
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Represents the profile of an account, returned to the account itself.
+///
+/// It is the [Account] without its password, so the hash of the password is never sent, and with
+/// the fields of the profile set by the account, see [ProfileUpdate].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AccountProfile {
+    /// The id of the account.
+    pub id: AccountId,
+    /// The email of the account.
+    pub email: String,
+    /// The time the account was registered at.
+    #[serde(with = "crate::types::timestamp")]
+    pub created_at: DateTime<Utc>,
+    /// The name the account is shown with, if set.
+    pub display_name: Option<String>,
+    /// A few words about the account, if set.
+    pub bio: Option<String>,
+    /// The website of the account, if set.
+    pub website: Option<String>,
+}
+
+impl TryFrom<PgRow> for AccountProfile {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: AccountId(row.try_get("id")?),
+            email: row.try_get("email")?,
+            created_at: row.try_get("created_at")?,
+            display_name: row.try_get("display_name")?,
+            bio: row.try_get("bio")?,
+            website: row.try_get("website")?,
+        })
+    }
+}
+
+/// Request body for updating the profile of an account.
+///
+/// It replaces all the fields of the profile, so a missing or empty field is cleared. The display
+/// name and the bio are normalized like the titles and the content, see
+/// [sanitize](crate::types::sanitize), and the website has to be an `http` or `https` URL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileUpdate {
+    /// The name the account is shown with.
+    #[serde(default)]
+    #[schema(max_length = 64)]
+    pub display_name: Option<String>,
+    /// A few words about the account, in plain text.
+    #[serde(default)]
+    #[schema(max_length = 1024)]
+    pub bio: Option<String>,
+    /// The website of the account.
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+impl ProfileUpdate {
+    /// The maximum length of the display name, in bytes, as it is stored as `VARCHAR(64)`.
+    pub const DISPLAY_NAME_BYTES: usize = 64;
+    /// The maximum length of the bio, in bytes.
+    pub const BIO_BYTES: usize = 1024;
+    /// The maximum length of the website, in bytes.
+    pub const WEBSITE_BYTES: usize = 255;
+}
+
+/// Represents the public profile of an account, shown to everyone.
+///
+/// Unlike the [AccountProfile], it does not include the email of the account, but includes the
+/// number of the questions and the answers the account posted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PublicProfile {
+    /// The id of the account.
+    pub id: AccountId,
+    /// The name the account is shown with, if set.
+    pub display_name: Option<String>,
+    /// A few words about the account, if set.
+    pub bio: Option<String>,
+    /// The website of the account, if set.
+    pub website: Option<String>,
+    /// The time the account was registered at.
+    #[serde(with = "crate::types::timestamp")]
+    pub created_at: DateTime<Utc>,
+    /// The number of the questions asked by the account.
+    pub question_count: i64,
+    /// The number of the answers posted by the account.
+    pub answer_count: i64,
+}
+
+impl TryFrom<PgRow> for PublicProfile {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: AccountId(row.try_get("id")?),
+            display_name: row.try_get("display_name")?,
+            bio: row.try_get("bio")?,
+            website: row.try_get("website")?,
+            created_at: row.try_get("created_at")?,
+            question_count: row.try_get("question_count")?,
+            answer_count: row.try_get("answer_count")?,
+        })
+    }
+}
+
+/// What happens to the questions and the answers of an account when it is deleted.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountContent {
+    /// The questions and the answers are kept, without their author.
+    #[default]
+    Anonymize,
+    /// The questions, with all their answers, and the answers are deleted.
+    Delete,
+}
+
+impl FromStr for AccountContent {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "anonymize" => Ok(Self::Anonymize),
+            "delete" => Ok(Self::Delete),
+            _ => Err(format!(
+                "unknown account content \"{value}\", expected \"anonymize\" or \"delete\""
+            )),
+        }
+    }
+}
+
+/// Query parameters of the deletion of an account.
+///
+/// The parameters are extracted with [DeleteAccountParams::extract], generated by the [QueryParams] derive.
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy, Default)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct DeleteAccountParams {
+    /// What happens to the questions and the answers of the account, `anonymize` by default
+    #[query(default = AccountContent::Anonymize)]
+    pub content: AccountContent,
+}
+
+/// Query parameters of the logins.
+///
+/// The parameters are extracted with [LoginParams::extract], generated by the [QueryParams] derive.
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy, Default)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct LoginParams {
+    /// Whether the session is remembered, with a refresh token renewing its token, see [SessionLifetimes]
+    #[query(default = false)]
+    pub remember_me: bool,
+}
+
+/// Represents the author of a question or an answer.
+///
+/// `Author` is the public part of an [Account], included in the responses next to the content.
+/// Like the [PublicProfile], it does not include the email of the account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Author {
+    /// The id of the account.
+    pub id: AccountId,
+    /// The name the account is shown with, if set.
+    pub display_name: Option<String>,
+}
+
+impl TryFrom<PgRow> for Author {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: AccountId(row.try_get("id")?),
+            display_name: row.try_get("display_name")?,
+        })
+    }
+}
+
+/// Represents a session.
+///
+/// `Session` is a struct that represents a session, read from the claims of its token.
+/// It contains the expiration date, not before date, and the account id, email and role of the session.
+/// The `jti` identifies the token the session was read from, so it can be revoked before it expires.
+///
+/// The role is the one the account had when the token was issued, so the roles are checked without
+/// reading the account. The sessions of an account are signed out when its role changes, see
+/// [Store::delete_sessions](crate::store::Store::delete_sessions).
+///
+/// The claims are versioned, see [Session::CLAIMS_VERSION], so the tokens issued with other claims
+/// are rejected as outdated, instead of failing to be read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// The version of the claims of the token.
+    pub ver: u32,
+    /// The expiration date of the session.
+    #[serde(with = "crate::types::timestamp")]
+    pub exp: DateTime<Utc>,
+    /// The not before date of the session.
+    #[serde(with = "crate::types::timestamp")]
+    pub nbf: DateTime<Utc>,
+    /// The account id associated with the session.
+    pub account_id: AccountId,
+    /// The email of the account, when the token was issued.
+    pub email: String,
+    /// The role of the account, when the token was issued.
+    pub role: AccountRole,
+    /// The unique id of the token of the session.
+    pub jti: String,
+    /// The id of the session in the table `sessions`, if the token was issued by logging in.
+    ///
+    /// The token is rejected once its session is deleted, see [Store::delete_session](crate::store::Store::delete_session).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<SessionId>,
+}
+
+impl Session {
+    /// The version of the claims of the tokens issued now.
+    ///
+    /// It is increased whenever the claims change, which rejects the tokens issued before. The
+    /// tokens without the `ver` claim, issued before the claims were versioned, are version 0,
+    /// except the PASETO v2 ones, whose claims are completed during the transition to the v4 tokens.
+    pub const CLAIMS_VERSION: u32 = 1;
+}
+
+/// Represents a session id.
+///
+/// `SessionId` is a wrapper around a i32. It represents the id of a session, started by logging in.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct SessionId(pub i32);
+
+/// Represents a session of an account, listed to the account so it can sign its devices out.
+///
+/// The token of the session is not included, only the device it was issued to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ActiveSession {
+    /// The id of the session.
+    pub id: SessionId,
+    /// The `User-Agent` of the client that logged in, if it sent one.
+    pub user_agent: Option<String>,
+    /// The time the session was started at.
+    #[serde(with = "crate::types::timestamp")]
+    pub created_on: DateTime<Utc>,
+    /// The time the session expires at, extended by the refreshes and the use of the remembered sessions.
+    #[serde(with = "crate::types::timestamp")]
+    pub expires_on: DateTime<Utc>,
+    /// Whether the request listing the sessions is authenticated with this session.
+    pub current: bool,
+}
+
+impl TryFrom<PgRow> for ActiveSession {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: SessionId(row.try_get("id")?),
+            user_agent: row.try_get("user_agent")?,
+            created_on: row.try_get("created_on")?,
+            expires_on: row.try_get("expires_on")?,
+            current: row.try_get("current")?,
+        })
+    }
+}
+
+/// Represents the failed logins of an account, or of a client address, counted by the store to
+/// slow the guessing of the passwords down, see [Store::check_login_attempts](crate::store::Store::check_login_attempts).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FailedLogins {
+    /// The number of failed logins since the first one.
+    pub count: u32,
+    /// The time of the first failed login, from which the failures are counted.
+    pub since: DateTime<Utc>,
+}
+
+/// How long the sessions started by the logins last.
+///
+/// The tokens of the sessions are valid for [SessionLifetimes::token]. The sessions of the logins
+/// with `remember_me` also get a refresh token, which renews the token of the session until the
+/// session expires. The expiration slides: every refresh extends the session by
+/// [SessionLifetimes::remember_me], like every request authenticated with the token of the session
+/// once [SessionLifetimes::remember_me_slide] has passed since its last extension, so only the
+/// sessions left unused that long expire, but never past [SessionLifetimes::remember_me_max] after
+/// the login.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SessionLifetimes {
+    /// How long the tokens of the sessions are valid for.
+    pub token: Duration,
+    /// How long a remembered session lasts after its last refresh.
+    pub remember_me: Duration,
+    /// How long a remembered session lasts at most after the login, however often it is refreshed.
+    pub remember_me_max: Duration,
+    /// How long after its last extension a remembered session is extended again by its use, so
+    /// not every request updates the session.
+    pub remember_me_slide: Duration,
+}
+
+impl Default for SessionLifetimes {
+    fn default() -> Self {
+        Self {
+            token: Duration::try_days(1).unwrap(),
+            remember_me: Duration::try_days(30).unwrap(),
+            remember_me_max: Duration::try_days(90).unwrap(),
+            remember_me_slide: Duration::try_hours(1).unwrap(),
+        }
+    }
+}
+
+/// Parameters of the Argon2 hashes of the passwords.
+///
+/// The defaults are the ones recommended by OWASP: argon2id, with 19 MiB of memory, 2 iterations
+/// and 1 lane. Every hash encodes the parameters it was computed with, so the passwords hashed
+/// with other parameters are still verified, and are hashed again on the next login, see
+/// [PasswordHashing::needs_rehash].
+///
+/// ```
+/// use webdev_core::types::authentication::PasswordHashing;
+///
+/// let hashing = PasswordHashing::new("argon2id", 1024, 1, 2).unwrap();
+/// let hash = argon2::hash_encoded(b"password", b"some salt", &hashing.config()).unwrap();
+/// assert!(!hashing.needs_rehash(&hash));
+/// assert!(PasswordHashing::default().needs_rehash(&hash));
+/// assert!(PasswordHashing::new("argon2x", 1024, 1, 2).is_err());
+/// assert!(PasswordHashing::new("argon2id", 8, 1, 2).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PasswordHashing {
+    /// The variant of Argon2.
+    pub variant: argon2::Variant,
+    /// The memory used by a hash, in KiB.
+    pub memory_kib: u32,
+    /// The number of passes over the memory.
+    pub iterations: u32,
+    /// The number of lanes the memory is split into.
+    pub parallelism: u32,
+}
+
+impl Default for PasswordHashing {
+    fn default() -> Self {
+        let config = argon2::Config::default();
+        Self {
+            variant: config.variant,
+            memory_kib: config.mem_cost,
+            iterations: config.time_cost,
+            parallelism: config.lanes,
+        }
+    }
+}
+
+impl PasswordHashing {
+    /// Returns the parameters, checking that Argon2 accepts them.
+    ///
+    /// # Parameters
+    /// - `variant` - The name of the variant: `argon2id`, `argon2i` or `argon2d`.
+    /// - `memory_kib` - The memory used by a hash, in KiB, at least 8 KiB for every lane.
+    /// - `iterations` - The number of passes over the memory, at least one.
+    /// - `parallelism` - The number of lanes, at least one.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidPasswordHashing] if any of the parameters is invalid.
+    pub fn new(variant: &str, memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, ServiceError> {
+        let invalid = |message: String| Err(ServiceError::InvalidPasswordHashing(message));
+        let Ok(variant) = argon2::Variant::from_str(variant) else {
+            return invalid(format!("unknown variant {variant:?}"));
+        };
+        if iterations == 0 {
+            return invalid("the iterations have to be at least 1".to_string());
+        }
+        if parallelism == 0 || parallelism > 0x00FF_FFFF {
+            return invalid(format!("the parallelism has to be between 1 and {}", 0x00FF_FFFF));
+        }
+        if memory_kib < 8 * parallelism {
+            return invalid(format!(
+                "the memory has to be at least {} KiB, 8 KiB for every lane",
+                8 * parallelism
+            ));
+        }
+        Ok(Self {
+            variant,
+            memory_kib,
+            iterations,
+            parallelism,
+        })
+    }
+
+    /// Returns the configuration of Argon2 the passwords are hashed with.
+    pub fn config(&self) -> argon2::Config<'static> {
+        argon2::Config {
+            variant: self.variant,
+            mem_cost: self.memory_kib,
+            time_cost: self.iterations,
+            lanes: self.parallelism,
+            ..argon2::Config::default()
+        }
+    }
+
+    /// Returns whether the hash was computed with other parameters, or another version of Argon2,
+    /// so the password should be hashed again.
+    ///
+    /// The hash is in the encoded form, e.g. `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`, and
+    /// the hashes that cannot be read are hashed again as well.
+    pub fn needs_rehash(&self, encoded: &str) -> bool {
+        let config = self.config();
+        let prefix = format!(
+            "${}$v={}$m={},t={},p={}$",
+            config.variant, config.version, config.mem_cost, config.time_cost, config.lanes
+        );
+        !encoded.starts_with(&prefix)
+    }
+}
+
+/// Keys the tokens of the sessions are encrypted and decrypted with.
+///
+/// The tokens are PASETO v4 local tokens, whose keys are exactly [AuthKeys::PASETO_KEY_LENGTH] bytes
+/// long. The keys are loaded and validated once, at startup, so a wrong key stops the service from
+/// starting, instead of failing every login. They are kept in the [Store](crate::store::Store).
+///
+/// Every key has an id, written in the footer of the tokens it encrypts. The new tokens are
+/// encrypted with the current key, while the tokens are decrypted with the key they name, the
+/// current one or one of the previous ones. So the keys are rotated without logging every account
+/// out: the new key becomes the current one, and the old one is kept as a previous key until the
+/// tokens it encrypted expire.
+///
+/// The tokens issued before the v4 ones, which are PASETO v2 local tokens encrypted with the same
+/// keys, are accepted during the transition, until the time set by
+/// [AuthKeys::with_v2_tokens_until], or as long as they are valid if it is not set.
+///
+/// ```
+/// use webdev_core::types::authentication::AuthKeys;
+///
+/// let keys = AuthKeys::new("2024-06", b"32 BYTES LONG KEY FOR THE TOKENS")
+///     .and_then(|keys| keys.with_previous_key("2024-01", b"OLDER KEY KEPT UNTIL IT EXPIRES!"))
+///     .unwrap();
+/// assert_eq!(keys.current_key().0, "2024-06");
+/// assert!(keys.key("2024-01").is_some());
+///
+/// assert!(AuthKeys::new("short", b"TOO SHORT").is_err());
+/// assert!(AuthKeys::new("not an id", b"32 BYTES LONG KEY FOR THE TOKENS").is_err());
+/// ```
+#[derive(Clone)]
+pub struct AuthKeys {
+    /// The keys by their ids, the current one first.
+    paseto_keys: Vec<(String, [u8; AuthKeys::PASETO_KEY_LENGTH])>,
+    /// The time the PASETO v2 tokens are accepted until, if the transition to v4 has an end.
+    v2_tokens_until: Option<DateTime<Utc>>,
+}
+
+impl AuthKeys {
+    /// The length of the PASETO keys, in bytes.
+    pub const PASETO_KEY_LENGTH: usize = 32;
+    /// The id of the current key, if `PASETO_KEY_ID` is not set.
+    pub const DEFAULT_KEY_ID: &'static str = "default";
+
+    /// Returns the keys with the given current PASETO key, and no previous ones.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidAuthKey] if the key is not [AuthKeys::PASETO_KEY_LENGTH] bytes long,
+    ///   or the id is not made of letters, digits, `-`, `_` and `.`.
+    pub fn new(id: &str, paseto_key: &[u8]) -> Result<Self, ServiceError> {
+        Ok(Self {
+            paseto_keys: vec![Self::validate(id, paseto_key)?],
+            v2_tokens_until: None,
+        })
+    }
+
+    /// Adds a previous PASETO key, which only decrypts the tokens naming it.
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidAuthKey] if the key is invalid, see [AuthKeys::new], or its id is
+    ///   already used by another key.
+    pub fn with_previous_key(mut self, id: &str, paseto_key: &[u8]) -> Result<Self, ServiceError> {
+        let key = Self::validate(id, paseto_key)?;
+        if self.key(id).is_some() {
+            return Err(ServiceError::InvalidAuthKey(format!("the key id {id:?} is used twice")));
+        }
+        self.paseto_keys.push(key);
+        Ok(self)
+    }
+
+    /// Sets the time the PASETO v2 tokens, issued before the v4 ones, are accepted until.
+    pub fn with_v2_tokens_until(self, until: DateTime<Utc>) -> Self {
+        Self {
+            v2_tokens_until: Some(until),
+            ..self
+        }
+    }
+
+    /// Returns whether the PASETO v2 tokens are still accepted at the given time.
+    pub fn accepts_v2_tokens(&self, now: DateTime<Utc>) -> bool {
+        self.v2_tokens_until.is_none_or(|until| now < until)
+    }
+
+    /// Returns the keys read from the environment variables:
+    /// - `PASETO_KEY`, the current key,
+    /// - `PASETO_KEY_ID`, the id of the current key, [AuthKeys::DEFAULT_KEY_ID] if not set,
+    /// - `PASETO_PREVIOUS_KEYS`, the previous keys, if any, as comma separated `id=key` pairs, so
+    ///   these keys cannot contain commas.
+    /// - `PASETO_V2_TOKENS_UNTIL`, the time the PASETO v2 tokens are accepted until, if set, see
+    ///   [AuthKeys::with_v2_tokens_until].
+    ///
+    /// # Errors
+    /// - [ServiceError::InvalidAuthKey] if `PASETO_KEY` is not set, any of the keys is invalid, or
+    ///   `PASETO_V2_TOKENS_UNTIL` is not a time.
+    pub fn from_env() -> Result<Self, ServiceError> {
+        let paseto_key = std::env::var("PASETO_KEY")
+            .map_err(|_| ServiceError::InvalidAuthKey("PASETO_KEY is not set".to_string()))?;
+        let id = std::env::var("PASETO_KEY_ID").unwrap_or_else(|_| Self::DEFAULT_KEY_ID.to_string());
+        let mut keys = Self::new(&id, paseto_key.as_bytes())?;
+        for entry in std::env::var("PASETO_PREVIOUS_KEYS")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|entry| !entry.is_empty())
+        {
+            let (id, paseto_key) = entry.split_once('=').ok_or_else(|| {
+                ServiceError::InvalidAuthKey("PASETO_PREVIOUS_KEYS has to list the keys as id=key pairs".to_string())
+            })?;
+            keys = keys.with_previous_key(id, paseto_key.as_bytes())?;
+        }
+        if let Ok(until) = std::env::var("PASETO_V2_TOKENS_UNTIL") {
+            let until = crate::types::timestamp::parse(&until)
+                .map_err(|error| ServiceError::InvalidAuthKey(format!("PASETO_V2_TOKENS_UNTIL: {error}")))?;
+            keys = keys.with_v2_tokens_until(until);
+        }
+        Ok(keys)
+    }
+
+    /// Returns a random key, so the tokens are only valid until the process exits.
+    pub fn random() -> Self {
+        Self {
+            paseto_keys: vec![("random".to_string(), rand::random())],
+            v2_tokens_until: None,
+        }
+    }
+
+    /// Returns the id of the current key, and the key, which encrypts the new tokens.
+    pub fn current_key(&self) -> (&str, &[u8]) {
+        let (id, paseto_key) = &self.paseto_keys[0];
+        (id, paseto_key)
+    }
+
+    /// Returns the ids of the keys, and the keys, the current one first.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.paseto_keys
+            .iter()
+            .map(|(id, paseto_key)| (id.as_str(), paseto_key.as_slice()))
+    }
+
+    /// Returns the key with the given id, the current one or a previous one.
+    pub fn key(&self, id: &str) -> Option<&[u8]> {
+        self.keys()
+            .find(|(key_id, _)| *key_id == id)
+            .map(|(_, paseto_key)| paseto_key)
+    }
+
+    /// Checks the id and the length of a key.
+    fn validate(id: &str, paseto_key: &[u8]) -> Result<(String, [u8; Self::PASETO_KEY_LENGTH]), ServiceError> {
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || "-_.".contains(char))
+        {
+            return Err(ServiceError::InvalidAuthKey(format!(
+                "the key id {id:?} has to be made of letters, digits, '-', '_' and '.'"
+            )));
+        }
+        let paseto_key = paseto_key.try_into().map_err(|_| {
+            ServiceError::InvalidAuthKey(format!(
+                "the key {id:?} is {} bytes long, it has to be {} bytes long",
+                paseto_key.len(),
+                Self::PASETO_KEY_LENGTH
+            ))
+        })?;
+        Ok((id.to_string(), paseto_key))
+    }
+}
+
+impl std::fmt::Debug for AuthKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Only the ids of the keys are logged
+        let ids: Vec<_> = self.paseto_keys.iter().map(|(id, _)| id).collect();
+        f.debug_struct("AuthKeys")
+            .field("ids", &ids)
+            .field("v2_tokens_until", &self.v2_tokens_until)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The external providers the accounts can log in with, using OAuth 2.0.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProvider {
+    /// Login with a GitHub account.
+    GitHub,
+    /// Login with a Google account.
+    Google,
+}
+
+impl OAuthProvider {
+    /// All the providers.
+    pub const ALL: [OAuthProvider; 2] = [OAuthProvider::GitHub, OAuthProvider::Google];
+
+    /// Returns the name of the provider, used in the paths of the routes, e.g. `/oauth/github`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OAuthProvider::GitHub => "github",
+            OAuthProvider::Google => "google",
+        }
+    }
+
+    /// Returns the prefix of the environment variables configuring the client of the provider.
+    fn env_prefix(&self) -> &'static str {
+        match self {
+            OAuthProvider::GitHub => "GITHUB",
+            OAuthProvider::Google => "GOOGLE",
+        }
+    }
+}
+
+impl FromStr for OAuthProvider {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|provider| provider.as_str() == value)
+            .ok_or_else(|| format!("unknown OAuth provider \"{value}\""))
+    }
+}
+
+/// The credentials of the client registered with an [OAuthProvider].
+#[derive(Clone)]
+pub struct OAuthClient {
+    /// The id of the client, sent in the authorization requests.
+    pub client_id: String,
+    /// The secret of the client, sent only to the provider.
+    client_secret: String,
+}
+
+impl OAuthClient {
+    /// Returns the credentials of a client.
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+        }
+    }
+
+    /// Returns the secret of the client.
+    pub fn client_secret(&self) -> &str {
+        &self.client_secret
+    }
+}
+
+impl std::fmt::Debug for OAuthClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The secret is never logged
+        f.debug_struct("OAuthClient")
+            .field("client_id", &self.client_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The configuration of the login with the external providers, kept in the [Store](crate::store::Store).
+///
+/// A provider is enabled once its client is configured. The provider redirects the accounts back to
+/// the service, at `{public_url}/oauth/{provider}/callback`, which has to be registered with the
+/// client. No provider is enabled by default.
+///
+/// ```
+/// use webdev_core::types::authentication::{OAuthClient, OAuthConfig, OAuthProvider};
+///
+/// let config = OAuthConfig::new("https://webdev.example.com/")
+///     .with_client(OAuthProvider::GitHub, OAuthClient::new("client-id", "client-secret"));
+/// assert!(config.client(OAuthProvider::GitHub).is_some());
+/// assert!(config.client(OAuthProvider::Google).is_none());
+/// assert_eq!(
+///     config.redirect_uri(OAuthProvider::GitHub),
+///     "https://webdev.example.com/oauth/github/callback"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OAuthConfig {
+    /// The URL the service is reached at, without the trailing slash.
+    public_url: String,
+    /// The clients of the enabled providers.
+    clients: Vec<(OAuthProvider, OAuthClient)>,
+}
+
+impl OAuthConfig {
+    /// Returns the configuration of the service reached at the given URL, without any provider.
+    pub fn new(public_url: &str) -> Self {
+        Self {
+            public_url: public_url.trim_end_matches('/').to_string(),
+            clients: Vec::new(),
+        }
+    }
+
+    /// Enables the provider, with the given client, replacing its previous client, if any.
+    pub fn with_client(mut self, provider: OAuthProvider, client: OAuthClient) -> Self {
+        self.clients.retain(|(enabled, _)| *enabled != provider);
+        self.clients.push((provider, client));
+        self
+    }
+
+    /// Returns the configuration read from the environment variables:
+    /// - `OAUTH_PUBLIC_URL`, the URL the service is reached at,
+    /// - `GITHUB_CLIENT_ID` and `GITHUB_CLIENT_SECRET`, the client of GitHub,
+    /// - `GOOGLE_CLIENT_ID` and `GOOGLE_CLIENT_SECRET`, the client of Google.
+    ///
+    /// A provider is enabled only if both its variables are set, and none is if `OAUTH_PUBLIC_URL`
+    /// is not set.
+    pub fn from_env() -> Self {
+        let Ok(public_url) = std::env::var("OAUTH_PUBLIC_URL") else {
+            return Self::default();
+        };
+        let mut config = Self::new(&public_url);
+        for provider in OAuthProvider::ALL {
+            let prefix = provider.env_prefix();
+            if let (Ok(client_id), Ok(client_secret)) = (
+                std::env::var(format!("{prefix}_CLIENT_ID")),
+                std::env::var(format!("{prefix}_CLIENT_SECRET")),
+            ) {
+                config = config.with_client(provider, OAuthClient::new(client_id, client_secret));
+            }
+        }
+        config
+    }
+
+    /// Returns the client of the provider, if it is enabled.
+    pub fn client(&self, provider: OAuthProvider) -> Option<&OAuthClient> {
+        self.clients
+            .iter()
+            .find(|(enabled, _)| *enabled == provider)
+            .map(|(_, client)| client)
+    }
+
+    /// Returns the URL the provider redirects the accounts back to.
+    pub fn redirect_uri(&self, provider: OAuthProvider) -> String {
+        format!("{}/oauth/{}/callback", self.public_url, provider.as_str())
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+/// Represents a badge, awarded to the accounts for their contributions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Badge {
+    /// Awarded for asking the first question.
+    FirstQuestion,
+    /// Awarded for posting [Badge::TEN_ANSWERS] answers.
+    TenAnswers,
+    /// Awarded for asking a question that got [Badge::POPULAR_QUESTION_ANSWERS] answers from the other accounts.
+    PopularQuestion,
+}
+
+impl Badge {
+    /// All the badges, in the order they are evaluated.
+    pub const ALL: [Badge; 3] = [Badge::FirstQuestion, Badge::TenAnswers, Badge::PopularQuestion];
+    /// The number of answers an account posts to get the [Badge::TenAnswers] badge.
+    pub const TEN_ANSWERS: i64 = 10;
+    /// The number of answers from the other accounts a question gets to earn its owner the
+    /// [Badge::PopularQuestion] badge.
+    pub const POPULAR_QUESTION_ANSWERS: i64 = 5;
+
+    /// Returns the name of the badge, as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Badge::FirstQuestion => "first_question",
+            Badge::TenAnswers => "ten_answers",
+            Badge::PopularQuestion => "popular_question",
+        }
+    }
+}
+
+impl Display for Badge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Badge {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Badge::ALL
+            .into_iter()
+            .find(|badge| badge.as_str() == s)
+            .ok_or_else(|| format!("invalid badge: {s:?}"))
+    }
+}
+
+/// Represents a badge awarded to an account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AwardedBadge {
+    /// The badge.
+    pub badge: Badge,
+    /// The time the badge was awarded.
+    #[serde(with = "crate::types::timestamp")]
+    pub awarded_on: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for AwardedBadge {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let badge: String = row.try_get("badge")?;
+        Ok(Self {
+            badge: badge.parse().map_err(|error: String| sqlx::Error::ColumnDecode {
+                index: "badge".to_string(),
+                source: error.into(),
+            })?,
+            awarded_on: row.try_get("awarded_on")?,
+        })
+    }
+}
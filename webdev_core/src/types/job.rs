@@ -0,0 +1,179 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use macros::DbObjectId;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+use crate::error::ServiceError;
+use crate::store::Store;
+use crate::types::answer::AnswerId;
+use crate::types::question::QuestionId;
+use crate::types::webhook::WebhookId;
+
+/// Represents a job id.
+///
+/// `JobId` is a wrapper around an i32. It represents the id of a job in the queue.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct JobId(pub i32);
+
+/// Represents the status of a job.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// The job waits to be run, either for the first time or for a retry.
+    Pending,
+    /// The job is being run by a worker.
+    Running,
+    /// The job finished successfully.
+    Done,
+    /// The job failed on its last attempt, and won't be retried.
+    Failed,
+}
+
+impl JobStatus {
+    /// Returns the name of the status, as stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Done => "done",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+impl Display for JobStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(JobStatus::Pending),
+            "running" => Ok(JobStatus::Running),
+            "done" => Ok(JobStatus::Done),
+            "failed" => Ok(JobStatus::Failed),
+            _ => Err(format!("invalid job status: {s:?}")),
+        }
+    }
+}
+
+/// Represents a job in the queue.
+///
+/// The payload describes the work to do, and is interpreted by the worker according to the kind of the job.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Job {
+    /// The id of the job.
+    pub id: JobId,
+    /// The kind of the job, e.g. `deliver_webhook`.
+    pub kind: String,
+    /// The description of the work to do.
+    pub payload: serde_json::Value,
+    /// The status of the job.
+    pub status: JobStatus,
+    /// The number of times the job was run.
+    pub attempts: i32,
+    /// The number of times the job is run before it is marked as failed.
+    pub max_attempts: i32,
+    /// The error of the last failed attempt.
+    pub last_error: Option<String>,
+    /// The time after which the job is run, or retried.
+    #[serde(with = "crate::types::timestamp")]
+    pub run_at: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for Job {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let status: String = row.try_get("status")?;
+        Ok(Self {
+            id: JobId(row.try_get("id")?),
+            kind: row.try_get("kind")?,
+            payload: row.try_get("payload")?,
+            status: status.parse().map_err(|error: String| sqlx::Error::ColumnDecode {
+                index: "status".to_string(),
+                source: error.into(),
+            })?,
+            attempts: row.try_get("attempts")?,
+            max_attempts: row.try_get("max_attempts")?,
+            last_error: row.try_get("last_error")?,
+            run_at: row.try_get("run_at")?,
+        })
+    }
+}
+
+/// Work described by a job.
+///
+/// The task is stored as the payload of the job, tagged with its kind, e.g.
+/// `{"kind": "deliver_webhook", "webhook_id": 1, ...}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Task {
+    /// Delivers an event to a webhook.
+    DeliverWebhook {
+        /// The webhook the event is delivered to.
+        webhook_id: WebhookId,
+        /// The name of the event.
+        event: String,
+        /// The JSON encoded event.
+        payload: String,
+    },
+    /// Notifies the owner and the watchers of a question about a new answer.
+    NotifyAnswer {
+        /// The new answer.
+        answer_id: AnswerId,
+    },
+    /// Awards the badges earned by the accounts since the last evaluation.
+    AwardBadges,
+    /// Censors the title and the content of a question, stored uncensored while the profanity
+    /// filter was unavailable.
+    CensorQuestion {
+        /// The question to censor.
+        question_id: QuestionId,
+    },
+    /// Censors the content of an answer, stored uncensored while the profanity filter was unavailable.
+    CensorAnswer {
+        /// The answer to censor.
+        answer_id: AnswerId,
+    },
+}
+
+impl Task {
+    /// Returns the kind of the task, the same one used for the `kind` field.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Task::DeliverWebhook { .. } => "deliver_webhook",
+            Task::NotifyAnswer { .. } => "notify_answer",
+            Task::AwardBadges => "award_badges",
+            Task::CensorQuestion { .. } => "censor_question",
+            Task::CensorAnswer { .. } => "censor_answer",
+        }
+    }
+
+    /// Returns the payload of the job for the task.
+    pub fn payload(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("tasks are always serializable")
+    }
+
+    /// Adds a job for the task to the queue.
+    ///
+    /// The jobs caused by the writes of the [Store] are added in the transactions of the writes
+    /// instead, so they are queued if and only if the writes are committed.
+    ///
+    /// # Parameters
+    /// - `store` - The [Store] holding the queue.
+    pub async fn enqueue(&self, store: &Store) -> Result<Job, ServiceError> {
+        store.enqueue_job(self.kind(), self.payload()).await
+    }
+}
@@ -0,0 +1,53 @@
+//! Rendering of the content of the questions and the answers, which is written in markdown.
+//!
+//! The content is stored as it was written, and rendered to HTML by the [Store](crate::store::Store)
+//! whenever it is created or updated. The rendered HTML is sanitized, so it can be displayed as is:
+//! the scripts, the styles, the event handlers and the other unsafe markup are removed, whether they
+//! were written as raw HTML in the markdown, or produced by the rendering.
+
+use std::str::FromStr;
+
+use pulldown_cmark::{html, Options, Parser};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Format of the content in the responses, chosen with the `format` query parameter.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentFormat {
+    /// The content as it was written.
+    #[default]
+    Markdown,
+    /// The content as it was written, and rendered to sanitized HTML in the `content_html` field.
+    Html,
+}
+
+impl FromStr for ContentFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(format!(
+                "unknown content format \"{value}\", expected \"markdown\" or \"html\""
+            )),
+        }
+    }
+}
+
+/// Renders the markdown to HTML, and sanitizes it.
+///
+/// ```
+/// use webdev_core::types::markdown::to_html;
+///
+/// let html = to_html("Use **`unsafe`** sparingly <script>alert(1)</script>");
+/// assert!(html.starts_with("<p>Use <strong><code>unsafe</code></strong> sparingly"));
+/// assert!(!html.contains("script"));
+/// ```
+pub fn to_html(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut rendered = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut rendered, Parser::new_ext(markdown, options));
+    ammonia::clean(&rendered)
+}
@@ -0,0 +1,34 @@
+//! Module containing types used for resources and other helper types.
+
+/// Module containing types used for `Answer` resource.
+pub mod answer;
+/// Module containing types used for `Attachment` resource.
+pub mod attachment;
+/// Module containing types used for authentication.
+pub mod authentication;
+/// Module containing types used for the badges awarded to the accounts.
+pub mod badge;
+/// Module containing types used for the queue of background jobs.
+pub mod job;
+/// Module containing the rendering of the markdown content to HTML.
+pub mod markdown;
+/// Module containing types used for the moderation of the accounts.
+pub mod moderation;
+/// Module containing types used for `Notification` resource.
+pub mod notification;
+/// Module contaitning [Pagination](pagination::Pagination) type.
+pub mod pagination;
+/// Module containing types used for `Question` resource.
+pub mod question;
+/// Module containing types used for the daily limits on the contributions.
+pub mod quota;
+/// Module containing the normalization of the text posted by the users.
+pub mod sanitize;
+/// Module containing types used for `Tag` resource.
+pub mod tag;
+/// Module containing the shared serde format for timestamps.
+pub mod timestamp;
+/// Module containing types used for the votes on the questions and the answers.
+pub mod vote;
+/// Module containing types used for `Webhook` resource.
+pub mod webhook;
@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+use crate::types::authentication::AccountId;
+use crate::types::question::QuestionId;
+
+/// Represents the request to ban an account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Ban {
+    /// The number of hours the account is banned for, or `None` to ban it for good.
+    #[serde(default)]
+    pub duration_hours: Option<u32>,
+    /// The reason of the ban, shown to the account when its requests are rejected.
+    pub reason: String,
+}
+
+/// Represents the ban of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AccountBan {
+    /// The id of the banned account.
+    pub account_id: AccountId,
+    /// The time the ban ends, or `None` if the account is banned for good.
+    #[serde(default, with = "crate::types::timestamp::option")]
+    pub until: Option<DateTime<Utc>>,
+    /// The reason of the ban.
+    pub reason: String,
+}
+
+impl TryFrom<PgRow> for AccountBan {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_id: AccountId(row.try_get("id")?),
+            until: row.try_get("banned_until")?,
+            reason: row.try_get("ban_reason")?,
+        })
+    }
+}
+
+/// The role of an account, which decides what else than its own content the account may change.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountRole {
+    /// The account may change only its own content.
+    #[default]
+    User,
+    /// The account may also edit and delete the content of the other accounts.
+    Moderator,
+    /// The account may also edit and delete the content of the other accounts, like a moderator.
+    Admin,
+}
+
+impl AccountRole {
+    /// Returns the value stored in the `role` column of the table `accounts`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountRole::User => "user",
+            AccountRole::Moderator => "moderator",
+            AccountRole::Admin => "admin",
+        }
+    }
+
+    /// Returns whether the account may edit and delete the content of the other accounts.
+    pub fn can_moderate(&self) -> bool {
+        matches!(self, AccountRole::Moderator | AccountRole::Admin)
+    }
+}
+
+impl FromStr for AccountRole {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "user" => Ok(Self::User),
+            "moderator" => Ok(Self::Moderator),
+            "admin" => Ok(Self::Admin),
+            _ => Err(format!(
+                "unknown account role \"{value}\", expected \"user\", \"moderator\" or \"admin\""
+            )),
+        }
+    }
+}
+
+/// Represents the maintenance mode of the service, in which only the reads and the administrative
+/// requests are served.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MaintenanceMode {
+    /// Whether the maintenance mode is on.
+    pub enabled: bool,
+}
+
+/// Represents the request to change the role of an account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RoleUpdate {
+    /// The new role of the account.
+    pub role: AccountRole,
+}
+
+/// The changes a moderator can make to the content of another account, recorded in the
+/// moderation log.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// The question was updated.
+    UpdateQuestion,
+    /// The question was deleted.
+    DeleteQuestion,
+}
+
+impl ModerationAction {
+    /// Returns the value stored in the `action` column of the table `moderation_log`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationAction::UpdateQuestion => "update_question",
+            ModerationAction::DeleteQuestion => "delete_question",
+        }
+    }
+}
+
+impl FromStr for ModerationAction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "update_question" => Ok(Self::UpdateQuestion),
+            "delete_question" => Ok(Self::DeleteQuestion),
+            _ => Err(format!("unknown moderation action \"{value}\"")),
+        }
+    }
+}
+
+/// Represents an entry of the moderation log, a change a moderator made to the content of another
+/// account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ModerationLogEntry {
+    /// The id of the entry.
+    pub id: i32,
+    /// The id of the moderator, or `None` if the moderator account was deleted since.
+    pub moderator_id: Option<AccountId>,
+    /// The change made by the moderator.
+    pub action: ModerationAction,
+    /// The id of the question that was changed, which may no longer exist.
+    pub question_id: QuestionId,
+    /// The id of the owner of the question, or `None` if the question was of a deleted account.
+    pub owner_id: Option<AccountId>,
+    /// The time the change was made at.
+    #[serde(with = "crate::types::timestamp")]
+    pub created_on: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for ModerationLogEntry {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        let action: String = row.try_get("action")?;
+        Ok(Self {
+            id: row.try_get("id")?,
+            moderator_id: row.try_get::<Option<i32>, _>("moderator_id")?.map(AccountId),
+            action: action.parse().map_err(|error: String| sqlx::Error::ColumnDecode {
+                index: "action".to_string(),
+                source: error.into(),
+            })?,
+            question_id: QuestionId(row.try_get("question_id")?),
+            owner_id: row.try_get::<Option<i32>, _>("owner_id")?.map(AccountId),
+            created_on: row.try_get("created_on")?,
+        })
+    }
+}
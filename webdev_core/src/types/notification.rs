@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use macros::DbObjectId;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+use crate::types::answer::AnswerId;
+use crate::types::question::QuestionId;
+
+/// Represents a notification id.
+///
+/// `NotificationId` is a wrapper around an i32. It represents the id of a notification.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct NotificationId(pub i32);
+
+/// Represents a notification about a new answer.
+///
+/// The notifications are created for the owner of the question and its watchers, the accounts
+/// that answered the question before, by the job queued when the answer is posted.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Notification {
+    /// The id of the notification.
+    pub id: NotificationId,
+    /// The id of the question that was answered.
+    pub question_id: QuestionId,
+    /// The id of the new answer.
+    pub answer_id: AnswerId,
+    /// Whether the notification was marked as read.
+    pub read: bool,
+    /// The time the notification was created.
+    #[serde(with = "crate::types::timestamp")]
+    pub created_on: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for Notification {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: NotificationId(row.try_get("id")?),
+            question_id: QuestionId(row.try_get("question_id")?),
+            answer_id: AnswerId(row.try_get("answer_id")?),
+            read: row.try_get("read")?,
+            created_on: row.try_get("created_on")?,
+        })
+    }
+}
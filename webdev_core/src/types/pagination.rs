@@ -0,0 +1,104 @@
+use macros::QueryParams;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Pagination struct that is getting extracted
+/// from the query params
+///
+/// The query params are extracted with [Pagination::extract], generated by the [QueryParams] derive.
+/// If the query params are not provided we just return the default values.
+/// Default values are `offset = 0` and `limit = None`, which the store replaces with its maximum
+/// page size, see [Pagination::bounded].
+/// If the provided query params are not valid (cannot be parsed as integers or are negative), or
+/// there are query params other than `offset` and `limit`, we return a [PaginationParsingError].
+/// # Example query
+/// GET requests to this route can have a pagination attached, so we just
+/// return the questions we need `/questions?offset=0&limit=10`
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct Pagination {
+    /// The index of the first item that has to be returned
+    #[query(default = 0, validate = non_negative)]
+    #[param(minimum = 0)]
+    pub offset: i64,
+    /// The maximum number of items that have to be returned, the maximum page size of the service
+    /// if missing and at most, `max_page_size` in its configuration, 1000 by default
+    #[query(validate = non_negative)]
+    #[param(minimum = 0)]
+    pub limit: Option<i64>,
+}
+
+impl Pagination {
+    /// The largest number of items that can be requested at once, unless configured otherwise.
+    pub const MAX_LIMIT: i64 = 1000;
+
+    /// Bounds the pagination by the maximum page size, the missing limit becomes the maximum one.
+    ///
+    /// The store bounds every pagination it lists with, so a listing never reads the whole table.
+    /// Limits larger than the maximum page size are rejected with a [PaginationParsingError].
+    pub fn bounded(self, max_limit: i64) -> Result<Self, PaginationParsingError> {
+        match self.limit {
+            Some(limit) if limit > max_limit => Err(PaginationParsingError::Invalid {
+                parameter: "limit",
+                value: limit.to_string(),
+                reason: format!("must not be larger than the maximum page size of {max_limit}"),
+            }),
+            limit => Ok(Self {
+                limit: Some(limit.unwrap_or(max_limit)),
+                ..self
+            }),
+        }
+    }
+
+    /// Checks the values of a pagination that was not extracted from the query params,
+    /// e.g. one received over gRPC.
+    pub fn validate(&self) -> Result<(), PaginationParsingError> {
+        let invalid = |parameter, value: i64, reason| PaginationParsingError::Invalid {
+            parameter,
+            value: value.to_string(),
+            reason,
+        };
+        non_negative(&self.offset).map_err(|reason| invalid("offset", self.offset, reason))?;
+        if let Some(limit) = self.limit {
+            non_negative(&limit).map_err(|reason| invalid("limit", limit, reason))?;
+        }
+        Ok(())
+    }
+}
+
+/// A page of a paginated listing, with the total number of items in the listing.
+///
+/// The `offset` and `limit` are the ones the page was requested with, so the clients can tell
+/// how many pages there are from the `total`, e.g. to render a pager.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Page<T> {
+    /// The items of the page
+    pub items: Vec<T>,
+    /// The number of items in the whole listing
+    pub total: i64,
+    /// The index of the first item of the page
+    pub offset: i64,
+    /// The maximum number of items of the page, if it was limited
+    pub limit: Option<i64>,
+}
+
+impl<T> Page<T> {
+    /// Creates the page of the items, requested with the given pagination.
+    pub fn new(items: Vec<T>, total: i64, pag: Pagination) -> Self {
+        Self {
+            items,
+            total,
+            offset: pag.offset,
+            limit: pag.limit,
+        }
+    }
+}
+
+/// Rejects negative offsets and limits, which the database refuses.
+fn non_negative(value: &i64) -> Result<(), String> {
+    match *value >= 0 {
+        true => Ok(()),
+        false => Err("must not be negative".to_string()),
+    }
+}
@@ -0,0 +1,299 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use macros::{Builder, DbObjectId, QueryParams};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::types::answer::Answer;
+use crate::types::authentication::Author;
+use crate::types::markdown::ContentFormat;
+
+/// Represents a question id.
+///
+/// `QuestionId` is a wrapper around an i32. It represents the id of a question.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct QuestionId(pub i32);
+
+/// Represents a question.
+///
+/// Questions can be constructed with [Question::builder], generated by the [Builder] derive.
+#[derive(Builder, Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Question {
+    /// The id of the question. It is an `Option<QuestionId>` because we want to be able to
+    /// create a question by parsing a JSON object that doesn't have an id field.
+    pub id: Option<QuestionId>,
+    /// The title of the question.
+    pub title: String,
+    /// The content of the question, in markdown.
+    pub content: String,
+    /// The content of the question, rendered to sanitized HTML.
+    ///
+    /// It is loaded by the [Store](crate::store::Store) only when requested with [ContentFormat::Html].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(read_only)]
+    pub content_html: Option<String>,
+    /// The tags of the question, in the order they were written.
+    ///
+    /// They are kept in the tables `tags` and `question_tags`, and aggregated by the
+    /// [Store](crate::store::Store) when the question is read.
+    pub tags: Option<Vec<String>>,
+    /// The version of the question, incremented on every update.
+    ///
+    /// It is used for optimistic locking, so concurrent updates don't silently overwrite each other.
+    /// It defaults to `0` when missing from the JSON object, as it is ignored on creation.
+    #[serde(default)]
+    #[builder(default)]
+    pub version: i32,
+    /// Whether the question is open to new answers, see [QuestionStatus].
+    ///
+    /// It defaults to [QuestionStatus::Open] when missing from the JSON object, and is changed only by
+    /// closing and reopening the question.
+    #[serde(default)]
+    #[builder(default)]
+    #[schema(read_only)]
+    pub status: QuestionStatus,
+    /// The number of answers to the question.
+    ///
+    /// It is loaded by the [Store](crate::store::Store) only when the questions are listed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(read_only)]
+    pub answer_count: Option<i64>,
+    /// The score of the question, from the votes of the accounts, see [Score](crate::types::vote::Score).
+    ///
+    /// It is kept with the question by the [Store](crate::store::Store), and changed whenever the question is voted on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(read_only)]
+    pub score: Option<i64>,
+    /// The author of the question.
+    ///
+    /// It is loaded by the [Store](crate::store::Store) only when the question is read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(read_only)]
+    pub author: Option<Author>,
+}
+
+/// Request body for asking a question.
+///
+/// Only the fields set by the author are accepted, any other field is rejected, so the clients
+/// cannot send the id or the status of the question. The title and the content are normalized, and
+/// rejected if they are empty, see [sanitize](crate::types::sanitize).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NewQuestion {
+    /// The title of the question.
+    #[schema(min_length = 1)]
+    pub title: String,
+    /// The content of the question, in markdown.
+    #[schema(min_length = 1)]
+    pub content: String,
+    /// The tags of the question.
+    ///
+    /// They are lowercased, trimmed and deduplicated, see [sanitize::tags](crate::types::sanitize::tags).
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Request body for updating a question.
+///
+/// It replaces the title, the content and the tags of the question, and is validated like [NewQuestion].
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateQuestion {
+    /// The new title of the question.
+    #[schema(min_length = 1)]
+    pub title: String,
+    /// The new content of the question, in markdown.
+    #[schema(min_length = 1)]
+    pub content: String,
+    /// The new tags of the question.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Query parameters of the requests asking a new question.
+///
+/// The parameters are extracted with [NewQuestionParams::extract], generated by the [QueryParams] derive.
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy, Default)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct NewQuestionParams {
+    /// Whether the question is asked even if similar questions were already asked
+    #[query(default = false)]
+    pub force: bool,
+}
+
+/// Related resources embedded in the question detail, chosen with the `include` query parameter.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QuestionInclude {
+    /// The answers to the question, in the default order of the answer listings.
+    Answers,
+}
+
+impl FromStr for QuestionInclude {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "answers" => Ok(Self::Answers),
+            _ => Err(format!("unknown include \"{value}\", expected \"answers\"")),
+        }
+    }
+}
+
+/// Query parameters of the requests reading a question.
+///
+/// The parameters are extracted with [QuestionParams::extract], generated by the [QueryParams] derive.
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy, Default)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct QuestionParams {
+    /// The related resources embedded in the question
+    pub include: Option<QuestionInclude>,
+    /// The format of the content of the question, and of the embedded answers
+    pub format: Option<ContentFormat>,
+}
+
+/// Represents a question, with the related resources requested with [QuestionParams].
+///
+/// It is serialized as the question itself, with the related resources as additional fields.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuestionDetail {
+    /// The question.
+    #[serde(flatten)]
+    pub question: Question,
+    /// The answers to the question, if they were requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answers: Option<Vec<Answer>>,
+}
+
+/// Represents the status of a question.
+///
+/// It is serialized as `"open"`, `"closed"`, or `{"duplicate_of": 1}` for the questions closed as
+/// duplicates of another question. Only the open questions can be answered.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionStatus {
+    /// The question can be answered.
+    #[default]
+    Open,
+    /// The question was closed, and cannot be answered.
+    Closed,
+    /// The question was closed as a duplicate of the question with the given id.
+    DuplicateOf(QuestionId),
+}
+
+impl QuestionStatus {
+    /// Returns the value stored in the `status` column of the table `questions`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuestionStatus::Open => "open",
+            QuestionStatus::Closed => "closed",
+            QuestionStatus::DuplicateOf(_) => "duplicate_of",
+        }
+    }
+
+    /// Returns the value stored in the `duplicate_of` column of the table `questions`.
+    pub fn duplicate_of(&self) -> Option<QuestionId> {
+        match self {
+            QuestionStatus::DuplicateOf(question_id) => Some(*question_id),
+            _ => None,
+        }
+    }
+
+    /// Reads the status from the `status` and `duplicate_of` columns of the table `questions`.
+    ///
+    /// A duplicate of a deleted question, whose `duplicate_of` column was cleared, is just closed.
+    fn from_columns(status: &str, duplicate_of: Option<i32>) -> Result<Self, sqlx::Error> {
+        match (status, duplicate_of) {
+            ("open", _) => Ok(QuestionStatus::Open),
+            ("closed", _) | ("duplicate_of", None) => Ok(QuestionStatus::Closed),
+            ("duplicate_of", Some(question_id)) => Ok(QuestionStatus::DuplicateOf(QuestionId(question_id))),
+            (status, _) => Err(sqlx::Error::ColumnDecode {
+                index: "status".to_string(),
+                source: format!("invalid question status: {status:?}").into(),
+            }),
+        }
+    }
+}
+
+/// Request body for closing a question.
+///
+/// The question is closed as a duplicate of another question if `duplicate_of` is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CloseQuestion {
+    /// The id of the question this question duplicates, if any.
+    #[serde(default)]
+    pub duplicate_of: Option<QuestionId>,
+}
+
+impl TryFrom<PgRow> for Question {
+    type Error = sqlx::Error;
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(QuestionId(value.try_get("id")?)),
+            title: value.try_get("title")?,
+            content: value.try_get("content")?,
+            content_html: None,
+            tags: match value.try_get("tags") {
+                Ok(tags) => tags,
+                Err(sqlx::Error::ColumnNotFound(_)) => None,
+                Err(error) => return Err(error),
+            },
+            version: value.try_get("version")?,
+            status: QuestionStatus::from_columns(value.try_get("status")?, value.try_get("duplicate_of")?)?,
+            answer_count: match value.try_get("answer_count") {
+                Ok(answer_count) => Some(answer_count),
+                Err(sqlx::Error::ColumnNotFound(_)) => None,
+                Err(error) => return Err(error),
+            },
+            score: match value.try_get("score") {
+                Ok(score) => Some(score),
+                Err(sqlx::Error::ColumnNotFound(_)) => None,
+                Err(error) => return Err(error),
+            },
+            author: None,
+        })
+    }
+}
+
+/// Represents a previous version of a question.
+///
+/// A revision is recorded by the [Store](crate::store::Store) whenever the question is updated,
+/// with the title, content and tags the question had before the update.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuestionRevision {
+    /// The id of the question.
+    pub question_id: QuestionId,
+    /// The version of the question this revision records.
+    pub version: i32,
+    /// The title of the question at this version.
+    pub title: String,
+    /// The content of the question at this version.
+    pub content: String,
+    /// The tags of the question at this version.
+    pub tags: Option<Vec<String>>,
+    /// The time this version was replaced by the next one.
+    #[serde(with = "crate::types::timestamp")]
+    pub replaced_on: DateTime<Utc>,
+}
+
+impl TryFrom<PgRow> for QuestionRevision {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            question_id: QuestionId(row.try_get("question_id")?),
+            version: row.try_get("version")?,
+            title: row.try_get("title")?,
+            content: row.try_get("content")?,
+            tags: row.try_get("tags")?,
+            replaced_on: row.try_get("replaced_on")?,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Represents the daily limits on the contributions of every account.
+///
+/// The days are counted in UTC, and a limit that is not set is not enforced.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quotas {
+    /// The number of questions an account can ask a day.
+    pub questions_per_day: Option<u32>,
+    /// The number of answers an account can post a day.
+    pub answers_per_day: Option<u32>,
+}
+
+/// Represents a daily limit reached by an account.
+///
+/// It is sent as the body of the responses rejecting the contributions over the limit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QuotaExceeded {
+    /// The kind of the limited contributions, `questions` or `answers`.
+    pub resource: String,
+    /// The number of contributions allowed a day.
+    pub limit: u32,
+    /// The number of contributions made today.
+    pub usage: i64,
+    /// The time the usage is reset, the next midnight in UTC.
+    #[serde(with = "crate::types::timestamp")]
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Represents the kind of the contributions with a daily limit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Contribution {
+    /// A question, limited by the `questions_per_day` of the quotas.
+    Question,
+    /// An answer, limited by the `answers_per_day` of the quotas.
+    Answer,
+}
+
+impl Contribution {
+    /// Returns the kind of the contribution, as it is recorded in the table `contributions`.
+    pub fn kind(self) -> &'static str {
+        match self {
+            Contribution::Question => "question",
+            Contribution::Answer => "answer",
+        }
+    }
+}
+
+impl Quotas {
+    /// Returns the daily limit on the contributions of the kind, or `None` if it is not set.
+    pub fn limit(&self, contribution: Contribution) -> Option<u32> {
+        match contribution {
+            Contribution::Question => self.questions_per_day,
+            Contribution::Answer => self.answers_per_day,
+        }
+    }
+
+    /// Checks that another contribution of the kind is allowed with the given usage.
+    ///
+    /// # Arguments
+    /// - `contribution`: The kind of the contribution.
+    /// - `usage`: The number of contributions of the kind made today, see [start_of_day].
+    /// - `now`: The current time, from which the reset time is computed.
+    ///
+    /// # Returns
+    /// - The [QuotaExceeded] if the usage reached the limit.
+    pub fn check(&self, contribution: Contribution, usage: i64, now: DateTime<Utc>) -> Result<(), QuotaExceeded> {
+        let Some(limit) = self.limit(contribution) else {
+            return Ok(());
+        };
+        if usage < i64::from(limit) {
+            return Ok(());
+        }
+        Err(QuotaExceeded {
+            resource: format!("{}s", contribution.kind()),
+            limit,
+            usage,
+            resets_at: start_of_day(now) + Duration::try_days(1).unwrap(),
+        })
+    }
+}
+
+/// Returns the last midnight in UTC, from which the daily usage is counted.
+pub fn start_of_day(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive().and_time(NaiveTime::MIN).and_utc()
+}
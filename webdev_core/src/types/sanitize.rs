@@ -0,0 +1,172 @@
+//! Normalization of the text posted by the users, and the limits on its length.
+//!
+//! The titles, the content and the tags are normalized the same way by every handler creating or
+//! updating them, before they are censored and stored:
+//! - the text is normalized to the Unicode NFC form, so the same text is always stored the same way
+//! - the control characters are removed, except for the line breaks and the tabs in the content
+//! - the whitespace is trimmed, and collapsed into single spaces in the titles and the tags, while
+//!   the content keeps its lines, without the trailing whitespace and with at most one empty line
+//!   in a row
+//!
+//! The tags are also lowercased, and may contain only the letters, the digits and [TAG_PUNCTUATION].
+//!
+//! The normalized text is then checked against the [Limits], in bytes of UTF-8.
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::error::{FieldError, ServiceError};
+
+/// The limits on the text posted by the users, the lengths in bytes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum length of a title.
+    pub title_bytes: usize,
+    /// The maximum length of the content of a question or an answer.
+    pub content_bytes: usize,
+    /// The maximum length of a tag.
+    pub tag_bytes: usize,
+    /// The maximum number of tags of a question.
+    pub tag_count: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            // The titles are stored as `VARCHAR(255)`
+            title_bytes: 255,
+            content_bytes: 64 * 1024,
+            tag_bytes: 64,
+            tag_count: 5,
+        }
+    }
+}
+
+/// Normalizes a single line of text, e.g. a title or a tag.
+///
+/// ```
+/// use webdev_core::types::sanitize::single_line;
+///
+/// assert_eq!(single_line("  Cafe\u{301}\tand \u{7}\n tea "), "Café and tea");
+/// ```
+pub fn single_line(text: &str) -> String {
+    let text = text
+        .nfc()
+        .filter(|char| char.is_whitespace() || !char.is_control())
+        .collect::<String>();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Normalizes a text of many lines, e.g. the content of a question.
+///
+/// ```
+/// use webdev_core::types::sanitize::multi_line;
+///
+/// assert_eq!(multi_line("\n  First  \r\n\n\n\n\tSecond\u{0}\n"), "First\n\n\tSecond");
+/// ```
+pub fn multi_line(text: &str) -> String {
+    let text = text
+        .replace("\r\n", "\n")
+        .nfc()
+        .map(|char| if char == '\r' { '\n' } else { char })
+        .filter(|&char| matches!(char, '\n' | '\t') || !char.is_control())
+        .collect::<String>();
+
+    let mut lines = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|last: &&str| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    if lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+    // The leading whitespace of the first line is not indentation
+    lines.join("\n").trim_start().to_string()
+}
+
+/// Normalizes a title, and checks that it is not empty, and not longer than the limit.
+pub fn title(title: &str, limits: &Limits) -> Result<String, ServiceError> {
+    let title = single_line(title);
+    if title.is_empty() {
+        return Err(ServiceError::ValidationError("title is empty".to_string()));
+    }
+    check_length("title", title, limits.title_bytes)
+}
+
+/// Normalizes the content of a question or an answer, and checks that it is not empty, and not
+/// longer than the limit.
+pub fn content(content: &str, limits: &Limits) -> Result<String, ServiceError> {
+    let content = multi_line(content);
+    if content.is_empty() {
+        return Err(ServiceError::ValidationError("content is empty".to_string()));
+    }
+    check_length("content", content, limits.content_bytes)
+}
+
+/// The characters allowed in the tags, besides the letters and the digits.
+pub const TAG_PUNCTUATION: &str = " -._+#";
+
+/// Normalizes the name of a tag as a single line, lowercased, so ` Rust ` and `rust` are the same tag.
+///
+/// ```
+/// use webdev_core::types::sanitize::tag_name;
+///
+/// assert_eq!(tag_name(" Web\n Dev "), "web dev");
+/// ```
+pub fn tag_name(tag: &str) -> String {
+    single_line(tag).to_lowercase()
+}
+
+/// Normalizes the tags, and checks them against the limits.
+///
+/// The tags are normalized by [tag_name].
+/// The tags that are empty once normalized are dropped, as are the repeated ones. A tag with other
+/// characters than the letters, the digits and [TAG_PUNCTUATION], or longer than the limit, is
+/// rejected with a [FieldError] naming it, e.g. `tags[1]`, as are too many tags.
+///
+/// ```
+/// use webdev_core::types::sanitize::{tags, Limits};
+///
+/// let tags = tags(Some(vec![" C++ ".into(), "c++".into(), "Web  Dev".into()]), &Limits::default());
+/// assert_eq!(tags.unwrap(), Some(vec!["c++".to_string(), "web dev".to_string()]));
+/// ```
+pub fn tags(tags: Option<Vec<String>>, limits: &Limits) -> Result<Option<Vec<String>>, ServiceError> {
+    let Some(tags) = tags else {
+        return Ok(None);
+    };
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for (index, tag) in tags.into_iter().enumerate() {
+        let field = format!("tags[{index}]");
+        let tag = tag_name(&tag);
+        if let Some(char) = tag
+            .chars()
+            .find(|&char| !char.is_alphanumeric() && !TAG_PUNCTUATION.contains(char))
+        {
+            let message = format!("tag contains {char:?}, only letters, digits and {TAG_PUNCTUATION:?} are allowed");
+            return Err(ServiceError::InvalidField(FieldError::new(field, message)));
+        }
+        if tag.len() > limits.tag_bytes {
+            let message = format!("tag is longer than {} bytes", limits.tag_bytes);
+            return Err(ServiceError::InvalidField(FieldError::new(field, message)));
+        }
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.len() > limits.tag_count {
+        let message = format!("question has more than {} tags", limits.tag_count);
+        return Err(ServiceError::InvalidField(FieldError::new("tags", message)));
+    }
+    Ok(Some(normalized))
+}
+
+/// Checks that the text is not longer than the limit, in bytes.
+fn check_length(name: &str, text: String, limit: usize) -> Result<String, ServiceError> {
+    if text.len() > limit {
+        return Err(ServiceError::ValidationError(format!(
+            "{name} is longer than {limit} bytes"
+        )));
+    }
+    Ok(text)
+}
@@ -0,0 +1,114 @@
+use std::str::FromStr;
+
+use chrono::TimeDelta;
+use macros::{DbObjectId, QueryParams};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
+
+/// Represents a tag id.
+///
+/// `TagId` is a wrapper around an i32. It represents the id of a tag.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct TagId(pub i32);
+
+/// Represents a tag, with the number of the questions using it.
+///
+/// The tags are created by the [Store](crate::store::Store) when the questions using them are asked
+/// or updated, and kept when the questions stop using them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Tag {
+    /// The id of the tag.
+    pub id: TagId,
+    /// The name of the tag.
+    pub name: String,
+    /// The number of the questions using the tag.
+    pub question_count: i64,
+}
+
+impl TryFrom<PgRow> for Tag {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: TagId(row.try_get("id")?),
+            name: row.try_get("name")?,
+            question_count: row.try_get("question_count")?,
+        })
+    }
+}
+
+/// Represents the recent window the popular tags are counted over, e.g. `12h`, `7d` or `4w`.
+///
+/// The window is a whole number of hours, days or weeks, at least an hour and at most
+/// [TagWindow::MAX_HOURS] hours long.
+///
+/// ```
+/// use webdev_core::types::tag::TagWindow;
+///
+/// assert_eq!("7d".parse::<TagWindow>(), Ok(TagWindow::DEFAULT));
+/// assert_eq!("1w".parse::<TagWindow>(), Ok(TagWindow::DEFAULT));
+/// assert!("0h".parse::<TagWindow>().is_err());
+/// assert!("7 days".parse::<TagWindow>().is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TagWindow {
+    hours: u32,
+}
+
+impl TagWindow {
+    /// The window the popular tags are counted over, unless another one is requested.
+    pub const DEFAULT: TagWindow = TagWindow { hours: 7 * 24 };
+    /// The longest window, a year.
+    pub const MAX_HOURS: u32 = 365 * 24;
+
+    /// Returns the length of the window.
+    pub fn duration(&self) -> TimeDelta {
+        TimeDelta::try_hours(i64::from(self.hours)).expect("the window is at most a year")
+    }
+}
+
+impl Default for TagWindow {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl FromStr for TagWindow {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid window \"{value}\", expected a number of hours, days or weeks, e.g. \"7d\"");
+        let unit = value.chars().last().ok_or_else(invalid)?;
+        let count: u32 = value[..value.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+        let hours = match unit {
+            'h' => Some(count),
+            'd' => count.checked_mul(24),
+            'w' => count.checked_mul(7 * 24),
+            _ => return Err(invalid()),
+        };
+        match hours {
+            Some(hours @ 1..=Self::MAX_HOURS) => Ok(Self { hours }),
+            _ => Err(format!(
+                "window \"{value}\" must be between 1h and {}d",
+                Self::MAX_HOURS / 24
+            )),
+        }
+    }
+}
+
+/// Query parameters of the popular tags.
+///
+/// The parameters are extracted with [PopularTagsParams::extract], generated by the [QueryParams] derive.
+#[derive(QueryParams, IntoParams, Debug, Clone, Copy, Default)]
+#[into_params(parameter_in = Query)]
+#[query(deny_unknown)]
+pub struct PopularTagsParams {
+    /// The recent window the questions using the tags are counted over, `7d` by default
+    #[query(default = TagWindow::DEFAULT)]
+    #[param(value_type = String, example = "7d")]
+    pub window: TagWindow,
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+
+/// Serializes a timestamp as an RFC 3339 string in UTC, e.g. `2024-03-18T12:00:00Z`.
+///
+/// Intended to be used with `#[serde(with = "crate::types::timestamp")]`.
+pub fn serialize<S>(datetime: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&datetime.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Deserializes a timestamp from either an RFC 3339 string or an integer number of seconds
+/// since the Unix epoch.
+///
+/// Intended to be used with `#[serde(with = "crate::types::timestamp")]`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(TimestampVisitor)
+}
+
+/// Parses a timestamp from either of the formats accepted by [deserialize], e.g. from a query parameter.
+///
+/// ```
+/// use webdev_core::types::timestamp;
+///
+/// assert_eq!(timestamp::parse("2024-03-18T12:00:00Z"), timestamp::parse("1710763200"));
+/// assert!(timestamp::parse("yesterday").is_err());
+/// ```
+pub fn parse(value: &str) -> Result<DateTime<Utc>, String> {
+    match value.parse::<i64>() {
+        Ok(seconds) => TimestampVisitor.visit_i64(seconds),
+        Err(_) => TimestampVisitor.visit_str(value),
+    }
+    .map_err(|error: de::value::Error| error.to_string())
+}
+
+/// Visitor accepting both of the timestamp formats supported on input.
+struct TimestampVisitor;
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = DateTime<Utc>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an RFC 3339 timestamp or an integer number of seconds since the Unix epoch")
+    }
+
+    fn visit_i64<E: de::Error>(self, seconds: i64) -> Result<Self::Value, E> {
+        Utc.timestamp_opt(seconds, 0)
+            .single()
+            .ok_or_else(|| E::custom(format!("timestamp out of range: {seconds}")))
+    }
+
+    fn visit_u64<E: de::Error>(self, seconds: u64) -> Result<Self::Value, E> {
+        let seconds = i64::try_from(seconds).map_err(|_| E::custom(format!("timestamp out of range: {seconds}")))?;
+        self.visit_i64(seconds)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|datetime| datetime.with_timezone(&Utc))
+            .map_err(E::custom)
+    }
+}
+
+/// Same as the [timestamp](self) functions, for the optional timestamps, which are `null` when missing.
+///
+/// Intended to be used with `#[serde(default, with = "crate::types::timestamp::option")]`, so the
+/// missing fields are deserialized as `None` too.
+pub mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    /// Serializes the timestamp like [serialize](super::serialize), or `None` as `null`.
+    pub fn serialize<S>(datetime: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match datetime {
+            Some(datetime) => super::serialize(datetime, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes the timestamp like [deserialize](super::deserialize), or `null` as `None`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// Wrapper deserializing the timestamp inside the `Option`.
+        #[derive(Deserialize)]
+        struct Timestamp(#[serde(with = "super")] DateTime<Utc>);
+
+        Ok(Option::<Timestamp>::deserialize(deserializer)?.map(|Timestamp(datetime)| datetime))
+    }
+}
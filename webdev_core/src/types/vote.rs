@@ -0,0 +1,56 @@
+//! Types used for the votes on the questions and the answers.
+//!
+//! Every account has at most one vote on a question or an answer, either up or down. The score of
+//! the item is the number of the votes up, minus the number of the votes down.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Direction of a vote.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteDirection {
+    /// The vote adds one to the score.
+    Up,
+    /// The vote subtracts one from the score.
+    Down,
+}
+
+impl VoteDirection {
+    /// Returns the value stored in the `value` column of the votes, which is added to the score.
+    pub fn value(&self) -> i16 {
+        match self {
+            VoteDirection::Up => 1,
+            VoteDirection::Down => -1,
+        }
+    }
+
+    /// Reads the direction from the `value` column of the votes.
+    pub fn from_value(value: i16) -> Option<Self> {
+        match value {
+            1 => Some(VoteDirection::Up),
+            -1 => Some(VoteDirection::Down),
+            _ => None,
+        }
+    }
+}
+
+/// Request body for voting.
+///
+/// Voting again in the same direction retracts the vote, and voting in the other direction
+/// changes it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Vote {
+    /// The direction of the vote.
+    pub direction: VoteDirection,
+}
+
+/// Represents the score of a voted item, with the vote of the account that voted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Score {
+    /// The number of the votes up, minus the number of the votes down.
+    pub score: i64,
+    /// The vote of the account, `null` if the account retracted it.
+    pub vote: Option<VoteDirection>,
+}
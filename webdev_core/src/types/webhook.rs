@@ -0,0 +1,53 @@
+use macros::DbObjectId;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+/// Represents a webhook id.
+///
+/// `WebhookId` is a wrapper around an i32. It represents the id of a webhook subscription.
+/// It is serialized as a plain integer, e.g. `{"id": 1}`.
+#[derive(DbObjectId, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct WebhookId(pub i32);
+
+/// Represents a webhook subscription.
+///
+/// The events named in `events` are delivered to `url` as JSON encoded `POST` requests,
+/// signed with the `secret` of the subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Webhook {
+    /// The id of the webhook.
+    ///
+    /// Is an `Option` because the id is not known when registering a new webhook.
+    pub id: Option<WebhookId>,
+    /// The URL the events are delivered to.
+    pub url: String,
+    /// The names of the events delivered to the URL, e.g. `question_created`.
+    pub events: Vec<String>,
+    /// The secret used to sign the deliveries.
+    ///
+    /// It is never sent back to the client.
+    #[serde(skip_serializing)]
+    #[schema(write_only)]
+    pub secret: String,
+}
+
+impl Webhook {
+    /// Names of the events that webhooks can subscribe to.
+    pub const EVENTS: [&'static str; 2] = ["question_created", "answer_created"];
+}
+
+impl TryFrom<PgRow> for Webhook {
+    type Error = sqlx::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: Some(WebhookId(row.try_get("id")?)),
+            url: row.try_get("url")?,
+            events: row.try_get("events")?,
+            secret: row.try_get("secret")?,
+        })
+    }
+}
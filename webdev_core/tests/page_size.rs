@@ -0,0 +1,28 @@
+//! Tests for the bounding of the paginations by the maximum page size of the store.
+use std::collections::HashMap;
+
+use webdev_core::error::ServiceError;
+use webdev_core::types::pagination::{Pagination, PaginationParsingError};
+
+#[test]
+fn limits_above_the_maximum_page_size_are_rejected() {
+    let limit = |limit: i64| Pagination::extract(&HashMap::from([("limit".to_string(), limit.to_string())])).unwrap();
+
+    assert_eq!(limit(50).bounded(50).unwrap().limit, Some(50));
+    let error = limit(51).bounded(50).unwrap_err();
+    assert!(matches!(
+        error,
+        PaginationParsingError::Invalid { parameter: "limit", .. }
+    ));
+    assert!(error.to_string().contains("maximum page size of 50"), "{error}");
+    assert_eq!(ServiceError::from(error).status_code().as_u16(), 400);
+}
+
+#[test]
+fn a_missing_limit_becomes_the_maximum_page_size() {
+    let pagination = Pagination::extract(&HashMap::from([("offset".to_string(), "10".to_string())])).unwrap();
+    assert_eq!(pagination.limit, None);
+
+    let pagination = pagination.bounded(50).unwrap();
+    assert_eq!((pagination.offset, pagination.limit), (10, Some(50)));
+}
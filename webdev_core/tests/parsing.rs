@@ -0,0 +1,103 @@
+//! Property tests for the parsing of the values taken from the requests: the pagination query
+//! parameters and the ids in the paths.
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+
+use webdev_core::error::ServiceError;
+use webdev_core::types::pagination::{Pagination, PaginationParsingError};
+use webdev_core::types::question::{ParseQuestionIdError, QuestionId};
+
+fn query(params: &[(&str, String)]) -> HashMap<String, String> {
+    params
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn valid_pagination_is_extracted(offset in 0..=i64::MAX, limit in proptest::option::of(0..=Pagination::MAX_LIMIT)) {
+        let mut params = query(&[("offset", offset.to_string())]);
+        if let Some(limit) = limit {
+            params.insert("limit".to_string(), limit.to_string());
+        }
+
+        let pagination = Pagination::extract(&params).unwrap();
+        prop_assert_eq!(pagination.offset, offset);
+        prop_assert_eq!(pagination.limit, limit);
+        prop_assert!(pagination.validate().is_ok());
+    }
+
+    #[test]
+    fn negative_offset_is_rejected(offset in i64::MIN..0) {
+        let error = Pagination::extract(&query(&[("offset", offset.to_string())])).unwrap_err();
+        let is_offset_error = matches!(error, PaginationParsingError::Invalid { parameter: "offset", .. });
+        prop_assert!(is_offset_error);
+        prop_assert_eq!(ServiceError::from(error).status_code().as_u16(), 400);
+    }
+
+    #[test]
+    fn negative_limit_is_rejected(limit in i64::MIN..0) {
+        let error = Pagination::extract(&query(&[("limit", limit.to_string())])).unwrap_err();
+        let is_limit_error = matches!(error, PaginationParsingError::Invalid { parameter: "limit", .. });
+        prop_assert!(is_limit_error);
+        let pagination = Pagination { offset: 0, limit: Some(limit) };
+        prop_assert!(pagination.validate().is_err());
+    }
+
+    #[test]
+    fn limit_above_the_maximum_page_size_is_rejected(limit in Pagination::MAX_LIMIT + 1..=i64::MAX) {
+        let pagination = Pagination::extract(&query(&[("limit", limit.to_string())])).unwrap();
+        let error = pagination.bounded(Pagination::MAX_LIMIT).unwrap_err();
+        let is_limit_error = matches!(error, PaginationParsingError::Invalid { parameter: "limit", .. });
+        prop_assert!(is_limit_error);
+    }
+
+    #[test]
+    fn overflowing_values_are_rejected(digits in "[1-9][0-9]{19,30}", parameter in prop_oneof![Just("offset"), Just("limit")]) {
+        let error = Pagination::extract(&query(&[(parameter, digits)])).unwrap_err();
+        let is_invalid = matches!(error, PaginationParsingError::Invalid { .. });
+        prop_assert!(is_invalid);
+    }
+
+    #[test]
+    fn unknown_parameters_are_rejected(parameter in "[a-z_]{1,12}", value in ".*") {
+        prop_assume!(!Pagination::PARAMETERS.contains(&parameter.as_str()));
+
+        let error = Pagination::extract(&query(&[(&parameter, value)])).unwrap_err();
+        prop_assert_eq!(error, PaginationParsingError::Unknown { parameter });
+    }
+
+    #[test]
+    fn arbitrary_pagination_never_panics(offset in ".*", limit in ".*") {
+        let params = query(&[("offset", offset), ("limit", limit)]);
+        if let Ok(pagination) = Pagination::extract(&params) {
+            prop_assert!(pagination.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn valid_id_is_parsed(id in 0..=i32::MAX) {
+        let parsed: QuestionId = id.to_string().parse().unwrap();
+        prop_assert_eq!(parsed.0, id);
+    }
+
+    #[test]
+    fn negative_id_is_rejected(id in i32::MIN..0) {
+        let id = id.to_string();
+        prop_assert_eq!(id.parse::<QuestionId>().unwrap_err(), ParseQuestionIdError::Invalid(id));
+    }
+
+    #[test]
+    fn malformed_id_is_rejected(id in ".+") {
+        prop_assume!(id.parse::<u32>().map_or(true, |id| id > i32::MAX as u32) || !id.bytes().all(|b| b.is_ascii_digit()));
+
+        prop_assert_eq!(id.parse::<QuestionId>().unwrap_err(), ParseQuestionIdError::Invalid(id));
+    }
+}
+
+#[test]
+fn empty_id_is_rejected() {
+    assert_eq!("".parse::<QuestionId>().unwrap_err(), ParseQuestionIdError::Empty);
+}
@@ -0,0 +1,72 @@
+//! Tests of the [RedisCache], against the Redis server at `TEST_REDIS_URL`.
+//!
+//! The tests are only built with the `redis-cache` feature, e.g.
+//! `TEST_REDIS_URL=redis://localhost:6379 cargo test -p webdev_core --features redis-cache`.
+#![cfg(feature = "redis-cache")]
+
+use std::time::Duration;
+
+use webdev_core::cache::RedisCache;
+use webdev_core::types::pagination::Pagination;
+use webdev_core::types::question::{Question, QuestionId};
+
+/// Name of the environment variable with the URL of the Redis server the tests run against.
+const REDIS_URL_VAR: &str = "TEST_REDIS_URL";
+
+/// Connects to the Redis server the tests run against.
+async fn cache() -> RedisCache {
+    let url = std::env::var(REDIS_URL_VAR)
+        .unwrap_or_else(|_| panic!("{REDIS_URL_VAR} is not set: set it to the URL of a Redis server"));
+    RedisCache::connect(&url, Duration::from_secs(60))
+        .await
+        .expect("cannot connect to the test Redis server")
+}
+
+/// Returns a question with an id no other run of the tests uses, as the cached values outlive the tests.
+fn a_question() -> Question {
+    Question::builder()
+        .id(QuestionId(rand::random::<i32>().abs()))
+        .title("How do I share a cache between instances?")
+        .content("With Redis.")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn the_questions_are_cached_until_invalidated() {
+    let cache = cache().await;
+    let question = a_question();
+    let question_id = question.id.unwrap();
+
+    assert!(cache.get_question(question_id).await.is_none());
+    cache.set_question(&question).await;
+    let cached = cache.get_question(question_id).await.unwrap();
+    assert_eq!(cached.title, question.title);
+
+    cache.invalidate_question(question_id).await;
+    assert!(cache.get_question(question_id).await.is_none());
+}
+
+#[tokio::test]
+async fn the_pages_are_cached_until_all_of_them_are_invalidated() {
+    let cache = cache().await;
+    let questions = vec![a_question(), a_question()];
+    let pagination = Pagination {
+        offset: rand::random::<u16>().into(),
+        limit: Some(2),
+    };
+
+    cache.set_page(pagination, &questions).await;
+    let cached = cache.get_page(pagination).await.unwrap();
+    assert_eq!(cached.len(), 2);
+    assert_eq!(cached[0].id, questions[0].id);
+
+    cache.invalidate_pages().await;
+    assert!(cache.get_page(pagination).await.is_none());
+}
+
+#[tokio::test]
+async fn the_connection_to_a_missing_server_fails() {
+    let result = RedisCache::connect("redis://127.0.0.1:1", Duration::from_secs(60)).await;
+    assert!(result.is_err());
+}
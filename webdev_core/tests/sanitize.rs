@@ -0,0 +1,83 @@
+//! Property tests for the normalization of the text posted by the users.
+use proptest::prelude::*;
+
+use webdev_core::error::{FieldError, ServiceError};
+use webdev_core::types::sanitize::{self, Limits};
+
+proptest! {
+    #[test]
+    fn normalization_is_idempotent(text in any::<String>()) {
+        let line = sanitize::single_line(&text);
+        prop_assert_eq!(sanitize::single_line(&line), line.clone());
+        prop_assert!(!line.chars().any(|char| char.is_control()));
+
+        let lines = sanitize::multi_line(&text);
+        prop_assert_eq!(sanitize::multi_line(&lines), lines.clone());
+        prop_assert!(!lines.chars().any(|char| char.is_control() && char != '\n' && char != '\t'));
+    }
+
+    #[test]
+    fn normalized_text_is_within_the_limits(text in any::<String>(), limit in 1..64usize) {
+        let limits = Limits {
+            title_bytes: limit,
+            content_bytes: limit,
+            tag_bytes: limit,
+            tag_count: 1,
+        };
+
+        match sanitize::title(&text, &limits) {
+            Ok(title) => prop_assert!(!title.is_empty() && title.len() <= limit),
+            Err(error) => prop_assert!(matches!(error, ServiceError::ValidationError(_))),
+        }
+        match sanitize::content(&text, &limits) {
+            Ok(content) => prop_assert!(!content.is_empty() && content.len() <= limit),
+            Err(error) => prop_assert!(matches!(error, ServiceError::ValidationError(_))),
+        }
+        match sanitize::tags(Some(vec![text.clone()]), &limits) {
+            Ok(tags) => prop_assert!(tags.unwrap().iter().all(|tag| tag.len() <= limit)),
+            Err(error) => prop_assert!(matches!(error, ServiceError::InvalidField(_))),
+        }
+    }
+}
+
+#[test]
+fn tags_are_normalized_and_deduplicated() {
+    let tags = vec![
+        " rust ".to_string(),
+        "\t".to_string(),
+        "Rust".to_string(),
+        "web  dev".to_string(),
+    ];
+    let tags = sanitize::tags(Some(tags), &Limits::default()).unwrap();
+    assert_eq!(tags, Some(vec!["rust".to_string(), "web dev".to_string()]));
+}
+
+#[test]
+fn too_long_tags_are_rejected() {
+    let limits = Limits {
+        tag_bytes: 4,
+        ..Limits::default()
+    };
+    let error = sanitize::tags(Some(vec!["tokio".to_string()]), &limits).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        ServiceError::InvalidField(FieldError::new("tags[0]", "tag is longer than 4 bytes")).to_string()
+    );
+}
+
+#[test]
+fn invalid_tags_are_rejected_by_their_field() {
+    let tags = vec!["rust".to_string(), "<script>".to_string()];
+    let error = sanitize::tags(Some(tags), &Limits::default()).unwrap_err();
+    assert!(matches!(error, ServiceError::InvalidField(FieldError { field, .. }) if field == "tags[1]"));
+
+    let limits = Limits {
+        tag_count: 2,
+        ..Limits::default()
+    };
+    let tags = vec!["rust".to_string(), "RUST".to_string(), "warp".to_string()];
+    assert!(sanitize::tags(Some(tags), &limits).is_ok());
+    let tags = vec!["rust".to_string(), "warp".to_string(), "tokio".to_string()];
+    let error = sanitize::tags(Some(tags), &limits).unwrap_err();
+    assert!(matches!(error, ServiceError::InvalidField(FieldError { field, .. }) if field == "tags"));
+}
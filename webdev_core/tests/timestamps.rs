@@ -0,0 +1,74 @@
+//! Property tests for the format of the timestamps in the bodies, see `types::timestamp`.
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use proptest::prelude::*;
+use serde_json::json;
+
+use webdev_core::types::authentication::AccountId;
+use webdev_core::types::job::{Job, JobId, JobStatus};
+use webdev_core::types::moderation::AccountBan;
+
+/// The seconds since the Unix epoch of the timestamps of the tests, from 1970 to the year 9999.
+const SECONDS: std::ops::Range<i64> = 0..253_402_300_800;
+
+fn timestamp(seconds: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(seconds, 0).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn timestamps_round_trip_as_rfc_3339_in_utc(until in proptest::option::of(SECONDS)) {
+        let ban = AccountBan {
+            account_id: AccountId(1),
+            until: until.map(timestamp),
+            reason: "spam".to_string(),
+        };
+
+        let serialized = serde_json::to_value(&ban).unwrap();
+        let expected = ban.until.map(|until| until.to_rfc3339_opts(SecondsFormat::Secs, true));
+        prop_assert_eq!(&serialized["until"], &json!(expected));
+        prop_assert_eq!(serde_json::from_value::<AccountBan>(serialized).unwrap(), ban);
+    }
+
+    #[test]
+    fn the_run_time_of_the_jobs_round_trips_as_rfc_3339_in_utc(run_at in SECONDS) {
+        let job = Job {
+            id: JobId(1),
+            kind: "deliver_webhook".to_string(),
+            payload: json!({}),
+            status: JobStatus::Pending,
+            attempts: 0,
+            max_attempts: 5,
+            last_error: None,
+            run_at: timestamp(run_at),
+        };
+
+        let serialized = serde_json::to_value(&job).unwrap();
+        prop_assert_eq!(&serialized["run_at"], &json!(job.run_at.to_rfc3339_opts(SecondsFormat::Secs, true)));
+        prop_assert_eq!(serde_json::from_value::<Job>(serialized).unwrap().run_at, job.run_at);
+    }
+
+    #[test]
+    fn timestamps_are_read_from_the_seconds_since_the_epoch(seconds in SECONDS) {
+        let ban = json!({ "account_id": 1, "until": seconds, "reason": "spam" });
+        let ban = serde_json::from_value::<AccountBan>(ban).unwrap();
+        prop_assert_eq!(ban.until, Some(timestamp(seconds)));
+    }
+}
+
+#[test]
+fn missing_optional_timestamps_are_none() {
+    for ban in [
+        json!({ "account_id": 1, "reason": "spam" }),
+        json!({ "account_id": 1, "until": null, "reason": "spam" }),
+    ] {
+        assert_eq!(serde_json::from_value::<AccountBan>(ban).unwrap().until, None);
+    }
+}
+
+#[test]
+fn invalid_timestamps_are_rejected() {
+    for until in [json!("yesterday"), json!(i64::MAX), json!(true)] {
+        let ban = json!({ "account_id": 1, "until": until, "reason": "spam" });
+        assert!(serde_json::from_value::<AccountBan>(ban).is_err(), "{until}");
+    }
+}
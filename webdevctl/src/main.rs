@@ -0,0 +1,258 @@
+//! Command line client for the routine operations on the webdev book service.
+//!
+//! The commands are sent to the REST API with [webdev_client], and the resources are printed as JSON.
+//!
+//! ```text
+//! webdevctl login user@example.com
+//! export WEBDEV_TOKEN=...
+//! webdevctl questions list --limit 10
+//! webdevctl questions show 1
+//! webdevctl questions delete 1
+//! webdevctl questions export --answers > questions.json
+//!
+//! export WEBDEV_ADMIN_TOKEN=...
+//! webdevctl accounts ban 7 --reason spam --hours 24
+//! webdevctl accounts unlock 7
+//! webdevctl maintenance on
+//! ```
+//!
+//! The password of `login` is read from a prompt that does not echo it, or from the standard input
+//! with `--password-stdin`, so it never shows up in the arguments of the process or in the history
+//! of the shell.
+#![warn(clippy::all)]
+
+use std::io::BufRead;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use serde_json::to_string_pretty;
+use webdev_client::types::answer::AnswerId;
+use webdev_client::types::authentication::AccountId;
+use webdev_client::types::moderation::Ban;
+use webdev_client::types::pagination::Pagination;
+use webdev_client::types::question::QuestionId;
+use webdev_client::{Client, ClientError};
+
+/// Command line client for the webdev book service
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    /// URL at which the service is served
+    #[arg(long, env = "WEBDEV_URL", default_value = "http://localhost:8080")]
+    url: String,
+    /// Token for the commands that require authentication, as printed by `login`
+    #[arg(long, env = "WEBDEV_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+    /// Administrator token for the commands on the accounts and the maintenance mode
+    #[arg(long, env = "WEBDEV_ADMIN_TOKEN", hide_env_values = true)]
+    admin_token: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Logs in to an account and prints the token, the password is prompted for
+    Login {
+        email: String,
+        /// Reads the password from the first line of the standard input instead of prompting for it
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// Operations on questions
+    #[command(subcommand)]
+    Questions(QuestionsCommand),
+    /// Operations on answers
+    #[command(subcommand)]
+    Answers(AnswersCommand),
+    /// Operations on accounts, require the administrator token
+    #[command(subcommand)]
+    Accounts(AccountsCommand),
+    /// Maintenance mode of the service, in which only the reads are served, requires the administrator token
+    Maintenance {
+        #[arg(value_enum, default_value_t = MaintenanceAction::Status)]
+        action: MaintenanceAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum QuestionsCommand {
+    /// Lists a page of questions
+    List {
+        /// The index of the first question
+        #[arg(long, default_value_t = 0)]
+        offset: i64,
+        /// The maximum number of questions
+        #[arg(long)]
+        limit: Option<i64>,
+    },
+    /// Shows a single question
+    Show { id: QuestionId },
+    /// Deletes a question, requires a token
+    Delete { id: QuestionId },
+    /// Prints all questions as a JSON array, fetched page by page
+    Export {
+        /// Embeds the answers in every question
+        #[arg(long)]
+        answers: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AnswersCommand {
+    /// Shows a single answer
+    Show { id: AnswerId },
+    /// Deletes an answer, requires a token
+    Delete { id: AnswerId },
+}
+
+#[derive(Subcommand, Debug)]
+enum AccountsCommand {
+    /// Bans an account, for a number of hours or for good
+    Ban {
+        id: AccountId,
+        /// The reason of the ban, shown to the account
+        #[arg(long)]
+        reason: String,
+        /// The number of hours the account is banned for, for good if not given
+        #[arg(long)]
+        hours: Option<u32>,
+    },
+    /// Unlocks an account locked after too many failed logins
+    Unlock { id: AccountId },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum MaintenanceAction {
+    /// Prints whether the maintenance mode is on
+    Status,
+    /// Switches the maintenance mode on
+    On,
+    /// Switches the maintenance mode off
+    Off,
+}
+
+/// The number of questions fetched per request by `questions export`.
+const EXPORT_PAGE_SIZE: i64 = 100;
+
+/// Reads the password of `login`, from the standard input or from a prompt that does not echo it.
+fn read_password(from_stdin: bool) -> std::io::Result<String> {
+    if !from_stdin {
+        return rpassword::prompt_password("Password: ");
+    }
+    let mut password = String::new();
+    std::io::stdin().lock().read_line(&mut password)?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Fetches all questions page by page, with their answers if `answers` is set, and returns them
+/// as a JSON array.
+async fn export_questions(client: &Client, answers: bool) -> Result<String, ClientError> {
+    let mut questions = Vec::new();
+    let mut offset = 0;
+    loop {
+        let pagination = Pagination {
+            offset,
+            limit: Some(EXPORT_PAGE_SIZE),
+        };
+        let page = client.get_questions(pagination).await?;
+        let fetched = page.items.len() as i64;
+        for question in page.items {
+            let question = match (answers, question.id) {
+                (true, Some(id)) => serde_json::to_value(client.get_question_with_answers(id).await?),
+                _ => serde_json::to_value(question),
+            };
+            questions.push(question.expect("questions are always serializable"));
+        }
+        offset += fetched;
+        if fetched == 0 || offset >= page.total {
+            break;
+        }
+    }
+    Ok(to_string_pretty(&questions).expect("questions are always serializable"))
+}
+
+/// Runs the command, and returns the output to print.
+///
+/// The errors are the ones returned by the service, or the ones reading the password.
+async fn run(client: &Client, command: Command) -> Result<String, Box<dyn std::error::Error>> {
+    let output = match command {
+        Command::Login { email, password_stdin } => {
+            let password = read_password(password_stdin)?;
+            client.login(&email, &password).await?
+        }
+        Command::Questions(QuestionsCommand::List { offset, limit }) => {
+            let page = client.get_questions(Pagination { offset, limit }).await?;
+            to_string_pretty(&page).expect("questions are always serializable")
+        }
+        Command::Questions(QuestionsCommand::Show { id }) => {
+            let question = client.get_question(id).await?;
+            to_string_pretty(&question).expect("questions are always serializable")
+        }
+        Command::Questions(QuestionsCommand::Delete { id }) => {
+            client.delete_question(id).await?;
+            format!("question {} deleted", id.0)
+        }
+        Command::Questions(QuestionsCommand::Export { answers }) => export_questions(client, answers).await?,
+        Command::Answers(AnswersCommand::Show { id }) => {
+            let answer = client.get_answer(id).await?;
+            to_string_pretty(&answer).expect("answers are always serializable")
+        }
+        Command::Answers(AnswersCommand::Delete { id }) => {
+            client.delete_answer(id).await?;
+            format!("answer {} deleted", id.0)
+        }
+        Command::Accounts(AccountsCommand::Ban { id, reason, hours }) => {
+            let ban = Ban {
+                duration_hours: hours,
+                reason,
+            };
+            let ban = client.ban_account(id, &ban).await?;
+            to_string_pretty(&ban).expect("bans are always serializable")
+        }
+        Command::Accounts(AccountsCommand::Unlock { id }) => {
+            client.unlock_account(id).await?;
+            format!("account {} unlocked", id.0)
+        }
+        Command::Maintenance { action } => {
+            let mode = match action {
+                MaintenanceAction::Status => client.get_maintenance().await?,
+                MaintenanceAction::On => client.set_maintenance(true).await?,
+                MaintenanceAction::Off => client.set_maintenance(false).await?,
+            };
+            format!("maintenance mode is {}", if mode.enabled { "on" } else { "off" })
+        }
+    };
+    Ok(output)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let Cli {
+        url,
+        token,
+        admin_token,
+        command,
+    } = Cli::parse();
+
+    let client = match Client::new(&url) {
+        Ok(client) => client,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    client.set_token(token);
+    client.set_admin_token(admin_token);
+
+    match run(&client, command).await {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
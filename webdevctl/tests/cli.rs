@@ -0,0 +1,230 @@
+//! Tests of the commands of the binary, against a stub of the service served on a local port.
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::process::{Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use warp::http::StatusCode;
+use warp::Filter;
+use webdev_client::types::authentication::AccountId;
+use webdev_client::types::moderation::{AccountBan, Ban, MaintenanceMode};
+use webdev_client::types::pagination::{Page, Pagination};
+use webdev_client::types::question::{Question, QuestionId};
+
+/// Token the stub accepts for the commands that require authentication.
+const TOKEN: &str = "TEST TOKEN";
+/// Administrator token the stub accepts for the administrative commands.
+const ADMIN_TOKEN: &str = "TEST ADMIN TOKEN";
+/// Password the stub accepts for the logins.
+const PASSWORD: &str = "correct horse battery staple";
+/// The number of questions listed by the stub.
+const QUESTIONS: i32 = 150;
+
+/// Returns the question the stub serves under the id.
+fn question(id: i32) -> Question {
+    Question::builder()
+        .id(QuestionId(id))
+        .title("Stubbed title")
+        .content("Stubbed content")
+        .build()
+        .unwrap()
+}
+
+/// Filter accepting the requests that carry the administrator token.
+fn admin() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::header::exact("X-Admin-Token", ADMIN_TOKEN)
+}
+
+/// Serves a stub of the service on a local port, which has the question with id 1, and returns its address.
+fn stub() -> SocketAddr {
+    let show = warp::get().and(warp::path!("questions" / i32)).map(|id| {
+        let question = question(id);
+        match id {
+            1 => warp::reply::with_status(warp::reply::json(&question), StatusCode::OK),
+            _ => warp::reply::with_status(warp::reply::json(&"question not found"), StatusCode::NOT_FOUND),
+        }
+    });
+    let delete = warp::delete()
+        .and(warp::path!("questions" / i32))
+        .and(warp::header::optional::<String>("authorization"))
+        .map(|_, token: Option<String>| match token.as_deref() {
+            Some(TOKEN) => StatusCode::OK,
+            _ => StatusCode::UNAUTHORIZED,
+        });
+    let list = warp::get()
+        .and(warp::path!("questions"))
+        .and(warp::query::<HashMap<String, i64>>())
+        .map(|params: HashMap<String, i64>| {
+            let (offset, limit) = (params["offset"], params.get("limit").copied());
+            let end = (offset + limit.unwrap()).min(QUESTIONS as i64);
+            let items = (offset..end).map(|id| question(id as i32 + 1)).collect();
+            warp::reply::json(&Page::new(items, QUESTIONS as i64, Pagination { offset, limit }))
+        });
+    let login = warp::post()
+        .and(warp::path!("login"))
+        .and(warp::body::json())
+        .map(|account: Value| match account["password"].as_str() {
+            Some(PASSWORD) => warp::reply::with_status(warp::reply::json(&TOKEN), StatusCode::OK),
+            _ => warp::reply::with_status(warp::reply::json(&"wrong password"), StatusCode::UNAUTHORIZED),
+        });
+    let ban = warp::post()
+        .and(warp::path!("admin" / "accounts" / i32 / "ban"))
+        .and(admin())
+        .and(warp::body::json())
+        .map(|id, ban: Ban| {
+            warp::reply::json(&AccountBan {
+                account_id: AccountId(id),
+                until: None,
+                reason: ban.reason,
+            })
+        });
+    let maintenance = Arc::new(AtomicBool::new(false));
+    let get_maintenance = warp::get().and(warp::path!("admin" / "maintenance")).and(admin()).map({
+        let maintenance = maintenance.clone();
+        move || {
+            warp::reply::json(&MaintenanceMode {
+                enabled: maintenance.load(Ordering::Relaxed),
+            })
+        }
+    });
+    let set_maintenance = warp::put()
+        .and(warp::path!("admin" / "maintenance"))
+        .and(admin())
+        .and(warp::body::json())
+        .map(move |mode: MaintenanceMode| {
+            maintenance.store(mode.enabled, Ordering::Relaxed);
+            warp::reply::json(&mode)
+        });
+    let routes = show
+        .or(delete)
+        .or(list)
+        .or(login)
+        .or(ban)
+        .or(get_maintenance)
+        .or(set_maintenance);
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    addr
+}
+
+/// Runs the binary with the arguments, against the service at the address.
+async fn webdevctl(addr: SocketAddr, args: &[&str]) -> Output {
+    webdevctl_with_stdin(addr, args, "").await
+}
+
+/// Runs the binary with the arguments and the standard input, against the service at the address.
+async fn webdevctl_with_stdin(addr: SocketAddr, args: &[&str], stdin: &str) -> Output {
+    let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_webdevctl"));
+    command
+        .env_remove("WEBDEV_TOKEN")
+        .env_remove("WEBDEV_ADMIN_TOKEN")
+        .arg("--url")
+        .arg(format!("http://{addr}"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let stdin = stdin.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut child = command.spawn().unwrap();
+        child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+        child.wait_with_output().unwrap()
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_question_is_printed_as_json() {
+    let addr = stub();
+
+    let output = webdevctl(addr, &["questions", "show", "1"]).await;
+    assert!(output.status.success(), "{output:?}");
+    let question: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(question["id"], 1);
+    assert_eq!(question["title"], "Stubbed title");
+
+    let output = webdevctl(addr, &["questions", "show", "2"]).await;
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(error.starts_with("error: not found"), "{error}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_token_is_sent_with_the_commands_that_require_it() {
+    let addr = stub();
+
+    let output = webdevctl(addr, &["questions", "delete", "1"]).await;
+    assert!(!output.status.success());
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(error.starts_with("error: unauthorized"), "{error}");
+
+    let output = webdevctl(addr, &["--token", TOKEN, "questions", "delete", "1"]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "question 1 deleted\n");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_password_is_read_from_the_standard_input() {
+    let addr = stub();
+
+    let stdin = format!("{PASSWORD}\n");
+    let output = webdevctl_with_stdin(addr, &["login", "user@example.com", "--password-stdin"], &stdin).await;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), format!("{TOKEN}\n"));
+
+    let output = webdevctl_with_stdin(addr, &["login", "user@example.com", "--password-stdin"], "wrong\n").await;
+    assert!(!output.status.success());
+    let error = String::from_utf8(output.stderr).unwrap();
+    assert!(error.starts_with("error: unauthorized"), "{error}");
+
+    let output = webdevctl(addr, &["login", "user@example.com", PASSWORD]).await;
+    assert!(!output.status.success(), "the password is not accepted as an argument");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn all_questions_are_exported_page_by_page() {
+    let addr = stub();
+
+    let output = webdevctl(addr, &["questions", "export"]).await;
+    assert!(output.status.success(), "{output:?}");
+    let questions: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let ids: Vec<_> = questions
+        .iter()
+        .map(|question| question["id"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, (1..=QUESTIONS as i64).collect::<Vec<_>>());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_administrative_commands_send_the_administrator_token() {
+    let addr = stub();
+
+    let output = webdevctl(addr, &["accounts", "ban", "7", "--reason", "spam", "--hours", "24"]).await;
+    assert!(!output.status.success());
+
+    let output = webdevctl(
+        addr,
+        &["--admin-token", ADMIN_TOKEN, "accounts", "ban", "7", "--reason", "spam"],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+    let ban: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(ban, json!({ "account_id": 7, "until": null, "reason": "spam" }));
+
+    for (args, printed) in [
+        (&["maintenance"][..], "maintenance mode is off\n"),
+        (&["maintenance", "on"][..], "maintenance mode is on\n"),
+        (&["maintenance", "status"][..], "maintenance mode is on\n"),
+        (&["maintenance", "off"][..], "maintenance mode is off\n"),
+    ] {
+        let args: Vec<_> = ["--admin-token", ADMIN_TOKEN].iter().chain(args).copied().collect();
+        let output = webdevctl(addr, &args).await;
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), printed);
+    }
+}